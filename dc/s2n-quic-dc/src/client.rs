@@ -0,0 +1,388 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A dcQUIC client which performs path secret handshakes on demand
+//!
+//! The [`Client`] owns an s2n-quic endpoint for handshaking with peers, the [`secret::Map`]
+//! storing the negotiated path secrets, and the [`Environment`] for driving streams.
+
+use crate::{
+    path::secret::{self, HandshakeKind},
+    stream::{
+        application::Stream, client::tokio as stream_client, environment::tokio::Environment,
+        socket::Protocol,
+    },
+};
+use s2n_quic::provider::{dc::ConfirmComplete, tls::default as tls};
+use s2n_quic_core::application::ServerName;
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    fmt, io,
+    net::SocketAddr,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex, Weak,
+    },
+    time::Duration,
+};
+use tokio::task::JoinHandle;
+
+/// The amount of time a background renewal is given before it's considered failed
+const RENEWAL_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[inline]
+pub(crate) fn start_error<E: fmt::Display>(error: E) -> io::Error {
+    io::Error::new(io::ErrorKind::Other, error.to_string())
+}
+
+#[derive(Default)]
+pub struct Builder {
+    tls: Option<tls::Client>,
+    address: Option<SocketAddr>,
+    protocol: Option<Protocol>,
+    environment: Option<Environment>,
+    map: Option<secret::Map>,
+    map_capacity: Option<usize>,
+}
+
+impl Builder {
+    /// Sets the TLS configuration used to handshake with servers
+    pub fn with_tls(mut self, tls: tls::Client) -> Self {
+        self.tls = Some(tls);
+        self
+    }
+
+    /// Sets the local address of the handshake endpoint
+    ///
+    /// Defaults to `0.0.0.0:0`.
+    pub fn with_address(mut self, address: SocketAddr) -> Self {
+        self.address = Some(address);
+        self
+    }
+
+    /// Sets the transport used for streams
+    ///
+    /// Defaults to [`Protocol::Udp`].
+    pub fn with_protocol(mut self, protocol: Protocol) -> Self {
+        self.protocol = Some(protocol);
+        self
+    }
+
+    /// Sets the environment used to drive streams
+    pub fn with_environment(mut self, environment: Environment) -> Self {
+        self.environment = Some(environment);
+        self
+    }
+
+    /// Shares an existing path secret map with the client
+    pub fn with_map(mut self, map: secret::Map) -> Self {
+        self.map = Some(map);
+        self
+    }
+
+    /// Sets the capacity of the path secret map, if one wasn't provided with [`Self::with_map`]
+    ///
+    /// Defaults to [`secret::map::DEFAULT_CAPACITY`].
+    pub fn with_map_capacity(mut self, capacity: usize) -> Self {
+        self.map_capacity = Some(capacity);
+        self
+    }
+
+    /// Starts the handshake endpoint
+    ///
+    /// This must be called from within a tokio runtime.
+    pub fn build(self) -> io::Result<Client> {
+        let protocol = self.protocol.unwrap_or(Protocol::Udp);
+        ensure_protocol(protocol)?;
+
+        let tls = self.tls.ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidInput, "missing TLS configuration")
+        })?;
+        let address = self
+            .address
+            .unwrap_or_else(|| (std::net::Ipv4Addr::UNSPECIFIED, 0).into());
+        let map = self.map.unwrap_or_else(|| {
+            let capacity = self.map_capacity.unwrap_or(secret::map::DEFAULT_CAPACITY);
+            secret::Map::new(secret::stateless_reset::Signer::random(), capacity)
+        });
+        let environment = match self.environment {
+            Some(environment) => environment,
+            None => Environment::builder().build()?,
+        };
+
+        let endpoint = s2n_quic::Client::builder()
+            .with_tls(tls)
+            .map_err(start_error)?
+            .with_io(address)
+            .map_err(start_error)?
+            .with_dc(map.clone())
+            .map_err(start_error)?
+            .with_event(ConfirmComplete)
+            .map_err(start_error)?
+            .start()
+            .map_err(start_error)?;
+
+        let state = Arc::new_cyclic(|weak: &Weak<State>| State {
+            renewals: tokio::spawn(renew(map.renewal_queue(), weak.clone())),
+            endpoint,
+            map,
+            environment,
+            protocol,
+            handshakes: Default::default(),
+            server_names: Default::default(),
+            is_shutdown: AtomicBool::new(false),
+        });
+
+        Ok(Client(state))
+    }
+}
+
+/// Refreshes the path secrets for actively used peers before they expire
+async fn renew(queue: secret::map::RenewalQueue, state: Weak<State>) {
+    loop {
+        let peer = queue.next().await;
+
+        let Some(state) = state.upgrade() else {
+            break;
+        };
+
+        tokio::spawn(async move {
+            let client = Client(state);
+            let result = tokio::time::timeout(RENEWAL_TIMEOUT, client.renew(peer))
+                .await
+                .unwrap_or_else(|_| Err(io::ErrorKind::TimedOut.into()));
+
+            if let Err(error) = result {
+                tracing::debug!(%peer, %error, "path secret renewal failed");
+                client.0.map.on_renewal_failed(peer);
+            }
+        });
+    }
+}
+
+#[inline]
+pub(crate) fn ensure_protocol(protocol: Protocol) -> io::Result<()> {
+    if matches!(protocol, Protocol::Udp | Protocol::Tcp) {
+        Ok(())
+    } else {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            format!("unsupported stream protocol: {protocol:?}"),
+        ))
+    }
+}
+
+/// The server names of peers which have been handshaked with
+#[derive(Default)]
+struct ServerNames {
+    names: HashMap<SocketAddr, ServerName>,
+    /// The number of names at which peers without a path secret are pruned
+    prune_at: usize,
+}
+
+impl ServerNames {
+    fn insert(&mut self, map: &secret::Map, peer: SocketAddr, server_name: ServerName) {
+        self.names.insert(peer, server_name);
+
+        if self.names.len() < self.prune_at {
+            return;
+        }
+
+        self.names.retain(|peer, _| map.contains(*peer));
+
+        // wait for the map to fill up again before pruning
+        self.prune_at = (self.names.len() + map.secrets_capacity()).max(self.names.len() * 2);
+    }
+}
+
+/// A handle to a dcQUIC client
+///
+/// The handle is cheap to clone and all of the clones share the same path secrets.
+#[derive(Clone)]
+pub struct Client(Arc<State>);
+
+struct State {
+    endpoint: s2n_quic::Client,
+    map: secret::Map,
+    environment: Environment,
+    protocol: Protocol,
+    /// In-progress handshakes, keyed by the peer's handshake address
+    handshakes: Mutex<HashMap<SocketAddr, Arc<tokio::sync::Mutex<()>>>>,
+    /// The server name used for each peer, for background renewals
+    server_names: Mutex<ServerNames>,
+    renewals: JoinHandle<()>,
+    is_shutdown: AtomicBool,
+}
+
+impl Drop for State {
+    fn drop(&mut self) {
+        self.renewals.abort();
+    }
+}
+
+impl Client {
+    #[inline]
+    pub fn builder() -> Builder {
+        Builder::default()
+    }
+
+    /// Returns the local address of the handshake endpoint
+    #[inline]
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.0.endpoint.local_addr()
+    }
+
+    #[inline]
+    pub fn map(&self) -> &secret::Map {
+        &self.0.map
+    }
+
+    #[inline]
+    pub fn environment(&self) -> &Environment {
+        &self.0.environment
+    }
+
+    /// Ensures a path secret exists for the peer at `handshake_addr`
+    ///
+    /// Concurrent calls for the same peer share a single handshake.
+    pub async fn handshake_with(
+        &self,
+        handshake_addr: SocketAddr,
+        server_name: ServerName,
+    ) -> io::Result<HandshakeKind> {
+        if self.0.map.contains(handshake_addr) {
+            return Ok(HandshakeKind::Cached);
+        }
+
+        self.locked(handshake_addr, || async {
+            // another task may have completed the handshake while we were waiting
+            if self.0.map.contains(handshake_addr) {
+                return Ok(HandshakeKind::Cached);
+            }
+
+            let kind = self.handshake(handshake_addr, server_name.clone()).await?;
+
+            self.0
+                .server_names
+                .lock()
+                .unwrap()
+                .insert(&self.0.map, handshake_addr, server_name);
+
+            Ok(kind)
+        })
+        .await
+    }
+
+    /// Performs a new handshake with a peer, replacing its current path secret
+    async fn renew(&self, handshake_addr: SocketAddr) -> io::Result<()> {
+        let server_name = self
+            .0
+            .server_names
+            .lock()
+            .unwrap()
+            .names
+            .get(&handshake_addr)
+            .cloned();
+
+        let Some(server_name) = server_name else {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                "missing server name for peer",
+            ));
+        };
+
+        self.locked(handshake_addr, || {
+            self.handshake(handshake_addr, server_name)
+        })
+        .await?;
+
+        Ok(())
+    }
+
+    /// Runs `f` while holding the handshake lock for the peer
+    async fn locked<F, Fut, T>(&self, handshake_addr: SocketAddr, f: F) -> T
+    where
+        F: FnOnce() -> Fut,
+        Fut: core::future::Future<Output = T>,
+    {
+        let lock = self
+            .0
+            .handshakes
+            .lock()
+            .unwrap()
+            .entry(handshake_addr)
+            .or_default()
+            .clone();
+
+        let result = {
+            let _guard = lock.lock().await;
+            f().await
+        };
+
+        // clean up the entry if nobody else is waiting on it
+        if let Entry::Occupied(entry) = self.0.handshakes.lock().unwrap().entry(handshake_addr) {
+            if Arc::ptr_eq(entry.get(), &lock) && Arc::strong_count(&lock) == 2 {
+                entry.remove();
+            }
+        }
+
+        result
+    }
+
+    async fn handshake(
+        &self,
+        handshake_addr: SocketAddr,
+        server_name: ServerName,
+    ) -> io::Result<HandshakeKind> {
+        if self.0.is_shutdown.load(Ordering::Acquire) {
+            return Err(io::Error::new(
+                io::ErrorKind::NotConnected,
+                "client has been shut down",
+            ));
+        }
+
+        let connect = s2n_quic::client::Connect::new(handshake_addr).with_server_name(server_name);
+        let mut connection = self.0.endpoint.connect(connect).await?;
+        ConfirmComplete::wait_ready(&mut connection).await?;
+
+        // the path secrets are stored in the map so the connection is no longer needed
+        drop(connection);
+
+        Ok(HandshakeKind::Fresh)
+    }
+
+    /// Stops renewing path secrets and waits for in-progress handshakes to finish
+    ///
+    /// This applies to every clone of the client. New handshakes fail afterwards, while streams
+    /// to peers with an existing path secret can still be opened.
+    pub async fn shutdown(&self) {
+        self.0.is_shutdown.store(true, Ordering::Release);
+        self.0.renewals.abort();
+
+        let mut endpoint = self.0.endpoint.clone();
+        let _ = endpoint.wait_idle().await;
+    }
+
+    /// Opens a stream to the server acceptor at `acceptor_addr`
+    ///
+    /// A handshake is performed with `handshake_addr` if a path secret isn't already present.
+    pub async fn connect(
+        &self,
+        handshake_addr: SocketAddr,
+        acceptor_addr: SocketAddr,
+        server_name: ServerName,
+    ) -> io::Result<Stream> {
+        let handshake = self.handshake_with(handshake_addr, server_name);
+        let env = &self.0.environment;
+        let map = &self.0.map;
+
+        match self.0.protocol {
+            Protocol::Tcp => {
+                stream_client::connect_tcp(handshake_addr, handshake, acceptor_addr, env, map).await
+            }
+            _ => {
+                stream_client::connect_udp(handshake_addr, handshake, acceptor_addr, env, map).await
+            }
+        }
+    }
+}
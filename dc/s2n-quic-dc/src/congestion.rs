@@ -1,30 +1,140 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::event::{self, builder as events, IntoEvent as _};
+use core::time::Duration;
 use s2n_quic_core::{
-    event, random,
+    endpoint, random,
     recovery::{
-        bandwidth::Bandwidth, bbr::BbrCongestionController, congestion_controller::Publisher,
-        CongestionController, RttEstimator,
+        bandwidth::{self, Bandwidth},
+        bbr::BbrCongestionController,
+        congestion_controller::Publisher,
+        CongestionController, CubicCongestionController, RttEstimator,
     },
     time::{timer, Timestamp},
 };
+use std::sync::Arc;
 
-pub type PacketInfo = <BbrCongestionController as CongestionController>::PacketInfo;
+/// The congestion control algorithm used by a stream
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Algorithm {
+    /// BBRv2
+    #[default]
+    Bbr,
+    /// CUBIC, as specified in RFC 8312
+    Cubic,
+    /// Paces packets at a fixed rate and ignores loss and congestion signals
+    ///
+    /// This is only appropriate for lossless fabrics which provide their own flow control.
+    Fixed { rate: Bandwidth },
+    /// Sends packets as fast as possible and ignores loss and congestion signals
+    ///
+    /// This is only appropriate for lossless fabrics which provide their own flow control.
+    Unlimited,
+}
+
+impl Algorithm {
+    #[inline]
+    fn as_event(self) -> events::CongestionControlAlgorithm {
+        match self {
+            Self::Bbr => events::CongestionControlAlgorithm::Bbr,
+            Self::Cubic => events::CongestionControlAlgorithm::Cubic,
+            Self::Fixed { .. } => events::CongestionControlAlgorithm::Fixed,
+            Self::Unlimited => events::CongestionControlAlgorithm::Unlimited,
+        }
+    }
+}
+
+/// Per-packet state for the congestion controller, returned by [`Controller::on_packet_sent`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PacketInfo {
+    Bbr(<BbrCongestionController as CongestionController>::PacketInfo),
+    Cubic,
+    Fixed,
+}
+
+/// Publishes the congestion controller events to the path secret map's subscriber
+#[derive(Clone)]
+pub struct Subscriber {
+    subscriber: Arc<dyn event::DynSubscriber>,
+    endpoint_type: endpoint::Type,
+}
+
+impl Subscriber {
+    #[inline]
+    pub(crate) fn new(
+        subscriber: Arc<dyn event::DynSubscriber>,
+        endpoint_type: endpoint::Type,
+    ) -> Self {
+        Self {
+            subscriber,
+            endpoint_type,
+        }
+    }
+}
+
+impl core::fmt::Debug for Subscriber {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("Subscriber")
+            .field("endpoint_type", &self.endpoint_type)
+            .finish_non_exhaustive()
+    }
+}
+
+#[derive(Clone, Debug)]
+#[allow(clippy::large_enum_variant)] // each stream owns a single controller so boxing isn't worth it
+enum Inner {
+    Bbr(BbrCongestionController),
+    Cubic(CubicCongestionController),
+    Fixed(Fixed),
+}
 
 #[derive(Clone, Debug)]
 pub struct Controller {
-    controller: BbrCongestionController,
+    controller: Inner,
+    algorithm: Algorithm,
+    subscriber: Option<Subscriber>,
 }
 
 impl Controller {
     #[inline]
     pub fn new(max_datagram_size: u16) -> Self {
+        Self::with_algorithm(Algorithm::default(), max_datagram_size, None)
+    }
+
+    #[inline]
+    pub fn with_algorithm(
+        algorithm: Algorithm,
+        max_datagram_size: u16,
+        subscriber: Option<Subscriber>,
+    ) -> Self {
+        let controller = match algorithm {
+            Algorithm::Bbr => Inner::Bbr(BbrCongestionController::new(
+                max_datagram_size,
+                Default::default(),
+            )),
+            Algorithm::Cubic => Inner::Cubic(CubicCongestionController::new(
+                max_datagram_size,
+                Default::default(),
+            )),
+            Algorithm::Fixed { rate } => Inner::Fixed(Fixed::new(rate, max_datagram_size)),
+            Algorithm::Unlimited => {
+                Inner::Fixed(Fixed::new(Bandwidth::INFINITY, max_datagram_size))
+            }
+        };
+
         Self {
-            controller: BbrCongestionController::new(max_datagram_size, Default::default()),
+            controller,
+            algorithm,
+            subscriber,
         }
     }
 
+    #[inline]
+    pub fn algorithm(&self) -> Algorithm {
+        self.algorithm
+    }
+
     #[inline]
     pub fn on_packet_sent(
         &mut self,
@@ -35,38 +145,88 @@ impl Controller {
     ) -> PacketInfo {
         let sent_bytes = sent_bytes as usize;
         let app_limited = Some(!has_more_app_data);
-        let publisher = &mut NoopPublisher;
-        self.controller
-            .on_packet_sent(time_sent, sent_bytes, app_limited, rtt_estimator, publisher)
+        let publisher = &mut EventPublisher {
+            subscriber: self.subscriber.as_ref(),
+            algorithm: self.algorithm,
+            now: time_sent,
+        };
+        match &mut self.controller {
+            Inner::Bbr(cca) => PacketInfo::Bbr(cca.on_packet_sent(
+                time_sent,
+                sent_bytes,
+                app_limited,
+                rtt_estimator,
+                publisher,
+            )),
+            Inner::Cubic(cca) => {
+                cca.on_packet_sent(time_sent, sent_bytes, app_limited, rtt_estimator, publisher);
+                PacketInfo::Cubic
+            }
+            Inner::Fixed(cca) => {
+                cca.on_packet_sent(time_sent, sent_bytes);
+                PacketInfo::Fixed
+            }
+        }
     }
 
+    /// Called when the newest acknowledged packet in an ACK frame was sent at `time_sent`
     #[inline]
     pub fn on_packet_ack(
         &mut self,
-        newest_acked_time_sent: Timestamp,
+        time_sent: Timestamp,
         bytes_acked: usize,
         newest_acked_packet_info: PacketInfo,
         rtt_estimator: &RttEstimator,
         random_generator: &mut dyn random::Generator,
         ack_receive_time: Timestamp,
     ) {
-        let publisher = &mut NoopPublisher;
-        self.controller.on_ack(
-            newest_acked_time_sent,
-            bytes_acked,
-            newest_acked_packet_info,
-            rtt_estimator,
-            random_generator,
-            ack_receive_time,
-            publisher,
-        )
+        let publisher = &mut EventPublisher {
+            subscriber: self.subscriber.as_ref(),
+            algorithm: self.algorithm,
+            now: ack_receive_time,
+        };
+        match (&mut self.controller, newest_acked_packet_info) {
+            (Inner::Bbr(cca), PacketInfo::Bbr(info)) => {
+                cca.on_rtt_update(time_sent, ack_receive_time, rtt_estimator, publisher);
+                cca.on_ack(
+                    info.first_sent_time,
+                    bytes_acked,
+                    info,
+                    rtt_estimator,
+                    random_generator,
+                    ack_receive_time,
+                    publisher,
+                )
+            }
+            (Inner::Cubic(cca), PacketInfo::Cubic) => {
+                cca.on_rtt_update(time_sent, ack_receive_time, rtt_estimator, publisher);
+                cca.on_ack(
+                    time_sent,
+                    bytes_acked,
+                    (),
+                    rtt_estimator,
+                    random_generator,
+                    ack_receive_time,
+                    publisher,
+                )
+            }
+            (Inner::Fixed(cca), PacketInfo::Fixed) => cca.on_ack(bytes_acked),
+            (_, info) => debug_assert!(false, "mismatched packet info: {info:?}"),
+        }
     }
 
     #[inline]
     pub fn on_explicit_congestion(&mut self, ce_count: u64, now: Timestamp) {
-        let publisher = &mut NoopPublisher;
-        self.controller
-            .on_explicit_congestion(ce_count, now, publisher);
+        let publisher = &mut EventPublisher {
+            subscriber: self.subscriber.as_ref(),
+            algorithm: self.algorithm,
+            now,
+        };
+        match &mut self.controller {
+            Inner::Bbr(cca) => cca.on_explicit_congestion(ce_count, now, publisher),
+            Inner::Cubic(cca) => cca.on_explicit_congestion(ce_count, now, publisher),
+            Inner::Fixed(_) => {}
+        }
     }
 
     #[inline]
@@ -81,51 +241,101 @@ impl Controller {
         let persistent_congestion = false;
         let new_loss_burst = false;
 
-        let publisher = &mut NoopPublisher;
-        self.controller.on_packet_lost(
-            bytes_lost,
-            packet_info,
-            persistent_congestion,
-            new_loss_burst,
-            random_generator,
+        let publisher = &mut EventPublisher {
+            subscriber: self.subscriber.as_ref(),
+            algorithm: self.algorithm,
             now,
-            publisher,
-        );
+        };
+        match (&mut self.controller, packet_info) {
+            (Inner::Bbr(cca), PacketInfo::Bbr(info)) => cca.on_packet_lost(
+                bytes_lost,
+                info,
+                persistent_congestion,
+                new_loss_burst,
+                random_generator,
+                now,
+                publisher,
+            ),
+            (Inner::Cubic(cca), PacketInfo::Cubic) => cca.on_packet_lost(
+                bytes_lost,
+                (),
+                persistent_congestion,
+                new_loss_burst,
+                random_generator,
+                now,
+                publisher,
+            ),
+            (Inner::Fixed(cca), PacketInfo::Fixed) => cca.on_packet_lost(bytes_lost),
+            (_, info) => debug_assert!(false, "mismatched packet info: {info:?}"),
+        }
     }
 
     #[inline]
     pub fn is_congestion_limited(&self) -> bool {
-        self.controller.is_congestion_limited()
+        match &self.controller {
+            Inner::Bbr(cca) => cca.is_congestion_limited(),
+            Inner::Cubic(cca) => cca.is_congestion_limited(),
+            Inner::Fixed(_) => false,
+        }
     }
 
     #[inline]
     pub fn requires_fast_retransmission(&self) -> bool {
-        self.controller.requires_fast_retransmission()
+        match &self.controller {
+            Inner::Bbr(cca) => cca.requires_fast_retransmission(),
+            Inner::Cubic(cca) => cca.requires_fast_retransmission(),
+            Inner::Fixed(_) => false,
+        }
     }
 
     #[inline]
     pub fn congestion_window(&self) -> u32 {
-        self.controller.congestion_window()
+        match &self.controller {
+            Inner::Bbr(cca) => cca.congestion_window(),
+            Inner::Cubic(cca) => cca.congestion_window(),
+            Inner::Fixed(_) => u32::MAX,
+        }
     }
 
     #[inline]
     pub fn bytes_in_flight(&self) -> u32 {
-        self.controller.bytes_in_flight()
+        match &self.controller {
+            Inner::Bbr(cca) => cca.bytes_in_flight(),
+            Inner::Cubic(cca) => cca.bytes_in_flight(),
+            Inner::Fixed(cca) => cca.bytes_in_flight,
+        }
     }
 
     #[inline]
     pub fn send_quantum(&self) -> usize {
-        self.controller.send_quantum().unwrap_or(usize::MAX)
+        let send_quantum = match &self.controller {
+            Inner::Bbr(cca) => cca.send_quantum(),
+            Inner::Cubic(cca) => cca.send_quantum(),
+            Inner::Fixed(cca) => cca.send_quantum(),
+        };
+        send_quantum.unwrap_or(usize::MAX)
     }
 
     #[inline]
     pub fn earliest_departure_time(&self) -> Option<Timestamp> {
-        self.controller.earliest_departure_time()
+        match &self.controller {
+            Inner::Bbr(cca) => cca.earliest_departure_time(),
+            Inner::Cubic(cca) => cca.earliest_departure_time(),
+            Inner::Fixed(cca) => cca.next_departure_time,
+        }
     }
 
+    /// Returns the estimated sending rate of the stream
     #[inline]
-    pub fn bandwidth(&self) -> Bandwidth {
-        self.controller.pacing_rate()
+    pub fn bandwidth(&self, rtt_estimator: &RttEstimator) -> Bandwidth {
+        match &self.controller {
+            Inner::Bbr(cca) => cca.pacing_rate(),
+            Inner::Cubic(cca) => {
+                // CUBIC doesn't estimate the bandwidth so approximate it with a window per RTT
+                Bandwidth::new(cca.congestion_window() as u64, rtt_estimator.smoothed_rtt())
+            }
+            Inner::Fixed(cca) => cca.rate,
+        }
     }
 }
 
@@ -141,38 +351,266 @@ impl timer::Provider for Controller {
     }
 }
 
-struct NoopPublisher;
+/// A controller which paces packets at a fixed rate without any congestion window
+#[derive(Clone, Debug)]
+struct Fixed {
+    rate: Bandwidth,
+    max_datagram_size: u16,
+    bytes_in_flight: u32,
+    next_departure_time: Option<Timestamp>,
+}
+
+impl Fixed {
+    /// The amount of time worth of data to send in a single burst
+    const BURST_INTERVAL: Duration = Duration::from_millis(1);
+    /// The maximum number of bytes sent in a single burst
+    const MAX_BURST_SIZE: u64 = 64_000;
 
-impl Publisher for NoopPublisher {
+    #[inline]
+    fn new(rate: Bandwidth, max_datagram_size: u16) -> Self {
+        Self {
+            rate,
+            max_datagram_size,
+            bytes_in_flight: 0,
+            next_departure_time: None,
+        }
+    }
+
+    #[inline]
+    fn on_packet_sent(&mut self, time_sent: Timestamp, sent_bytes: usize) {
+        self.bytes_in_flight = self
+            .bytes_in_flight
+            .saturating_add(sent_bytes.try_into().unwrap_or(u32::MAX));
+
+        if self.rate == Bandwidth::INFINITY {
+            return;
+        }
+
+        // don't let idle periods accumulate credit for a burst above the rate
+        let start = self
+            .next_departure_time
+            .map_or(time_sent, |next| next.max(time_sent));
+        self.next_departure_time = Some(start + sent_bytes as u64 / self.rate);
+    }
+
+    #[inline]
+    fn on_ack(&mut self, bytes_acked: usize) {
+        let bytes_acked = bytes_acked.try_into().unwrap_or(u32::MAX);
+        self.bytes_in_flight = self.bytes_in_flight.saturating_sub(bytes_acked);
+    }
+
+    #[inline]
+    fn on_packet_lost(&mut self, bytes_lost: u32) {
+        self.bytes_in_flight = self.bytes_in_flight.saturating_sub(bytes_lost);
+    }
+
+    #[inline]
+    fn send_quantum(&self) -> Option<usize> {
+        let min = self.max_datagram_size as u64 * 2;
+        let quantum = (self.rate * Self::BURST_INTERVAL).clamp(min, Self::MAX_BURST_SIZE.max(min));
+        Some(quantum as usize)
+    }
+}
+
+/// Forwards congestion controller events to the dc event subscriber
+struct EventPublisher<'a> {
+    subscriber: Option<&'a Subscriber>,
+    algorithm: Algorithm,
+    now: Timestamp,
+}
+
+impl EventPublisher<'_> {
+    #[inline]
+    fn publish(&self, mut f: impl FnMut(&dyn event::EndpointPublisher)) {
+        let Some(subscriber) = self.subscriber else {
+            return;
+        };
+        let meta = events::EndpointMeta {
+            endpoint_type: subscriber.endpoint_type,
+            timestamp: self.now,
+        };
+        subscriber.subscriber.publish(meta, &mut f);
+    }
+}
+
+impl Publisher for EventPublisher<'_> {
     #[inline]
     fn on_slow_start_exited(
         &mut self,
-        _cause: event::builder::SlowStartExitCause,
-        _congestion_window: u32,
+        cause: s2n_quic_core::event::builder::SlowStartExitCause,
+        congestion_window: u32,
     ) {
-        // TODO
+        let algorithm = self.algorithm.as_event();
+        let cause = cause.into_event();
+        self.publish(|publisher| {
+            publisher.on_congestion_slow_start_exited(events::CongestionSlowStartExited {
+                algorithm,
+                cause,
+                congestion_window,
+            });
+        });
     }
 
     #[inline]
-    fn on_delivery_rate_sampled(
-        &mut self,
-        _rate_sample: s2n_quic_core::recovery::bandwidth::RateSample,
-    ) {
-        // TODO
+    fn on_delivery_rate_sampled(&mut self, _rate_sample: bandwidth::RateSample) {
+        // delivery rate samples are emitted on every ACK, which is too noisy for dc streams
     }
 
     #[inline]
     fn on_pacing_rate_updated(
         &mut self,
-        _pacing_rate: s2n_quic_core::recovery::bandwidth::Bandwidth,
-        _burst_size: u32,
-        _pacing_gain: num_rational::Ratio<u64>,
+        pacing_rate: Bandwidth,
+        burst_size: u32,
+        pacing_gain: num_rational::Ratio<u64>,
     ) {
-        // TODO
+        let algorithm = self.algorithm.as_event();
+        let pacing_gain = *pacing_gain.numer() as f32 / *pacing_gain.denom() as f32;
+        self.publish(|publisher| {
+            publisher.on_congestion_pacing_rate_updated(events::CongestionPacingRateUpdated {
+                algorithm,
+                bytes_per_second: pacing_rate.as_bytes_per_second(),
+                burst_size,
+                pacing_gain,
+            });
+        });
     }
 
     #[inline]
-    fn on_bbr_state_changed(&mut self, _state: event::builder::BbrState) {
-        // TODO
+    fn on_bbr_state_changed(&mut self, state: s2n_quic_core::event::builder::BbrState) {
+        let state = state.into_event();
+        self.publish(|publisher| {
+            publisher.on_congestion_bbr_state_changed(events::CongestionBbrStateChanged { state });
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use s2n_quic_core::{
+        packet::number::PacketNumberSpace, random::testing::Generator, time::clock::testing::now,
+    };
+    use std::sync::Mutex;
+
+    #[derive(Clone, Default)]
+    struct Events(Arc<Mutex<Vec<&'static str>>>);
+
+    impl event::Subscriber for Events {
+        type ConnectionContext = ();
+
+        fn create_connection_context(
+            &self,
+            _meta: &event::api::ConnectionMeta,
+            _info: &event::api::ConnectionInfo,
+        ) -> Self::ConnectionContext {
+        }
+
+        fn on_event<M: event::Meta, E: event::Event>(&self, _meta: &M, _event: &E) {
+            self.0.lock().unwrap().push(E::NAME);
+        }
+    }
+
+    /// Sends a single packet and acknowledges it after `rtt`
+    fn round_trip(cca: &mut Controller, rtt: Duration) {
+        let mut rtt_estimator = RttEstimator::default();
+        let time_sent = now();
+        let info = cca.on_packet_sent(time_sent, 1200, true, &rtt_estimator);
+        let ack_time = time_sent + rtt;
+        rtt_estimator.update_rtt(
+            Duration::ZERO,
+            rtt,
+            ack_time,
+            true,
+            PacketNumberSpace::ApplicationData,
+        );
+        cca.on_packet_ack(
+            time_sent,
+            1200,
+            info,
+            &rtt_estimator,
+            &mut Generator::default(),
+            ack_time,
+        );
+    }
+
+    #[test]
+    fn fixed_rate_pacing() {
+        // 1KiB per millisecond
+        let rate = Bandwidth::new(1024, Duration::from_millis(1));
+        let mut cca = Controller::with_algorithm(Algorithm::Fixed { rate }, 1200, None);
+        let rtt_estimator = RttEstimator::default();
+
+        let start = now();
+        assert!(cca.earliest_departure_time().is_none());
+
+        cca.on_packet_sent(start, 1024, true, &rtt_estimator);
+        let next = cca.earliest_departure_time().unwrap();
+        assert_eq!(
+            next.saturating_duration_since(start),
+            Duration::from_millis(1)
+        );
+
+        // sending early continues from the previous departure time
+        cca.on_packet_sent(start, 1024, true, &rtt_estimator);
+        let next = cca.earliest_departure_time().unwrap();
+        assert_eq!(
+            next.saturating_duration_since(start),
+            Duration::from_millis(2)
+        );
+
+        // idle periods don't accumulate credit
+        let later = start + Duration::from_secs(1);
+        cca.on_packet_sent(later, 1024, true, &rtt_estimator);
+        let next = cca.earliest_departure_time().unwrap();
+        assert_eq!(
+            next.saturating_duration_since(later),
+            Duration::from_millis(1)
+        );
+
+        assert_eq!(cca.bytes_in_flight(), 3072);
+        assert!(!cca.is_congestion_limited());
+        assert_eq!(cca.bandwidth(&rtt_estimator), rate);
+    }
+
+    #[test]
+    fn unlimited() {
+        let mut cca = Controller::with_algorithm(Algorithm::Unlimited, 1200, None);
+        let rtt_estimator = RttEstimator::default();
+
+        let info = cca.on_packet_sent(now(), 1200, true, &rtt_estimator);
+        assert!(cca.earliest_departure_time().is_none());
+        assert_eq!(cca.congestion_window(), u32::MAX);
+        assert_eq!(cca.bytes_in_flight(), 1200);
+
+        cca.on_packet_lost(1200, info, &mut Generator::default(), now());
+        assert_eq!(cca.bytes_in_flight(), 0);
+    }
+
+    #[test]
+    fn cubic_round_trip() {
+        let mut cca = Controller::with_algorithm(Algorithm::Cubic, 1200, None);
+        let initial_window = cca.congestion_window();
+        round_trip(&mut cca, Duration::from_millis(10));
+
+        assert!(cca.congestion_window() >= initial_window);
+        assert_eq!(cca.bytes_in_flight(), 0);
+        assert!(cca.bandwidth(&RttEstimator::default()) > Bandwidth::ZERO);
+    }
+
+    #[test]
+    fn bbr_events() {
+        let events = Events::default();
+        let subscriber: Arc<dyn event::DynSubscriber> = Arc::new(events.clone());
+        let subscriber = Subscriber::new(subscriber, endpoint::Type::Client);
+        let mut cca = Controller::with_algorithm(Algorithm::Bbr, 1200, Some(subscriber));
+
+        round_trip(&mut cca, Duration::from_millis(10));
+
+        // the first RTT sample initializes the pacing rate
+        let events = events.0.lock().unwrap();
+        assert!(
+            events.contains(&"congestion:pacing_rate_updated"),
+            "{events:?}"
+        );
     }
 }
@@ -75,7 +75,7 @@ impl s2n_quic_core::probe::Arg for Id {
 
 zerocopy_value_codec!(Id);
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 #[cfg_attr(
     any(test, feature = "testing"),
     derive(bolero_generator::TypeGenerator)
@@ -5,7 +5,7 @@ use super::IntoNonce;
 use aws_lc_rs::aead::{Aad, Algorithm, LessSafeKey, Nonce, UnboundKey, NONCE_LEN};
 use s2n_quic_core::{assume, packet::KeyPhase};
 
-pub use aws_lc_rs::aead::{AES_128_GCM, AES_256_GCM};
+pub use aws_lc_rs::aead::{AES_128_GCM, AES_256_GCM, CHACHA20_POLY1305};
 
 const TAG_LEN: usize = 16;
 
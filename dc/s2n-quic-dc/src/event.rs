@@ -0,0 +1,46 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use core::time::Duration;
+pub use s2n_quic_core::event::{IntoEvent, Timestamp};
+
+// dc doesn't emit any connection-level events yet so some of the generated connection plumbing
+// goes unused
+#[allow(dead_code, unused_imports)]
+mod generated;
+pub use generated::*;
+
+/// A type-erased [`Subscriber`] for publishing endpoint-level events
+pub(crate) trait DynSubscriber: 'static + Send + Sync {
+    fn publish(&self, meta: builder::EndpointMeta, f: &mut dyn FnMut(&dyn EndpointPublisher));
+}
+
+impl<S: Subscriber + Sync> DynSubscriber for S {
+    #[inline]
+    fn publish(&self, meta: builder::EndpointMeta, f: &mut dyn FnMut(&dyn EndpointPublisher)) {
+        let publisher = EndpointPublisherSubscriber::new(meta, None, self);
+        f(&publisher)
+    }
+}
+
+/// All event types which can be emitted from this library.
+///
+/// This is separate from the s2n-quic-core trait so the `Serialize` bound follows this crate's
+/// `event-serde` feature rather than s2n-quic-core's.
+pub trait Event: core::fmt::Debug + Serialize {
+    const NAME: &'static str;
+}
+
+/// Implemented by all event types when the `event-serde` feature is enabled
+#[cfg(feature = "event-serde")]
+pub use serde::Serialize;
+
+/// Implemented by all types when the `event-serde` feature is disabled
+#[cfg(not(feature = "event-serde"))]
+pub trait Serialize {}
+
+#[cfg(not(feature = "event-serde"))]
+impl<T: ?Sized> Serialize for T {}
+
+#[cfg(all(test, feature = "event-serde"))]
+mod tests;
@@ -1,10 +1,260 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
-#[event("transport:frame_sent")]
-/// Frame was sent
-struct FrameSent {
-    packet_header: PacketHeader,
-    path_id: u64,
-    frame: Frame,
+struct ConnectionMeta {
+    #[builder(s2n_quic_core::endpoint::Type)]
+    endpoint_type: EndpointType,
+
+    id: u64,
+
+    #[builder(s2n_quic_core::time::Timestamp)]
+    timestamp: crate::event::Timestamp,
+}
+
+struct EndpointMeta {
+    #[builder(s2n_quic_core::endpoint::Type)]
+    endpoint_type: EndpointType,
+
+    #[builder(s2n_quic_core::time::Timestamp)]
+    timestamp: crate::event::Timestamp,
+}
+
+struct ConnectionInfo {}
+
+/// A context from which the event is being emitted
+///
+/// An event can occur in the context of an Endpoint or Connection
+enum Subject {
+    Endpoint,
+
+    /// This maps to an internal connection id, which is a stable identifier across CID changes.
+    Connection { id: u64 },
+}
+
+/// An endpoint may be either a Server or a Client
+#[exhaustive]
+enum EndpointType {
+    Server,
+    Client,
+}
+
+impl IntoEvent<api::EndpointType> for s2n_quic_core::endpoint::Type {
+    #[inline]
+    fn into_event(self) -> api::EndpointType {
+        match self {
+            Self::Client => api::EndpointType::Client {},
+            Self::Server => api::EndpointType::Server {},
+        }
+    }
+}
+
+impl IntoEvent<builder::EndpointType> for s2n_quic_core::endpoint::Type {
+    #[inline]
+    fn into_event(self) -> builder::EndpointType {
+        match self {
+            Self::Client => builder::EndpointType::Client {},
+            Self::Server => builder::EndpointType::Server {},
+        }
+    }
+}
+
+#[event("application:write")]
+/// Application data was written to a stream
+struct ApplicationWrite {
+    /// The number of bytes that the application tried to write
+    len: usize,
+}
+
+#[derive(Clone)]
+#[builder_derive(derive(Copy))]
+enum SocketAddress<'a> {
+    IpV4 { ip: &'a [u8; 4], port: u16 },
+    IpV6 { ip: &'a [u8; 16], port: u16 },
+}
+
+impl<'a> core::fmt::Debug for SocketAddress<'a> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        core::fmt::Display::fmt(self, f)
+    }
+}
+
+impl<'a> core::fmt::Display for SocketAddress<'a> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            Self::IpV4 { ip, port } => {
+                let addr = s2n_quic_core::inet::SocketAddressV4::new(**ip, *port);
+                addr.fmt(f)
+            }
+            Self::IpV6 { ip, port } => {
+                let addr = s2n_quic_core::inet::SocketAddressV6::new(**ip, *port);
+                addr.fmt(f)
+            }
+        }
+    }
+}
+
+impl<'a> IntoEvent<builder::SocketAddress<'a>> for &'a s2n_quic_core::inet::SocketAddress {
+    #[inline]
+    fn into_event(self) -> builder::SocketAddress<'a> {
+        match self {
+            s2n_quic_core::inet::SocketAddress::IpV4(addr) => builder::SocketAddress::IpV4 {
+                ip: addr.ip().as_bytes().try_into().unwrap(),
+                port: addr.port(),
+            },
+            s2n_quic_core::inet::SocketAddress::IpV6(addr) => builder::SocketAddress::IpV6 {
+                ip: addr.ip().as_bytes().try_into().unwrap(),
+                port: addr.port(),
+            },
+        }
+    }
+}
+
+impl From<SocketAddress<'_>> for std::net::SocketAddr {
+    #[inline]
+    fn from(address: SocketAddress) -> Self {
+        use std::net;
+        match address {
+            SocketAddress::IpV4 { ip, port } => {
+                let ip = net::IpAddr::V4(net::Ipv4Addr::from(*ip));
+                Self::new(ip, port)
+            }
+            SocketAddress::IpV6 { ip, port } => {
+                let ip = net::IpAddr::V6(net::Ipv6Addr::from(*ip));
+                Self::new(ip, port)
+            }
+        }
+    }
+}
+
+/// The reason a path secret renewal was requested
+#[builder_derive(derive(Copy))]
+enum PathSecretRenewalReason {
+    /// The entry reached its randomized rehandshake time
+    Scheduled,
+    /// The entry is close to exhausting the configured maximum number of key uses
+    KeyUses,
+}
+
+/// The reason a path secret was evicted from the map
+#[builder_derive(derive(Copy))]
+enum PathSecretEvictionReason {
+    /// The entry exceeded the configured maximum lifetime
+    LifetimeExpired,
+    /// The entry exhausted the configured maximum number of key uses
+    KeyUsesExhausted,
+}
+
+#[event("path_secret_map:renewal_requested")]
+#[subject(endpoint)]
+/// The path secret for a peer was queued to be renewed in the background
+struct PathSecretMapRenewalRequested<'a> {
+    peer_address: SocketAddress<'a>,
+    reason: PathSecretRenewalReason,
+}
+
+#[event("path_secret_map:entry_renewed")]
+#[subject(endpoint)]
+/// The path secret for a peer was replaced with one from a newer handshake
+struct PathSecretMapEntryRenewed<'a> {
+    peer_address: SocketAddress<'a>,
+    /// The amount of time the replaced entry was in the map
+    age: Duration,
+}
+
+#[event("path_secret_map:entry_evicted")]
+#[subject(endpoint)]
+/// The path secret for a peer was removed from the map by the entry policy
+struct PathSecretMapEntryEvicted<'a> {
+    peer_address: SocketAddress<'a>,
+    reason: PathSecretEvictionReason,
+    /// The amount of time the entry was in the map
+    age: Duration,
+}
+
+/// The congestion control algorithm used by a stream
+#[builder_derive(derive(Copy))]
+enum CongestionControlAlgorithm {
+    Bbr,
+    Cubic,
+    Fixed,
+    Unlimited,
+}
+
+/// The reason the slow start congestion controller state has been exited
+#[builder_derive(derive(Copy))]
+enum SlowStartExitCause {
+    /// A packet was determined lost
+    PacketLoss,
+    /// An Explicit Congestion Notification: Congestion Experienced marking was received
+    Ecn,
+    /// The round trip time estimate was updated
+    Rtt,
+    /// Slow Start exited due to a reason other than those above
+    Other,
+}
+
+impl IntoEvent<builder::SlowStartExitCause> for s2n_quic_core::event::builder::SlowStartExitCause {
+    #[inline]
+    fn into_event(self) -> builder::SlowStartExitCause {
+        use s2n_quic_core::event::builder::SlowStartExitCause as Cause;
+        match self {
+            Cause::PacketLoss => builder::SlowStartExitCause::PacketLoss,
+            Cause::Ecn => builder::SlowStartExitCause::Ecn,
+            Cause::Rtt => builder::SlowStartExitCause::Rtt,
+            Cause::Other => builder::SlowStartExitCause::Other,
+        }
+    }
+}
+
+#[builder_derive(derive(Copy))]
+enum BbrState {
+    Startup,
+    Drain,
+    ProbeBwDown,
+    ProbeBwCruise,
+    ProbeBwRefill,
+    ProbeBwUp,
+    ProbeRtt,
+}
+
+impl IntoEvent<builder::BbrState> for s2n_quic_core::event::builder::BbrState {
+    #[inline]
+    fn into_event(self) -> builder::BbrState {
+        use s2n_quic_core::event::builder::BbrState as State;
+        match self {
+            State::Startup => builder::BbrState::Startup,
+            State::Drain => builder::BbrState::Drain,
+            State::ProbeBwDown => builder::BbrState::ProbeBwDown,
+            State::ProbeBwCruise => builder::BbrState::ProbeBwCruise,
+            State::ProbeBwRefill => builder::BbrState::ProbeBwRefill,
+            State::ProbeBwUp => builder::BbrState::ProbeBwUp,
+            State::ProbeRtt => builder::BbrState::ProbeRtt,
+        }
+    }
+}
+
+#[event("congestion:slow_start_exited")]
+#[subject(endpoint)]
+/// A stream's congestion controller exited slow start
+struct CongestionSlowStartExited {
+    algorithm: CongestionControlAlgorithm,
+    cause: SlowStartExitCause,
+    congestion_window: u32,
+}
+
+#[event("congestion:pacing_rate_updated")]
+#[subject(endpoint)]
+/// A stream's pacing rate has been updated
+struct CongestionPacingRateUpdated {
+    algorithm: CongestionControlAlgorithm,
+    bytes_per_second: u64,
+    burst_size: u32,
+    pacing_gain: f32,
+}
+
+#[event("congestion:bbr_state_changed")]
+#[subject(endpoint)]
+/// The BBR state of a stream's congestion controller has changed
+struct CongestionBbrStateChanged {
+    state: BbrState,
 }
@@ -12,14 +12,305 @@ pub mod api {
     pub use traits::Subscriber;
     #[derive(Clone, Debug)]
     #[non_exhaustive]
-    #[doc = " Frame was sent"]
-    pub struct FrameSent {
-        pub packet_header: PacketHeader,
-        pub path_id: u64,
-        pub frame: Frame,
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
+    pub struct ConnectionMeta {
+        pub endpoint_type: EndpointType,
+        pub id: u64,
+        pub timestamp: crate::event::Timestamp,
     }
-    impl Event for FrameSent {
-        const NAME: &'static str = "transport:frame_sent";
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
+    pub struct EndpointMeta {
+        pub endpoint_type: EndpointType,
+        pub timestamp: crate::event::Timestamp,
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
+    pub struct ConnectionInfo {}
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
+    #[doc = " Application data was written to a stream"]
+    pub struct ApplicationWrite {
+        #[doc = " The number of bytes that the application tried to write"]
+        pub len: usize,
+    }
+    impl Event for ApplicationWrite {
+        const NAME: &'static str = "application:write";
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
+    #[doc = " The path secret for a peer was queued to be renewed in the background"]
+    pub struct PathSecretMapRenewalRequested<'a> {
+        pub peer_address: SocketAddress<'a>,
+        pub reason: PathSecretRenewalReason,
+    }
+    impl<'a> Event for PathSecretMapRenewalRequested<'a> {
+        const NAME: &'static str = "path_secret_map:renewal_requested";
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
+    #[doc = " The path secret for a peer was replaced with one from a newer handshake"]
+    pub struct PathSecretMapEntryRenewed<'a> {
+        pub peer_address: SocketAddress<'a>,
+        #[doc = " The amount of time the replaced entry was in the map"]
+        pub age: Duration,
+    }
+    impl<'a> Event for PathSecretMapEntryRenewed<'a> {
+        const NAME: &'static str = "path_secret_map:entry_renewed";
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
+    #[doc = " The path secret for a peer was removed from the map by the entry policy"]
+    pub struct PathSecretMapEntryEvicted<'a> {
+        pub peer_address: SocketAddress<'a>,
+        pub reason: PathSecretEvictionReason,
+        #[doc = " The amount of time the entry was in the map"]
+        pub age: Duration,
+    }
+    impl<'a> Event for PathSecretMapEntryEvicted<'a> {
+        const NAME: &'static str = "path_secret_map:entry_evicted";
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
+    #[doc = " A stream's congestion controller exited slow start"]
+    pub struct CongestionSlowStartExited {
+        pub algorithm: CongestionControlAlgorithm,
+        pub cause: SlowStartExitCause,
+        pub congestion_window: u32,
+    }
+    impl Event for CongestionSlowStartExited {
+        const NAME: &'static str = "congestion:slow_start_exited";
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
+    #[doc = " A stream's pacing rate has been updated"]
+    pub struct CongestionPacingRateUpdated {
+        pub algorithm: CongestionControlAlgorithm,
+        pub bytes_per_second: u64,
+        pub burst_size: u32,
+        pub pacing_gain: f32,
+    }
+    impl Event for CongestionPacingRateUpdated {
+        const NAME: &'static str = "congestion:pacing_rate_updated";
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
+    #[doc = " The BBR state of a stream's congestion controller has changed"]
+    pub struct CongestionBbrStateChanged {
+        pub state: BbrState,
+    }
+    impl Event for CongestionBbrStateChanged {
+        const NAME: &'static str = "congestion:bbr_state_changed";
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
+    #[doc = " A context from which the event is being emitted"]
+    #[doc = ""]
+    #[doc = " An event can occur in the context of an Endpoint or Connection"]
+    pub enum Subject {
+        #[non_exhaustive]
+        Endpoint {},
+        #[non_exhaustive]
+        #[doc = " This maps to an internal connection id, which is a stable identifier across CID changes."]
+        Connection { id: u64 },
+    }
+    #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
+    #[doc = " An endpoint may be either a Server or a Client"]
+    pub enum EndpointType {
+        #[non_exhaustive]
+        Server {},
+        #[non_exhaustive]
+        Client {},
+    }
+    #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
+    #[derive(Clone)]
+    pub enum SocketAddress<'a> {
+        #[non_exhaustive]
+        IpV4 { ip: &'a [u8; 4], port: u16 },
+        #[non_exhaustive]
+        IpV6 { ip: &'a [u8; 16], port: u16 },
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
+    #[doc = " The reason a path secret renewal was requested"]
+    pub enum PathSecretRenewalReason {
+        #[non_exhaustive]
+        #[doc = " The entry reached its randomized rehandshake time"]
+        Scheduled {},
+        #[non_exhaustive]
+        #[doc = " The entry is close to exhausting the configured maximum number of key uses"]
+        KeyUses {},
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
+    #[doc = " The reason a path secret was evicted from the map"]
+    pub enum PathSecretEvictionReason {
+        #[non_exhaustive]
+        #[doc = " The entry exceeded the configured maximum lifetime"]
+        LifetimeExpired {},
+        #[non_exhaustive]
+        #[doc = " The entry exhausted the configured maximum number of key uses"]
+        KeyUsesExhausted {},
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
+    #[doc = " The congestion control algorithm used by a stream"]
+    pub enum CongestionControlAlgorithm {
+        #[non_exhaustive]
+        Bbr {},
+        #[non_exhaustive]
+        Cubic {},
+        #[non_exhaustive]
+        Fixed {},
+        #[non_exhaustive]
+        Unlimited {},
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
+    #[doc = " The reason the slow start congestion controller state has been exited"]
+    pub enum SlowStartExitCause {
+        #[non_exhaustive]
+        #[doc = " A packet was determined lost"]
+        PacketLoss {},
+        #[non_exhaustive]
+        #[doc = " An Explicit Congestion Notification: Congestion Experienced marking was received"]
+        Ecn {},
+        #[non_exhaustive]
+        #[doc = " The round trip time estimate was updated"]
+        Rtt {},
+        #[non_exhaustive]
+        #[doc = " Slow Start exited due to a reason other than those above"]
+        Other {},
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
+    pub enum BbrState {
+        #[non_exhaustive]
+        Startup {},
+        #[non_exhaustive]
+        Drain {},
+        #[non_exhaustive]
+        ProbeBwDown {},
+        #[non_exhaustive]
+        ProbeBwCruise {},
+        #[non_exhaustive]
+        ProbeBwRefill {},
+        #[non_exhaustive]
+        ProbeBwUp {},
+        #[non_exhaustive]
+        ProbeRtt {},
+    }
+    impl IntoEvent<api::EndpointType> for s2n_quic_core::endpoint::Type {
+        #[inline]
+        fn into_event(self) -> api::EndpointType {
+            match self {
+                Self::Client => api::EndpointType::Client {},
+                Self::Server => api::EndpointType::Server {},
+            }
+        }
+    }
+    impl IntoEvent<builder::EndpointType> for s2n_quic_core::endpoint::Type {
+        #[inline]
+        fn into_event(self) -> builder::EndpointType {
+            match self {
+                Self::Client => builder::EndpointType::Client {},
+                Self::Server => builder::EndpointType::Server {},
+            }
+        }
+    }
+    impl<'a> core::fmt::Debug for SocketAddress<'a> {
+        fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+            core::fmt::Display::fmt(self, f)
+        }
+    }
+    impl<'a> core::fmt::Display for SocketAddress<'a> {
+        fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+            match self {
+                Self::IpV4 { ip, port } => {
+                    let addr = s2n_quic_core::inet::SocketAddressV4::new(**ip, *port);
+                    addr.fmt(f)
+                }
+                Self::IpV6 { ip, port } => {
+                    let addr = s2n_quic_core::inet::SocketAddressV6::new(**ip, *port);
+                    addr.fmt(f)
+                }
+            }
+        }
+    }
+    impl<'a> IntoEvent<builder::SocketAddress<'a>> for &'a s2n_quic_core::inet::SocketAddress {
+        #[inline]
+        fn into_event(self) -> builder::SocketAddress<'a> {
+            match self {
+                s2n_quic_core::inet::SocketAddress::IpV4(addr) => builder::SocketAddress::IpV4 {
+                    ip: addr.ip().as_bytes().try_into().unwrap(),
+                    port: addr.port(),
+                },
+                s2n_quic_core::inet::SocketAddress::IpV6(addr) => builder::SocketAddress::IpV6 {
+                    ip: addr.ip().as_bytes().try_into().unwrap(),
+                    port: addr.port(),
+                },
+            }
+        }
+    }
+    impl From<SocketAddress<'_>> for std::net::SocketAddr {
+        #[inline]
+        fn from(address: SocketAddress) -> Self {
+            use std::net;
+            match address {
+                SocketAddress::IpV4 { ip, port } => {
+                    let ip = net::IpAddr::V4(net::Ipv4Addr::from(*ip));
+                    Self::new(ip, port)
+                }
+                SocketAddress::IpV6 { ip, port } => {
+                    let ip = net::IpAddr::V6(net::Ipv6Addr::from(*ip));
+                    Self::new(ip, port)
+                }
+            }
+        }
+    }
+    impl IntoEvent<builder::SlowStartExitCause> for s2n_quic_core::event::builder::SlowStartExitCause {
+        #[inline]
+        fn into_event(self) -> builder::SlowStartExitCause {
+            use s2n_quic_core::event::builder::SlowStartExitCause as Cause;
+            match self {
+                Cause::PacketLoss => builder::SlowStartExitCause::PacketLoss,
+                Cause::Ecn => builder::SlowStartExitCause::Ecn,
+                Cause::Rtt => builder::SlowStartExitCause::Rtt,
+                Cause::Other => builder::SlowStartExitCause::Other,
+            }
+        }
+    }
+    impl IntoEvent<builder::BbrState> for s2n_quic_core::event::builder::BbrState {
+        #[inline]
+        fn into_event(self) -> builder::BbrState {
+            use s2n_quic_core::event::builder::BbrState as State;
+            match self {
+                State::Startup => builder::BbrState::Startup,
+                State::Drain => builder::BbrState::Drain,
+                State::ProbeBwDown => builder::BbrState::ProbeBwDown,
+                State::ProbeBwCruise => builder::BbrState::ProbeBwCruise,
+                State::ProbeBwRefill => builder::BbrState::ProbeBwRefill,
+                State::ProbeBwUp => builder::BbrState::ProbeBwUp,
+                State::ProbeRtt => builder::BbrState::ProbeRtt,
+            }
+        }
     }
 }
 #[cfg(feature = "event-tracing")]
@@ -56,43 +347,459 @@ pub mod tracing {
             tracing :: span ! (target : "s2n_quic_dc" , parent : parent , tracing :: Level :: DEBUG , "conn" , id = meta . id)
         }
         #[inline]
-        fn on_frame_sent(
+        fn on_application_write(
             &self,
             context: &Self::ConnectionContext,
             _meta: &api::ConnectionMeta,
-            event: &api::FrameSent,
+            event: &api::ApplicationWrite,
         ) {
             let id = context.id();
-            let api::FrameSent {
-                packet_header,
-                path_id,
-                frame,
+            let api::ApplicationWrite { len } = event;
+            tracing :: event ! (target : "application_write" , parent : id , tracing :: Level :: DEBUG , len = tracing :: field :: debug (len));
+        }
+        #[inline]
+        fn on_path_secret_map_renewal_requested(
+            &self,
+            meta: &api::EndpointMeta,
+            event: &api::PathSecretMapRenewalRequested,
+        ) {
+            let parent = match meta.endpoint_type {
+                api::EndpointType::Client {} => self.client.id(),
+                api::EndpointType::Server {} => self.server.id(),
+            };
+            let api::PathSecretMapRenewalRequested {
+                peer_address,
+                reason,
+            } = event;
+            tracing :: event ! (target : "path_secret_map_renewal_requested" , parent : parent , tracing :: Level :: DEBUG , peer_address = tracing :: field :: debug (peer_address) , reason = tracing :: field :: debug (reason));
+        }
+        #[inline]
+        fn on_path_secret_map_entry_renewed(
+            &self,
+            meta: &api::EndpointMeta,
+            event: &api::PathSecretMapEntryRenewed,
+        ) {
+            let parent = match meta.endpoint_type {
+                api::EndpointType::Client {} => self.client.id(),
+                api::EndpointType::Server {} => self.server.id(),
+            };
+            let api::PathSecretMapEntryRenewed { peer_address, age } = event;
+            tracing :: event ! (target : "path_secret_map_entry_renewed" , parent : parent , tracing :: Level :: DEBUG , peer_address = tracing :: field :: debug (peer_address) , age = tracing :: field :: debug (age));
+        }
+        #[inline]
+        fn on_path_secret_map_entry_evicted(
+            &self,
+            meta: &api::EndpointMeta,
+            event: &api::PathSecretMapEntryEvicted,
+        ) {
+            let parent = match meta.endpoint_type {
+                api::EndpointType::Client {} => self.client.id(),
+                api::EndpointType::Server {} => self.server.id(),
+            };
+            let api::PathSecretMapEntryEvicted {
+                peer_address,
+                reason,
+                age,
+            } = event;
+            tracing :: event ! (target : "path_secret_map_entry_evicted" , parent : parent , tracing :: Level :: DEBUG , peer_address = tracing :: field :: debug (peer_address) , reason = tracing :: field :: debug (reason) , age = tracing :: field :: debug (age));
+        }
+        #[inline]
+        fn on_congestion_slow_start_exited(
+            &self,
+            meta: &api::EndpointMeta,
+            event: &api::CongestionSlowStartExited,
+        ) {
+            let parent = match meta.endpoint_type {
+                api::EndpointType::Client {} => self.client.id(),
+                api::EndpointType::Server {} => self.server.id(),
+            };
+            let api::CongestionSlowStartExited {
+                algorithm,
+                cause,
+                congestion_window,
+            } = event;
+            tracing :: event ! (target : "congestion_slow_start_exited" , parent : parent , tracing :: Level :: DEBUG , algorithm = tracing :: field :: debug (algorithm) , cause = tracing :: field :: debug (cause) , congestion_window = tracing :: field :: debug (congestion_window));
+        }
+        #[inline]
+        fn on_congestion_pacing_rate_updated(
+            &self,
+            meta: &api::EndpointMeta,
+            event: &api::CongestionPacingRateUpdated,
+        ) {
+            let parent = match meta.endpoint_type {
+                api::EndpointType::Client {} => self.client.id(),
+                api::EndpointType::Server {} => self.server.id(),
+            };
+            let api::CongestionPacingRateUpdated {
+                algorithm,
+                bytes_per_second,
+                burst_size,
+                pacing_gain,
             } = event;
-            tracing :: event ! (target : "frame_sent" , parent : id , tracing :: Level :: DEBUG , packet_header = tracing :: field :: debug (packet_header) , path_id = tracing :: field :: debug (path_id) , frame = tracing :: field :: debug (frame));
+            tracing :: event ! (target : "congestion_pacing_rate_updated" , parent : parent , tracing :: Level :: DEBUG , algorithm = tracing :: field :: debug (algorithm) , bytes_per_second = tracing :: field :: debug (bytes_per_second) , burst_size = tracing :: field :: debug (burst_size) , pacing_gain = tracing :: field :: debug (pacing_gain));
+        }
+        #[inline]
+        fn on_congestion_bbr_state_changed(
+            &self,
+            meta: &api::EndpointMeta,
+            event: &api::CongestionBbrStateChanged,
+        ) {
+            let parent = match meta.endpoint_type {
+                api::EndpointType::Client {} => self.client.id(),
+                api::EndpointType::Server {} => self.server.id(),
+            };
+            let api::CongestionBbrStateChanged { state } = event;
+            tracing :: event ! (target : "congestion_bbr_state_changed" , parent : parent , tracing :: Level :: DEBUG , state = tracing :: field :: debug (state));
         }
     }
 }
 pub mod builder {
     use super::*;
     #[derive(Clone, Debug)]
-    #[doc = " Frame was sent"]
-    pub struct FrameSent {
-        pub packet_header: PacketHeader,
-        pub path_id: u64,
-        pub frame: Frame,
+    pub struct ConnectionMeta {
+        pub endpoint_type: s2n_quic_core::endpoint::Type,
+        pub id: u64,
+        pub timestamp: s2n_quic_core::time::Timestamp,
+    }
+    impl IntoEvent<api::ConnectionMeta> for ConnectionMeta {
+        #[inline]
+        fn into_event(self) -> api::ConnectionMeta {
+            let ConnectionMeta {
+                endpoint_type,
+                id,
+                timestamp,
+            } = self;
+            api::ConnectionMeta {
+                endpoint_type: endpoint_type.into_event(),
+                id: id.into_event(),
+                timestamp: timestamp.into_event(),
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
+    pub struct EndpointMeta {
+        pub endpoint_type: s2n_quic_core::endpoint::Type,
+        pub timestamp: s2n_quic_core::time::Timestamp,
+    }
+    impl IntoEvent<api::EndpointMeta> for EndpointMeta {
+        #[inline]
+        fn into_event(self) -> api::EndpointMeta {
+            let EndpointMeta {
+                endpoint_type,
+                timestamp,
+            } = self;
+            api::EndpointMeta {
+                endpoint_type: endpoint_type.into_event(),
+                timestamp: timestamp.into_event(),
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
+    pub struct ConnectionInfo {}
+    impl IntoEvent<api::ConnectionInfo> for ConnectionInfo {
+        #[inline]
+        fn into_event(self) -> api::ConnectionInfo {
+            let ConnectionInfo {} = self;
+            api::ConnectionInfo {}
+        }
+    }
+    #[derive(Clone, Debug)]
+    #[doc = " Application data was written to a stream"]
+    pub struct ApplicationWrite {
+        #[doc = " The number of bytes that the application tried to write"]
+        pub len: usize,
+    }
+    impl IntoEvent<api::ApplicationWrite> for ApplicationWrite {
+        #[inline]
+        fn into_event(self) -> api::ApplicationWrite {
+            let ApplicationWrite { len } = self;
+            api::ApplicationWrite {
+                len: len.into_event(),
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
+    #[doc = " The path secret for a peer was queued to be renewed in the background"]
+    pub struct PathSecretMapRenewalRequested<'a> {
+        pub peer_address: SocketAddress<'a>,
+        pub reason: PathSecretRenewalReason,
     }
-    impl IntoEvent<api::FrameSent> for FrameSent {
+    impl<'a> IntoEvent<api::PathSecretMapRenewalRequested<'a>> for PathSecretMapRenewalRequested<'a> {
         #[inline]
-        fn into_event(self) -> api::FrameSent {
-            let FrameSent {
-                packet_header,
-                path_id,
-                frame,
+        fn into_event(self) -> api::PathSecretMapRenewalRequested<'a> {
+            let PathSecretMapRenewalRequested {
+                peer_address,
+                reason,
             } = self;
-            api::FrameSent {
-                packet_header: packet_header.into_event(),
-                path_id: path_id.into_event(),
-                frame: frame.into_event(),
+            api::PathSecretMapRenewalRequested {
+                peer_address: peer_address.into_event(),
+                reason: reason.into_event(),
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
+    #[doc = " The path secret for a peer was replaced with one from a newer handshake"]
+    pub struct PathSecretMapEntryRenewed<'a> {
+        pub peer_address: SocketAddress<'a>,
+        #[doc = " The amount of time the replaced entry was in the map"]
+        pub age: Duration,
+    }
+    impl<'a> IntoEvent<api::PathSecretMapEntryRenewed<'a>> for PathSecretMapEntryRenewed<'a> {
+        #[inline]
+        fn into_event(self) -> api::PathSecretMapEntryRenewed<'a> {
+            let PathSecretMapEntryRenewed { peer_address, age } = self;
+            api::PathSecretMapEntryRenewed {
+                peer_address: peer_address.into_event(),
+                age: age.into_event(),
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
+    #[doc = " The path secret for a peer was removed from the map by the entry policy"]
+    pub struct PathSecretMapEntryEvicted<'a> {
+        pub peer_address: SocketAddress<'a>,
+        pub reason: PathSecretEvictionReason,
+        #[doc = " The amount of time the entry was in the map"]
+        pub age: Duration,
+    }
+    impl<'a> IntoEvent<api::PathSecretMapEntryEvicted<'a>> for PathSecretMapEntryEvicted<'a> {
+        #[inline]
+        fn into_event(self) -> api::PathSecretMapEntryEvicted<'a> {
+            let PathSecretMapEntryEvicted {
+                peer_address,
+                reason,
+                age,
+            } = self;
+            api::PathSecretMapEntryEvicted {
+                peer_address: peer_address.into_event(),
+                reason: reason.into_event(),
+                age: age.into_event(),
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
+    #[doc = " A stream's congestion controller exited slow start"]
+    pub struct CongestionSlowStartExited {
+        pub algorithm: CongestionControlAlgorithm,
+        pub cause: SlowStartExitCause,
+        pub congestion_window: u32,
+    }
+    impl IntoEvent<api::CongestionSlowStartExited> for CongestionSlowStartExited {
+        #[inline]
+        fn into_event(self) -> api::CongestionSlowStartExited {
+            let CongestionSlowStartExited {
+                algorithm,
+                cause,
+                congestion_window,
+            } = self;
+            api::CongestionSlowStartExited {
+                algorithm: algorithm.into_event(),
+                cause: cause.into_event(),
+                congestion_window: congestion_window.into_event(),
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
+    #[doc = " A stream's pacing rate has been updated"]
+    pub struct CongestionPacingRateUpdated {
+        pub algorithm: CongestionControlAlgorithm,
+        pub bytes_per_second: u64,
+        pub burst_size: u32,
+        pub pacing_gain: f32,
+    }
+    impl IntoEvent<api::CongestionPacingRateUpdated> for CongestionPacingRateUpdated {
+        #[inline]
+        fn into_event(self) -> api::CongestionPacingRateUpdated {
+            let CongestionPacingRateUpdated {
+                algorithm,
+                bytes_per_second,
+                burst_size,
+                pacing_gain,
+            } = self;
+            api::CongestionPacingRateUpdated {
+                algorithm: algorithm.into_event(),
+                bytes_per_second: bytes_per_second.into_event(),
+                burst_size: burst_size.into_event(),
+                pacing_gain: pacing_gain.into_event(),
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
+    #[doc = " The BBR state of a stream's congestion controller has changed"]
+    pub struct CongestionBbrStateChanged {
+        pub state: BbrState,
+    }
+    impl IntoEvent<api::CongestionBbrStateChanged> for CongestionBbrStateChanged {
+        #[inline]
+        fn into_event(self) -> api::CongestionBbrStateChanged {
+            let CongestionBbrStateChanged { state } = self;
+            api::CongestionBbrStateChanged {
+                state: state.into_event(),
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
+    #[doc = " A context from which the event is being emitted"]
+    #[doc = ""]
+    #[doc = " An event can occur in the context of an Endpoint or Connection"]
+    pub enum Subject {
+        Endpoint,
+        #[doc = " This maps to an internal connection id, which is a stable identifier across CID changes."]
+        Connection {
+            id: u64,
+        },
+    }
+    impl IntoEvent<api::Subject> for Subject {
+        #[inline]
+        fn into_event(self) -> api::Subject {
+            use api::Subject::*;
+            match self {
+                Self::Endpoint => Endpoint {},
+                Self::Connection { id } => Connection {
+                    id: id.into_event(),
+                },
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
+    #[doc = " An endpoint may be either a Server or a Client"]
+    pub enum EndpointType {
+        Server,
+        Client,
+    }
+    impl IntoEvent<api::EndpointType> for EndpointType {
+        #[inline]
+        fn into_event(self) -> api::EndpointType {
+            use api::EndpointType::*;
+            match self {
+                Self::Server => Server {},
+                Self::Client => Client {},
+            }
+        }
+    }
+    #[derive(Copy, Clone, Debug)]
+    pub enum SocketAddress<'a> {
+        IpV4 { ip: &'a [u8; 4], port: u16 },
+        IpV6 { ip: &'a [u8; 16], port: u16 },
+    }
+    impl<'a> IntoEvent<api::SocketAddress<'a>> for SocketAddress<'a> {
+        #[inline]
+        fn into_event(self) -> api::SocketAddress<'a> {
+            use api::SocketAddress::*;
+            match self {
+                Self::IpV4 { ip, port } => IpV4 {
+                    ip: ip.into_event(),
+                    port: port.into_event(),
+                },
+                Self::IpV6 { ip, port } => IpV6 {
+                    ip: ip.into_event(),
+                    port: port.into_event(),
+                },
+            }
+        }
+    }
+    #[derive(Copy, Clone, Debug)]
+    #[doc = " The reason a path secret renewal was requested"]
+    pub enum PathSecretRenewalReason {
+        #[doc = " The entry reached its randomized rehandshake time"]
+        Scheduled,
+        #[doc = " The entry is close to exhausting the configured maximum number of key uses"]
+        KeyUses,
+    }
+    impl IntoEvent<api::PathSecretRenewalReason> for PathSecretRenewalReason {
+        #[inline]
+        fn into_event(self) -> api::PathSecretRenewalReason {
+            use api::PathSecretRenewalReason::*;
+            match self {
+                Self::Scheduled => Scheduled {},
+                Self::KeyUses => KeyUses {},
+            }
+        }
+    }
+    #[derive(Copy, Clone, Debug)]
+    #[doc = " The reason a path secret was evicted from the map"]
+    pub enum PathSecretEvictionReason {
+        #[doc = " The entry exceeded the configured maximum lifetime"]
+        LifetimeExpired,
+        #[doc = " The entry exhausted the configured maximum number of key uses"]
+        KeyUsesExhausted,
+    }
+    impl IntoEvent<api::PathSecretEvictionReason> for PathSecretEvictionReason {
+        #[inline]
+        fn into_event(self) -> api::PathSecretEvictionReason {
+            use api::PathSecretEvictionReason::*;
+            match self {
+                Self::LifetimeExpired => LifetimeExpired {},
+                Self::KeyUsesExhausted => KeyUsesExhausted {},
+            }
+        }
+    }
+    #[derive(Copy, Clone, Debug)]
+    #[doc = " The congestion control algorithm used by a stream"]
+    pub enum CongestionControlAlgorithm {
+        Bbr,
+        Cubic,
+        Fixed,
+        Unlimited,
+    }
+    impl IntoEvent<api::CongestionControlAlgorithm> for CongestionControlAlgorithm {
+        #[inline]
+        fn into_event(self) -> api::CongestionControlAlgorithm {
+            use api::CongestionControlAlgorithm::*;
+            match self {
+                Self::Bbr => Bbr {},
+                Self::Cubic => Cubic {},
+                Self::Fixed => Fixed {},
+                Self::Unlimited => Unlimited {},
+            }
+        }
+    }
+    #[derive(Copy, Clone, Debug)]
+    #[doc = " The reason the slow start congestion controller state has been exited"]
+    pub enum SlowStartExitCause {
+        #[doc = " A packet was determined lost"]
+        PacketLoss,
+        #[doc = " An Explicit Congestion Notification: Congestion Experienced marking was received"]
+        Ecn,
+        #[doc = " The round trip time estimate was updated"]
+        Rtt,
+        #[doc = " Slow Start exited due to a reason other than those above"]
+        Other,
+    }
+    impl IntoEvent<api::SlowStartExitCause> for SlowStartExitCause {
+        #[inline]
+        fn into_event(self) -> api::SlowStartExitCause {
+            use api::SlowStartExitCause::*;
+            match self {
+                Self::PacketLoss => PacketLoss {},
+                Self::Ecn => Ecn {},
+                Self::Rtt => Rtt {},
+                Self::Other => Other {},
+            }
+        }
+    }
+    #[derive(Copy, Clone, Debug)]
+    pub enum BbrState {
+        Startup,
+        Drain,
+        ProbeBwDown,
+        ProbeBwCruise,
+        ProbeBwRefill,
+        ProbeBwUp,
+        ProbeRtt,
+    }
+    impl IntoEvent<api::BbrState> for BbrState {
+        #[inline]
+        fn into_event(self) -> api::BbrState {
+            use api::BbrState::*;
+            match self {
+                Self::Startup => Startup {},
+                Self::Drain => Drain {},
+                Self::ProbeBwDown => ProbeBwDown {},
+                Self::ProbeBwCruise => ProbeBwCruise {},
+                Self::ProbeBwRefill => ProbeBwRefill {},
+                Self::ProbeBwUp => ProbeBwUp {},
+                Self::ProbeRtt => ProbeRtt {},
             }
         }
     }
@@ -100,11 +807,11 @@ pub mod builder {
 pub use traits::*;
 mod traits {
     use super::*;
-    use crate::query;
     use api::*;
     use core::fmt;
+    use s2n_quic_core::query;
     #[doc = r" Provides metadata related to an event"]
-    pub trait Meta: fmt::Debug {
+    pub trait Meta: fmt::Debug + crate::event::Serialize {
         #[doc = r" Returns whether the local endpoint is a Client or Server"]
         fn endpoint_type(&self) -> &EndpointType;
         #[doc = r" A context from which the event is being emitted"]
@@ -187,18 +894,78 @@ mod traits {
             meta: &ConnectionMeta,
             info: &ConnectionInfo,
         ) -> Self::ConnectionContext;
-        #[doc = "Called when the `FrameSent` event is triggered"]
+        #[doc = "Called when the `ApplicationWrite` event is triggered"]
         #[inline]
-        fn on_frame_sent(
+        fn on_application_write(
             &self,
             context: &Self::ConnectionContext,
             meta: &ConnectionMeta,
-            event: &FrameSent,
+            event: &ApplicationWrite,
         ) {
             let _ = context;
             let _ = meta;
             let _ = event;
         }
+        #[doc = "Called when the `PathSecretMapRenewalRequested` event is triggered"]
+        #[inline]
+        fn on_path_secret_map_renewal_requested(
+            &self,
+            meta: &EndpointMeta,
+            event: &PathSecretMapRenewalRequested,
+        ) {
+            let _ = meta;
+            let _ = event;
+        }
+        #[doc = "Called when the `PathSecretMapEntryRenewed` event is triggered"]
+        #[inline]
+        fn on_path_secret_map_entry_renewed(
+            &self,
+            meta: &EndpointMeta,
+            event: &PathSecretMapEntryRenewed,
+        ) {
+            let _ = meta;
+            let _ = event;
+        }
+        #[doc = "Called when the `PathSecretMapEntryEvicted` event is triggered"]
+        #[inline]
+        fn on_path_secret_map_entry_evicted(
+            &self,
+            meta: &EndpointMeta,
+            event: &PathSecretMapEntryEvicted,
+        ) {
+            let _ = meta;
+            let _ = event;
+        }
+        #[doc = "Called when the `CongestionSlowStartExited` event is triggered"]
+        #[inline]
+        fn on_congestion_slow_start_exited(
+            &self,
+            meta: &EndpointMeta,
+            event: &CongestionSlowStartExited,
+        ) {
+            let _ = meta;
+            let _ = event;
+        }
+        #[doc = "Called when the `CongestionPacingRateUpdated` event is triggered"]
+        #[inline]
+        fn on_congestion_pacing_rate_updated(
+            &self,
+            meta: &EndpointMeta,
+            event: &CongestionPacingRateUpdated,
+        ) {
+            let _ = meta;
+            let _ = event;
+        }
+        #[doc = "Called when the `CongestionBbrStateChanged` event is triggered"]
+        #[inline]
+        fn on_congestion_bbr_state_changed(
+            &self,
+            meta: &EndpointMeta,
+            event: &CongestionBbrStateChanged,
+        ) {
+            let _ = meta;
+            let _ = event;
+        }
         #[doc = r" Called for each event that relates to the endpoint and all connections"]
         #[inline]
         fn on_event<M: Meta, E: Event>(&self, meta: &M, event: &E) {
@@ -246,14 +1013,68 @@ mod traits {
             )
         }
         #[inline]
-        fn on_frame_sent(
+        fn on_application_write(
             &self,
             context: &Self::ConnectionContext,
             meta: &ConnectionMeta,
-            event: &FrameSent,
+            event: &ApplicationWrite,
+        ) {
+            (self.0).on_application_write(&context.0, meta, event);
+            (self.1).on_application_write(&context.1, meta, event);
+        }
+        #[inline]
+        fn on_path_secret_map_renewal_requested(
+            &self,
+            meta: &EndpointMeta,
+            event: &PathSecretMapRenewalRequested,
         ) {
-            (self.0).on_frame_sent(&context.0, meta, event);
-            (self.1).on_frame_sent(&context.1, meta, event);
+            (self.0).on_path_secret_map_renewal_requested(meta, event);
+            (self.1).on_path_secret_map_renewal_requested(meta, event);
+        }
+        #[inline]
+        fn on_path_secret_map_entry_renewed(
+            &self,
+            meta: &EndpointMeta,
+            event: &PathSecretMapEntryRenewed,
+        ) {
+            (self.0).on_path_secret_map_entry_renewed(meta, event);
+            (self.1).on_path_secret_map_entry_renewed(meta, event);
+        }
+        #[inline]
+        fn on_path_secret_map_entry_evicted(
+            &self,
+            meta: &EndpointMeta,
+            event: &PathSecretMapEntryEvicted,
+        ) {
+            (self.0).on_path_secret_map_entry_evicted(meta, event);
+            (self.1).on_path_secret_map_entry_evicted(meta, event);
+        }
+        #[inline]
+        fn on_congestion_slow_start_exited(
+            &self,
+            meta: &EndpointMeta,
+            event: &CongestionSlowStartExited,
+        ) {
+            (self.0).on_congestion_slow_start_exited(meta, event);
+            (self.1).on_congestion_slow_start_exited(meta, event);
+        }
+        #[inline]
+        fn on_congestion_pacing_rate_updated(
+            &self,
+            meta: &EndpointMeta,
+            event: &CongestionPacingRateUpdated,
+        ) {
+            (self.0).on_congestion_pacing_rate_updated(meta, event);
+            (self.1).on_congestion_pacing_rate_updated(meta, event);
+        }
+        #[inline]
+        fn on_congestion_bbr_state_changed(
+            &self,
+            meta: &EndpointMeta,
+            event: &CongestionBbrStateChanged,
+        ) {
+            (self.0).on_congestion_bbr_state_changed(meta, event);
+            (self.1).on_congestion_bbr_state_changed(meta, event);
         }
         #[inline]
         fn on_event<M: Meta, E: Event>(&self, meta: &M, event: &E) {
@@ -282,6 +1103,21 @@ mod traits {
         }
     }
     pub trait EndpointPublisher {
+        #[doc = "Publishes a `PathSecretMapRenewalRequested` event to the publisher's subscriber"]
+        fn on_path_secret_map_renewal_requested(
+            &self,
+            event: builder::PathSecretMapRenewalRequested,
+        );
+        #[doc = "Publishes a `PathSecretMapEntryRenewed` event to the publisher's subscriber"]
+        fn on_path_secret_map_entry_renewed(&self, event: builder::PathSecretMapEntryRenewed);
+        #[doc = "Publishes a `PathSecretMapEntryEvicted` event to the publisher's subscriber"]
+        fn on_path_secret_map_entry_evicted(&self, event: builder::PathSecretMapEntryEvicted);
+        #[doc = "Publishes a `CongestionSlowStartExited` event to the publisher's subscriber"]
+        fn on_congestion_slow_start_exited(&self, event: builder::CongestionSlowStartExited);
+        #[doc = "Publishes a `CongestionPacingRateUpdated` event to the publisher's subscriber"]
+        fn on_congestion_pacing_rate_updated(&self, event: builder::CongestionPacingRateUpdated);
+        #[doc = "Publishes a `CongestionBbrStateChanged` event to the publisher's subscriber"]
+        fn on_congestion_bbr_state_changed(&self, event: builder::CongestionBbrStateChanged);
         #[doc = r" Returns the QUIC version, if any"]
         fn quic_version(&self) -> Option<u32>;
     }
@@ -300,27 +1136,72 @@ mod traits {
     }
     impl<'a, Sub: Subscriber> EndpointPublisherSubscriber<'a, Sub> {
         #[inline]
-        pub fn new(
-            meta: builder::EndpointMeta,
-            quic_version: Option<u32>,
-            subscriber: &'a Sub,
-        ) -> Self {
-            Self {
-                meta: meta.into_event(),
-                quic_version,
-                subscriber,
-            }
+        pub fn new(
+            meta: builder::EndpointMeta,
+            quic_version: Option<u32>,
+            subscriber: &'a Sub,
+        ) -> Self {
+            Self {
+                meta: meta.into_event(),
+                quic_version,
+                subscriber,
+            }
+        }
+    }
+    impl<'a, Sub: Subscriber> EndpointPublisher for EndpointPublisherSubscriber<'a, Sub> {
+        #[inline]
+        fn on_path_secret_map_renewal_requested(
+            &self,
+            event: builder::PathSecretMapRenewalRequested,
+        ) {
+            let event = event.into_event();
+            self.subscriber
+                .on_path_secret_map_renewal_requested(&self.meta, &event);
+            self.subscriber.on_event(&self.meta, &event);
+        }
+        #[inline]
+        fn on_path_secret_map_entry_renewed(&self, event: builder::PathSecretMapEntryRenewed) {
+            let event = event.into_event();
+            self.subscriber
+                .on_path_secret_map_entry_renewed(&self.meta, &event);
+            self.subscriber.on_event(&self.meta, &event);
+        }
+        #[inline]
+        fn on_path_secret_map_entry_evicted(&self, event: builder::PathSecretMapEntryEvicted) {
+            let event = event.into_event();
+            self.subscriber
+                .on_path_secret_map_entry_evicted(&self.meta, &event);
+            self.subscriber.on_event(&self.meta, &event);
+        }
+        #[inline]
+        fn on_congestion_slow_start_exited(&self, event: builder::CongestionSlowStartExited) {
+            let event = event.into_event();
+            self.subscriber
+                .on_congestion_slow_start_exited(&self.meta, &event);
+            self.subscriber.on_event(&self.meta, &event);
+        }
+        #[inline]
+        fn on_congestion_pacing_rate_updated(&self, event: builder::CongestionPacingRateUpdated) {
+            let event = event.into_event();
+            self.subscriber
+                .on_congestion_pacing_rate_updated(&self.meta, &event);
+            self.subscriber.on_event(&self.meta, &event);
+        }
+        #[inline]
+        fn on_congestion_bbr_state_changed(&self, event: builder::CongestionBbrStateChanged) {
+            let event = event.into_event();
+            self.subscriber
+                .on_congestion_bbr_state_changed(&self.meta, &event);
+            self.subscriber.on_event(&self.meta, &event);
         }
-    }
-    impl<'a, Sub: Subscriber> EndpointPublisher for EndpointPublisherSubscriber<'a, Sub> {
         #[inline]
         fn quic_version(&self) -> Option<u32> {
             self.quic_version
         }
     }
     pub trait ConnectionPublisher {
-        #[doc = "Publishes a `FrameSent` event to the publisher's subscriber"]
-        fn on_frame_sent(&self, event: builder::FrameSent);
+        #[doc = "Publishes a `ApplicationWrite` event to the publisher's subscriber"]
+        fn on_application_write(&self, event: builder::ApplicationWrite);
         #[doc = r" Returns the QUIC version negotiated for the current connection, if any"]
         fn quic_version(&self) -> u32;
         #[doc = r" Returns the [`Subject`] for the current publisher"]
@@ -358,10 +1239,10 @@ mod traits {
     }
     impl<'a, Sub: Subscriber> ConnectionPublisher for ConnectionPublisherSubscriber<'a, Sub> {
         #[inline]
-        fn on_frame_sent(&self, event: builder::FrameSent) {
+        fn on_application_write(&self, event: builder::ApplicationWrite) {
             let event = event.into_event();
             self.subscriber
-                .on_frame_sent(self.context, &self.meta, &event);
+                .on_application_write(self.context, &self.meta, &event);
             self.subscriber
                 .on_connection_event(self.context, &self.meta, &event);
             self.subscriber.on_event(&self.meta, &event);
@@ -397,7 +1278,7 @@ pub mod metrics {
     }
     pub struct Context<R: Recorder> {
         recorder: R,
-        frame_sent: AtomicU32,
+        application_write: AtomicU32,
     }
     impl<S: super::Subscriber> super::Subscriber for Subscriber<S>
     where
@@ -411,25 +1292,485 @@ pub mod metrics {
         ) -> Self::ConnectionContext {
             Context {
                 recorder: self.subscriber.create_connection_context(meta, info),
-                frame_sent: AtomicU32::new(0),
+                application_write: AtomicU32::new(0),
             }
         }
         #[inline]
-        fn on_frame_sent(
+        fn on_application_write(
             &self,
             context: &Self::ConnectionContext,
             meta: &api::ConnectionMeta,
-            event: &api::FrameSent,
+            event: &api::ApplicationWrite,
         ) {
-            context.frame_sent.fetch_add(1, Ordering::Relaxed);
+            context.application_write.fetch_add(1, Ordering::Relaxed);
             self.subscriber
-                .on_frame_sent(&mut context.recorder, meta, event);
+                .on_application_write(&context.recorder, meta, event);
         }
     }
     impl<R: Recorder> Drop for Context<R> {
         fn drop(&mut self) {
-            self.recorder
-                .increment_counter("frame_sent", self.frame_sent.load(Ordering::Relaxed) as _);
+            self.recorder.increment_counter(
+                "application_write",
+                self.application_write.load(Ordering::Relaxed) as _,
+            );
+        }
+    }
+}
+pub mod sample {
+    #![doc = r" Forwards events to a subscriber for a sample of connections"]
+    #![doc = r""]
+    #![doc = r" The sampling decision is made once, when the connection context is created, so"]
+    #![doc = r" a subscriber either sees all of a connection's events or none of them."]
+    #![doc = r" Endpoint events are always forwarded."]
+    use super::*;
+    use s2n_quic_core::query;
+    #[doc = r" Decides if a connection's events should be forwarded to the subscriber"]
+    pub trait Sampler: 'static + Send + Sync {
+        fn sample(&self, meta: &api::ConnectionMeta, info: &api::ConnectionInfo) -> bool;
+    }
+    impl<F> Sampler for F
+    where
+        F: 'static + Send + Sync + Fn(&api::ConnectionMeta, &api::ConnectionInfo) -> bool,
+    {
+        #[inline]
+        fn sample(&self, meta: &api::ConnectionMeta, info: &api::ConnectionInfo) -> bool {
+            (self)(meta, info)
+        }
+    }
+    #[doc = r" Samples one in every `n` connections, based on the connection id"]
+    #[derive(Clone, Copy, Debug)]
+    pub struct OneIn(u64);
+    impl OneIn {
+        #[doc = r" Creates a sampler which samples one in every `n` connections"]
+        #[doc = r""]
+        #[doc = r" A value of `0` disables sampling of all connections."]
+        pub fn new(n: u64) -> Self {
+            Self(n)
+        }
+    }
+    impl Sampler for OneIn {
+        #[inline]
+        fn sample(&self, meta: &api::ConnectionMeta, _info: &api::ConnectionInfo) -> bool {
+            self.0 != 0 && meta.id % self.0 == 0
+        }
+    }
+    #[derive(Clone, Debug)]
+    pub struct Subscriber<S: super::Subscriber, F: Sampler> {
+        subscriber: S,
+        sampler: F,
+    }
+    impl<S: super::Subscriber, F: Sampler> Subscriber<S, F> {
+        #[doc = r" Forwards events for the connections accepted by the `sampler`"]
+        pub fn new(subscriber: S, sampler: F) -> Self {
+            Self {
+                subscriber,
+                sampler,
+            }
+        }
+    }
+    impl<S: super::Subscriber> Subscriber<S, OneIn> {
+        #[doc = r" Forwards events for one in every `n` connections"]
+        pub fn one_in(subscriber: S, n: u64) -> Self {
+            Self::new(subscriber, OneIn::new(n))
+        }
+    }
+    impl<S: super::Subscriber, F: Sampler> super::Subscriber for Subscriber<S, F> {
+        type ConnectionContext = Option<S::ConnectionContext>;
+        #[inline]
+        fn create_connection_context(
+            &self,
+            meta: &api::ConnectionMeta,
+            info: &api::ConnectionInfo,
+        ) -> Self::ConnectionContext {
+            if self.sampler.sample(meta, info) {
+                Some(self.subscriber.create_connection_context(meta, info))
+            } else {
+                None
+            }
+        }
+        #[inline]
+        fn query(
+            context: &Self::ConnectionContext,
+            query: &mut dyn query::Query,
+        ) -> query::ControlFlow {
+            query.execute(context).and_then(|| match context {
+                Some(context) => S::query(context, query),
+                None => query::ControlFlow::Continue,
+            })
+        }
+        #[inline]
+        fn on_application_write(
+            &self,
+            context: &Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::ApplicationWrite,
+        ) {
+            if let Some(context) = context {
+                self.subscriber.on_application_write(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_path_secret_map_renewal_requested(
+            &self,
+            meta: &api::EndpointMeta,
+            event: &api::PathSecretMapRenewalRequested,
+        ) {
+            self.subscriber
+                .on_path_secret_map_renewal_requested(meta, event);
+            self.subscriber.on_event(meta, event);
+        }
+        #[inline]
+        fn on_path_secret_map_entry_renewed(
+            &self,
+            meta: &api::EndpointMeta,
+            event: &api::PathSecretMapEntryRenewed,
+        ) {
+            self.subscriber
+                .on_path_secret_map_entry_renewed(meta, event);
+            self.subscriber.on_event(meta, event);
+        }
+        #[inline]
+        fn on_path_secret_map_entry_evicted(
+            &self,
+            meta: &api::EndpointMeta,
+            event: &api::PathSecretMapEntryEvicted,
+        ) {
+            self.subscriber
+                .on_path_secret_map_entry_evicted(meta, event);
+            self.subscriber.on_event(meta, event);
+        }
+        #[inline]
+        fn on_congestion_slow_start_exited(
+            &self,
+            meta: &api::EndpointMeta,
+            event: &api::CongestionSlowStartExited,
+        ) {
+            self.subscriber.on_congestion_slow_start_exited(meta, event);
+            self.subscriber.on_event(meta, event);
+        }
+        #[inline]
+        fn on_congestion_pacing_rate_updated(
+            &self,
+            meta: &api::EndpointMeta,
+            event: &api::CongestionPacingRateUpdated,
+        ) {
+            self.subscriber
+                .on_congestion_pacing_rate_updated(meta, event);
+            self.subscriber.on_event(meta, event);
+        }
+        #[inline]
+        fn on_congestion_bbr_state_changed(
+            &self,
+            meta: &api::EndpointMeta,
+            event: &api::CongestionBbrStateChanged,
+        ) {
+            self.subscriber.on_congestion_bbr_state_changed(meta, event);
+            self.subscriber.on_event(meta, event);
+        }
+    }
+}
+pub mod filter {
+    #![doc = r" Forwards events to a subscriber based on the event type"]
+    #![doc = r""]
+    #![doc = r" Events are matched by their [`Event::NAME`](crate::event::Event::NAME), e.g."]
+    #![doc = r#" `"transport:packet_sent"`."#]
+    use super::*;
+    use s2n_quic_core::query;
+    #[doc = r" The names of all of the events, in the order of their filter index"]
+    const NAMES: [&str; 7usize] = [
+        "application:write",
+        "path_secret_map:renewal_requested",
+        "path_secret_map:entry_renewed",
+        "path_secret_map:entry_evicted",
+        "congestion:slow_start_exited",
+        "congestion:pacing_rate_updated",
+        "congestion:bbr_state_changed",
+    ];
+    #[derive(Clone, Debug)]
+    pub struct Subscriber<S: super::Subscriber> {
+        subscriber: S,
+        enabled: [bool; 7usize],
+    }
+    impl<S: super::Subscriber> Subscriber<S> {
+        #[doc = r" Forwards the events for which `predicate` returns `true`"]
+        pub fn new<F: FnMut(&'static str) -> bool>(subscriber: S, mut predicate: F) -> Self {
+            let enabled = core::array::from_fn(|index| predicate(NAMES[index]));
+            Self {
+                subscriber,
+                enabled,
+            }
+        }
+        #[doc = r" Only forwards the events with the given names"]
+        pub fn allow(subscriber: S, names: &[&str]) -> Self {
+            Self::new(subscriber, |name| names.contains(&name))
+        }
+        #[doc = r" Forwards all of the events except for the ones with the given names"]
+        pub fn deny(subscriber: S, names: &[&str]) -> Self {
+            Self::new(subscriber, |name| !names.contains(&name))
+        }
+    }
+    impl<S: super::Subscriber> super::Subscriber for Subscriber<S> {
+        type ConnectionContext = S::ConnectionContext;
+        #[inline]
+        fn create_connection_context(
+            &self,
+            meta: &api::ConnectionMeta,
+            info: &api::ConnectionInfo,
+        ) -> Self::ConnectionContext {
+            self.subscriber.create_connection_context(meta, info)
+        }
+        #[inline]
+        fn query(
+            context: &Self::ConnectionContext,
+            query: &mut dyn query::Query,
+        ) -> query::ControlFlow {
+            S::query(context, query)
+        }
+        #[inline]
+        fn on_application_write(
+            &self,
+            context: &Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::ApplicationWrite,
+        ) {
+            if self.enabled[0] {
+                self.subscriber.on_application_write(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_path_secret_map_renewal_requested(
+            &self,
+            meta: &api::EndpointMeta,
+            event: &api::PathSecretMapRenewalRequested,
+        ) {
+            if self.enabled[1] {
+                self.subscriber
+                    .on_path_secret_map_renewal_requested(meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_path_secret_map_entry_renewed(
+            &self,
+            meta: &api::EndpointMeta,
+            event: &api::PathSecretMapEntryRenewed,
+        ) {
+            if self.enabled[2] {
+                self.subscriber
+                    .on_path_secret_map_entry_renewed(meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_path_secret_map_entry_evicted(
+            &self,
+            meta: &api::EndpointMeta,
+            event: &api::PathSecretMapEntryEvicted,
+        ) {
+            if self.enabled[3] {
+                self.subscriber
+                    .on_path_secret_map_entry_evicted(meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_congestion_slow_start_exited(
+            &self,
+            meta: &api::EndpointMeta,
+            event: &api::CongestionSlowStartExited,
+        ) {
+            if self.enabled[4] {
+                self.subscriber.on_congestion_slow_start_exited(meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_congestion_pacing_rate_updated(
+            &self,
+            meta: &api::EndpointMeta,
+            event: &api::CongestionPacingRateUpdated,
+        ) {
+            if self.enabled[5] {
+                self.subscriber
+                    .on_congestion_pacing_rate_updated(meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_congestion_bbr_state_changed(
+            &self,
+            meta: &api::EndpointMeta,
+            event: &api::CongestionBbrStateChanged,
+        ) {
+            if self.enabled[6] {
+                self.subscriber.on_congestion_bbr_state_changed(meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+    }
+}
+pub mod rate_limit {
+    #![doc = r" Limits the rate at which events are forwarded to a subscriber"]
+    #![doc = r""]
+    #![doc = r" Each event type is limited separately, so frequent events such as"]
+    #![doc = r" `PacketSent` don't crowd out infrequent ones. The limits are shared by all of"]
+    #![doc = r" the connections on the endpoint. Events over the limit are dropped."]
+    use super::*;
+    use core::{
+        sync::atomic::{AtomicU32, Ordering},
+        time::Duration,
+    };
+    use s2n_quic_core::query;
+    #[derive(Debug)]
+    pub struct Subscriber<S: super::Subscriber> {
+        subscriber: S,
+        limit: u32,
+        interval: u64,
+        windows: [Window; 7usize],
+    }
+    impl<S: super::Subscriber> Subscriber<S> {
+        #[doc = r" Forwards at most `limit` events of each type per `interval`"]
+        pub fn new(subscriber: S, limit: u32, interval: Duration) -> Self {
+            Self {
+                subscriber,
+                limit,
+                interval: interval.as_nanos().try_into().unwrap_or(u64::MAX),
+                windows: core::array::from_fn(|_| Window::default()),
+            }
+        }
+        #[inline]
+        fn try_acquire<M: Meta>(&self, index: usize, meta: &M) -> bool {
+            let now = meta.timestamp().duration_since_start().as_nanos() as u64;
+            self.windows[index].try_acquire(now, self.interval, self.limit)
+        }
+    }
+    #[derive(Debug, Default)]
+    struct Window {
+        start: core::sync::atomic::AtomicU64,
+        count: AtomicU32,
+    }
+    impl Window {
+        #[inline]
+        fn try_acquire(&self, now: u64, interval: u64, limit: u32) -> bool {
+            let start = self.start.load(Ordering::Relaxed);
+            if now.saturating_sub(start) >= interval
+                && self
+                    .start
+                    .compare_exchange(start, now, Ordering::Relaxed, Ordering::Relaxed)
+                    .is_ok()
+            {
+                self.count.store(0, Ordering::Relaxed);
+            }
+            self.count
+                .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |count| {
+                    count.checked_add(1).filter(|count| *count <= limit)
+                })
+                .is_ok()
+        }
+    }
+    impl<S: super::Subscriber> super::Subscriber for Subscriber<S> {
+        type ConnectionContext = S::ConnectionContext;
+        #[inline]
+        fn create_connection_context(
+            &self,
+            meta: &api::ConnectionMeta,
+            info: &api::ConnectionInfo,
+        ) -> Self::ConnectionContext {
+            self.subscriber.create_connection_context(meta, info)
+        }
+        #[inline]
+        fn query(
+            context: &Self::ConnectionContext,
+            query: &mut dyn query::Query,
+        ) -> query::ControlFlow {
+            S::query(context, query)
+        }
+        #[inline]
+        fn on_application_write(
+            &self,
+            context: &Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::ApplicationWrite,
+        ) {
+            if self.try_acquire(0, meta) {
+                self.subscriber.on_application_write(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_path_secret_map_renewal_requested(
+            &self,
+            meta: &api::EndpointMeta,
+            event: &api::PathSecretMapRenewalRequested,
+        ) {
+            if self.try_acquire(1, meta) {
+                self.subscriber
+                    .on_path_secret_map_renewal_requested(meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_path_secret_map_entry_renewed(
+            &self,
+            meta: &api::EndpointMeta,
+            event: &api::PathSecretMapEntryRenewed,
+        ) {
+            if self.try_acquire(2, meta) {
+                self.subscriber
+                    .on_path_secret_map_entry_renewed(meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_path_secret_map_entry_evicted(
+            &self,
+            meta: &api::EndpointMeta,
+            event: &api::PathSecretMapEntryEvicted,
+        ) {
+            if self.try_acquire(3, meta) {
+                self.subscriber
+                    .on_path_secret_map_entry_evicted(meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_congestion_slow_start_exited(
+            &self,
+            meta: &api::EndpointMeta,
+            event: &api::CongestionSlowStartExited,
+        ) {
+            if self.try_acquire(4, meta) {
+                self.subscriber.on_congestion_slow_start_exited(meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_congestion_pacing_rate_updated(
+            &self,
+            meta: &api::EndpointMeta,
+            event: &api::CongestionPacingRateUpdated,
+        ) {
+            if self.try_acquire(5, meta) {
+                self.subscriber
+                    .on_congestion_pacing_rate_updated(meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_congestion_bbr_state_changed(
+            &self,
+            meta: &api::EndpointMeta,
+            event: &api::CongestionBbrStateChanged,
+        ) {
+            if self.try_acquire(6, meta) {
+                self.subscriber.on_congestion_bbr_state_changed(meta, event);
+                self.subscriber.on_event(meta, event);
+            }
         }
     }
 }
@@ -438,11 +1779,17 @@ pub mod testing {
     use super::*;
     use core::sync::atomic::{AtomicU32, Ordering};
     use std::sync::Mutex;
-    #[derive(Clone, Debug)]
+    #[derive(Debug)]
     pub struct Subscriber {
         location: Option<Location>,
         output: Mutex<Vec<String>>,
-        pub frame_sent: AtomicU32,
+        pub application_write: AtomicU32,
+        pub path_secret_map_renewal_requested: AtomicU32,
+        pub path_secret_map_entry_renewed: AtomicU32,
+        pub path_secret_map_entry_evicted: AtomicU32,
+        pub congestion_slow_start_exited: AtomicU32,
+        pub congestion_pacing_rate_updated: AtomicU32,
+        pub congestion_bbr_state_changed: AtomicU32,
     }
     impl Drop for Subscriber {
         fn drop(&mut self) {
@@ -467,7 +1814,13 @@ pub mod testing {
             Self {
                 location: None,
                 output: Default::default(),
-                frame_sent: AtomicU32::new(0),
+                application_write: AtomicU32::new(0),
+                path_secret_map_renewal_requested: AtomicU32::new(0),
+                path_secret_map_entry_renewed: AtomicU32::new(0),
+                path_secret_map_entry_evicted: AtomicU32::new(0),
+                congestion_slow_start_exited: AtomicU32::new(0),
+                congestion_pacing_rate_updated: AtomicU32::new(0),
+                congestion_bbr_state_changed: AtomicU32::new(0),
             }
         }
     }
@@ -479,13 +1832,13 @@ pub mod testing {
             _info: &api::ConnectionInfo,
         ) -> Self::ConnectionContext {
         }
-        fn on_frame_sent(
+        fn on_application_write(
             &self,
             _context: &Self::ConnectionContext,
             meta: &api::ConnectionMeta,
-            event: &api::FrameSent,
+            event: &api::ApplicationWrite,
         ) {
-            self.frame_sent.fetch_add(1, Ordering::Relaxed);
+            self.application_write.fetch_add(1, Ordering::Relaxed);
             if self.location.is_some() {
                 self.output
                     .lock()
@@ -493,12 +1846,90 @@ pub mod testing {
                     .push(format!("{meta:?} {event:?}"));
             }
         }
+        fn on_path_secret_map_renewal_requested(
+            &self,
+            meta: &api::EndpointMeta,
+            event: &api::PathSecretMapRenewalRequested,
+        ) {
+            self.path_secret_map_renewal_requested
+                .fetch_add(1, Ordering::Relaxed);
+            self.output
+                .lock()
+                .unwrap()
+                .push(format!("{meta:?} {event:?}"));
+        }
+        fn on_path_secret_map_entry_renewed(
+            &self,
+            meta: &api::EndpointMeta,
+            event: &api::PathSecretMapEntryRenewed,
+        ) {
+            self.path_secret_map_entry_renewed
+                .fetch_add(1, Ordering::Relaxed);
+            self.output
+                .lock()
+                .unwrap()
+                .push(format!("{meta:?} {event:?}"));
+        }
+        fn on_path_secret_map_entry_evicted(
+            &self,
+            meta: &api::EndpointMeta,
+            event: &api::PathSecretMapEntryEvicted,
+        ) {
+            self.path_secret_map_entry_evicted
+                .fetch_add(1, Ordering::Relaxed);
+            self.output
+                .lock()
+                .unwrap()
+                .push(format!("{meta:?} {event:?}"));
+        }
+        fn on_congestion_slow_start_exited(
+            &self,
+            meta: &api::EndpointMeta,
+            event: &api::CongestionSlowStartExited,
+        ) {
+            self.congestion_slow_start_exited
+                .fetch_add(1, Ordering::Relaxed);
+            self.output
+                .lock()
+                .unwrap()
+                .push(format!("{meta:?} {event:?}"));
+        }
+        fn on_congestion_pacing_rate_updated(
+            &self,
+            meta: &api::EndpointMeta,
+            event: &api::CongestionPacingRateUpdated,
+        ) {
+            self.congestion_pacing_rate_updated
+                .fetch_add(1, Ordering::Relaxed);
+            self.output
+                .lock()
+                .unwrap()
+                .push(format!("{meta:?} {event:?}"));
+        }
+        fn on_congestion_bbr_state_changed(
+            &self,
+            meta: &api::EndpointMeta,
+            event: &api::CongestionBbrStateChanged,
+        ) {
+            self.congestion_bbr_state_changed
+                .fetch_add(1, Ordering::Relaxed);
+            self.output
+                .lock()
+                .unwrap()
+                .push(format!("{meta:?} {event:?}"));
+        }
     }
-    #[derive(Clone, Debug)]
+    #[derive(Debug)]
     pub struct Publisher {
         location: Option<Location>,
         output: Mutex<Vec<String>>,
-        pub frame_sent: AtomicU32,
+        pub application_write: AtomicU32,
+        pub path_secret_map_renewal_requested: AtomicU32,
+        pub path_secret_map_entry_renewed: AtomicU32,
+        pub path_secret_map_entry_evicted: AtomicU32,
+        pub congestion_slow_start_exited: AtomicU32,
+        pub congestion_pacing_rate_updated: AtomicU32,
+        pub congestion_bbr_state_changed: AtomicU32,
     }
     impl Publisher {
         #[doc = r" Creates a publisher with snapshot assertions enabled"]
@@ -513,18 +1944,63 @@ pub mod testing {
             Self {
                 location: None,
                 output: Default::default(),
-                frame_sent: AtomicU32::new(0),
+                application_write: AtomicU32::new(0),
+                path_secret_map_renewal_requested: AtomicU32::new(0),
+                path_secret_map_entry_renewed: AtomicU32::new(0),
+                path_secret_map_entry_evicted: AtomicU32::new(0),
+                congestion_slow_start_exited: AtomicU32::new(0),
+                congestion_pacing_rate_updated: AtomicU32::new(0),
+                congestion_bbr_state_changed: AtomicU32::new(0),
             }
         }
     }
     impl super::EndpointPublisher for Publisher {
+        fn on_path_secret_map_renewal_requested(
+            &self,
+            event: builder::PathSecretMapRenewalRequested,
+        ) {
+            self.path_secret_map_renewal_requested
+                .fetch_add(1, Ordering::Relaxed);
+            let event = event.into_event();
+            self.output.lock().unwrap().push(format!("{event:?}"));
+        }
+        fn on_path_secret_map_entry_renewed(&self, event: builder::PathSecretMapEntryRenewed) {
+            self.path_secret_map_entry_renewed
+                .fetch_add(1, Ordering::Relaxed);
+            let event = event.into_event();
+            self.output.lock().unwrap().push(format!("{event:?}"));
+        }
+        fn on_path_secret_map_entry_evicted(&self, event: builder::PathSecretMapEntryEvicted) {
+            self.path_secret_map_entry_evicted
+                .fetch_add(1, Ordering::Relaxed);
+            let event = event.into_event();
+            self.output.lock().unwrap().push(format!("{event:?}"));
+        }
+        fn on_congestion_slow_start_exited(&self, event: builder::CongestionSlowStartExited) {
+            self.congestion_slow_start_exited
+                .fetch_add(1, Ordering::Relaxed);
+            let event = event.into_event();
+            self.output.lock().unwrap().push(format!("{event:?}"));
+        }
+        fn on_congestion_pacing_rate_updated(&self, event: builder::CongestionPacingRateUpdated) {
+            self.congestion_pacing_rate_updated
+                .fetch_add(1, Ordering::Relaxed);
+            let event = event.into_event();
+            self.output.lock().unwrap().push(format!("{event:?}"));
+        }
+        fn on_congestion_bbr_state_changed(&self, event: builder::CongestionBbrStateChanged) {
+            self.congestion_bbr_state_changed
+                .fetch_add(1, Ordering::Relaxed);
+            let event = event.into_event();
+            self.output.lock().unwrap().push(format!("{event:?}"));
+        }
         fn quic_version(&self) -> Option<u32> {
             Some(1)
         }
     }
     impl super::ConnectionPublisher for Publisher {
-        fn on_frame_sent(&self, event: builder::FrameSent) {
-            self.frame_sent.fetch_add(1, Ordering::Relaxed);
+        fn on_application_write(&self, event: builder::ApplicationWrite) {
+            self.application_write.fetch_add(1, Ordering::Relaxed);
             let event = event.into_event();
             if self.location.is_some() {
                 self.output.lock().unwrap().push(format!("{event:?}"));
@@ -0,0 +1,28 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+
+#[test]
+fn serialize_event() {
+    let event = api::ApplicationWrite { len: 123 };
+    let meta = api::ConnectionMeta {
+        endpoint_type: api::EndpointType::Client {},
+        id: 4,
+        timestamp: s2n_quic_core::time::clock::testing::now().into_event(),
+    };
+
+    let value = serde_json::json!({
+        "name": <api::ApplicationWrite as Event>::NAME,
+        "meta": meta,
+        "event": event,
+    });
+
+    assert_eq!(value["name"], "application:write");
+    assert_eq!(
+        value["meta"]["endpoint_type"],
+        serde_json::json!({ "Client": {} })
+    );
+    assert_eq!(value["meta"]["id"], 4);
+    assert_eq!(value["event"], serde_json::json!({ "len": 123 }));
+}
@@ -90,6 +90,11 @@ where
         self.slot_by_hash(&key).put(key, value)
     }
 
+    /// Removes the value for `key` if `f` returns true for it.
+    pub fn remove_if(&self, key: &K, f: impl FnOnce(&V) -> bool) -> Option<V> {
+        self.slot_by_hash(key).remove_if(key, f)
+    }
+
     pub fn contains_key(&self, key: &K) -> bool {
         self.get_by_key(key).is_some()
     }
@@ -162,6 +167,18 @@ where
         None
     }
 
+    fn remove_if(&self, needle: &K, f: impl FnOnce(&V) -> bool) -> Option<V> {
+        let mut values = self.values.write();
+        let entry = values
+            .iter_mut()
+            .find(|value| value.as_ref().map_or(false, |(k, _)| *k == *needle))?;
+        if f(&entry.as_ref().unwrap().1) {
+            entry.take().map(|v| v.1)
+        } else {
+            None
+        }
+    }
+
     fn len(&self) -> usize {
         let values = self.values.read();
         let mut len = 0;
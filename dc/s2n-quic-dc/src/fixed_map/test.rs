@@ -40,3 +40,13 @@ fn capacity_size() {
     }
     assert!(map.len() >= 400_000, "{}", map.len());
 }
+
+#[test]
+fn slot_remove_if() {
+    let slot = Slot::new();
+    assert_eq!(slot.put(3, "key 1"), None);
+    assert_eq!(slot.remove_if(&3, |v| *v == "key 2"), None);
+    assert_eq!(slot.remove_if(&3, |v| *v == "key 1"), Some("key 1"));
+    assert!(slot.get_by_key(&3).is_none());
+    assert_eq!(slot.remove_if(&3, |_| true), None);
+}
@@ -2,12 +2,15 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub mod allocator;
+#[cfg(feature = "quic")]
+pub mod client;
 pub mod clock;
 pub mod congestion;
 pub mod control;
 pub mod credentials;
 pub mod crypto;
 pub mod datagram;
+pub mod event;
 mod fixed_map;
 pub mod msg;
 pub mod packet;
@@ -15,6 +18,8 @@ pub mod path;
 pub mod pool;
 pub mod random;
 pub mod recovery;
+#[cfg(feature = "quic")]
+pub mod server;
 pub mod socket;
 pub mod stream;
 pub mod sync;
@@ -23,4 +28,8 @@ pub mod task;
 #[cfg(any(test, feature = "testing"))]
 pub mod testing;
 
+#[cfg(feature = "quic")]
+pub use client::Client;
 pub use s2n_quic_core::dc::{Version, SUPPORTED_VERSIONS};
+#[cfg(feature = "quic")]
+pub use server::Server;
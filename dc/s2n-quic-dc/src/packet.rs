@@ -27,6 +27,7 @@ pub enum Kind {
     Control,
     StaleKey,
     ReplayDetected,
+    ServerBusy,
     UnknownPathSecret,
 }
 
@@ -37,6 +38,7 @@ pub enum Packet<'a> {
     Control(control::decoder::Packet<'a>),
     StaleKey(secret_control::stale_key::Packet<'a>),
     ReplayDetected(secret_control::replay_detected::Packet<'a>),
+    ServerBusy(secret_control::server_busy::Packet<'a>),
     UnknownPathSecret(secret_control::unknown_path_secret::Packet<'a>),
 }
 
@@ -49,6 +51,7 @@ impl<'a> Packet<'a> {
             Packet::Control(_) => Kind::Control,
             Packet::StaleKey(_) => Kind::StaleKey,
             Packet::ReplayDetected(_) => Kind::ReplayDetected,
+            Packet::ServerBusy(_) => Kind::ServerBusy,
             Packet::UnknownPathSecret(_) => Kind::UnknownPathSecret,
         }
     }
@@ -83,6 +86,10 @@ impl<'a> s2n_codec::DecoderParameterizedValueMut<'a> for Packet<'a> {
                 let (packet, decoder) = secret_control::replay_detected::Packet::decode(decoder)?;
                 Ok((Self::ReplayDetected(packet), decoder))
             }
+            Tag::ServerBusy(_) => {
+                let (packet, decoder) = secret_control::server_busy::Packet::decode(decoder)?;
+                Ok((Self::ServerBusy(packet), decoder))
+            }
             Tag::UnknownPathSecret(_) => {
                 let (packet, decoder) =
                     secret_control::unknown_path_secret::Packet::decode(decoder)?;
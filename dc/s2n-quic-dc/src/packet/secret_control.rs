@@ -17,6 +17,7 @@ mod encoder;
 const UNKNOWN_PATH_SECRET: u8 = 0b0110_0000;
 const STALE_KEY: u8 = 0b0110_0001;
 const REPLAY_DETECTED: u8 = 0b0110_0010;
+const SERVER_BUSY: u8 = 0b0110_0011;
 
 pub const MAX_PACKET_SIZE: usize = 64;
 pub const TAG_LEN: usize = 16;
@@ -117,10 +118,12 @@ macro_rules! impl_tests {
 }
 
 pub mod replay_detected;
+pub mod server_busy;
 pub mod stale_key;
 pub mod unknown_path_secret;
 
 pub use replay_detected::ReplayDetected;
+pub use server_busy::ServerBusy;
 pub use stale_key::StaleKey;
 pub use unknown_path_secret::UnknownPathSecret;
 
@@ -129,6 +132,7 @@ pub enum Packet<'a> {
     UnknownPathSecret(unknown_path_secret::Packet<'a>),
     StaleKey(stale_key::Packet<'a>),
     ReplayDetected(replay_detected::Packet<'a>),
+    ServerBusy(server_busy::Packet<'a>),
 }
 
 impl<'a> Packet<'a> {
@@ -149,6 +153,10 @@ impl<'a> Packet<'a> {
                 let (packet, buffer) = replay_detected::Packet::decode(buffer)?;
                 (Self::ReplayDetected(packet), buffer)
             }
+            SERVER_BUSY => {
+                let (packet, buffer) = server_busy::Packet::decode(buffer)?;
+                (Self::ServerBusy(packet), buffer)
+            }
             _ => return Err(DecoderError::InvariantViolation("invalid tag")),
         })
     }
@@ -159,6 +167,7 @@ impl<'a> Packet<'a> {
             Self::UnknownPathSecret(p) => p.credential_id(),
             Self::StaleKey(p) => p.credential_id(),
             Self::ReplayDetected(p) => p.credential_id(),
+            Self::ServerBusy(p) => p.credential_id(),
         }
     }
 }
@@ -177,3 +186,4 @@ macro_rules! impl_convert {
 impl_convert!(UnknownPathSecret, unknown_path_secret);
 impl_convert!(StaleKey, stale_key);
 impl_convert!(ReplayDetected, replay_detected);
+impl_convert!(ServerBusy, server_busy);
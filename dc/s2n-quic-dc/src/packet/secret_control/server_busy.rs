@@ -0,0 +1,86 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use core::time::Duration;
+
+impl_tag!(SERVER_BUSY);
+impl_packet!(ServerBusy);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(test, derive(bolero_generator::TypeGenerator))]
+pub struct ServerBusy {
+    pub wire_version: WireVersion,
+    pub credential_id: credentials::Id,
+    pub rejected_key_id: VarInt,
+    /// The number of milliseconds the peer should wait before opening another stream
+    pub retry_after_ms: VarInt,
+}
+
+impl ServerBusy {
+    #[inline]
+    pub fn encode<C>(&self, mut encoder: EncoderBuffer, crypto: &C) -> usize
+    where
+        C: seal::control::Secret,
+    {
+        encoder.encode(&Tag::default());
+        encoder.encode(&self.credential_id);
+        encoder.encode(&self.wire_version);
+        encoder.encode(&self.rejected_key_id);
+        encoder.encode(&self.retry_after_ms);
+
+        encoder::finish(encoder, crypto)
+    }
+
+    #[inline]
+    pub fn retry_after(&self) -> Duration {
+        Duration::from_millis(self.retry_after_ms.as_u64())
+    }
+
+    #[inline]
+    pub fn credentials(&self) -> credentials::Credentials {
+        credentials::Credentials {
+            id: self.credential_id,
+            key_id: self.rejected_key_id,
+        }
+    }
+
+    #[cfg(test)]
+    fn validate(&self) -> Option<()> {
+        Some(())
+    }
+}
+
+impl<'a> Packet<'a> {
+    /// Returns the credentials of the rejected stream, before the packet is authenticated
+    #[inline]
+    pub fn credentials(&self) -> credentials::Credentials {
+        self.value.credentials()
+    }
+}
+
+impl<'a> DecoderValue<'a> for ServerBusy {
+    #[inline]
+    fn decode(buffer: DecoderBuffer<'a>) -> R<'a, Self> {
+        let (tag, buffer) = buffer.decode::<Tag>()?;
+        decoder_invariant!(tag == Tag::default(), "invalid tag");
+        let (credential_id, buffer) = buffer.decode()?;
+        let (wire_version, buffer) = buffer.decode()?;
+        let (rejected_key_id, buffer) = buffer.decode()?;
+        let (retry_after_ms, buffer) = buffer.decode()?;
+        let value = Self {
+            wire_version,
+            credential_id,
+            rejected_key_id,
+            retry_after_ms,
+        };
+        Ok((value, buffer))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    impl_tests!(ServerBusy);
+}
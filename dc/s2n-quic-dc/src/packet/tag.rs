@@ -33,6 +33,7 @@ pub enum Tag {
     Control(super::control::Tag),
     StaleKey(super::secret_control::stale_key::Tag),
     ReplayDetected(super::secret_control::replay_detected::Tag),
+    ServerBusy(super::secret_control::server_busy::Tag),
     UnknownPathSecret(super::secret_control::unknown_path_secret::Tag),
 }
 
@@ -45,6 +46,7 @@ impl From<Tag> for u8 {
             Tag::Control(v) => v.into(),
             Tag::StaleKey(v) => v.into(),
             Tag::ReplayDetected(v) => v.into(),
+            Tag::ServerBusy(v) => v.into(),
             Tag::UnknownPathSecret(v) => v.into(),
         }
     }
@@ -74,6 +76,10 @@ decoder_value!(
                     let (tag, buffer) = buffer.decode()?;
                     Ok((Self::ReplayDetected(tag), buffer))
                 }
+                super::secret_control::server_busy::Tag::VALUE => {
+                    let (tag, buffer) = buffer.decode()?;
+                    Ok((Self::ServerBusy(tag), buffer))
+                }
                 super::secret_control::unknown_path_secret::Tag::VALUE => {
                     let (tag, buffer) = buffer.decode()?;
                     Ok((Self::UnknownPathSecret(tag), buffer))
@@ -47,6 +47,13 @@ pub mod seal {
                 LIMIT - THRESHOLD
             };
 
+            //= https://www.rfc-editor.org/rfc/rfc9001#section-6.6
+            //# For AEAD_CHACHA20_POLY1305, the confidentiality limit is greater than
+            //# the number of possible packets (2^62) and so can be disregarded.
+            if !cfg!(debug_assertions) && self.sealer.algorithm() == &awslc::CHACHA20_POLY1305 {
+                return false;
+            }
+
             self.encrypted_records.load(Ordering::Relaxed) >= MAX_RECORDS
         }
 
@@ -0,0 +1,310 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A text format for exporting path secrets to external tooling, such as the dcQUIC Wireshark
+//! plugin.
+//!
+//! Each path secret is written on its own line:
+//!
+//! ```text
+//! DCQUIC_PATH_SECRET <credential id> <ciphersuite> <export secret>
+//! ```
+//!
+//! The credential ID and export secret are lowercase hex encoded. Every key used by the path
+//! (stream, datagram and control keys for all key IDs) is derived from the export secret, so a
+//! single line is enough to decrypt and authenticate all of the packets for the path secret.
+//!
+//! Empty lines and lines starting with `#` are ignored when parsing.
+//!
+//! **Warning**: anyone with access to the key log can decrypt and forge traffic for the logged
+//! path secrets. It should only ever be enabled for debugging.
+
+use super::schedule::{Ciphersuite, ExportSecret, Secret, EXPORT_SECRET_LEN};
+use crate::credentials::Id;
+use s2n_quic_core::{dc, endpoint};
+use std::{fmt, io, str::FromStr, sync::Mutex};
+use zeroize::Zeroizing;
+
+/// The label which starts each path secret line
+pub const LABEL: &str = "DCQUIC_PATH_SECRET";
+
+/// A single path secret read from or written to the key log
+pub struct Entry {
+    pub id: Id,
+    pub ciphersuite: Ciphersuite,
+    pub export_secret: Zeroizing<ExportSecret>,
+}
+
+impl fmt::Debug for Entry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // don't print the export secret
+        f.debug_struct("Entry")
+            .field("id", &self.id)
+            .field("ciphersuite", &self.ciphersuite)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Entry {
+    /// Derives the path secret for the given local endpoint type
+    ///
+    /// Keys derived for the client can open packets sent by the server and the other way around,
+    /// so tooling which observes both peers should derive a secret for each endpoint type.
+    #[inline]
+    pub fn secret(&self, version: dc::Version, endpoint: endpoint::Type) -> Secret {
+        Secret::new(self.ciphersuite, version, endpoint, &self.export_secret)
+    }
+}
+
+impl fmt::Display for Entry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{LABEL} ")?;
+        write_hex(f, &*self.id)?;
+        write!(f, " {} ", ciphersuite_name(self.ciphersuite))?;
+        write_hex(f, &*self.export_secret)
+    }
+}
+
+impl FromStr for Entry {
+    type Err = ParseError;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let mut parts = line.split_ascii_whitespace();
+
+        if parts.next() != Some(LABEL) {
+            return Err(ParseError::UnknownLabel);
+        }
+
+        let mut id = Id::default();
+        read_hex(parts.next(), &mut *id)?;
+
+        let ciphersuite = match parts.next() {
+            Some("AES_GCM_128_SHA256") => Ciphersuite::AES_GCM_128_SHA256,
+            Some("AES_GCM_256_SHA384") => Ciphersuite::AES_GCM_256_SHA384,
+            Some("CHACHA20_POLY1305_SHA256") => Ciphersuite::CHACHA20_POLY1305_SHA256,
+            _ => return Err(ParseError::InvalidCiphersuite),
+        };
+
+        let mut export_secret = Zeroizing::new([0; EXPORT_SECRET_LEN]);
+        read_hex(parts.next(), &mut *export_secret)?;
+
+        if parts.next().is_some() {
+            return Err(ParseError::TrailingData);
+        }
+
+        let entry = Self {
+            id,
+            ciphersuite,
+            export_secret,
+        };
+
+        // the ID is derived from the export secret so make sure the two are consistent
+        let secret = entry.secret(dc::SUPPORTED_VERSIONS[0], endpoint::Type::Client);
+        if *secret.id() != entry.id {
+            return Err(ParseError::IdMismatch);
+        }
+
+        Ok(entry)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParseError {
+    /// The line doesn't start with [`LABEL`]
+    UnknownLabel,
+    /// A hex encoded value is missing, malformed or has the wrong length
+    InvalidHex,
+    /// The ciphersuite is missing or isn't supported
+    InvalidCiphersuite,
+    /// The line contains more values than expected
+    TrailingData,
+    /// The credential ID wasn't derived from the export secret
+    IdMismatch,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::UnknownLabel => write!(f, "line does not start with {LABEL}"),
+            Self::InvalidHex => write!(f, "invalid hex value"),
+            Self::InvalidCiphersuite => write!(f, "invalid ciphersuite"),
+            Self::TrailingData => write!(f, "unexpected trailing data"),
+            Self::IdMismatch => write!(f, "credential id does not match the export secret"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Reads all of the path secrets from a key log
+///
+/// Lines with unknown labels are skipped so the key log can be shared with other tools.
+pub fn read<R: io::BufRead>(reader: R) -> io::Result<Vec<Entry>> {
+    let mut entries = vec![];
+
+    for line in reader.lines() {
+        let line = line?;
+        let line = line.trim();
+
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        match line.parse() {
+            Ok(entry) => entries.push(entry),
+            Err(ParseError::UnknownLabel) => continue,
+            Err(err) => return Err(io::Error::new(io::ErrorKind::InvalidData, err)),
+        }
+    }
+
+    Ok(entries)
+}
+
+/// Appends path secrets to a key log as they are inserted into the map
+pub(super) struct Writer {
+    writer: Mutex<Box<dyn io::Write + Send>>,
+}
+
+impl Writer {
+    pub(super) fn new<W: io::Write + Send + 'static>(writer: W) -> Self {
+        Self {
+            writer: Mutex::new(Box::new(writer)),
+        }
+    }
+
+    pub(super) fn write(&self, secret: &Secret) {
+        let entry = Entry {
+            id: *secret.id(),
+            ciphersuite: secret.ciphersuite(),
+            export_secret: Zeroizing::new(*secret.export_secret()),
+        };
+
+        // the line is formatted up front so it's written with a single call
+        let line = Zeroizing::new(format!("{entry}\n"));
+
+        let Ok(mut writer) = self.writer.lock() else {
+            return;
+        };
+
+        let res = writer
+            .write_all(line.as_bytes())
+            .and_then(|()| writer.flush());
+
+        if let Err(err) = res {
+            tracing::warn!("failed to write to the path secret key log: {err}");
+        }
+    }
+}
+
+#[inline]
+fn ciphersuite_name(ciphersuite: Ciphersuite) -> &'static str {
+    match ciphersuite {
+        Ciphersuite::AES_GCM_128_SHA256 => "AES_GCM_128_SHA256",
+        Ciphersuite::AES_GCM_256_SHA384 => "AES_GCM_256_SHA384",
+        Ciphersuite::CHACHA20_POLY1305_SHA256 => "CHACHA20_POLY1305_SHA256",
+    }
+}
+
+#[inline]
+fn write_hex(f: &mut fmt::Formatter, bytes: &[u8]) -> fmt::Result {
+    for byte in bytes {
+        write!(f, "{byte:02x}")?;
+    }
+    Ok(())
+}
+
+#[inline]
+fn read_hex(value: Option<&str>, out: &mut [u8]) -> Result<(), ParseError> {
+    let value = value.ok_or(ParseError::InvalidHex)?;
+
+    if !value.is_ascii() || value.len() != out.len() * 2 {
+        return Err(ParseError::InvalidHex);
+    }
+
+    for (byte, chunk) in out.iter_mut().zip(value.as_bytes().chunks(2)) {
+        // the value is ascii so the chunk is always valid utf-8
+        let chunk = core::str::from_utf8(chunk).map_err(|_| ParseError::InvalidHex)?;
+        *byte = u8::from_str_radix(chunk, 16).map_err(|_| ParseError::InvalidHex)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(ciphersuite: Ciphersuite) -> Entry {
+        let mut export_secret = Zeroizing::new([0; EXPORT_SECRET_LEN]);
+        aws_lc_rs::rand::fill(&mut *export_secret).unwrap();
+        let secret = Secret::new(
+            ciphersuite,
+            dc::SUPPORTED_VERSIONS[0],
+            endpoint::Type::Server,
+            &export_secret,
+        );
+        Entry {
+            id: *secret.id(),
+            ciphersuite,
+            export_secret,
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        for ciphersuite in [
+            Ciphersuite::AES_GCM_128_SHA256,
+            Ciphersuite::AES_GCM_256_SHA384,
+            Ciphersuite::CHACHA20_POLY1305_SHA256,
+        ] {
+            let expected = entry(ciphersuite);
+            let line = expected.to_string();
+            let actual: Entry = line.parse().unwrap();
+
+            assert_eq!(actual.id, expected.id);
+            assert_eq!(actual.ciphersuite, expected.ciphersuite);
+            assert_eq!(*actual.export_secret, *expected.export_secret);
+        }
+    }
+
+    #[test]
+    fn read_skips_other_lines() {
+        let first = entry(Ciphersuite::AES_GCM_128_SHA256);
+        let second = entry(Ciphersuite::AES_GCM_256_SHA384);
+        let log = format!(
+            "# comment\n\n{first}\nCLIENT_RANDOM 00 00\n  {second}  \n",
+            first = first,
+            second = second,
+        );
+
+        let entries = read(log.as_bytes()).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].id, first.id);
+        assert_eq!(entries[1].id, second.id);
+    }
+
+    #[test]
+    fn invalid_lines() {
+        let valid = entry(Ciphersuite::AES_GCM_128_SHA256).to_string();
+        let parts: Vec<_> = valid.split(' ').collect();
+
+        for (line, error) in [
+            (
+                format!("{} {} CHACHA {}", parts[0], parts[1], parts[3]),
+                ParseError::InvalidCiphersuite,
+            ),
+            (
+                format!("{} {} {} zz", parts[0], parts[1], parts[2]),
+                ParseError::InvalidHex,
+            ),
+            (format!("{valid} 00"), ParseError::TrailingData),
+            (
+                format!("{} {} {} {}", parts[0], "00".repeat(16), parts[2], parts[3]),
+                ParseError::IdMismatch,
+            ),
+        ] {
+            assert_eq!(line.parse::<Entry>().unwrap_err(), error, "{line}");
+            assert!(read(line.as_bytes()).is_err());
+        }
+    }
+}
@@ -2,13 +2,15 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::{
-    open, receiver,
+    key_log, open, receiver,
     schedule::{self, Initiator},
     seal, sender, stateless_reset,
 };
 use crate::{
     credentials::{Credentials, Id},
-    crypto, fixed_map,
+    crypto,
+    event::{self, builder as events, IntoEvent as _},
+    fixed_map,
     packet::{secret_control as control, Packet, WireVersion},
     stream::TransportFeatures,
 };
@@ -16,11 +18,14 @@ use rand::Rng as _;
 use s2n_codec::EncoderBuffer;
 use s2n_quic_core::{
     dc::{self, ApplicationParams, DatagramInfo},
-    ensure,
+    endpoint, ensure,
     event::api::EndpointType,
+    inet,
+    time::{Clock as _, StdClock},
     varint::VarInt,
 };
 use std::{
+    collections::VecDeque,
     fmt,
     hash::{BuildHasherDefault, Hasher},
     net::{Ipv4Addr, SocketAddr},
@@ -32,6 +37,11 @@ use std::{
 };
 use zeroize::Zeroizing;
 
+mod builder;
+pub mod snapshot;
+
+pub use builder::{Builder, DEFAULT_CAPACITY, DEFAULT_REHANDSHAKE_PERIOD};
+
 const TLS_EXPORTER_LABEL: &str = "EXPERIMENTAL EXPORTER s2n-quic-dc";
 const TLS_EXPORTER_CONTEXT: &str = "";
 const TLS_EXPORTER_LENGTH: usize = schedule::EXPORT_SECRET_LEN;
@@ -125,6 +135,74 @@ pub(super) struct State {
     handled_control_packets: AtomicUsize,
 
     cleaner: Cleaner,
+
+    // Entries are evicted once they are older than this.
+    max_entry_lifetime: Option<Duration>,
+
+    // Entries are evicted once this many keys have been derived for locally-initiated streams.
+    max_key_uses: Option<u64>,
+
+    renewals: Renewals,
+
+    clock: StdClock,
+
+    subscriber: Option<Arc<dyn event::DynSubscriber>>,
+
+    key_log: Option<key_log::Writer>,
+}
+
+/// Peers with actively used entries which should be renewed before they expire
+///
+/// Renewals are only queued while at least one [`RenewalQueue`] is alive. Otherwise, entries
+/// fall back to being renewed on the next `handshake_with` for the peer.
+#[derive(Default)]
+struct Renewals {
+    /// The queued peers along with the number of live [`RenewalQueue`]s
+    queue: Mutex<(VecDeque<SocketAddr>, usize)>,
+    notify: tokio::sync::Notify,
+}
+
+/// Receives the peers with actively used path secrets which should be renewed
+///
+/// Each peer returned from [`Self::next`] should be handshaked with again. If the handshake
+/// fails, [`Map::on_renewal_failed`] should be called so the entry can be retried.
+pub struct RenewalQueue {
+    map: Map,
+}
+
+impl RenewalQueue {
+    /// Waits for the next peer which should be renewed
+    pub async fn next(&self) -> SocketAddr {
+        let renewals = &self.map.state.renewals;
+
+        loop {
+            let notified = renewals.notify.notified();
+
+            if let Some(peer) = renewals.queue.lock().unwrap().0.pop_front() {
+                return peer;
+            }
+
+            notified.await;
+        }
+    }
+}
+
+impl Drop for RenewalQueue {
+    fn drop(&mut self) {
+        let state = &self.map.state;
+
+        let queued = {
+            let mut queue = state.renewals.queue.lock().unwrap();
+            queue.1 -= 1;
+            ensure!(queue.1 == 0);
+            core::mem::take(&mut queue.0)
+        };
+
+        // nothing is left to renew the queued peers so fall back to handshaking on demand
+        for peer in queued {
+            state.on_renewal_failed(peer);
+        }
+    }
 }
 
 struct Cleaner {
@@ -186,17 +264,28 @@ impl Cleaner {
         let current_epoch = self.epoch.fetch_add(1, Ordering::Relaxed);
         let now = Instant::now();
 
+        let mut expired = vec![];
+
         // For non-retired entries, if it's time for them to handshake again, request a
-        // handshake to happen. This handshake will currently happen on the next request for this
-        // particular peer.
+        // handshake to happen. Entries which have been used since the last clean are renewed
+        // in the background, if possible. Otherwise the handshake will happen on the next request
+        // for this particular peer.
         state.ids.retain(|_, entry| {
             let retired_at = entry.retired.0.load(Ordering::Relaxed);
             if retired_at == 0 {
+                if state.is_expired(entry, now) {
+                    expired.push(entry.clone());
+                    return false;
+                }
+
+                let used = entry.used.swap(false, Ordering::Relaxed);
                 if entry.rehandshake_time() <= now {
-                    state.request_handshake(entry.peer);
+                    let reason = events::PathSecretRenewalReason::Scheduled;
+                    if !(used && state.request_renewal(entry, reason)) {
+                        state.request_handshake(entry.peer);
+                    }
                 }
 
-                // always retain
                 true
             } else {
                 // retain if we aren't yet ready to evict.
@@ -204,6 +293,14 @@ impl Cleaner {
             }
         });
 
+        for entry in expired {
+            state.on_evicted(
+                &entry,
+                events::PathSecretEvictionReason::LifetimeExpired,
+                now,
+            );
+        }
+
         // Drop IP entries if we no longer have the path secret ID entry.
         // FIXME: Don't require a loop to do this. This is likely somewhat slow since it takes a
         // write lock + read lock essentially per-entry, but should be near-constant-time.
@@ -241,6 +338,78 @@ impl State {
         }
     }
 
+    /// Queues the entry's peer to be renewed in the background
+    ///
+    /// Returns `false` if the entry can't be renewed in the background.
+    fn request_renewal(&self, entry: &Entry, reason: events::PathSecretRenewalReason) -> bool {
+        // only clients can initiate handshakes with the peer
+        ensure!(entry.secret.endpoint() == endpoint::Type::Client, false);
+
+        {
+            let mut queue = self.renewals.queue.lock().unwrap();
+
+            // nothing is consuming renewals
+            ensure!(queue.1 > 0, false);
+
+            // the renewal is already in progress
+            if entry.renewal_requested.swap(true, Ordering::Relaxed) {
+                return true;
+            }
+
+            queue.0.push_back(entry.peer);
+        }
+
+        self.renewals.notify.notify_one();
+
+        let peer: inet::SocketAddress = entry.peer.into();
+        self.publish(entry, |publisher| {
+            publisher.on_path_secret_map_renewal_requested(events::PathSecretMapRenewalRequested {
+                peer_address: (&peer).into_event(),
+                reason,
+            });
+        });
+
+        true
+    }
+
+    /// Allows the peer's entry to be renewed again and handshakes on the next request instead
+    fn on_renewal_failed(&self, peer: SocketAddr) {
+        if let Some(entry) = self.peers.get_by_key(&peer) {
+            entry.renewal_requested.store(false, Ordering::Relaxed);
+        }
+        self.request_handshake(peer);
+    }
+
+    #[inline]
+    fn is_expired(&self, entry: &Entry, now: Instant) -> bool {
+        self.max_entry_lifetime
+            .map_or(false, |lifetime| entry.creation_time + lifetime <= now)
+    }
+
+    fn on_evicted(&self, entry: &Entry, reason: events::PathSecretEvictionReason, now: Instant) {
+        let peer: inet::SocketAddress = entry.peer.into();
+        let age = now.saturating_duration_since(entry.creation_time);
+        self.publish(entry, |publisher| {
+            publisher.on_path_secret_map_entry_evicted(events::PathSecretMapEntryEvicted {
+                peer_address: (&peer).into_event(),
+                reason,
+                age,
+            });
+        });
+    }
+
+    #[inline]
+    fn publish(&self, entry: &Entry, mut f: impl FnMut(&dyn event::EndpointPublisher)) {
+        let Some(subscriber) = self.subscriber.as_ref() else {
+            return;
+        };
+        let meta = events::EndpointMeta {
+            endpoint_type: entry.secret.endpoint(),
+            timestamp: self.clock.get_time(),
+        };
+        subscriber.publish(meta, &mut f);
+    }
+
     // for tests
     #[allow(unused)]
     fn set_max_capacity(&mut self, new: usize) {
@@ -252,6 +421,25 @@ impl State {
 
 impl Map {
     pub fn new(signer: stateless_reset::Signer, capacity: usize) -> Self {
+        Self::builder(signer).with_capacity(capacity).build()
+    }
+
+    #[inline]
+    pub fn builder(signer: stateless_reset::Signer) -> Builder {
+        Builder::new(signer)
+    }
+
+    fn from_builder(builder: Builder) -> Self {
+        let Builder {
+            signer,
+            capacity,
+            rehandshake_period,
+            max_entry_lifetime,
+            max_key_uses,
+            subscriber,
+            key_log,
+        } = builder;
+
         // FIXME: Avoid unwrap and the whole socket.
         //
         // We only ever send on this socket - but we really should be sending on the same
@@ -261,11 +449,15 @@ impl Map {
         // of implementation).
         let control_socket = std::net::UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0)).unwrap();
         control_socket.set_nonblocking(true).unwrap();
+        // make sure entries are renewed well before they expire
+        let rehandshake_period = max_entry_lifetime.map_or(rehandshake_period, |lifetime| {
+            rehandshake_period.min(lifetime * 3 / 4)
+        });
+
         let state = State {
             // This is around 500MB with current entry size.
             max_capacity: capacity,
-            // FIXME: Allow configuring the rehandshake_period.
-            rehandshake_period: Duration::from_secs(3600 * 24),
+            rehandshake_period,
             peers: fixed_map::Map::with_capacity(capacity, Default::default()),
             ids: fixed_map::Map::with_capacity(capacity, Default::default()),
             requested_handshakes: Default::default(),
@@ -276,6 +468,13 @@ impl Map {
 
             handled_control_packets: AtomicUsize::new(0),
             control_socket,
+
+            max_entry_lifetime,
+            max_key_uses,
+            renewals: Default::default(),
+            clock: StdClock::default(),
+            subscriber,
+            key_log,
         };
 
         let state = Arc::new(state);
@@ -301,22 +500,143 @@ impl Map {
         self.state.max_capacity
     }
 
+    /// Returns the event subscriber configured with [`Builder::with_event_subscriber`]
+    #[inline]
+    pub(crate) fn subscriber(&self) -> Option<Arc<dyn event::DynSubscriber>> {
+        self.state.subscriber.clone()
+    }
+
     pub fn drop_state(&self) {
         self.state.peers.clear();
         self.state.ids.clear();
     }
 
+    /// Seals the map's live entries under `key` and writes them to `writer`
+    ///
+    /// The exported entries are removed from the map, even if writing the snapshot fails, so that
+    /// they can't be used past the key IDs recorded in the snapshot. This is intended to be called
+    /// while shutting down.
+    ///
+    /// Returns the number of exported entries. See [`snapshot`] for details on the format and how
+    /// key IDs are handled.
+    pub fn export_snapshot(
+        &self,
+        key: &snapshot::Key,
+        writer: impl std::io::Write,
+    ) -> std::io::Result<usize> {
+        snapshot::export(self, key, writer)
+    }
+
+    /// Restores the entries from a snapshot written by [`Self::export_snapshot`]
+    ///
+    /// `generation` selects the range of key IDs the restored entries use. It must be different
+    /// for every import of the same snapshot, e.g. a counter which is persisted and incremented
+    /// before each import. Importing a snapshot twice with the same generation reuses keys.
+    ///
+    /// Entries for peers which are already in the map, or which have exceeded the maximum entry
+    /// lifetime, are skipped. Returns the number of restored entries.
+    pub fn import_snapshot(
+        &self,
+        key: &snapshot::Key,
+        generation: u32,
+        reader: impl std::io::Read,
+    ) -> std::io::Result<usize> {
+        snapshot::import(self, key, generation, reader)
+    }
+
     pub fn contains(&self, peer: SocketAddr) -> bool {
-        self.state.peers.contains_key(&peer)
-            && !self.state.requested_handshakes.pin().contains(&peer)
+        self.peer_entry(&peer).is_some() && !self.state.requested_handshakes.pin().contains(&peer)
+    }
+
+    /// Returns a queue of peers with actively used path secrets which should be renewed
+    ///
+    /// While the queue is alive, entries are renewed through it instead of on the next
+    /// `handshake_with` for the peer. Only peers which were handshaked with as a client are
+    /// queued.
+    pub fn renewal_queue(&self) -> RenewalQueue {
+        self.state.renewals.queue.lock().unwrap().1 += 1;
+        RenewalQueue { map: self.clone() }
+    }
+
+    /// Notifies the map that renewing the path secret for `peer` failed
+    ///
+    /// The entry is handshaked with on the next request for the peer and can be queued for
+    /// renewal again.
+    pub fn on_renewal_failed(&self, peer: SocketAddr) {
+        self.state.on_renewal_failed(peer);
+    }
+
+    /// Returns the entry for the peer, if it hasn't exceeded its lifetime
+    fn peer_entry(&self, peer: &SocketAddr) -> Option<Arc<Entry>> {
+        let entry = self.state.peers.get_by_key(peer)?.clone();
+
+        if self.state.max_entry_lifetime.is_some() {
+            let now = Instant::now();
+            if self.state.is_expired(&entry, now) {
+                let is_entry = |v: &Arc<Entry>| Arc::ptr_eq(v, &entry);
+                self.state.peers.remove_if(peer, is_entry);
+                // only report the eviction if the cleaner didn't beat us to it
+                if self
+                    .state
+                    .ids
+                    .remove_if(entry.secret.id(), is_entry)
+                    .is_some()
+                {
+                    let reason = events::PathSecretEvictionReason::LifetimeExpired;
+                    self.state.on_evicted(&entry, reason, now);
+                }
+                return None;
+            }
+        }
+
+        Some(entry)
+    }
+
+    /// Checks the key ID allocated for a locally-initiated stream against the key use limit
+    ///
+    /// Returns `false` if the entry is exhausted and the key ID shouldn't be used.
+    fn on_local_key_use(&self, entry: &Arc<Entry>, key_id: VarInt) -> bool {
+        entry.used.store(true, Ordering::Relaxed);
+
+        let key_id = key_id.as_u64();
+
+        // restored entries are renewed once they've used 3/4 of the key IDs reserved for them
+        let mut limit = entry.key_id_limits.sender;
+        let mut renew_at = limit.saturating_sub(snapshot::KEY_ID_WINDOW / 4);
+
+        if let Some(max_key_uses) = self.state.max_key_uses {
+            limit = limit.min(max_key_uses);
+            renew_at = renew_at.min(max_key_uses - max_key_uses / 4);
+        }
+
+        if key_id >= limit {
+            let is_entry = |v: &Arc<Entry>| Arc::ptr_eq(v, entry);
+            if self.state.peers.remove_if(&entry.peer, is_entry).is_some() {
+                // keep the entry around for the peer's in-flight streams
+                entry.retire(self.state.cleaner.epoch());
+                let reason = events::PathSecretEvictionReason::KeyUsesExhausted;
+                self.state.on_evicted(entry, reason, Instant::now());
+            }
+            return false;
+        }
+
+        if key_id >= renew_at {
+            let reason = events::PathSecretRenewalReason::KeyUses;
+            if !self.state.request_renewal(entry, reason) {
+                self.state.request_handshake(entry.peer);
+            }
+        }
+
+        true
     }
 
     pub fn seal_once(
         &self,
         peer: SocketAddr,
     ) -> Option<(seal::Once, Credentials, ApplicationParams)> {
-        let state = self.state.peers.get_by_key(&peer)?;
+        let state = self.peer_entry(&peer)?;
         let (sealer, credentials) = state.uni_sealer();
+        ensure!(self.on_local_key_use(&state, credentials.key_id), None);
         Some((sealer, credentials, state.parameters.clone()))
     }
 
@@ -328,8 +648,9 @@ impl Map {
     /// Note that unlike by-IP lookup this should typically not be done significantly after the
     /// original secret was used for decryption.
     pub fn seal_once_id(&self, id: Id) -> Option<(seal::Once, Credentials, ApplicationParams)> {
-        let state = self.state.ids.get_by_key(&id)?;
+        let state = self.state.ids.get_by_key(&id)?.clone();
         let (sealer, credentials) = state.uni_sealer();
+        ensure!(self.on_local_key_use(&state, credentials.key_id), None);
         Some((sealer, credentials, state.parameters.clone()))
     }
 
@@ -348,8 +669,9 @@ impl Map {
         peer: SocketAddr,
         features: &TransportFeatures,
     ) -> Option<(Bidirectional, ApplicationParams)> {
-        let state = self.state.peers.get_by_key(&peer)?;
+        let state = self.peer_entry(&peer)?;
         let keys = state.bidi_local(features);
+        ensure!(self.on_local_key_use(&state, keys.credentials.key_id), None);
 
         Some((keys, state.parameters.clone()))
     }
@@ -361,6 +683,7 @@ impl Map {
         control_out: &mut Vec<u8>,
     ) -> Option<(Bidirectional, ApplicationParams)> {
         let state = self.pre_authentication(credentials, control_out)?;
+        state.used.store(true, Ordering::Relaxed);
 
         let params = state.parameters.clone();
         let keys = state.bidi_remote(self.clone(), credentials, features);
@@ -385,6 +708,7 @@ impl Map {
             }
             Packet::StaleKey(packet) => self.handle_control_packet(&(*packet).into()),
             Packet::ReplayDetected(packet) => self.handle_control_packet(&(*packet).into()),
+            Packet::ServerBusy(packet) => self.handle_control_packet(&(*packet).into()),
             Packet::UnknownPathSecret(packet) => self.handle_control_packet(&(*packet).into()),
         }
     }
@@ -454,16 +778,84 @@ impl Map {
                 // de-duplicated).
                 self.state.request_handshake(state.peer);
             }
+            control::Packet::ServerBusy(packet) => {
+                // the stream was already rejected by the peer so there's nothing to update on
+                // the path
+                if packet.authenticate(&key).is_none() {
+                    return;
+                }
+                self.state
+                    .handled_control_packets
+                    .fetch_add(1, Ordering::Relaxed);
+            }
             control::Packet::UnknownPathSecret(_) => unreachable!(),
         }
     }
 
+    /// Authenticates a packet from a peer which rejected a stream because it was overloaded
+    ///
+    /// Returns the amount of time the peer asked us to wait before opening another stream.
+    pub fn handle_server_busy_packet(
+        &self,
+        packet: &control::server_busy::Packet,
+    ) -> Option<Duration> {
+        let state = self.state.ids.get_by_key(packet.credential_id())?;
+        let key = state.sender.control_secret(&state.secret);
+        let packet = packet.authenticate(&key)?;
+
+        self.state
+            .handled_control_packets
+            .fetch_add(1, Ordering::Relaxed);
+
+        Some(packet.retry_after())
+    }
+
+    /// Writes a packet into `control_out` telling the peer the stream using `credentials` was
+    /// rejected because the server is overloaded
+    ///
+    /// Returns `false` if the path secret is no longer in the map.
+    pub fn server_busy_packet(
+        &self,
+        credentials: &Credentials,
+        retry_after: Duration,
+        control_out: &mut Vec<u8>,
+    ) -> bool {
+        let Some(state) = self.state.ids.get_by_key(&credentials.id) else {
+            return false;
+        };
+
+        let retry_after_ms = retry_after.as_millis().min(VarInt::MAX.as_u64() as u128) as u64;
+        let packet = control::ServerBusy {
+            wire_version: WireVersion::ZERO,
+            credential_id: credentials.id,
+            rejected_key_id: credentials.key_id,
+            retry_after_ms: VarInt::new(retry_after_ms).unwrap_or(VarInt::MAX),
+        };
+
+        let mut buffer = [0; control::MAX_PACKET_SIZE];
+        let len = packet.encode(
+            EncoderBuffer::new(&mut buffer),
+            &state.secret.control_sealer(),
+        );
+        control_out.clear();
+        control_out.extend_from_slice(&buffer[..len]);
+
+        true
+    }
+
     fn pre_authentication(
         &self,
         identity: &Credentials,
         control_out: &mut Vec<u8>,
     ) -> Option<Arc<Entry>> {
-        let Some(state) = self.state.ids.get_by_key(&identity.id) else {
+        // Key IDs past the receiver's limit are handled as if the secret was unknown, which
+        // makes the peer handshake again.
+        let state = self
+            .state
+            .ids
+            .get_by_key(&identity.id)
+            .filter(|state| *identity.key_id < state.key_id_limits.receiver);
+        let Some(state) = state else {
             let packet = control::UnknownPathSecret {
                 wire_version: WireVersion::ZERO,
                 credential_id: identity.id,
@@ -498,6 +890,10 @@ impl Map {
             panic!("inserting a path secret ID twice");
         }
 
+        if let Some(key_log) = self.state.key_log.as_ref() {
+            key_log.write(&entry.secret);
+        }
+
         if let Some(prev) = self.state.peers.insert(peer, entry) {
             // This shouldn't happen due to the panic above, but just in case something went wrong
             // with the secret map we double check here.
@@ -505,6 +901,15 @@ impl Map {
             assert_ne!(*prev.secret.id(), id, "duplicate path secret id");
 
             prev.retire(self.state.cleaner.epoch());
+
+            let peer: inet::SocketAddress = peer.into();
+            let age = prev.creation_time.elapsed();
+            self.state.publish(&prev, |publisher| {
+                publisher.on_path_secret_map_entry_renewed(events::PathSecretMapEntryRenewed {
+                    peer_address: (&peer).into_event(),
+                    age,
+                });
+            });
         }
     }
 
@@ -641,15 +1046,40 @@ pub(super) struct Entry {
     sender: sender::State,
     receiver: receiver::State,
     parameters: ApplicationParams,
+    // Set when the entry is used and cleared by the cleaner
+    used: AtomicBool,
+    renewal_requested: AtomicBool,
+    key_id_limits: KeyIdLimits,
+}
+
+/// Exclusive upper bounds on the key IDs an entry can use
+///
+/// Entries restored from a snapshot may only use the key IDs which were reserved for them.
+#[derive(Clone, Copy, Debug)]
+struct KeyIdLimits {
+    sender: u64,
+    receiver: u64,
+}
+
+impl Default for KeyIdLimits {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            sender: u64::MAX,
+            receiver: u64::MAX,
+        }
+    }
 }
 
 impl SizeOf for Instant {}
 impl SizeOf for u32 {}
 impl SizeOf for SocketAddr {}
 impl SizeOf for AtomicU64 {}
+impl SizeOf for AtomicBool {}
 
 impl SizeOf for IsRetired {}
 impl SizeOf for ApplicationParams {}
+impl SizeOf for KeyIdLimits {}
 
 impl SizeOf for Entry {
     fn size(&self) -> usize {
@@ -662,6 +1092,9 @@ impl SizeOf for Entry {
             sender,
             receiver,
             parameters,
+            used,
+            renewal_requested,
+            key_id_limits,
         } = self;
         creation_time.size()
             + rehandshake_delta_secs.size()
@@ -671,6 +1104,9 @@ impl SizeOf for Entry {
             + sender.size()
             + receiver.size()
             + parameters.size()
+            + used.size()
+            + renewal_requested.size()
+            + key_id_limits.size()
     }
 }
 
@@ -725,18 +1161,28 @@ impl Entry {
             .fetch_min(crate::stream::MAX_DATAGRAM_SIZE as _, Ordering::Relaxed);
 
         assert!(rehandshake_time.as_secs() <= u32::MAX as u64);
+
+        // Schedule another handshake sometime in [5 minutes, rehandshake_time] from now.
+        let max_delta = rehandshake_time.as_secs();
+        let min_delta = std::cmp::min(max_delta, 360);
+        let rehandshake_delta_secs = if min_delta < max_delta {
+            rand::thread_rng().gen_range(min_delta..max_delta)
+        } else {
+            max_delta
+        } as u32;
+
         Self {
             creation_time: Instant::now(),
-            // Schedule another handshake sometime in [5 minutes, rehandshake_time] from now.
-            rehandshake_delta_secs: rand::thread_rng().gen_range(
-                std::cmp::min(rehandshake_time.as_secs(), 360)..rehandshake_time.as_secs(),
-            ) as u32,
+            rehandshake_delta_secs,
             peer,
             secret,
             retired: Default::default(),
             sender,
             receiver,
             parameters,
+            used: AtomicBool::new(false),
+            renewal_requested: AtomicBool::new(false),
+            key_id_limits: Default::default(),
         }
     }
 
@@ -1018,6 +1464,9 @@ impl dc::Path for HandshakingPath {
             s2n_quic_core::crypto::tls::CipherSuite::TLS_AES_256_GCM_SHA384 => {
                 schedule::Ciphersuite::AES_GCM_256_SHA384
             }
+            s2n_quic_core::crypto::tls::CipherSuite::TLS_CHACHA20_POLY1305_SHA256 => {
+                schedule::Ciphersuite::CHACHA20_POLY1305_SHA256
+            }
             _ => return Err(s2n_quic_core::transport::Error::INTERNAL_ERROR),
         };
 
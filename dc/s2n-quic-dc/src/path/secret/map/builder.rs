@@ -0,0 +1,113 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::Map;
+use crate::{
+    event,
+    path::secret::{key_log, stateless_reset},
+};
+use std::{io, sync::Arc, time::Duration};
+
+/// The default maximum number of entries in a [`Map`]
+pub const DEFAULT_CAPACITY: usize = 50_000;
+
+/// The default amount of time after which a handshake is scheduled with a peer
+pub const DEFAULT_REHANDSHAKE_PERIOD: Duration = Duration::from_secs(3600 * 24);
+
+/// Configures a [`Map`]
+///
+/// By default entries live until they are replaced or pushed out by capacity, and have no limit
+/// on the number of keys derived from them.
+pub struct Builder {
+    pub(super) signer: stateless_reset::Signer,
+    pub(super) capacity: usize,
+    pub(super) rehandshake_period: Duration,
+    pub(super) max_entry_lifetime: Option<Duration>,
+    pub(super) max_key_uses: Option<u64>,
+    pub(super) subscriber: Option<Arc<dyn event::DynSubscriber>>,
+    pub(super) key_log: Option<key_log::Writer>,
+}
+
+impl Builder {
+    pub(super) fn new(signer: stateless_reset::Signer) -> Self {
+        Self {
+            signer,
+            capacity: DEFAULT_CAPACITY,
+            rehandshake_period: DEFAULT_REHANDSHAKE_PERIOD,
+            max_entry_lifetime: None,
+            max_key_uses: None,
+            subscriber: None,
+            key_log: None,
+        }
+    }
+
+    /// Sets the maximum number of entries in the map
+    ///
+    /// Defaults to [`DEFAULT_CAPACITY`].
+    pub fn with_capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity;
+        self
+    }
+
+    /// Sets the period after which a new handshake is scheduled with a peer
+    ///
+    /// Each entry picks a random point in the period so handshakes with many peers are spread out.
+    pub fn with_rehandshake_period(mut self, period: Duration) -> Self {
+        self.rehandshake_period = period;
+        self
+    }
+
+    /// Sets the maximum amount of time an entry can be used after its handshake
+    ///
+    /// Expired entries are evicted and a new handshake is required before the peer can be used
+    /// again. Rehandshakes are scheduled to happen before entries expire.
+    pub fn with_max_entry_lifetime(mut self, lifetime: Duration) -> Self {
+        self.max_entry_lifetime = Some(lifetime);
+        self
+    }
+
+    /// Sets the maximum number of keys which can be derived from an entry for locally-initiated
+    /// streams and datagrams
+    ///
+    /// A rehandshake is scheduled once three quarters of the keys have been used and the entry is
+    /// evicted once all of them have been used.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_key_uses` is zero.
+    pub fn with_max_key_uses(mut self, max_key_uses: u64) -> Self {
+        assert!(max_key_uses > 0, "max_key_uses must be non-zero");
+        self.max_key_uses = Some(max_key_uses);
+        self
+    }
+
+    /// Sets the subscriber for path secret map events
+    ///
+    /// The subscriber also receives the congestion control events for streams using the map.
+    pub fn with_event_subscriber<S>(mut self, subscriber: S) -> Self
+    where
+        S: event::Subscriber + Sync,
+    {
+        self.subscriber = Some(Arc::new(subscriber));
+        self
+    }
+
+    /// Writes each path secret inserted into the map to the given key log
+    ///
+    /// See [`key_log`] for the format. The key log can be loaded by the dcQUIC Wireshark plugin
+    /// to decrypt captured packets.
+    ///
+    /// **Warning**: the key log allows anyone who can read it to decrypt and forge traffic for
+    /// the logged path secrets. It should only be enabled for debugging.
+    pub fn with_key_log<W>(mut self, writer: W) -> Self
+    where
+        W: io::Write + Send + 'static,
+    {
+        self.key_log = Some(key_log::Writer::new(writer));
+        self
+    }
+
+    pub fn build(self) -> Map {
+        Map::from_builder(self)
+    }
+}
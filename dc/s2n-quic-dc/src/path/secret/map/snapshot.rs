@@ -0,0 +1,460 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Sealed snapshots of the path secret map
+//!
+//! A restarted process starts with an empty map, which forces every peer to handshake with it at
+//! the same time. [`Map::export_snapshot`] seals the map's live entries under a caller-provided
+//! [`Key`] so they can be restored with [`Map::import_snapshot`] once the process starts back up.
+//!
+//! The snapshot is laid out as:
+//!
+//! ```text
+//! MAGIC || VERSION (u16) || nonce (12 bytes) || AES-256-GCM sealed entries || tag (16 bytes)
+//! ```
+//!
+//! The magic and version are authenticated along with the entries. Snapshots written with a
+//! different version are rejected instead of being partially restored.
+//!
+//! Key IDs must never be reused for a path secret: a reused sender key ID reuses a key and nonce
+//! and a reused receiver key ID accepts replayed packets. Snapshots guard against this by
+//! reserving disjoint ranges of key IDs:
+//!
+//! * Exporting is terminal. The exported entries are removed from the map so the exporting process
+//!   stops using them. Streams which were being opened while the snapshot was taken can still use
+//!   a few more key IDs, which is covered by skipping [`KEY_ID_MARGIN`] key IDs on import.
+//! * Every import of a snapshot is given a `generation` by the caller, which must be different for
+//!   each import. Generation `n` restores entries with [`KEY_ID_WINDOW`] key IDs starting at
+//!   `recorded + KEY_ID_MARGIN + n * KEY_ID_WINDOW` for both the sender and the receiver.
+//! * Restored entries are renewed once they have sent on 3/4 of their window and stop using key
+//!   IDs past it. Peers sending past the receiver's window are told the path secret is unknown and
+//!   handshake again.
+//!
+//! Peers which send with key IDs below the restored window are sent a `StaleKey` packet and
+//! resynchronize on their own.
+//!
+//! **Warning**: anyone with access to the snapshot key can decrypt and forge traffic for every
+//! path secret in the snapshot. It should be protected as carefully as the TLS private key.
+
+use super::{Entry, KeyIdLimits, Map};
+use crate::{
+    packet::secret_control::TAG_LEN,
+    path::secret::{
+        schedule::{Ciphersuite, ExportSecret, Secret, EXPORT_SECRET_LEN},
+        sender,
+    },
+};
+use aws_lc_rs::aead::{self, Aad, LessSafeKey, Nonce, UnboundKey, NONCE_LEN};
+use core::fmt;
+use s2n_codec::{decoder_value, DecoderBuffer, DecoderError, Encoder, EncoderBuffer, EncoderValue};
+use s2n_quic_core::{
+    dc::{self, ApplicationParams},
+    endpoint,
+    varint::VarInt,
+};
+use std::{
+    io,
+    net::{IpAddr, SocketAddr},
+    num::NonZeroU32,
+    sync::{atomic::Ordering, Arc},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+use zeroize::Zeroizing;
+
+/// Identifies the file as a path secret map snapshot
+pub const MAGIC: [u8; 8] = *b"DCQUICPS";
+
+/// The version of the snapshot format
+///
+/// This is incremented on any change to the format.
+pub const VERSION: u16 = 1;
+
+/// The length of the [`Key`] used to seal snapshots
+pub const KEY_LEN: usize = 32;
+
+/// The number of key IDs restored entries skip ahead by
+///
+/// This covers streams which were opened concurrently with the export.
+pub const KEY_ID_MARGIN: u64 = 1 << 16;
+
+/// The number of key IDs reserved for each import of a snapshot
+pub const KEY_ID_WINDOW: u64 = 1 << 20;
+
+const AAD_LEN: usize = MAGIC.len() + 2;
+const HEADER_LEN: usize = AAD_LEN + NONCE_LEN;
+
+/// The key used to seal and open snapshots
+pub struct Key(LessSafeKey);
+
+impl Key {
+    #[inline]
+    pub fn new(key: &[u8; KEY_LEN]) -> Self {
+        let key = UnboundKey::new(&aead::AES_256_GCM, key).expect("key length is checked");
+        Self(LessSafeKey::new(key))
+    }
+}
+
+impl fmt::Debug for Key {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // don't print the key
+        f.debug_struct("Key").finish_non_exhaustive()
+    }
+}
+
+#[inline]
+fn invalid(error: impl fmt::Display) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, error.to_string())
+}
+
+#[inline]
+fn unix_time_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |time| time.as_secs())
+}
+
+pub(super) fn export(map: &Map, key: &Key, mut writer: impl io::Write) -> io::Result<usize> {
+    let now = Instant::now();
+
+    let mut entries = vec![];
+    map.state.peers.iter(|_, entry| {
+        if !entry.retired.retired() && !map.state.is_expired(entry, now) {
+            entries.push(entry.clone());
+        }
+    });
+
+    // Remove the entries before recording their key IDs so new streams can't use them anymore.
+    // Only streams which already looked up an entry can still use a key ID past the recorded one.
+    for entry in &entries {
+        let is_entry = |v: &Arc<Entry>| Arc::ptr_eq(v, entry);
+        map.state.peers.remove_if(&entry.peer, is_entry);
+        map.state.ids.remove_if(entry.secret.id(), is_entry);
+    }
+
+    let records: Vec<_> = entries
+        .iter()
+        .map(|entry| Record::new(entry, now))
+        .collect();
+    let exported_at = unix_time_secs();
+
+    // allocate everything up front so the secrets aren't left behind in a reallocation
+    let payload_len =
+        exported_at.encoding_size() + records.iter().map(|r| r.encoding_size()).sum::<usize>();
+    let mut out = Zeroizing::new(vec![0u8; HEADER_LEN + payload_len + aead::MAX_TAG_LEN]);
+
+    let mut nonce = [0u8; NONCE_LEN];
+    aws_lc_rs::rand::fill(&mut nonce).map_err(|_| invalid("could not generate a nonce"))?;
+
+    let (header, payload) = out.split_at_mut(HEADER_LEN);
+    let (payload, tag_out) = payload.split_at_mut(payload_len);
+
+    {
+        let mut encoder = EncoderBuffer::new(header);
+        encoder.write_slice(&MAGIC);
+        encoder.encode(&VERSION);
+        encoder.write_slice(&nonce);
+    }
+
+    {
+        let mut encoder = EncoderBuffer::new(payload);
+        encoder.encode(&exported_at);
+        for record in &records {
+            encoder.encode(record);
+        }
+    }
+
+    let tag = key
+        .0
+        .seal_in_place_separate_tag(
+            Nonce::assume_unique_for_key(nonce),
+            Aad::from(&header[..AAD_LEN]),
+            payload,
+        )
+        .map_err(|_| invalid("could not seal the snapshot"))?;
+    tag_out.copy_from_slice(tag.as_ref());
+
+    writer.write_all(&out)?;
+    writer.flush()?;
+
+    Ok(records.len())
+}
+
+pub(super) fn import(
+    map: &Map,
+    key: &Key,
+    generation: u32,
+    mut reader: impl io::Read,
+) -> io::Result<usize> {
+    let mut snapshot = Zeroizing::new(vec![]);
+    reader.read_to_end(&mut snapshot)?;
+
+    if snapshot.len() < HEADER_LEN + aead::MAX_TAG_LEN {
+        return Err(invalid("snapshot is truncated"));
+    }
+
+    let (header, sealed) = snapshot.split_at_mut(HEADER_LEN);
+    let (magic, rest) = header.split_at(MAGIC.len());
+    let (version, nonce) = rest.split_at(2);
+
+    if magic != MAGIC {
+        return Err(invalid("not a path secret map snapshot"));
+    }
+
+    let version = u16::from_be_bytes([version[0], version[1]]);
+    if version != VERSION {
+        return Err(invalid(format!("unsupported snapshot version: {version}")));
+    }
+
+    let nonce = Nonce::try_assume_unique_for_key(nonce).expect("nonce length is checked");
+    let payload = key
+        .0
+        .open_in_place(nonce, Aad::from(&header[..AAD_LEN]), sealed)
+        .map_err(|_| invalid("snapshot could not be authenticated"))?;
+
+    // decode everything before restoring any entries
+    let decode = || -> Result<_, DecoderError> {
+        let buffer = DecoderBuffer::new(payload);
+        let (exported_at, mut buffer) = buffer.decode::<u64>()?;
+        let mut records = vec![];
+        while !buffer.is_empty() {
+            let (record, remaining) = buffer.decode::<Record>()?;
+            records.push(record);
+            buffer = remaining;
+        }
+        Ok((exported_at, records))
+    };
+    let (exported_at, records) =
+        decode().map_err(|err| invalid(format!("malformed snapshot: {err}")))?;
+
+    // account for the time the process was down
+    let downtime = Duration::from_secs(unix_time_secs().saturating_sub(exported_at));
+    let now = Instant::now();
+
+    let mut restored = 0;
+    for record in records {
+        let state = &map.state;
+
+        // don't replace entries from handshakes which completed after the restart
+        if state.peers.contains_key(&record.peer) {
+            continue;
+        }
+
+        let Some(entry) = record.into_entry(map, generation, downtime, now) else {
+            continue;
+        };
+
+        if state.ids.contains_key(entry.secret.id()) || state.is_expired(&entry, now) {
+            continue;
+        }
+
+        map.insert(Arc::new(entry));
+        restored += 1;
+    }
+
+    Ok(restored)
+}
+
+/// A single exported map entry
+struct Record {
+    peer: SocketAddr,
+    endpoint: endpoint::Type,
+    ciphersuite: Ciphersuite,
+    export_secret: Zeroizing<ExportSecret>,
+    stateless_reset: [u8; TAG_LEN],
+    next_key_id: VarInt,
+    min_unseen_key_id: VarInt,
+    age: Duration,
+    parameters: ApplicationParams,
+}
+
+impl Record {
+    #[inline]
+    fn new(entry: &Entry, now: Instant) -> Self {
+        Self {
+            peer: entry.peer,
+            endpoint: entry.secret.endpoint(),
+            ciphersuite: entry.secret.ciphersuite(),
+            export_secret: Zeroizing::new(*entry.secret.export_secret()),
+            stateless_reset: entry.sender.stateless_reset,
+            next_key_id: entry.sender.peek_key_id(),
+            min_unseen_key_id: entry.receiver.minimum_unseen_key_id(),
+            age: now.saturating_duration_since(entry.creation_time),
+            parameters: entry.parameters.clone(),
+        }
+    }
+
+    #[inline]
+    fn into_entry(
+        self,
+        map: &Map,
+        generation: u32,
+        downtime: Duration,
+        now: Instant,
+    ) -> Option<Entry> {
+        // reserve a window of key IDs which no other import of this snapshot uses
+        let offset = KEY_ID_MARGIN + generation as u64 * KEY_ID_WINDOW;
+        let window = |key_id: VarInt| {
+            let start = VarInt::new(key_id.as_u64().checked_add(offset)?).ok()?;
+            let end = VarInt::new(start.as_u64().checked_add(KEY_ID_WINDOW)?).ok()?;
+            Some((start, end.as_u64()))
+        };
+        let (next_key_id, sender_limit) = window(self.next_key_id)?;
+        let (min_unseen_key_id, receiver_limit) = window(self.min_unseen_key_id)?;
+
+        // the version isn't used to derive keys
+        let secret = Secret::new(
+            self.ciphersuite,
+            dc::SUPPORTED_VERSIONS[0],
+            self.endpoint,
+            &self.export_secret,
+        );
+
+        let sender = sender::State::new(self.stateless_reset);
+        sender.update_for_stale_key(next_key_id);
+
+        let receiver = map.state.receiver_shared.clone().new_receiver();
+        receiver.skip_to(min_unseen_key_id);
+
+        let mut entry = Entry::new(
+            self.peer,
+            secret,
+            sender,
+            receiver,
+            self.parameters,
+            map.state.rehandshake_period,
+        );
+        entry.key_id_limits = KeyIdLimits {
+            sender: sender_limit,
+            receiver: receiver_limit,
+        };
+        // keep the original age so entries are still renewed and expired on schedule
+        entry.creation_time = now
+            .checked_sub(self.age.saturating_add(downtime))
+            .unwrap_or(now);
+
+        Some(entry)
+    }
+}
+
+impl EncoderValue for Record {
+    #[inline]
+    fn encode<E: Encoder>(&self, encoder: &mut E) {
+        match self.peer.ip() {
+            IpAddr::V4(ip) => {
+                encoder.encode(&4u8);
+                encoder.write_slice(&ip.octets());
+            }
+            IpAddr::V6(ip) => {
+                encoder.encode(&6u8);
+                encoder.write_slice(&ip.octets());
+            }
+        }
+        encoder.encode(&self.peer.port());
+
+        let endpoint: u8 = match self.endpoint {
+            endpoint::Type::Client => 0,
+            endpoint::Type::Server => 1,
+        };
+        encoder.encode(&endpoint);
+
+        let ciphersuite: u8 = match self.ciphersuite {
+            Ciphersuite::AES_GCM_128_SHA256 => 0,
+            Ciphersuite::AES_GCM_256_SHA384 => 1,
+            Ciphersuite::CHACHA20_POLY1305_SHA256 => 2,
+        };
+        encoder.encode(&ciphersuite);
+
+        encoder.write_slice(&*self.export_secret);
+        encoder.write_slice(&self.stateless_reset);
+        encoder.encode(&self.next_key_id);
+        encoder.encode(&self.min_unseen_key_id);
+        encoder.encode(&(self.age.as_millis().min(u64::MAX as u128) as u64));
+
+        let parameters = &self.parameters;
+        encoder.encode(&parameters.max_datagram_size.load(Ordering::Relaxed));
+        encoder.encode(&parameters.remote_max_data);
+        encoder.encode(&parameters.local_send_max_data);
+        encoder.encode(&parameters.local_recv_max_data);
+        encoder.encode(&parameters.max_idle_timeout.map_or(0, NonZeroU32::get));
+    }
+}
+
+decoder_value!(
+    impl<'a> Record {
+        fn decode(buffer: Buffer) -> Result<Self> {
+            let (family, buffer) = buffer.decode::<u8>()?;
+            let (ip, buffer): (IpAddr, _) = match family {
+                4 => {
+                    let (octets, buffer) = buffer.decode_slice(4)?;
+                    let octets: [u8; 4] = octets.into_less_safe_slice().try_into().unwrap();
+                    (octets.into(), buffer)
+                }
+                6 => {
+                    let (octets, buffer) = buffer.decode_slice(16)?;
+                    let octets: [u8; 16] = octets.into_less_safe_slice().try_into().unwrap();
+                    (octets.into(), buffer)
+                }
+                _ => return Err(DecoderError::InvariantViolation("invalid address family")),
+            };
+            let (port, buffer) = buffer.decode::<u16>()?;
+
+            let (endpoint, buffer) = buffer.decode::<u8>()?;
+            let endpoint = match endpoint {
+                0 => endpoint::Type::Client,
+                1 => endpoint::Type::Server,
+                _ => return Err(DecoderError::InvariantViolation("invalid endpoint type")),
+            };
+
+            let (ciphersuite, buffer) = buffer.decode::<u8>()?;
+            let ciphersuite = match ciphersuite {
+                0 => Ciphersuite::AES_GCM_128_SHA256,
+                1 => Ciphersuite::AES_GCM_256_SHA384,
+                2 => Ciphersuite::CHACHA20_POLY1305_SHA256,
+                _ => return Err(DecoderError::InvariantViolation("invalid ciphersuite")),
+            };
+
+            let (secret, buffer) = buffer.decode_slice(EXPORT_SECRET_LEN)?;
+            let mut export_secret = Zeroizing::new([0; EXPORT_SECRET_LEN]);
+            export_secret.copy_from_slice(secret.into_less_safe_slice());
+
+            let (stateless_reset, buffer) = buffer.decode_slice(TAG_LEN)?;
+            let stateless_reset = stateless_reset.into_less_safe_slice().try_into().unwrap();
+
+            let (next_key_id, buffer) = buffer.decode::<VarInt>()?;
+            let (min_unseen_key_id, buffer) = buffer.decode::<VarInt>()?;
+            let (age, buffer) = buffer.decode::<u64>()?;
+
+            let (max_datagram_size, buffer) = buffer.decode::<u16>()?;
+            let (remote_max_data, buffer) = buffer.decode::<VarInt>()?;
+            let (local_send_max_data, buffer) = buffer.decode::<VarInt>()?;
+            let (local_recv_max_data, buffer) = buffer.decode::<VarInt>()?;
+            let (max_idle_timeout, buffer) = buffer.decode::<u32>()?;
+
+            // the parameters can only be constructed from the handshake's limits, so start from the
+            // defaults and restore each field
+            let mut parameters =
+                ApplicationParams::new(max_datagram_size, &Default::default(), &Default::default());
+            parameters.remote_max_data = remote_max_data;
+            parameters.local_send_max_data = local_send_max_data;
+            parameters.local_recv_max_data = local_recv_max_data;
+            parameters.max_idle_timeout = NonZeroU32::new(max_idle_timeout);
+
+            let record = Record {
+                peer: SocketAddr::new(ip, port),
+                endpoint,
+                ciphersuite,
+                export_secret,
+                stateless_reset,
+                next_key_id,
+                min_unseen_key_id,
+                age: Duration::from_millis(age),
+                parameters,
+            };
+
+            Ok((record, buffer))
+        }
+    }
+);
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,308 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::{
+    credentials::Credentials, packet::secret_control as control, path::secret::stateless_reset,
+};
+use s2n_codec::DecoderBufferMut;
+
+fn map() -> Map {
+    Map::new(stateless_reset::Signer::random(), 100)
+}
+
+fn peer(idx: u16) -> SocketAddr {
+    SocketAddr::from(([127, 0, 0, 1], 4000 + idx))
+}
+
+fn entry(map: &Map, peer: SocketAddr) -> Arc<Entry> {
+    map.state.peers.get_by_key(&peer).unwrap().clone()
+}
+
+fn export(map: &Map, key: &Key) -> Vec<u8> {
+    let mut snapshot = vec![];
+    map.export_snapshot(key, &mut snapshot).unwrap();
+    snapshot
+}
+
+#[test]
+fn round_trip() {
+    let key = Key::new(&[1; KEY_LEN]);
+    let original = map();
+    for idx in 0..10 {
+        original.test_insert(peer(idx));
+    }
+
+    // use some key IDs before exporting
+    let (_sealer, credentials, _params) = original.seal_once(peer(0)).unwrap();
+    assert_eq!(*credentials.key_id, 0);
+
+    let originals: Vec<_> = (0..10).map(|idx| entry(&original, peer(idx))).collect();
+    let snapshot = export(&original, &key);
+
+    // exporting retires the entries
+    assert_eq!(original.secrets_len(), 0);
+    assert!(!original.contains(peer(0)));
+
+    let restored = map();
+    let count = restored.import_snapshot(&key, 0, &snapshot[..]).unwrap();
+    assert_eq!(count, 10);
+    assert_eq!(restored.secrets_len(), 10);
+
+    for idx in 0..10 {
+        let peer = peer(idx);
+        assert!(restored.contains(peer));
+
+        let expected = &originals[idx as usize];
+        let actual = entry(&restored, peer);
+        assert_eq!(actual.secret.id(), expected.secret.id());
+        assert_eq!(
+            actual.secret.export_secret(),
+            expected.secret.export_secret()
+        );
+        assert_eq!(actual.secret.ciphersuite(), expected.secret.ciphersuite());
+        assert_eq!(actual.secret.endpoint(), expected.secret.endpoint());
+        assert_eq!(
+            actual.sender.stateless_reset,
+            expected.sender.stateless_reset
+        );
+        assert_eq!(
+            format!("{:?}", actual.parameters),
+            format!("{:?}", expected.parameters)
+        );
+        // the age should be carried over
+        assert!(actual.creation_time <= expected.creation_time + Duration::from_secs(1));
+    }
+}
+
+#[test]
+fn key_ids_skip_ahead() {
+    let key = Key::new(&[2; KEY_LEN]);
+    let original = map();
+    original.test_insert(peer(0));
+
+    for expected in 0..3 {
+        let (_sealer, credentials, _params) = original.seal_once(peer(0)).unwrap();
+        assert_eq!(*credentials.key_id, expected);
+    }
+
+    let snapshot = export(&original, &key);
+    let restored = map();
+    restored.import_snapshot(&key, 0, &snapshot[..]).unwrap();
+
+    // the sender must never reuse a key ID from before the restart
+    let (_sealer, credentials, _params) = restored.seal_once(peer(0)).unwrap();
+    assert_eq!(*credentials.key_id, 3 + KEY_ID_MARGIN);
+
+    // the receiver rejects any key ID which the previous process may have accepted
+    let entry = entry(&restored, peer(0));
+    let id = *entry.secret.id();
+    for key_id in [0, 1, KEY_ID_MARGIN] {
+        let credentials = Credentials {
+            id,
+            key_id: VarInt::new(key_id).unwrap(),
+        };
+        assert!(entry.receiver.pre_authentication(&credentials).is_err());
+    }
+    assert_eq!(
+        *entry.receiver.minimum_unseen_key_id(),
+        1 + KEY_ID_MARGIN,
+        "peers should be told to resume after the margin"
+    );
+
+    let credentials = Credentials {
+        id,
+        key_id: VarInt::new(1 + KEY_ID_MARGIN).unwrap(),
+    };
+    assert!(entry.receiver.pre_authentication(&credentials).is_ok());
+    assert!(entry.receiver.post_authentication(&credentials).is_ok());
+}
+
+/// Returns true if the map responded to `key_id` with an `UnknownPathSecret` packet
+fn is_unknown(map: &Map, id: crate::credentials::Id, key_id: u64) -> bool {
+    let credentials = Credentials {
+        id,
+        key_id: VarInt::new(key_id).unwrap(),
+    };
+    let mut control_out = vec![];
+    if map.open_once(&credentials, &mut control_out).is_some() {
+        return false;
+    }
+    matches!(
+        control::Packet::decode(DecoderBufferMut::new(&mut control_out)),
+        Ok((control::Packet::UnknownPathSecret(_), _))
+    )
+}
+
+#[test]
+fn traffic_after_export() {
+    let key = Key::new(&[7; KEY_LEN]);
+    let original = map();
+    original.test_insert(peer(0));
+
+    for _ in 0..3 {
+        original.seal_once(peer(0)).unwrap();
+    }
+
+    // a stream which looked up the entry before the export
+    let in_flight = entry(&original, peer(0));
+    let id = *in_flight.secret.id();
+
+    let snapshot = export(&original, &key);
+
+    // the exporting process can't open any more streams with the exported entries
+    assert!(original.seal_once(peer(0)).is_none());
+    assert!(original.seal_once_id(id).is_none());
+    assert!(is_unknown(&original, id, 10));
+
+    // streams which were being opened during the export stay within the margin
+    let mut last = 0;
+    for _ in 0..100 {
+        last = *in_flight.sender.next_key_id();
+    }
+
+    let restored = map();
+    restored.import_snapshot(&key, 0, &snapshot[..]).unwrap();
+    let (_sealer, credentials, _params) = restored.seal_once(peer(0)).unwrap();
+    assert!(last < *credentials.key_id);
+}
+
+#[test]
+fn double_import() {
+    let key = Key::new(&[8; KEY_LEN]);
+    let original = map();
+    original.test_insert(peer(0));
+    let snapshot = export(&original, &key);
+
+    let first = map();
+    first.import_snapshot(&key, 0, &snapshot[..]).unwrap();
+    let second = map();
+    second.import_snapshot(&key, 1, &snapshot[..]).unwrap();
+
+    let first_entry = entry(&first, peer(0));
+    let second_entry = entry(&second, peer(0));
+    let id = *first_entry.secret.id();
+
+    // the imports send with disjoint ranges of key IDs
+    let (_sealer, credentials, _params) = first.seal_once(peer(0)).unwrap();
+    assert_eq!(*credentials.key_id, KEY_ID_MARGIN);
+    let (_sealer, credentials, _params) = second.seal_once(peer(0)).unwrap();
+    assert_eq!(*credentials.key_id, KEY_ID_MARGIN + KEY_ID_WINDOW);
+
+    // the first import stops sending at the start of the second import's window
+    first_entry
+        .sender
+        .update_for_stale_key(VarInt::new(KEY_ID_MARGIN + KEY_ID_WINDOW - 1).unwrap());
+    assert!(first.seal_once(peer(0)).is_some());
+    assert!(first.seal_once(peer(0)).is_none());
+    assert!(!first.contains(peer(0)));
+
+    // key IDs accepted by the first import are rejected by the second
+    let credentials = Credentials {
+        id,
+        key_id: VarInt::new(KEY_ID_MARGIN + 1).unwrap(),
+    };
+    assert!(first_entry
+        .receiver
+        .pre_authentication(&credentials)
+        .is_ok());
+    assert!(second_entry
+        .receiver
+        .pre_authentication(&credentials)
+        .is_err());
+
+    // and the first import doesn't accept key IDs from the second import's window
+    let min_unseen = *second_entry.receiver.minimum_unseen_key_id();
+    assert_eq!(min_unseen, 1 + KEY_ID_MARGIN + KEY_ID_WINDOW);
+    assert!(is_unknown(&first, id, min_unseen));
+    assert!(!is_unknown(&second, id, min_unseen));
+}
+
+#[test]
+fn restored_entries_are_renewed() {
+    let key = Key::new(&[9; KEY_LEN]);
+    let original = map();
+    original.test_insert(peer(0));
+    let snapshot = export(&original, &key);
+
+    let restored = map();
+    restored.import_snapshot(&key, 0, &snapshot[..]).unwrap();
+    let entry = entry(&restored, peer(0));
+
+    entry.sender.update_for_stale_key(
+        VarInt::new(KEY_ID_MARGIN + KEY_ID_WINDOW - KEY_ID_WINDOW / 4).unwrap(),
+    );
+    assert!(restored.seal_once(peer(0)).is_some());
+    // the handshake is requested before the window runs out
+    assert!(!restored.contains(peer(0)));
+}
+
+#[test]
+fn existing_peers_are_kept() {
+    let key = Key::new(&[3; KEY_LEN]);
+    let original = map();
+    original.test_insert(peer(0));
+    original.test_insert(peer(1));
+    let snapshot = export(&original, &key);
+
+    let restored = map();
+    restored.test_insert(peer(0));
+    let existing = *entry(&restored, peer(0)).secret.id();
+
+    let count = restored.import_snapshot(&key, 0, &snapshot[..]).unwrap();
+    assert_eq!(count, 1);
+    assert_eq!(*entry(&restored, peer(0)).secret.id(), existing);
+    assert!(restored.contains(peer(1)));
+}
+
+#[test]
+fn empty_map() {
+    let key = Key::new(&[4; KEY_LEN]);
+    let snapshot = export(&map(), &key);
+
+    let restored = map();
+    assert_eq!(restored.import_snapshot(&key, 0, &snapshot[..]).unwrap(), 0);
+    assert_eq!(restored.secrets_len(), 0);
+}
+
+#[test]
+fn invalid_snapshots() {
+    let key = Key::new(&[5; KEY_LEN]);
+    let original = map();
+    original.test_insert(peer(0));
+    let snapshot = export(&original, &key);
+
+    let import = |key: &Key, snapshot: &[u8]| {
+        let restored = map();
+        let error = restored.import_snapshot(key, 0, snapshot).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(restored.secrets_len(), 0);
+        error.to_string()
+    };
+
+    // wrong key
+    let error = import(&Key::new(&[6; KEY_LEN]), &snapshot);
+    assert!(error.contains("authenticated"), "{error}");
+
+    // tampered entries
+    let mut tampered = snapshot.clone();
+    tampered[HEADER_LEN] ^= 1;
+    let error = import(&key, &tampered);
+    assert!(error.contains("authenticated"), "{error}");
+
+    // unsupported version
+    let mut version = snapshot.clone();
+    version[MAGIC.len()..AAD_LEN].copy_from_slice(&(VERSION + 1).to_be_bytes());
+    let error = import(&key, &version);
+    assert!(error.contains("version"), "{error}");
+
+    // not a snapshot
+    let mut magic = snapshot.clone();
+    magic[0] ^= 1;
+    let error = import(&key, &magic);
+    assert!(error.contains("not a path secret map snapshot"), "{error}");
+
+    // truncated
+    let error = import(&key, &snapshot[..HEADER_LEN]);
+    assert!(error.contains("truncated"), "{error}");
+}
@@ -12,6 +12,10 @@ use super::*;
 const VERSION: dc::Version = dc::SUPPORTED_VERSIONS[0];
 
 fn fake_entry(peer: u16) -> Arc<Entry> {
+    fake_entry_with_rehandshake(peer, dc::testing::TEST_REHANDSHAKE_PERIOD)
+}
+
+fn fake_entry_with_rehandshake(peer: u16, rehandshake_period: Duration) -> Arc<Entry> {
     let mut secret = [0; 32];
     aws_lc_rs::rand::fill(&mut secret).unwrap();
     Arc::new(Entry::new(
@@ -25,7 +29,7 @@ fn fake_entry(peer: u16) -> Arc<Entry> {
         sender::State::new([0; control::TAG_LEN]),
         receiver::State::without_shared(),
         dc::testing::TEST_APPLICATION_PARAMS,
-        dc::testing::TEST_REHANDSHAKE_PERIOD,
+        rehandshake_period,
     ))
 }
 
@@ -334,6 +338,240 @@ fn entry_size() {
 
     // This gates to running only on specific GHA to reduce false positives.
     if should_check {
-        assert_eq!(fake_entry(0).size(), 238);
+        assert_eq!(fake_entry(0).size(), 240);
+    }
+}
+
+/// Records the names of the published events
+#[derive(Clone, Default)]
+struct Events(Arc<Mutex<Vec<&'static str>>>);
+
+impl Events {
+    fn take(&self) -> Vec<&'static str> {
+        core::mem::take(&mut *self.0.lock().unwrap())
+    }
+}
+
+impl event::Subscriber for Events {
+    type ConnectionContext = ();
+
+    fn create_connection_context(
+        &self,
+        _meta: &event::api::ConnectionMeta,
+        _info: &event::api::ConnectionInfo,
+    ) -> Self::ConnectionContext {
+    }
+
+    fn on_event<M: event::Meta, E: event::Event>(&self, _meta: &M, _event: &E) {
+        self.0.lock().unwrap().push(E::NAME);
+    }
+}
+
+fn policy_map(builder: impl FnOnce(Builder) -> Builder) -> (Map, Events) {
+    let events = Events::default();
+    let signer = stateless_reset::Signer::new(b"secret");
+    let map = builder(Map::builder(signer).with_capacity(50))
+        .with_event_subscriber(events.clone())
+        .build();
+
+    // Stop background processing. We expect to manually invoke clean, and a background worker
+    // might interfere with our state.
+    map.state.cleaner.stop();
+
+    (map, events)
+}
+
+#[test]
+fn lifetime_eviction_on_lookup() {
+    let (map, events) = policy_map(|b| b.with_max_entry_lifetime(Duration::from_millis(10)));
+    let peer = SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::LOCALHOST, 1));
+
+    map.test_insert(peer);
+    assert!(map.contains(peer));
+    assert!(map.seal_once(peer).is_some());
+
+    std::thread::sleep(Duration::from_millis(20));
+
+    assert!(!map.contains(peer));
+    assert!(map.seal_once(peer).is_none());
+    assert_eq!(map.secrets_len(), 0);
+    assert_eq!(events.take(), ["path_secret_map:entry_evicted"]);
+}
+
+#[test]
+fn lifetime_eviction_on_clean() {
+    let (map, events) = policy_map(|b| b.with_max_entry_lifetime(Duration::from_millis(10)));
+    let peer = SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::LOCALHOST, 1));
+
+    map.test_insert(peer);
+    std::thread::sleep(Duration::from_millis(20));
+    map.state.cleaner.clean(&map.state, 1);
+
+    assert_eq!(map.secrets_len(), 0);
+    assert_eq!(map.peers_len(), 0);
+    assert_eq!(events.take(), ["path_secret_map:entry_evicted"]);
+}
+
+#[test]
+fn key_uses_exhausted() {
+    let (map, events) = policy_map(|b| b.with_max_key_uses(4));
+    let peer = SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::LOCALHOST, 1));
+
+    map.test_insert(peer);
+
+    for _ in 0..3 {
+        assert!(map.seal_once(peer).is_some());
+    }
+    assert!(map.contains(peer));
+
+    // the last key schedules a handshake on the next request
+    assert!(map.seal_once(peer).is_some());
+    assert!(!map.contains(peer));
+    assert!(events.take().is_empty());
+
+    assert!(map.seal_once(peer).is_none());
+    assert_eq!(map.peers_len(), 0);
+    assert_eq!(events.take(), ["path_secret_map:entry_evicted"]);
+}
+
+#[test]
+fn single_key_use() {
+    let (map, events) = policy_map(|b| b.with_max_key_uses(1));
+    let peer = SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::LOCALHOST, 1));
+
+    map.test_insert(peer);
+
+    assert!(map.seal_once(peer).is_some());
+    assert!(map.seal_once(peer).is_none());
+    assert_eq!(map.peers_len(), 0);
+    assert_eq!(events.take(), ["path_secret_map:entry_evicted"]);
+}
+
+#[test]
+#[should_panic = "max_key_uses must be non-zero"]
+fn zero_key_uses() {
+    let _ = policy_map(|b| b.with_max_key_uses(0));
+}
+
+#[tokio::test]
+async fn key_uses_renewal() {
+    let (map, events) = policy_map(|b| b.with_max_key_uses(4));
+    let peer = SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::LOCALHOST, 1));
+    let renewals = map.renewal_queue();
+
+    map.test_insert(peer);
+    for _ in 0..4 {
+        assert!(map.seal_once(peer).is_some());
+    }
+
+    // the peer is renewed in the background so it can continue to be used in the meantime
+    assert!(map.contains(peer));
+    assert_eq!(renewals.next().await, peer);
+    assert_eq!(events.take(), ["path_secret_map:renewal_requested"]);
+
+    map.test_insert(peer);
+    assert_eq!(events.take(), ["path_secret_map:entry_renewed"]);
+    assert!(map.seal_once(peer).is_some());
+}
+
+#[tokio::test]
+async fn scheduled_renewal() {
+    let (map, events) = policy_map(|b| b);
+    let renewals = map.renewal_queue();
+
+    let idle = fake_entry_with_rehandshake(1, Duration::ZERO);
+    let active = fake_entry_with_rehandshake(2, Duration::ZERO);
+    map.insert(idle.clone());
+    map.insert(active.clone());
+    assert!(map.seal_once(active.peer).is_some());
+
+    map.state.cleaner.clean(&map.state, 1);
+
+    // the active peer is renewed in the background while the idle one waits for the next request
+    assert!(map.contains(active.peer));
+    assert!(!map.contains(idle.peer));
+    assert_eq!(renewals.next().await, active.peer);
+    assert_eq!(events.take(), ["path_secret_map:renewal_requested"]);
+}
+
+#[tokio::test]
+async fn failed_renewal() {
+    let (map, events) = policy_map(|b| b.with_max_key_uses(8));
+    let peer = SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::LOCALHOST, 1));
+    let renewals = map.renewal_queue();
+
+    map.test_insert(peer);
+    for _ in 0..7 {
+        assert!(map.seal_once(peer).is_some());
+    }
+    assert_eq!(renewals.next().await, peer);
+    assert_eq!(events.take(), ["path_secret_map:renewal_requested"]);
+
+    // the peer is handshaked with on the next request
+    map.on_renewal_failed(peer);
+    assert!(!map.contains(peer));
+
+    // and the entry can be queued for renewal again
+    map.state.requested_handshakes.pin().clear();
+    assert!(map.seal_once(peer).is_some());
+    assert_eq!(renewals.next().await, peer);
+    assert_eq!(events.take(), ["path_secret_map:renewal_requested"]);
+}
+
+#[test]
+fn dropped_renewal_queue() {
+    let (map, events) = policy_map(|b| b.with_max_key_uses(8));
+    let peer = SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::LOCALHOST, 1));
+    let renewals = map.renewal_queue();
+
+    map.test_insert(peer);
+    for _ in 0..7 {
+        assert!(map.seal_once(peer).is_some());
+    }
+    assert_eq!(events.take(), ["path_secret_map:renewal_requested"]);
+
+    // the queued peer falls back to handshaking on the next request
+    drop(renewals);
+    assert!(map.state.renewals.queue.lock().unwrap().0.is_empty());
+    assert!(!map.contains(peer));
+
+    // nothing is queued once the consumer is gone
+    assert!(map.seal_once(peer).is_some());
+    assert!(map.state.renewals.queue.lock().unwrap().0.is_empty());
+    assert!(events.take().is_empty());
+}
+
+/// Collects the key log output in memory
+#[derive(Clone, Default)]
+struct KeyLog(Arc<Mutex<Vec<u8>>>);
+
+impl std::io::Write for KeyLog {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn key_log() {
+    let log = KeyLog::default();
+    let (map, _events) = policy_map(|b| b.with_key_log(log.clone()));
+
+    let peers = [1, 2].map(|port| SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::LOCALHOST, port)));
+    for peer in peers {
+        map.test_insert(peer);
+    }
+
+    let entries = key_log::read(&log.0.lock().unwrap()[..]).unwrap();
+    assert_eq!(entries.len(), peers.len());
+
+    for (entry, peer) in entries.iter().zip(peers) {
+        let (_sealer, credentials, _params) = map.seal_once(peer).unwrap();
+        assert_eq!(entry.id, credentials.id);
+        assert_eq!(entry.ciphersuite, schedule::Ciphersuite::AES_GCM_128_SHA256);
     }
 }
@@ -331,6 +331,16 @@ impl State {
         KeyId::try_from(self.max_seen_key_id.load(Ordering::Relaxed) + 1).unwrap()
     }
 
+    /// Rejects every key ID below `key_id` as potentially replayed
+    ///
+    /// This is used when the replay window for the path secret was lost, e.g. after restoring it
+    /// from a snapshot. Peers are told to resume at `key_id` with a `StaleKey` packet.
+    pub fn skip_to(&self, key_id: KeyId) {
+        self.min_key_id.fetch_max(*key_id, Ordering::Relaxed);
+        self.max_seen_key_id
+            .fetch_max(key_id.as_u64().saturating_sub(1), Ordering::Relaxed);
+    }
+
     /// Called after decryption has been performed
     pub fn post_authentication(&self, identity: &Credentials) -> Result<(), Error> {
         let key_id = identity.key_id;
@@ -19,11 +19,15 @@ pub const MAX_KEY_LEN: usize = 32;
 const MAX_HMAC_KEY_LEN: usize = 1024 / 8;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
-#[cfg_attr(test, derive(bolero_generator::TypeGenerator))]
+#[cfg_attr(
+    any(feature = "testing", test),
+    derive(bolero_generator::TypeGenerator)
+)]
 #[allow(non_camel_case_types)]
 pub enum Ciphersuite {
     AES_GCM_128_SHA256,
     AES_GCM_256_SHA384,
+    CHACHA20_POLY1305_SHA256,
 }
 
 impl Ciphersuite {
@@ -32,6 +36,7 @@ impl Ciphersuite {
         match self {
             Self::AES_GCM_128_SHA256 => &aead::AES_128_GCM,
             Self::AES_GCM_256_SHA384 => &aead::AES_256_GCM,
+            Self::CHACHA20_POLY1305_SHA256 => &aead::CHACHA20_POLY1305,
         }
     }
 
@@ -40,6 +45,7 @@ impl Ciphersuite {
         match self {
             Self::AES_GCM_128_SHA256 => hkdf::HKDF_SHA256,
             Self::AES_GCM_256_SHA384 => hkdf::HKDF_SHA384,
+            Self::CHACHA20_POLY1305_SHA256 => hkdf::HKDF_SHA256,
         }
     }
 
@@ -48,6 +54,7 @@ impl Ciphersuite {
         match self {
             Self::AES_GCM_128_SHA256 => &hmac::HMAC_SHA256,
             Self::AES_GCM_256_SHA384 => &hmac::HMAC_SHA384,
+            Self::CHACHA20_POLY1305_SHA256 => &hmac::HMAC_SHA256,
         }
     }
 }
@@ -58,6 +65,7 @@ impl hkdf::KeyType for Ciphersuite {
         match self {
             Self::AES_GCM_128_SHA256 => 16,
             Self::AES_GCM_256_SHA384 => 32,
+            Self::CHACHA20_POLY1305_SHA256 => 32,
         }
     }
 }
@@ -167,6 +175,22 @@ impl Secret {
         &self.id
     }
 
+    /// Returns the local endpoint type for the handshake which created the secret
+    #[inline]
+    pub fn endpoint(&self) -> endpoint::Type {
+        self.endpoint
+    }
+
+    #[inline]
+    pub fn ciphersuite(&self) -> Ciphersuite {
+        self.ciphersuite
+    }
+
+    #[inline]
+    pub(super) fn export_secret(&self) -> &ExportSecret {
+        &self.export_secret
+    }
+
     #[inline]
     pub fn application_pair(
         &self,
@@ -623,4 +647,197 @@ mod tests {
             .with_type::<Pair>()
             .for_each(|input| input.check_control())
     }
+
+    /// Seals and opens each packet type with the keys derived for `ciphersuite`
+    mod packets {
+        use super::*;
+        use crate::{
+            credentials::{testing, Credentials},
+            crypto::{
+                open::{Application as _, Control as _},
+                UninitSlice,
+            },
+            packet::{control, datagram, stream},
+        };
+        use s2n_codec::{DecoderBufferMut, EncoderBuffer};
+        use s2n_quic_core::buffer::reader::Complete;
+
+        const PAYLOAD: &[u8] = b"hello from dc";
+
+        struct Keys {
+            sealer: seal::Application,
+            opener: open::Application,
+            control_sealer: seal::control::Stream,
+            control_opener: open::control::Stream,
+            credentials: Credentials,
+        }
+
+        impl Keys {
+            fn new(ciphersuite: Ciphersuite) -> Self {
+                let secret = &[42; 32];
+                let client = Secret::new(ciphersuite, 0, endpoint::Type::Client, secret);
+                let server = Secret::new(ciphersuite, 0, endpoint::Type::Server, secret);
+                let key_id = VarInt::from_u8(1);
+
+                let (sealer, _, _, _) = client.application_pair(key_id, Initiator::Local);
+                let (_, _, opener, _) = server.application_pair(key_id, Initiator::Remote);
+                let (control_sealer, _) = client.control_pair(key_id, Initiator::Local);
+                let (_, control_opener) = server.control_pair(key_id, Initiator::Remote);
+
+                Self {
+                    sealer,
+                    opener,
+                    control_sealer,
+                    control_opener,
+                    credentials: testing::new(1, 1),
+                }
+            }
+
+            fn stream(&self) -> Vec<u8> {
+                let stream_id = stream::Id {
+                    key_id: self.credentials.key_id,
+                    is_reliable: false,
+                    is_bidirectional: true,
+                };
+                let mut payload = PAYLOAD;
+                let mut payload = Complete::new(&mut payload).unwrap();
+                let mut buffer = vec![0; 1500];
+                let len = stream::encoder::encode(
+                    EncoderBuffer::new(&mut buffer),
+                    1234,
+                    None,
+                    stream_id,
+                    VarInt::from_u8(7),
+                    VarInt::ZERO,
+                    VarInt::ZERO,
+                    &mut &[][..],
+                    VarInt::ZERO,
+                    &(),
+                    &mut payload,
+                    &self.sealer,
+                    &self.credentials,
+                );
+                buffer.truncate(len);
+                buffer
+            }
+
+            fn open_stream(&self, packet: &mut [u8]) -> crate::crypto::open::Result<Vec<u8>> {
+                let tag_len = self.opener.tag_len();
+                let (mut packet, _) =
+                    stream::decoder::Packet::decode(DecoderBufferMut::new(packet), (), tag_len)
+                        .map_err(|_| crate::crypto::open::Error::InvalidTag)?;
+                packet.decrypt_in_place(&self.opener, &self.control_opener)?;
+                Ok(packet.payload().to_vec())
+            }
+
+            fn datagram(&self) -> Vec<u8> {
+                let mut buffer = vec![0; 1500];
+                let len = datagram::encoder::encode(
+                    EncoderBuffer::new(&mut buffer),
+                    1234,
+                    Some(VarInt::from_u8(7)),
+                    None,
+                    VarInt::ZERO,
+                    &mut &[][..],
+                    &(),
+                    VarInt::try_from(PAYLOAD.len()).unwrap(),
+                    &mut &PAYLOAD[..],
+                    &self.sealer,
+                    &self.credentials,
+                );
+                buffer.truncate(len);
+                buffer
+            }
+
+            fn open_datagram(&self, packet: &mut [u8]) -> crate::crypto::open::Result<Vec<u8>> {
+                let tag_len = self.opener.tag_len();
+                let (packet, _) =
+                    datagram::decoder::Packet::decode(DecoderBufferMut::new(packet), (), tag_len)
+                        .map_err(|_| crate::crypto::open::Error::InvalidTag)?;
+                let mut payload = vec![0; packet.payload().len()];
+                self.opener.decrypt(
+                    packet.tag().key_phase(),
+                    packet.crypto_nonce(),
+                    packet.header(),
+                    packet.payload(),
+                    packet.auth_tag(),
+                    UninitSlice::new(&mut payload),
+                )?;
+                Ok(payload)
+            }
+
+            fn control(&self) -> Vec<u8> {
+                let mut buffer = vec![0; 1500];
+                let len = control::encoder::encode(
+                    EncoderBuffer::new(&mut buffer),
+                    1234,
+                    None,
+                    VarInt::from_u8(7),
+                    VarInt::ZERO,
+                    &mut &[][..],
+                    VarInt::try_from(PAYLOAD.len()).unwrap(),
+                    &PAYLOAD,
+                    &self.control_sealer,
+                    &self.credentials,
+                );
+                buffer.truncate(len);
+                buffer
+            }
+
+            fn open_control(&self, packet: &mut [u8]) -> crate::crypto::open::Result<Vec<u8>> {
+                let tag_len = self.control_opener.tag_len();
+                let (packet, _) =
+                    control::decoder::Packet::decode(DecoderBufferMut::new(packet), (), tag_len)
+                        .map_err(|_| crate::crypto::open::Error::InvalidTag)?;
+                self.control_opener
+                    .verify(packet.header(), packet.auth_tag())?;
+                Ok(packet.control_data().to_vec())
+            }
+        }
+
+        /// Checks that every packet type round trips and that flipping any single bit fails to open
+        fn check(ciphersuite: Ciphersuite) {
+            let keys = Keys::new(ciphersuite);
+
+            type Seal = fn(&Keys) -> Vec<u8>;
+            type Open = fn(&Keys, &mut [u8]) -> crate::crypto::open::Result<Vec<u8>>;
+            let packets: [(&str, Seal, Open); 3] = [
+                ("stream", Keys::stream, Keys::open_stream),
+                ("datagram", Keys::datagram, Keys::open_datagram),
+                ("control", Keys::control, Keys::open_control),
+            ];
+
+            for (name, seal, open) in packets {
+                let packet = seal(&keys);
+
+                if name != "control" {
+                    // the payload should be encrypted on the wire
+                    assert!(!packet.windows(PAYLOAD.len()).any(|w| w == PAYLOAD));
+                }
+
+                let opened = open(&keys, &mut packet.clone()).unwrap();
+                assert_eq!(opened, PAYLOAD, "{ciphersuite:?} {name}");
+
+                for index in 0..packet.len() {
+                    let mut tampered = packet.clone();
+                    tampered[index] ^= 1;
+                    assert!(
+                        open(&keys, &mut tampered).is_err(),
+                        "{ciphersuite:?} {name} opened with byte {index} modified"
+                    );
+                }
+            }
+        }
+
+        #[test]
+        fn aes_gcm() {
+            check(Ciphersuite::AES_GCM_128_SHA256);
+            check(Ciphersuite::AES_GCM_256_SHA384);
+        }
+
+        #[test]
+        fn chacha20_poly1305() {
+            check(Ciphersuite::CHACHA20_POLY1305_SHA256);
+        }
+    }
 }
@@ -55,6 +55,12 @@ impl State {
         VarInt::try_from(id).unwrap()
     }
 
+    /// Returns the key ID which will be used next, without allocating it
+    #[inline]
+    pub(super) fn peek_key_id(&self) -> VarInt {
+        VarInt::try_from(self.current_id.load(Ordering::Relaxed)).unwrap()
+    }
+
     #[inline]
     pub fn control_secret(&self, secret: &schedule::Secret) -> open::control::Secret {
         // We don't try to cache this, hmac init is cheap (~200-600ns depending on algorithm) and
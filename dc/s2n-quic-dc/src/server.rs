@@ -0,0 +1,834 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A dcQUIC server which accepts path secret handshakes and streams
+//!
+//! The [`Server`] runs an s2n-quic endpoint for handshaking with clients, along with a stream
+//! acceptor which uses the negotiated path secrets to authenticate incoming streams.
+
+use crate::{
+    client::{ensure_protocol, start_error},
+    path::secret,
+    stream::{
+        application::Stream,
+        environment::{tokio::Environment, Environment as _},
+        server::tokio::{accept, admission, stats, tcp, udp},
+        socket::{Options, Protocol},
+    },
+    sync::channel,
+};
+use s2n_quic::provider::{dc::ConfirmComplete, tls::default as tls};
+use std::{io, net::SocketAddr};
+use tokio::{
+    io::unix::AsyncFd,
+    net::TcpListener,
+    sync::oneshot,
+    task::{JoinHandle, JoinSet},
+};
+
+/// The default number of streams which can be queued before being accepted
+pub const DEFAULT_BACKLOG: usize = 4096;
+
+#[derive(Default)]
+pub struct Builder {
+    tls: Option<tls::Server>,
+    handshake_addr: Option<SocketAddr>,
+    acceptor_addr: Option<SocketAddr>,
+    protocol: Option<Protocol>,
+    environment: Option<Environment>,
+    map: Option<secret::Map>,
+    map_capacity: Option<usize>,
+    backlog: Option<usize>,
+    accept_flavor: accept::Flavor,
+    admission: admission::Policy,
+}
+
+impl Builder {
+    /// Sets the TLS configuration used to handshake with clients
+    pub fn with_tls(mut self, tls: tls::Server) -> Self {
+        self.tls = Some(tls);
+        self
+    }
+
+    /// Sets the address of the handshake endpoint
+    ///
+    /// Defaults to `0.0.0.0:0`.
+    pub fn with_handshake_addr(mut self, address: SocketAddr) -> Self {
+        self.handshake_addr = Some(address);
+        self
+    }
+
+    /// Sets the address of the stream acceptor
+    ///
+    /// Defaults to `0.0.0.0:0`.
+    pub fn with_acceptor_addr(mut self, address: SocketAddr) -> Self {
+        self.acceptor_addr = Some(address);
+        self
+    }
+
+    /// Sets the transport used for streams
+    ///
+    /// Defaults to [`Protocol::Udp`].
+    pub fn with_protocol(mut self, protocol: Protocol) -> Self {
+        self.protocol = Some(protocol);
+        self
+    }
+
+    /// Sets the environment used to drive streams
+    pub fn with_environment(mut self, environment: Environment) -> Self {
+        self.environment = Some(environment);
+        self
+    }
+
+    /// Shares an existing path secret map with the server
+    pub fn with_map(mut self, map: secret::Map) -> Self {
+        self.map = Some(map);
+        self
+    }
+
+    /// Sets the capacity of the path secret map, if one wasn't provided with [`Self::with_map`]
+    ///
+    /// Defaults to [`secret::map::DEFAULT_CAPACITY`].
+    pub fn with_map_capacity(mut self, capacity: usize) -> Self {
+        self.map_capacity = Some(capacity);
+        self
+    }
+
+    /// Sets the number of streams which can be queued before being accepted
+    ///
+    /// Defaults to [`DEFAULT_BACKLOG`].
+    pub fn with_backlog(mut self, backlog: usize) -> Self {
+        self.backlog = Some(backlog);
+        self
+    }
+
+    /// Sets the order in which queued streams are accepted
+    pub fn with_accept_flavor(mut self, flavor: accept::Flavor) -> Self {
+        self.accept_flavor = flavor;
+        self
+    }
+
+    /// Sets the policy used to reject new streams when the server is overloaded
+    ///
+    /// Rejected clients are notified with a retry hint rather than waiting for the stream to time
+    /// out. Defaults to admitting all streams.
+    pub fn with_admission(mut self, policy: admission::Policy) -> Self {
+        self.admission = policy;
+        self
+    }
+
+    /// Starts the handshake endpoint and stream acceptor
+    ///
+    /// This must be called from within a tokio runtime.
+    pub fn build(self) -> io::Result<Server> {
+        let protocol = self.protocol.unwrap_or(Protocol::Udp);
+        ensure_protocol(protocol)?;
+
+        let tls = self.tls.ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidInput, "missing TLS configuration")
+        })?;
+        let unspecified: SocketAddr = (std::net::Ipv4Addr::UNSPECIFIED, 0).into();
+        let handshake_addr = self.handshake_addr.unwrap_or(unspecified);
+        let acceptor_addr = self.acceptor_addr.unwrap_or(unspecified);
+        let backlog = self.backlog.unwrap_or(DEFAULT_BACKLOG);
+        let map = self.map.unwrap_or_else(|| {
+            let capacity = self.map_capacity.unwrap_or(secret::map::DEFAULT_CAPACITY);
+            secret::Map::new(secret::stateless_reset::Signer::random(), capacity)
+        });
+        let env = match self.environment {
+            Some(environment) => environment,
+            None => Environment::builder().build()?,
+        };
+
+        let endpoint = s2n_quic::Server::builder()
+            .with_tls(tls)
+            .map_err(start_error)?
+            .with_io(handshake_addr)
+            .map_err(start_error)?
+            .with_dc(map.clone())
+            .map_err(start_error)?
+            .with_event(ConfirmComplete)
+            .map_err(start_error)?
+            .start()
+            .map_err(start_error)?;
+        let handshake_addr = endpoint.local_addr()?;
+
+        let (sender, receiver) = channel::new(backlog);
+        let (stats_sender, stats_worker, stats) = stats::channel();
+        let admission = admission::Controller::new(self.admission, stats.clone());
+
+        let (shutdown, shutdown_signal) = oneshot::channel();
+        let handshakes = tokio::spawn(handshakes(endpoint, shutdown_signal));
+
+        let mut tasks = vec![];
+
+        let acceptor_addr = match protocol {
+            Protocol::Tcp => {
+                let socket = Options::new(acceptor_addr).build_tcp_listener()?;
+                let socket = TcpListener::from_std(socket)?;
+                let acceptor_addr = socket.local_addr()?;
+                let acceptor = tcp::Acceptor::new(
+                    socket,
+                    &sender,
+                    &env,
+                    &map,
+                    backlog,
+                    self.accept_flavor,
+                    &admission,
+                );
+                tasks.push(tokio::spawn(acceptor.run()));
+                acceptor_addr
+            }
+            _ => {
+                let socket = Options::new(acceptor_addr).build_udp()?;
+                let acceptor_addr = socket.local_addr()?;
+                let socket = AsyncFd::new(socket)?;
+                let acceptor =
+                    udp::Acceptor::new(socket, &sender, &env, &map, self.accept_flavor, &admission);
+                tasks.push(tokio::spawn(acceptor.run()));
+                acceptor_addr
+            }
+        };
+
+        tasks.push(tokio::spawn(stats_worker.run(env.clock().clone())));
+        tasks.push(tokio::spawn(accept::Pruner::default().run(
+            env.clone(),
+            receiver.downgrade(),
+            stats,
+        )));
+
+        Ok(Server {
+            receiver,
+            stats: stats_sender,
+            map,
+            env,
+            handshake_addr,
+            acceptor_addr,
+            tasks,
+            handshakes: Some((shutdown, handshakes)),
+        })
+    }
+}
+
+/// Accepts handshakes until the server is shut down
+///
+/// Each connection is tracked so shutting down can wait for in-progress handshakes to finish.
+async fn handshakes(mut endpoint: s2n_quic::Server, mut shutdown: oneshot::Receiver<()>) {
+    let mut connections = JoinSet::new();
+
+    loop {
+        tokio::select! {
+            connection = endpoint.accept() => {
+                let Some(mut connection) = connection else {
+                    break;
+                };
+
+                connections.spawn(async move {
+                    if ConfirmComplete::wait_ready(&mut connection).await.is_err() {
+                        return;
+                    }
+
+                    // hold on to the connection until the peer closes it
+                    while let Ok(Some(_stream)) = connection.accept().await {}
+                });
+            }
+            // reap finished connections so the set doesn't grow without bound
+            Some(_) = connections.join_next(), if !connections.is_empty() => {}
+            _ = &mut shutdown => break,
+        }
+    }
+
+    // stop accepting new handshakes and wait for the current ones to finish
+    drop(endpoint);
+    while connections.join_next().await.is_some() {}
+}
+
+/// A dcQUIC server
+///
+/// The handshake endpoint and stream acceptor are stopped when the server is dropped.
+pub struct Server {
+    receiver: accept::Receiver,
+    stats: stats::Sender,
+    map: secret::Map,
+    env: Environment,
+    handshake_addr: SocketAddr,
+    acceptor_addr: SocketAddr,
+    tasks: Vec<JoinHandle<()>>,
+    handshakes: Option<(oneshot::Sender<()>, JoinHandle<()>)>,
+}
+
+impl Server {
+    #[inline]
+    pub fn builder() -> Builder {
+        Builder::default()
+    }
+
+    /// Accepts the next stream from a client
+    #[inline]
+    pub async fn accept(&self) -> io::Result<(Stream, SocketAddr)> {
+        accept::accept(&self.receiver, &self.stats).await
+    }
+
+    /// Returns the address clients should handshake with
+    #[inline]
+    pub fn handshake_addr(&self) -> SocketAddr {
+        self.handshake_addr
+    }
+
+    /// Returns the address clients should open streams to
+    #[inline]
+    pub fn acceptor_addr(&self) -> SocketAddr {
+        self.acceptor_addr
+    }
+
+    #[inline]
+    pub fn map(&self) -> &secret::Map {
+        &self.map
+    }
+
+    #[inline]
+    pub fn environment(&self) -> &Environment {
+        &self.env
+    }
+
+    /// Stops accepting handshakes and streams and waits for the server to wind down
+    ///
+    /// Streams which were queued but not yet accepted are closed, so their peers are notified
+    /// instead of waiting for a timeout. Handshakes which are in progress are allowed to
+    /// complete. Streams which were already accepted are not affected.
+    pub async fn shutdown(mut self) {
+        // close queued streams while the acceptor is still running to deliver the notifications
+        self.drain();
+
+        // stop queueing new streams
+        for task in self.tasks.drain(..) {
+            task.abort();
+            let _ = task.await;
+        }
+
+        // release anything queued while the acceptor was stopping
+        self.drain();
+
+        if let Some((shutdown, handshakes)) = self.handshakes.take() {
+            let _ = shutdown.send(());
+            let _ = handshakes.await;
+        }
+    }
+}
+
+impl Server {
+    /// Closes every stream waiting in the accept queue
+    fn drain(&self) {
+        while let Ok(Some((stream, _queue_time, _permit))) = self.receiver.try_recv_front() {
+            // building the stream and dropping it closes it with the peer
+            let _ = stream.build();
+        }
+    }
+}
+
+impl Drop for Server {
+    #[inline]
+    fn drop(&mut self) {
+        for task in &self.tasks {
+            task.abort();
+        }
+        if let Some((_shutdown, handshakes)) = &self.handshakes {
+            // aborting the task drops its connection set, which aborts each connection
+            handshakes.abort();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{client::Client, congestion, packet, stream::TransportFeatures};
+    use s2n_codec::{DecoderBufferMut, EncoderBuffer};
+    use s2n_quic_core::{
+        buffer::reader::Complete, crypto::tls::testing::certificates, ensure,
+        recovery::bandwidth::Bandwidth, varint::VarInt,
+    };
+    use tokio::io::{AsyncReadExt as _, AsyncWriteExt as _};
+
+    async fn round_trip(protocol: Protocol) {
+        round_trip_with(protocol, congestion::Algorithm::default()).await
+    }
+
+    async fn round_trip_with(protocol: Protocol, algorithm: congestion::Algorithm) {
+        let localhost: SocketAddr = "127.0.0.1:0".parse().unwrap();
+
+        let tls = tls::Server::builder()
+            .with_certificate(certificates::CERT_PEM, certificates::KEY_PEM)
+            .unwrap()
+            .build()
+            .unwrap();
+        let server = Server::builder()
+            .with_tls(tls)
+            .with_handshake_addr(localhost)
+            .with_acceptor_addr(localhost)
+            .with_protocol(protocol)
+            .with_environment(environment(algorithm))
+            .build()
+            .unwrap();
+
+        let tls = tls::Client::builder()
+            .with_certificate(certificates::CERT_PEM)
+            .unwrap()
+            .build()
+            .unwrap();
+        let client = Client::builder()
+            .with_tls(tls)
+            .with_address(localhost)
+            .with_protocol(protocol)
+            .with_environment(environment(algorithm))
+            .build()
+            .unwrap();
+
+        let handshake_addr = server.handshake_addr();
+        let acceptor_addr = server.acceptor_addr();
+
+        let server_task = tokio::spawn(async move {
+            let (mut stream, _peer) = server.accept().await.unwrap();
+            let mut request = vec![];
+            stream.read_to_end(&mut request).await.unwrap();
+            stream.write_all(&request).await.unwrap();
+            stream.shutdown().await.unwrap();
+            server
+        });
+
+        let mut stream = client
+            .connect(handshake_addr, acceptor_addr, "localhost".into())
+            .await
+            .unwrap();
+        stream.write_all(b"hello").await.unwrap();
+        stream.shutdown().await.unwrap();
+        let mut response = vec![];
+        stream.read_to_end(&mut response).await.unwrap();
+        assert_eq!(response, b"hello");
+
+        let server = server_task.await.unwrap();
+
+        // the second handshake should reuse the path secret
+        let kind = client
+            .handshake_with(handshake_addr, "localhost".into())
+            .await
+            .unwrap();
+        assert!(matches!(kind, secret::HandshakeKind::Cached));
+
+        server.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn udp_round_trip() {
+        round_trip(Protocol::Udp).await;
+    }
+
+    #[tokio::test]
+    async fn tcp_round_trip() {
+        round_trip(Protocol::Tcp).await;
+    }
+
+    fn environment(algorithm: congestion::Algorithm) -> Environment {
+        Environment::builder()
+            .with_congestion_controller(algorithm)
+            .build()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn congestion_controllers() {
+        let rate = Bandwidth::new(100_000_000, core::time::Duration::from_secs(1));
+        for algorithm in [
+            congestion::Algorithm::Bbr,
+            congestion::Algorithm::Cubic,
+            congestion::Algorithm::Fixed { rate },
+            congestion::Algorithm::Unlimited,
+        ] {
+            round_trip_with(Protocol::Udp, algorithm).await;
+        }
+    }
+
+    async fn server_busy(protocol: Protocol) {
+        let localhost: SocketAddr = "127.0.0.1:0".parse().unwrap();
+        let min_retry_after = core::time::Duration::from_millis(50);
+
+        let tls = tls::Server::builder()
+            .with_certificate(certificates::CERT_PEM, certificates::KEY_PEM)
+            .unwrap()
+            .build()
+            .unwrap();
+        let server = Server::builder()
+            .with_tls(tls)
+            .with_handshake_addr(localhost)
+            .with_acceptor_addr(localhost)
+            .with_protocol(protocol)
+            .with_admission(admission::Policy {
+                max_queue_depth: Some(1),
+                min_retry_after,
+                ..Default::default()
+            })
+            .build()
+            .unwrap();
+
+        let tls = tls::Client::builder()
+            .with_certificate(certificates::CERT_PEM)
+            .unwrap()
+            .build()
+            .unwrap();
+        let client = Client::builder()
+            .with_tls(tls)
+            .with_address(localhost)
+            .with_protocol(protocol)
+            .build()
+            .unwrap();
+
+        let handshake_addr = server.handshake_addr();
+        let acceptor_addr = server.acceptor_addr();
+
+        // the first stream fills up the queue since the application never accepts it
+        let mut queued = client
+            .connect(handshake_addr, acceptor_addr, "localhost".into())
+            .await
+            .unwrap();
+        queued.write_all(b"queued").await.unwrap();
+
+        let mut stream = client
+            .connect(handshake_addr, acceptor_addr, "localhost".into())
+            .await
+            .unwrap();
+        stream.write_all(b"rejected").await.unwrap();
+
+        // the client should be notified well before the idle timeout
+        let mut response = vec![];
+        let error = tokio::time::timeout(
+            core::time::Duration::from_secs(5),
+            stream.read_to_end(&mut response),
+        )
+        .await
+        .unwrap()
+        .unwrap_err();
+
+        assert_eq!(error.kind(), io::ErrorKind::ConnectionRefused);
+        let retry_after = crate::stream::retry_after(&error).unwrap();
+        assert!(retry_after >= min_retry_after, "{retry_after:?}");
+
+        drop(server);
+    }
+
+    #[tokio::test]
+    async fn udp_server_busy() {
+        server_busy(Protocol::Udp).await;
+    }
+
+    #[tokio::test]
+    async fn tcp_server_busy() {
+        server_busy(Protocol::Tcp).await;
+    }
+
+    /// Sends a raw initial packet to the acceptor and returns the response, if any
+    async fn send_initial(
+        protocol: Protocol,
+        acceptor_addr: SocketAddr,
+        packet: &[u8],
+    ) -> Option<Vec<u8>> {
+        let timeout = core::time::Duration::from_millis(500);
+        let mut response = vec![0; 1500];
+
+        let len = match protocol {
+            Protocol::Tcp => {
+                let mut socket = tokio::net::TcpStream::connect(acceptor_addr).await.unwrap();
+                socket.write_all(packet).await.unwrap();
+                response.clear();
+                tokio::time::timeout(timeout, socket.read_to_end(&mut response))
+                    .await
+                    .ok()?
+                    .ok()?
+            }
+            _ => {
+                let socket = tokio::net::UdpSocket::bind("127.0.0.1:0").await.unwrap();
+                socket.send_to(packet, acceptor_addr).await.unwrap();
+                tokio::time::timeout(timeout, socket.recv(&mut response))
+                    .await
+                    .ok()?
+                    .unwrap()
+            }
+        };
+
+        ensure!(len > 0, None);
+        response.truncate(len);
+        Some(response)
+    }
+
+    async fn server_busy_authentication(protocol: Protocol) {
+        let localhost: SocketAddr = "127.0.0.1:0".parse().unwrap();
+
+        let tls = tls::Server::builder()
+            .with_certificate(certificates::CERT_PEM, certificates::KEY_PEM)
+            .unwrap()
+            .build()
+            .unwrap();
+        let server = Server::builder()
+            .with_tls(tls)
+            .with_handshake_addr(localhost)
+            .with_acceptor_addr(localhost)
+            .with_protocol(protocol)
+            .with_admission(admission::Policy {
+                max_queue_depth: Some(1),
+                ..Default::default()
+            })
+            .build()
+            .unwrap();
+
+        let tls = tls::Client::builder()
+            .with_certificate(certificates::CERT_PEM)
+            .unwrap()
+            .build()
+            .unwrap();
+        let client = Client::builder()
+            .with_tls(tls)
+            .with_address(localhost)
+            .with_protocol(protocol)
+            .build()
+            .unwrap();
+
+        let handshake_addr = server.handshake_addr();
+        let acceptor_addr = server.acceptor_addr();
+
+        // the first stream fills up the queue since the application never accepts it
+        let mut queued = client
+            .connect(handshake_addr, acceptor_addr, "localhost".into())
+            .await
+            .unwrap();
+        queued.write_all(b"queued").await.unwrap();
+
+        // build an initial packet for a new stream with the client's path secret
+        let features = match protocol {
+            Protocol::Tcp => TransportFeatures::TCP,
+            _ => TransportFeatures::UDP,
+        };
+        let (crypto, _params) = client
+            .map()
+            .pair_for_peer(handshake_addr, &features)
+            .unwrap();
+        let stream_id = packet::stream::Id {
+            key_id: crypto.credentials.key_id,
+            is_reliable: true,
+            is_bidirectional: true,
+        };
+        let mut payload = &b"hello"[..];
+        let mut payload = Complete::new(&mut payload).unwrap();
+        let mut packet = vec![0u8; 1500];
+        let len = packet::stream::encoder::encode(
+            EncoderBuffer::new(&mut packet),
+            1234,
+            None,
+            stream_id,
+            VarInt::ZERO,
+            VarInt::ZERO,
+            VarInt::ZERO,
+            &mut &[][..],
+            VarInt::ZERO,
+            &(),
+            &mut payload,
+            &crypto.application.sealer,
+            &crypto.credentials,
+        );
+        packet.truncate(len);
+
+        // the authentic packet is rejected with a busy notification
+        let mut response = send_initial(protocol, acceptor_addr, &packet)
+            .await
+            .expect("missing server busy notification");
+        let (response, _remaining) = DecoderBufferMut::new(&mut response)
+            .decode_parameterized::<packet::Packet>(16)
+            .unwrap();
+        assert!(matches!(response, packet::Packet::ServerBusy(_)));
+
+        // replaying the packet doesn't get a busy notification
+        assert_eq!(send_initial(protocol, acceptor_addr, &packet).await, None);
+
+        // neither does a forged packet
+        let mut forged = packet.clone();
+        *forged.last_mut().unwrap() ^= 1;
+        assert_eq!(send_initial(protocol, acceptor_addr, &forged).await, None);
+
+        drop(server);
+    }
+
+    #[tokio::test]
+    async fn udp_server_busy_authentication() {
+        server_busy_authentication(Protocol::Udp).await;
+    }
+
+    #[tokio::test]
+    async fn tcp_server_busy_authentication() {
+        server_busy_authentication(Protocol::Tcp).await;
+    }
+
+    #[tokio::test]
+    async fn background_renewal() {
+        let localhost: SocketAddr = "127.0.0.1:0".parse().unwrap();
+
+        let tls = tls::Server::builder()
+            .with_certificate(certificates::CERT_PEM, certificates::KEY_PEM)
+            .unwrap()
+            .build()
+            .unwrap();
+        let server = Server::builder()
+            .with_tls(tls)
+            .with_handshake_addr(localhost)
+            .with_acceptor_addr(localhost)
+            .build()
+            .unwrap();
+
+        let tls = tls::Client::builder()
+            .with_certificate(certificates::CERT_PEM)
+            .unwrap()
+            .build()
+            .unwrap();
+        let map = secret::Map::builder(secret::stateless_reset::Signer::random())
+            .with_max_key_uses(4)
+            .build();
+        let client = Client::builder()
+            .with_tls(tls)
+            .with_address(localhost)
+            .with_map(map)
+            .build()
+            .unwrap();
+
+        let handshake_addr = server.handshake_addr();
+        let acceptor_addr = server.acceptor_addr();
+
+        tokio::spawn(async move {
+            while let Ok((mut stream, _peer)) = server.accept().await {
+                tokio::spawn(async move {
+                    let mut request = vec![];
+                    let _ = stream.read_to_end(&mut request).await;
+                });
+            }
+        });
+
+        for _ in 0..4 {
+            let mut stream = client
+                .connect(handshake_addr, acceptor_addr, "localhost".into())
+                .await
+                .unwrap();
+            stream.write_all(b"hello").await.unwrap();
+            stream.shutdown().await.unwrap();
+        }
+
+        // the handshake happens in the background and replaces the original entry
+        tokio::time::timeout(core::time::Duration::from_secs(10), async {
+            while client.map().secrets_len() < 2 {
+                tokio::time::sleep(core::time::Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .unwrap();
+
+        // the new entry doesn't require another handshake
+        let kind = client
+            .handshake_with(handshake_addr, "localhost".into())
+            .await
+            .unwrap();
+        assert!(matches!(kind, secret::HandshakeKind::Cached));
+    }
+
+    fn endpoints() -> (Server, Client) {
+        let localhost: SocketAddr = "127.0.0.1:0".parse().unwrap();
+
+        let tls = tls::Server::builder()
+            .with_certificate(certificates::CERT_PEM, certificates::KEY_PEM)
+            .unwrap()
+            .build()
+            .unwrap();
+        let server = Server::builder()
+            .with_tls(tls)
+            .with_handshake_addr(localhost)
+            .with_acceptor_addr(localhost)
+            .build()
+            .unwrap();
+
+        let tls = tls::Client::builder()
+            .with_certificate(certificates::CERT_PEM)
+            .unwrap()
+            .build()
+            .unwrap();
+        let client = Client::builder()
+            .with_tls(tls)
+            .with_address(localhost)
+            .build()
+            .unwrap();
+
+        (server, client)
+    }
+
+    #[tokio::test]
+    async fn concurrent_handshakes() {
+        let (server, client) = endpoints();
+        let handshake_addr = server.handshake_addr();
+
+        let tasks: Vec<_> = (0..8)
+            .map(|_| {
+                let client = client.clone();
+                tokio::spawn(async move {
+                    client
+                        .handshake_with(handshake_addr, "localhost".into())
+                        .await
+                        .unwrap()
+                })
+            })
+            .collect();
+
+        let mut fresh = 0;
+        for task in tasks {
+            if matches!(task.await.unwrap(), secret::HandshakeKind::Fresh) {
+                fresh += 1;
+            }
+        }
+
+        // every caller waited on the same handshake
+        assert_eq!(fresh, 1);
+        assert_eq!(client.map().secrets_len(), 1);
+
+        server.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn shutdown_closes_queued_streams() {
+        let (server, client) = endpoints();
+        let handshake_addr = server.handshake_addr();
+        let acceptor_addr = server.acceptor_addr();
+
+        let mut stream = client
+            .connect(handshake_addr, acceptor_addr, "localhost".into())
+            .await
+            .unwrap();
+        stream.write_all(b"hello").await.unwrap();
+
+        // wait for the stream to be queued before shutting down
+        tokio::time::sleep(core::time::Duration::from_millis(100)).await;
+        tokio::time::timeout(core::time::Duration::from_secs(5), server.shutdown())
+            .await
+            .unwrap();
+
+        // the queued stream is closed rather than left to time out
+        let mut response = vec![];
+        let _ = tokio::time::timeout(
+            core::time::Duration::from_secs(5),
+            stream.read_to_end(&mut response),
+        )
+        .await
+        .unwrap();
+        assert!(response.is_empty());
+
+        client.shutdown().await;
+
+        // new handshakes are rejected after the client is shut down
+        let error = client
+            .handshake_with("127.0.0.1:1".parse().unwrap(), "localhost".into())
+            .await
+            .unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::NotConnected);
+    }
+}
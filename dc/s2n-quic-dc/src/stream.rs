@@ -15,6 +15,10 @@ pub mod client;
 pub mod crypto;
 pub mod endpoint;
 pub mod environment;
+#[cfg(feature = "tokio")]
+pub mod local;
+#[cfg(feature = "tokio")]
+pub mod message;
 pub mod pacer;
 pub mod packet_map;
 pub mod packet_number;
@@ -65,3 +69,19 @@ impl TransportFeatures {
     is_feature!(is_stream, STREAM);
     is_feature!(is_connected, CONNECTED);
 }
+
+/// Returns how long to wait before opening another stream, if `error` was caused by a busy server
+/// rejecting the stream
+///
+/// Servers reject streams with a retry hint when their accept queue is overloaded. This can be
+/// used to back off rather than immediately reconnecting.
+#[inline]
+pub fn retry_after(error: &std::io::Error) -> Option<Duration> {
+    let error = error.get_ref()?;
+
+    if let Some(error) = error.downcast_ref::<recv::Error>() {
+        return error.retry_after();
+    }
+
+    error.downcast_ref::<send::error::Error>()?.retry_after()
+}
@@ -49,6 +49,11 @@ impl fmt::Debug for Stream {
 }
 
 impl Stream {
+    #[inline]
+    pub(crate) fn from_split(read: Reader, write: Writer) -> Self {
+        Self { read, write }
+    }
+
     #[inline]
     pub fn peer_addr(&self) -> io::Result<SocketAddr> {
         self.read.peer_addr()
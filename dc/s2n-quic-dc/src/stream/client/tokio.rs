@@ -28,13 +28,23 @@ where
     // ensure we have a secret for the peer
     handshake.await?;
 
-    let stream = endpoint::open_stream(
-        env,
-        handshake_addr.into(),
-        env::UdpUnbound(acceptor_addr.into()),
-        map,
-        None,
-    )?;
+    let stream = if env.has_socket_pool() {
+        endpoint::open_stream(
+            env,
+            handshake_addr.into(),
+            env::UdpPooled(acceptor_addr.into()),
+            map,
+            None,
+        )?
+    } else {
+        endpoint::open_stream(
+            env,
+            handshake_addr.into(),
+            env::UdpUnbound(acceptor_addr.into()),
+            map,
+            None,
+        )?
+    };
 
     // build the stream inside the application context
     let mut stream = stream.build()?;
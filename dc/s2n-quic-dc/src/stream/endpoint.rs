@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    msg, packet,
+    congestion, msg, packet,
     path::secret::{self, Map},
     random::Random,
     stream::{
@@ -10,10 +10,11 @@ use crate::{
         environment::{Environment, Peer},
         recv,
         send::{self, flow},
-        server, shared,
+        server, shared, TransportFeatures,
     },
 };
 use core::cell::UnsafeCell;
+use s2n_codec::DecoderBufferMut;
 use s2n_quic_core::{
     dc, endpoint,
     inet::{ExplicitCongestionNotification, SocketAddress},
@@ -82,6 +83,7 @@ pub fn accept_stream<Env, P>(
     env: &Env,
     mut peer: P,
     packet: &server::InitialPacket,
+    authenticated: Authenticated,
     handshake: Option<server::handshake::Receiver>,
     buffer: Option<&mut msg::recv::Message>,
     map: &Map,
@@ -91,22 +93,10 @@ where
     Env: Environment,
     P: Peer<Env>,
 {
-    let credentials = &packet.credentials;
-    let mut secret_control = vec![];
-    let Some((crypto, mut parameters)) =
-        map.pair_for_credentials(credentials, &peer.features(), &mut secret_control)
-    else {
-        let error = io::Error::new(
-            io::ErrorKind::NotFound,
-            format!("missing credentials for client: {credentials:?}"),
-        );
-        let error = AcceptError {
-            secret_control,
-            peer: Some(peer),
-            error,
-        };
-        return Err(error);
-    };
+    let Authenticated {
+        crypto,
+        mut parameters,
+    } = authenticated;
 
     if let Some(o) = parameter_override {
         parameters = o(parameters);
@@ -132,7 +122,7 @@ where
         Ok(stream) => Ok(stream),
         Err(error) => {
             let error = AcceptError {
-                secret_control,
+                secret_control: vec![],
                 peer: None,
                 error,
             };
@@ -141,6 +131,77 @@ where
     }
 }
 
+/// The keys for a stream with an authenticated initial packet
+pub struct Authenticated {
+    crypto: secret::map::Bidirectional,
+    parameters: dc::ApplicationParams,
+}
+
+/// Authenticates the initial packet at the front of `recv_buffer` and checks it for replays
+///
+/// Nothing in the packet can be trusted until this succeeds, so it needs to happen before doing
+/// any other work for the stream, including admission control.
+pub fn authenticate_stream(
+    packet: &server::InitialPacket,
+    recv_buffer: &mut msg::recv::Message,
+    features: &TransportFeatures,
+    map: &Map,
+) -> Result<Authenticated, AcceptError<()>> {
+    let credentials = &packet.credentials;
+    let mut secret_control = vec![];
+    let Some((crypto, parameters)) =
+        map.pair_for_credentials(credentials, features, &mut secret_control)
+    else {
+        let error = io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("missing credentials for client: {credentials:?}"),
+        );
+        let error = AcceptError {
+            secret_control,
+            peer: None,
+            error,
+        };
+        return Err(error);
+    };
+
+    // Decrypt a copy of the packet so the stream can still process the original. The result of
+    // the replay check is cached in the opener so the stream sees the same outcome.
+    let mut segment = recv_buffer
+        .peek_segments()
+        .next()
+        .map(|segment| segment.to_vec())
+        .unwrap_or_default();
+
+    let res = match DecoderBufferMut::new(&mut segment).decode_parameterized(16) {
+        Ok((packet::Packet::Stream(mut packet), _remaining)) => {
+            let opener = &crypto.application.opener;
+            if let Some(control) = crypto.control.as_ref() {
+                packet.decrypt_in_place(opener, &control.opener)
+            } else {
+                // reliable transports don't use control packets
+                let control = &crate::crypto::open::control::stream::Reliable::default();
+                packet.decrypt_in_place(opener, control)
+            }
+        }
+        _ => Err(crate::crypto::open::Error::InvalidTag),
+    };
+
+    if let Err(error) = res {
+        let error = io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            format!("could not authenticate initial packet: {error}"),
+        );
+        let error = AcceptError {
+            secret_control,
+            peer: None,
+            error,
+        };
+        return Err(error);
+    }
+
+    Ok(Authenticated { crypto, parameters })
+}
+
 #[inline]
 fn build_stream<Env, P>(
     env: &Env,
@@ -160,21 +221,30 @@ where
 {
     let features = peer.features();
 
-    let sockets = peer.setup(env)?;
+    let sockets = peer.setup(env, &crypto.credentials)?;
 
     // construct shared reader state
     let reader = recv::shared::State::new(stream_id, &parameters, handshake, features, recv_buffer);
 
     let writer = {
-        let worker = sockets
-            .write_worker
-            .map(|socket| (send::state::State::new(stream_id, &parameters), socket));
+        let worker = sockets.write_worker.map(|socket| {
+            let subscriber = map
+                .subscriber()
+                .map(|subscriber| congestion::Subscriber::new(subscriber, endpoint_type));
+            let state = send::state::State::new(
+                stream_id,
+                &parameters,
+                env.congestion_controller(),
+                subscriber,
+            );
+            (state, socket)
+        });
 
         let (flow_offset, send_quantum, bandwidth) =
             if let Some((worker, _socket)) = worker.as_ref() {
                 let flow_offset = worker.flow_offset();
                 let send_quantum = worker.send_quantum_packets();
-                let bandwidth = Some(worker.cca.bandwidth());
+                let bandwidth = Some(worker.cca.bandwidth(&worker.rtt_estimator));
 
                 (flow_offset, send_quantum, bandwidth)
             } else {
@@ -2,7 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    clock,
+    clock, congestion,
+    credentials::Credentials,
     stream::{runtime, socket, TransportFeatures},
 };
 use core::future::Future;
@@ -24,6 +25,7 @@ pub trait Environment {
     fn spawn_reader<F: 'static + Send + Future<Output = ()>>(&self, f: F);
     fn writer_rt(&self) -> runtime::ArcHandle;
     fn spawn_writer<F: 'static + Send + Future<Output = ()>>(&self, f: F);
+    fn congestion_controller(&self) -> congestion::Algorithm;
 }
 
 pub struct SocketSet<S> {
@@ -40,7 +42,7 @@ pub trait Peer<E: Environment> {
 
     fn features(&self) -> TransportFeatures;
     fn with_source_control_port(&mut self, port: u16);
-    fn setup(self, env: &E) -> Result<SocketSet<Self::WorkerSocket>>;
+    fn setup(self, env: &E, credentials: &Credentials) -> Result<SocketSet<Self::WorkerSocket>>;
 }
 
 pub struct AcceptError<Peer> {
@@ -3,6 +3,8 @@
 
 use crate::{
     clock::tokio::Clock,
+    congestion,
+    credentials::Credentials,
     stream::{
         runtime::{tokio as runtime, ArcHandle},
         socket::{self, Socket as _},
@@ -26,6 +28,8 @@ pub struct Builder {
     writer_rt: Option<runtime::Shared>,
     thread_name_prefix: Option<String>,
     threads: Option<usize>,
+    socket_pool: Option<usize>,
+    congestion_controller: Option<congestion::Algorithm>,
 }
 
 impl Builder {
@@ -34,11 +38,28 @@ impl Builder {
         self
     }
 
+    /// Shares a fixed pool of `sockets` UDP socket pairs between all of the UDP streams
+    ///
+    /// By default, a new socket pair is bound for every stream.
+    pub fn with_socket_pool(mut self, sockets: usize) -> Self {
+        self.socket_pool = Some(sockets);
+        self
+    }
+
+    /// Sets the congestion control algorithm used by streams
+    ///
+    /// Defaults to [`congestion::Algorithm::Bbr`].
+    pub fn with_congestion_controller(mut self, algorithm: congestion::Algorithm) -> Self {
+        self.congestion_controller = Some(algorithm);
+        self
+    }
+
     #[inline]
     pub fn build(self) -> io::Result<Environment> {
         let clock = self.clock.unwrap_or_default();
         let gso = self.gso.unwrap_or_default();
         let socket_options = self.socket_options.unwrap_or_default();
+        let congestion_controller = self.congestion_controller.unwrap_or_default();
 
         let thread_name_prefix = self.thread_name_prefix.as_deref().unwrap_or("dc_quic");
 
@@ -63,12 +84,22 @@ impl Builder {
             .map(<io::Result<_>>::Ok)
             .unwrap_or_else(|| make_rt("writer", self.threads))?;
 
+        let socket_pool = if let Some(sockets) = self.socket_pool {
+            let pool =
+                socket::pool::Pool::new(socket_options.clone(), sockets, &reader_rt, &writer_rt)?;
+            Some(Arc::new(pool))
+        } else {
+            None
+        };
+
         Ok(Environment {
             clock,
             gso,
             socket_options,
             reader_rt,
             writer_rt,
+            socket_pool,
+            congestion_controller,
         })
     }
 }
@@ -80,6 +111,8 @@ pub struct Environment {
     socket_options: socket::Options,
     reader_rt: runtime::Shared,
     writer_rt: runtime::Shared,
+    socket_pool: Option<Arc<socket::pool::Pool>>,
+    congestion_controller: congestion::Algorithm,
 }
 
 impl Default for Environment {
@@ -94,6 +127,23 @@ impl Environment {
     pub fn builder() -> Builder {
         Default::default()
     }
+
+    /// Returns `true` if UDP streams share the sockets from [`Builder::with_socket_pool`]
+    #[inline]
+    pub fn has_socket_pool(&self) -> bool {
+        self.socket_pool.is_some()
+    }
+
+    /// Returns a copy of the environment which uses `algorithm` for its streams
+    ///
+    /// The copy shares the runtimes and socket pool with the original environment so this can be
+    /// used to pick a congestion controller for individual streams.
+    #[inline]
+    pub fn with_congestion_controller(&self, algorithm: congestion::Algorithm) -> Self {
+        let mut env = self.clone();
+        env.congestion_controller = algorithm;
+        env
+    }
 }
 
 impl super::Environment for Environment {
@@ -128,6 +178,11 @@ impl super::Environment for Environment {
     fn spawn_writer<F: 'static + Send + std::future::Future<Output = ()>>(&self, f: F) {
         self.writer_rt.spawn(f);
     }
+
+    #[inline]
+    fn congestion_controller(&self) -> congestion::Algorithm {
+        self.congestion_controller
+    }
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -147,7 +202,11 @@ impl super::Peer<Environment> for UdpUnbound {
     }
 
     #[inline]
-    fn setup(self, env: &Environment) -> super::Result<super::SocketSet<Self::WorkerSocket>> {
+    fn setup(
+        self,
+        env: &Environment,
+        _credentials: &Credentials,
+    ) -> super::Result<super::SocketSet<Self::WorkerSocket>> {
         let mut options = env.socket_options.clone();
         let remote_addr = self.0;
 
@@ -226,6 +285,86 @@ impl super::Peer<Environment> for UdpUnbound {
     }
 }
 
+/// A UDP peer which shares the sockets from the environment's socket pool
+#[derive(Clone, Copy, Debug)]
+pub struct UdpPooled(pub SocketAddress);
+
+impl super::Peer<Environment> for UdpPooled {
+    type WorkerSocket = socket::pool::Socket;
+
+    #[inline]
+    fn features(&self) -> TransportFeatures {
+        TransportFeatures::UDP
+    }
+
+    #[inline]
+    fn with_source_control_port(&mut self, port: u16) {
+        self.0.set_port(port);
+    }
+
+    #[inline]
+    fn setup(
+        self,
+        env: &Environment,
+        credentials: &Credentials,
+    ) -> super::Result<super::SocketSet<Self::WorkerSocket>> {
+        let Some(pool) = env.socket_pool.as_ref() else {
+            return Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "the environment was not configured with a socket pool",
+            ));
+        };
+
+        // the pooled sockets are already bound so map the peer to the local address family
+        let remote_addr = match (self.0, pool.local_addr()?) {
+            (SocketAddress::IpV4(addr), std::net::SocketAddr::V6(_)) => {
+                addr.to_ipv6_mapped().into()
+            }
+            (addr @ SocketAddress::IpV6(_), std::net::SocketAddr::V4(_)) => {
+                let addr = addr.unmap();
+                ensure!(
+                    matches!(addr, SocketAddress::IpV4(_)),
+                    Err(io::ErrorKind::Unsupported.into())
+                );
+                addr
+            }
+            (addr, _) => addr,
+        };
+
+        let (reader, writer) = pool.register(credentials)?;
+
+        // if we're on a platform that requires two different ports then the peer needs to know
+        // where to send the stream packets
+        let multi_port = reader.local_port()? != writer.local_port()?;
+
+        let source_control_port = writer.local_port()?;
+
+        let source_stream_port = if multi_port {
+            Some(reader.local_port()?)
+        } else {
+            None
+        };
+
+        let application: Box<dyn socket::application::Builder> = if multi_port {
+            Box::new(socket::application::builder::PoolPair {
+                reader: reader.clone(),
+                writer: writer.clone(),
+            })
+        } else {
+            Box::new(reader.clone())
+        };
+
+        Ok(super::SocketSet {
+            application,
+            read_worker: Some(reader),
+            write_worker: Some(writer),
+            remote_addr,
+            source_control_port,
+            source_stream_port,
+        })
+    }
+}
+
 /// A socket that is already registered with the application runtime
 pub struct TcpRegistered(pub TcpStream);
 
@@ -242,7 +381,11 @@ impl super::Peer<Environment> for TcpRegistered {
     }
 
     #[inline]
-    fn setup(self, _env: &Environment) -> super::Result<super::SocketSet<Self::WorkerSocket>> {
+    fn setup(
+        self,
+        _env: &Environment,
+        _credentials: &Credentials,
+    ) -> super::Result<super::SocketSet<Self::WorkerSocket>> {
         let remote_addr = self.0.peer_addr()?.into();
         let source_control_port = self.0.local_addr()?.port();
         let application = Box::new(self.0);
@@ -273,7 +416,11 @@ impl super::Peer<Environment> for TcpReregistered {
     }
 
     #[inline]
-    fn setup(self, _env: &Environment) -> super::Result<super::SocketSet<Self::WorkerSocket>> {
+    fn setup(
+        self,
+        _env: &Environment,
+        _credentials: &Credentials,
+    ) -> super::Result<super::SocketSet<Self::WorkerSocket>> {
         let remote_addr = self.0.peer_addr()?.into();
         let source_control_port = self.0.local_addr()?.port();
         let application = Box::new(self.0.into_std()?);
@@ -0,0 +1,378 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Same-host streams over Unix domain sockets
+//!
+//! When both peers run on the same host, the traffic never leaves the machine and the kernel can
+//! identify the process on the other end of the socket with `SO_PEERCRED`. Local streams check
+//! the peer's credentials against a [`Policy`] when the stream is established, rather than using
+//! path secrets, and write the application's bytes directly to the socket without any per-packet
+//! encryption.
+//!
+//! Local streams use the same [`Stream`] type as UDP and TCP streams and report
+//! [`Protocol::Unix`](crate::stream::socket::Protocol::Unix). Since Unix domain sockets aren't
+//! addressed by IP, `peer_addr` and `local_addr` return [`ADDR`].
+
+use crate::stream::{
+    application::Stream, recv::application::Reader, send::application::Writer, MAX_DATAGRAM_SIZE,
+};
+use core::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+use s2n_quic_core::{buffer, ensure, ready};
+use std::{
+    io,
+    net::{Ipv4Addr, SocketAddr, SocketAddrV4},
+    os::unix::fs::{FileTypeExt as _, MetadataExt as _},
+    path::{Path, PathBuf},
+};
+use tokio::{
+    io::{AsyncRead, AsyncWrite, AsyncWriteExt as _, ReadBuf},
+    net::{
+        unix::{OwnedReadHalf, OwnedWriteHalf},
+        UnixListener, UnixStream,
+    },
+};
+
+pub use tokio::net::unix::UCred;
+
+/// The address reported for both ends of a local stream
+pub const ADDR: SocketAddr = SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::LOCALHOST, 0));
+
+/// Determines which local processes are allowed on the other end of a stream
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Policy {
+    /// Allows peers running as any of these user IDs
+    pub allowed_uids: Vec<libc::uid_t>,
+
+    /// Allows peers running as any of these group IDs
+    pub allowed_gids: Vec<libc::gid_t>,
+}
+
+impl Default for Policy {
+    /// Only allows peers running as the same user as the current process
+    fn default() -> Self {
+        // SAFETY: `geteuid` is always successful
+        let uid = unsafe { libc::geteuid() };
+        Self {
+            allowed_uids: vec![uid],
+            allowed_gids: vec![],
+        }
+    }
+}
+
+impl Policy {
+    #[inline]
+    pub fn is_allowed(&self, cred: &UCred) -> bool {
+        self.allowed_uids.contains(&cred.uid()) || self.allowed_gids.contains(&cred.gid())
+    }
+
+    #[inline]
+    fn authenticate(&self, stream: &UnixStream) -> io::Result<UCred> {
+        let cred = stream.peer_cred()?;
+        ensure!(
+            self.is_allowed(&cred),
+            Err(io::Error::new(
+                io::ErrorKind::PermissionDenied,
+                format!("peer is not allowed by the local stream policy: {cred:?}"),
+            ))
+        );
+        Ok(cred)
+    }
+}
+
+/// Accepts local streams on a Unix domain socket
+///
+/// The socket file is removed when the listener is dropped.
+#[derive(Debug)]
+pub struct Listener {
+    listener: UnixListener,
+    policy: Policy,
+    path: PathBuf,
+    /// The device and inode of the socket file, to avoid removing a file bound by someone else
+    file_id: (u64, u64),
+}
+
+impl Listener {
+    /// Binds a listener to `path` which accepts peers running as the current user
+    #[inline]
+    pub fn bind(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::bind_with_policy(path, Policy::default())
+    }
+
+    /// Binds a listener to `path` which accepts peers allowed by `policy`
+    ///
+    /// A socket file left behind by a listener which is no longer running is replaced. If
+    /// another listener is still accepting streams on `path` then an
+    /// [`io::ErrorKind::AddrInUse`] error is returned.
+    #[inline]
+    pub fn bind_with_policy(path: impl AsRef<Path>, policy: Policy) -> io::Result<Self> {
+        let path = path.as_ref();
+        let listener = match UnixListener::bind(path) {
+            Err(err) if err.kind() == io::ErrorKind::AddrInUse && is_stale(path) => {
+                std::fs::remove_file(path)?;
+                UnixListener::bind(path)?
+            }
+            res => res?,
+        };
+        let metadata = std::fs::metadata(path)?;
+
+        Ok(Self {
+            listener,
+            policy,
+            path: path.to_path_buf(),
+            file_id: (metadata.dev(), metadata.ino()),
+        })
+    }
+
+    #[inline]
+    pub fn local_addr(&self) -> io::Result<tokio::net::unix::SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Accepts the next stream, along with the credentials of the peer process
+    ///
+    /// Streams from peers which aren't allowed by the policy are closed and return a
+    /// [`io::ErrorKind::PermissionDenied`] error. The listener can continue to be used afterwards.
+    #[inline]
+    pub async fn accept(&self) -> io::Result<(Stream, UCred)> {
+        let (stream, _addr) = self.listener.accept().await?;
+        let cred = self.policy.authenticate(&stream)?;
+        Ok((build(stream), cred))
+    }
+}
+
+impl Drop for Listener {
+    #[inline]
+    fn drop(&mut self) {
+        // only remove the file if it hasn't been replaced since we bound it
+        let Ok(metadata) = std::fs::metadata(&self.path) else {
+            return;
+        };
+        if (metadata.dev(), metadata.ino()) == self.file_id {
+            let _ = std::fs::remove_file(&self.path);
+        }
+    }
+}
+
+/// Returns `true` if `path` is a socket file which nothing is listening on
+#[inline]
+fn is_stale(path: &Path) -> bool {
+    let Ok(metadata) = std::fs::symlink_metadata(path) else {
+        return false;
+    };
+    ensure!(metadata.file_type().is_socket(), false);
+
+    matches!(
+        std::os::unix::net::UnixStream::connect(path),
+        Err(err) if err.kind() == io::ErrorKind::ConnectionRefused
+    )
+}
+
+/// Connects to a [`Listener`] bound to `path` which is running as the current user
+#[inline]
+pub async fn connect(path: impl AsRef<Path>) -> io::Result<Stream> {
+    connect_with_policy(path, &Policy::default()).await
+}
+
+/// Connects to a [`Listener`] bound to `path`, checking the listener's credentials against `policy`
+#[inline]
+pub async fn connect_with_policy(path: impl AsRef<Path>, policy: &Policy) -> io::Result<Stream> {
+    let stream = UnixStream::connect(path).await?;
+    from_unix_stream(stream, policy)
+}
+
+/// Creates a stream from an already connected Unix domain socket, such as one passed from
+/// another process
+#[inline]
+pub fn from_unix_stream(stream: UnixStream, policy: &Policy) -> io::Result<Stream> {
+    policy.authenticate(&stream)?;
+    Ok(build(stream))
+}
+
+/// Creates a pair of connected streams
+///
+/// This must be called from within a tokio runtime.
+#[inline]
+pub fn pair() -> io::Result<(Stream, Stream)> {
+    let (a, b) = UnixStream::pair()?;
+    Ok((build(a), build(b)))
+}
+
+#[inline]
+fn build(stream: UnixStream) -> Stream {
+    let runtime = tokio::runtime::Handle::current();
+    let (read, write) = stream.into_split();
+    let read = Reader::local(ReadHalf {
+        stream: read,
+        buffer: vec![],
+    });
+    let write = Writer::local(WriteHalf {
+        stream: Some(write),
+        pending: vec![],
+        runtime,
+    });
+    Stream::from_split(read, write)
+}
+
+pub(crate) struct ReadHalf {
+    stream: OwnedReadHalf,
+    /// Scratch space for storage types which can't be read into directly
+    buffer: Vec<u8>,
+}
+
+impl ReadHalf {
+    #[inline]
+    pub(crate) fn poll_read_into<S>(
+        &mut self,
+        cx: &mut Context,
+        out_buf: &mut S,
+    ) -> Poll<io::Result<usize>>
+    where
+        S: buffer::writer::Storage,
+    {
+        let len = out_buf.remaining_capacity().min(MAX_DATAGRAM_SIZE);
+        ensure!(len > 0, Ok(0).into());
+
+        if self.buffer.len() < len {
+            self.buffer.resize(len, 0);
+        }
+
+        let mut buf = ReadBuf::new(&mut self.buffer[..len]);
+        ready!(Pin::new(&mut self.stream).poll_read(cx, &mut buf))?;
+
+        let filled = buf.filled();
+        out_buf.put_slice(filled);
+        Ok(filled.len()).into()
+    }
+
+    #[inline]
+    pub(crate) fn poll_read(
+        &mut self,
+        cx: &mut Context,
+        buf: &mut ReadBuf,
+    ) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.stream).poll_read(cx, buf)
+    }
+}
+
+pub(crate) struct WriteHalf {
+    /// The socket, until the stream is shut down
+    stream: Option<OwnedWriteHalf>,
+    /// Bytes taken from the application which the socket hasn't accepted yet
+    pending: Vec<u8>,
+    runtime: tokio::runtime::Handle,
+}
+
+impl WriteHalf {
+    #[inline]
+    pub(crate) fn poll_write_from<S>(
+        &mut self,
+        cx: &mut Context,
+        buf: &mut S,
+        is_fin: bool,
+    ) -> Poll<io::Result<usize>>
+    where
+        S: buffer::reader::storage::Infallible,
+    {
+        let Some(stream) = self.stream.as_mut() else {
+            // if we've already shut down the stream then return early
+            ensure!(
+                buf.buffer_is_empty() && is_fin,
+                Err(io::Error::from(io::ErrorKind::BrokenPipe)).into()
+            );
+            return Ok(0).into();
+        };
+
+        // make sure the previously accepted bytes are written before taking more
+        ready!(poll_flush_pending(stream, &mut self.pending, cx))?;
+
+        let mut len = 0;
+        while !buf.buffer_is_empty() {
+            let chunk = buf.infallible_read_chunk(MAX_DATAGRAM_SIZE);
+            len += chunk.len();
+
+            match Pin::new(&mut *stream).poll_write(cx, &chunk) {
+                Poll::Ready(Ok(written)) if written == chunk.len() => continue,
+                Poll::Ready(Ok(written)) => {
+                    self.pending.extend_from_slice(&chunk[written..]);
+                }
+                Poll::Ready(Err(err)) => return Err(err).into(),
+                Poll::Pending => {
+                    self.pending.extend_from_slice(&chunk);
+                }
+            }
+
+            // the socket is full so return what we've accepted so far
+            return Ok(len).into();
+        }
+
+        if is_fin {
+            ready!(self.poll_shutdown(cx))?;
+        }
+
+        Ok(len).into()
+    }
+
+    #[inline]
+    pub(crate) fn poll_shutdown(&mut self, cx: &mut Context) -> Poll<io::Result<()>> {
+        let Some(stream) = self.stream.as_mut() else {
+            return Ok(()).into();
+        };
+
+        ready!(poll_flush_pending(stream, &mut self.pending, cx))?;
+        let res = ready!(Pin::new(stream).poll_shutdown(cx));
+        self.stream = None;
+        res.into()
+    }
+
+    /// Shuts down the stream without waiting for the pending bytes to be written
+    #[inline]
+    pub(crate) fn shutdown(&mut self) -> io::Result<()> {
+        let Some(mut stream) = self.stream.take() else {
+            return Ok(());
+        };
+
+        if self.pending.is_empty() {
+            let waker = s2n_quic_core::task::waker::noop();
+            let mut cx = Context::from_waker(&waker);
+            // shutting down a Unix socket doesn't block
+            return match Pin::new(&mut stream).poll_shutdown(&mut cx) {
+                Poll::Ready(res) => res,
+                Poll::Pending => Ok(()),
+            };
+        }
+
+        // spawn a task to finish writing the bytes we've already accepted
+        let pending = core::mem::take(&mut self.pending);
+        self.runtime.spawn(async move {
+            if stream.write_all(&pending).await.is_ok() {
+                let _ = stream.shutdown().await;
+            }
+        });
+
+        Ok(())
+    }
+}
+
+#[inline]
+fn poll_flush_pending(
+    stream: &mut OwnedWriteHalf,
+    pending: &mut Vec<u8>,
+    cx: &mut Context,
+) -> Poll<io::Result<()>> {
+    while !pending.is_empty() {
+        let written = ready!(Pin::new(&mut *stream).poll_write(cx, pending))?;
+        ensure!(
+            written > 0,
+            Err(io::Error::from(io::ErrorKind::WriteZero)).into()
+        );
+        pending.drain(..written);
+    }
+
+    Ok(()).into()
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,169 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::stream::socket::Protocol;
+use std::path::PathBuf;
+use tokio::io::AsyncReadExt as _;
+
+/// Returns a unique socket path for each test
+fn socket_path(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!(
+        "s2n-quic-dc-local-{}-{name}.sock",
+        std::process::id()
+    ));
+    let _ = std::fs::remove_file(&path);
+    path
+}
+
+fn denied() -> Policy {
+    Policy {
+        allowed_uids: vec![],
+        allowed_gids: vec![],
+    }
+}
+
+#[tokio::test]
+async fn round_trip() {
+    let path = socket_path("round_trip");
+    let listener = Listener::bind(&path).unwrap();
+
+    let server = tokio::spawn(async move {
+        let (mut stream, cred) = listener.accept().await.unwrap();
+        assert!(Policy::default().is_allowed(&cred));
+        assert_eq!(stream.protocol(), Protocol::Unix);
+
+        let mut request = vec![];
+        stream.read_to_end(&mut request).await.unwrap();
+        stream.write_all(&request).await.unwrap();
+        stream.shutdown().await.unwrap();
+    });
+
+    let mut stream = connect(&path).await.unwrap();
+    assert_eq!(stream.protocol(), Protocol::Unix);
+    assert_eq!(stream.peer_addr().unwrap(), ADDR);
+    assert_eq!(stream.local_addr().unwrap(), ADDR);
+
+    stream.write_all(b"hello").await.unwrap();
+    stream.shutdown().await.unwrap();
+    let mut response = vec![];
+    stream.read_to_end(&mut response).await.unwrap();
+    assert_eq!(response, b"hello");
+
+    server.await.unwrap();
+}
+
+#[tokio::test]
+async fn storage_round_trip() {
+    let (mut a, mut b) = pair().unwrap();
+
+    // large enough to fill up the socket buffer
+    let payload: Vec<u8> = (0..(4 << 20)).map(|v| v as u8).collect();
+    let expected = payload.clone();
+
+    let writer = tokio::spawn(async move {
+        let mut buf = &payload[..];
+        while !buf.is_empty() {
+            a.write_from(&mut buf).await.unwrap();
+        }
+        drop(a);
+    });
+
+    let mut received: Vec<u8> = vec![];
+    loop {
+        let mut chunk: Vec<u8> = Vec::with_capacity(10_000);
+        let len = b.read_into(&mut chunk).await.unwrap();
+        if len == 0 {
+            break;
+        }
+        assert_eq!(len, chunk.len());
+        received.extend_from_slice(&chunk);
+    }
+
+    writer.await.unwrap();
+    assert_eq!(received.len(), expected.len());
+    assert!(received == expected);
+}
+
+#[tokio::test]
+async fn write_after_shutdown() {
+    let (mut a, mut b) = pair().unwrap();
+
+    a.shutdown().await.unwrap();
+    let error = a.write_all(b"hello").await.unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::BrokenPipe);
+
+    let mut response = vec![];
+    b.read_to_end(&mut response).await.unwrap();
+    assert!(response.is_empty());
+}
+
+#[tokio::test]
+async fn listener_rejects_peer() {
+    let path = socket_path("listener_rejects_peer");
+    let listener = Listener::bind_with_policy(&path, denied()).unwrap();
+
+    let mut stream = connect(&path).await.unwrap();
+
+    let error = listener.accept().await.unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::PermissionDenied);
+
+    // the rejected stream is closed without sending any data
+    let mut response = vec![];
+    let _ = stream.read_to_end(&mut response).await;
+    assert!(response.is_empty());
+}
+
+#[tokio::test]
+async fn client_rejects_listener() {
+    let path = socket_path("client_rejects_listener");
+    let _listener = Listener::bind(&path).unwrap();
+
+    let error = connect_with_policy(&path, &denied()).await.unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::PermissionDenied);
+}
+
+#[tokio::test]
+async fn rebind() {
+    let path = socket_path("rebind");
+
+    // the socket file is removed when the listener is dropped
+    let listener = Listener::bind(&path).unwrap();
+    drop(listener);
+    assert!(!path.exists());
+
+    let listener = Listener::bind(&path).unwrap();
+    let (stream, accepted) = tokio::join!(connect(&path), listener.accept());
+    stream.unwrap();
+    accepted.unwrap();
+    drop(listener);
+    assert!(!path.exists());
+}
+
+#[tokio::test]
+async fn bind_replaces_stale_socket() {
+    let path = socket_path("bind_replaces_stale_socket");
+
+    // std listeners leave the socket file behind when dropped
+    drop(std::os::unix::net::UnixListener::bind(&path).unwrap());
+    assert!(path.exists());
+
+    let listener = Listener::bind(&path).unwrap();
+    let (stream, accepted) = tokio::join!(connect(&path), listener.accept());
+    stream.unwrap();
+    accepted.unwrap();
+}
+
+#[tokio::test]
+async fn bind_in_use() {
+    let path = socket_path("bind_in_use");
+    let listener = Listener::bind(&path).unwrap();
+
+    let error = Listener::bind(&path).unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::AddrInUse);
+
+    // the existing listener is unaffected
+    let (stream, accepted) = tokio::join!(connect(&path), listener.accept());
+    stream.unwrap();
+    accepted.unwrap();
+}
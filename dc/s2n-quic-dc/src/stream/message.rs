@@ -0,0 +1,165 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Request/response messages on top of a byte stream
+//!
+//! Each message is length-delimited and tagged with a request ID so many requests can be
+//! pipelined on a single stream and responses can be returned in any order.
+//!
+//! Requests can carry a deadline, which is sent to the peer along with the request. If the
+//! caller gives up on a request, either because the deadline passed or the call was dropped, a
+//! cancellation is sent to the peer so it can stop working on it. The server also notifies the
+//! client when it drops a request without responding.
+//!
+//! Cancellations are sent in-band as message frames rather than over the dc control channel.
+//! Control packets can only reset the entire stream, which would fail every other request
+//! multiplexed on it, so per-request cancellation needs its own frame.
+//!
+//! Outgoing frames are queued for a background writer task. The queue is bounded so a peer that
+//! stops reading applies backpressure to [`Client::call`] and [`Request::respond`].
+
+use crate::stream::application::Stream;
+use s2n_quic_core::varint::VarInt;
+use std::io;
+use tokio::{
+    io::{AsyncRead, AsyncWrite},
+    sync::mpsc,
+};
+
+pub mod client;
+pub mod frame;
+pub mod server;
+
+#[cfg(test)]
+mod tests;
+
+pub use client::Client;
+pub use server::{Request, Server};
+
+/// The default maximum length of a single request or response payload
+pub const DEFAULT_MAX_MESSAGE_LEN: usize = 16 * 1024 * 1024;
+
+/// The default number of received requests which can be queued before [`Server::accept`] is
+/// called
+pub const DEFAULT_BACKLOG: usize = 1024;
+
+/// The default number of outgoing frames which can be queued before senders wait for the stream
+/// to be written
+pub const DEFAULT_WRITE_QUEUE: usize = 64;
+
+#[derive(Clone, Copy, Debug)]
+pub struct Builder {
+    max_message_len: usize,
+    backlog: usize,
+    write_queue: usize,
+}
+
+impl Default for Builder {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            max_message_len: DEFAULT_MAX_MESSAGE_LEN,
+            backlog: DEFAULT_BACKLOG,
+            write_queue: DEFAULT_WRITE_QUEUE,
+        }
+    }
+}
+
+impl Builder {
+    /// Sets the maximum length of a single request or response payload
+    ///
+    /// Receiving a larger message closes the stream with an error.
+    #[inline]
+    pub fn with_max_message_len(mut self, len: usize) -> Self {
+        self.max_message_len = len;
+        self
+    }
+
+    /// Sets the number of received requests which can be queued before [`Server::accept`] is
+    /// called
+    ///
+    /// Once the backlog is full, the server stops reading from the stream.
+    #[inline]
+    pub fn with_backlog(mut self, backlog: usize) -> Self {
+        assert!(backlog > 0, "backlog must be at least 1");
+        self.backlog = backlog;
+        self
+    }
+
+    /// Sets the number of outgoing frames which can be queued before senders wait for the stream
+    /// to be written
+    #[inline]
+    pub fn with_write_queue(mut self, len: usize) -> Self {
+        assert!(len > 0, "write queue must be at least 1");
+        self.write_queue = len;
+        self
+    }
+
+    /// Sends requests on the dc stream
+    #[inline]
+    pub fn client(self, stream: Stream) -> Client {
+        let (reader, writer) = stream.into_split();
+        self.client_with_io(reader, writer)
+    }
+
+    /// Sends requests on any byte stream
+    #[inline]
+    pub fn client_with_io<R, W>(self, reader: R, writer: W) -> Client
+    where
+        R: AsyncRead + Send + Unpin + 'static,
+        W: AsyncWrite + Send + Unpin + 'static,
+    {
+        Client::spawn(self, reader, writer)
+    }
+
+    /// Receives requests from the dc stream
+    #[inline]
+    pub fn server(self, stream: Stream) -> Server {
+        let (reader, writer) = stream.into_split();
+        self.server_with_io(reader, writer)
+    }
+
+    /// Receives requests from any byte stream
+    #[inline]
+    pub fn server_with_io<R, W>(self, reader: R, writer: W) -> Server
+    where
+        R: AsyncRead + Send + Unpin + 'static,
+        W: AsyncWrite + Send + Unpin + 'static,
+    {
+        Server::spawn(self, reader, writer)
+    }
+}
+
+/// Notifies the peer that a request was cancelled without waiting for capacity in the write queue
+///
+/// This is called from `Drop` implementations so it can't wait. If the queue is full, the frame is
+/// sent from a background task instead.
+#[inline]
+fn send_cancel(frames: &mpsc::Sender<frame::Frame>, id: VarInt) {
+    let frame = match frames.try_send(frame::Frame::Cancel { id }) {
+        Ok(()) | Err(mpsc::error::TrySendError::Closed(_)) => return,
+        Err(mpsc::error::TrySendError::Full(frame)) => frame,
+    };
+
+    let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+        return;
+    };
+
+    let frames = frames.clone();
+    runtime.spawn(async move {
+        let _ = frames.send(frame).await;
+    });
+}
+
+#[inline]
+fn closed_error() -> io::Error {
+    io::Error::new(io::ErrorKind::BrokenPipe, "message stream closed")
+}
+
+#[inline]
+fn cancelled_error() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::ConnectionAborted,
+        "request cancelled by the peer",
+    )
+}
@@ -0,0 +1,252 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{cancelled_error, closed_error, frame, send_cancel, Builder};
+use crate::stream::application::Stream;
+use bytes::Bytes;
+use core::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+use s2n_quic_core::varint::VarInt;
+use std::{
+    collections::HashMap,
+    io,
+    sync::{Arc, Mutex},
+};
+use tokio::{
+    io::{AsyncRead, AsyncWrite},
+    sync::{mpsc, oneshot},
+    task::JoinHandle,
+};
+
+type Response = io::Result<Bytes>;
+
+/// Sends requests to a [`super::Server`] and waits for the responses
+///
+/// Requests can be sent concurrently from multiple tasks. The stream is driven by background tasks
+/// so the client must be created within a tokio runtime.
+pub struct Client {
+    shared: Arc<Shared>,
+    frames: mpsc::Sender<frame::Frame>,
+    reader: JoinHandle<()>,
+}
+
+impl Client {
+    /// Sends requests on the dc stream with the default configuration
+    #[inline]
+    pub fn new(stream: Stream) -> Self {
+        Builder::default().client(stream)
+    }
+
+    #[inline]
+    pub fn builder() -> Builder {
+        Builder::default()
+    }
+
+    pub(super) fn spawn<R, W>(builder: Builder, reader: R, writer: W) -> Self
+    where
+        R: AsyncRead + Send + Unpin + 'static,
+        W: AsyncWrite + Send + Unpin + 'static,
+    {
+        let shared = Arc::new(Shared::default());
+        let (frames, queue) = mpsc::channel(builder.write_queue);
+
+        let reader = frame::Reader::new(reader, builder.max_message_len);
+        let reader = tokio::spawn(read(reader, shared.clone()));
+
+        let writer_shared = shared.clone();
+        tokio::spawn(async move {
+            if let Err(err) = frame::write(writer, queue).await {
+                writer_shared.close(err);
+            }
+        });
+
+        Self {
+            shared,
+            frames,
+            reader,
+        }
+    }
+
+    /// Sends a request and waits for its response
+    ///
+    /// If `deadline` is set, the peer is notified of the deadline along with the request and the
+    /// call fails with [`io::ErrorKind::TimedOut`] once it passes. A call that times out or is
+    /// dropped before completing sends a cancellation to the peer.
+    ///
+    /// Waits for capacity if the write queue is full. Time spent waiting counts against the
+    /// deadline.
+    ///
+    /// Fails with [`io::ErrorKind::ConnectionAborted`] if the peer cancels the request.
+    pub async fn call(&self, payload: impl Into<Bytes>, deadline: Option<Duration>) -> Response {
+        let id = self.shared.next_id()?;
+        let (sender, receiver) = oneshot::channel();
+        self.shared.insert(id, sender)?;
+
+        let mut guard = Guard {
+            id,
+            shared: &self.shared,
+            frames: &self.frames,
+            is_sent: false,
+            is_armed: true,
+        };
+
+        let request = frame::Frame::Request {
+            id,
+            deadline,
+            payload: payload.into(),
+        };
+
+        let response = async {
+            self.frames
+                .send(request)
+                .await
+                .map_err(|_| closed_error())?;
+            guard.is_sent = true;
+            io::Result::Ok(receiver.await)
+        };
+
+        let response = if let Some(deadline) = deadline {
+            tokio::time::timeout(deadline, response)
+                .await
+                .map_err(|_| {
+                    io::Error::new(io::ErrorKind::TimedOut, "request deadline exceeded")
+                })??
+        } else {
+            response.await?
+        };
+
+        // the request completed so the peer doesn't need to be notified
+        guard.is_armed = false;
+
+        response.unwrap_or_else(|_| Err(closed_error()))
+    }
+
+    /// Returns the number of requests waiting for a response
+    #[inline]
+    pub fn pending(&self) -> usize {
+        self.shared
+            .state
+            .lock()
+            .map_or(0, |state| state.pending.len())
+    }
+}
+
+impl Drop for Client {
+    #[inline]
+    fn drop(&mut self) {
+        // the writer task shuts down the stream once all of the queued frames are written
+        self.reader.abort();
+    }
+}
+
+/// Cancels the request if the call doesn't complete
+struct Guard<'a> {
+    id: VarInt,
+    shared: &'a Shared,
+    frames: &'a mpsc::Sender<frame::Frame>,
+    /// The peer only needs to be notified if the request made it into the write queue
+    is_sent: bool,
+    is_armed: bool,
+}
+
+impl Drop for Guard<'_> {
+    #[inline]
+    fn drop(&mut self) {
+        if !self.is_armed {
+            return;
+        }
+
+        if self.shared.remove(self.id).is_some() && self.is_sent {
+            send_cancel(self.frames, self.id);
+        }
+    }
+}
+
+#[derive(Default)]
+struct Shared {
+    next_id: AtomicU64,
+    state: Mutex<State>,
+}
+
+#[derive(Default)]
+struct State {
+    pending: HashMap<VarInt, oneshot::Sender<Response>>,
+    error: Option<io::ErrorKind>,
+}
+
+impl Shared {
+    #[inline]
+    fn next_id(&self) -> io::Result<VarInt> {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        VarInt::new(id).map_err(|_| io::Error::new(io::ErrorKind::Other, "request IDs exhausted"))
+    }
+
+    #[inline]
+    fn insert(&self, id: VarInt, sender: oneshot::Sender<Response>) -> io::Result<()> {
+        let mut state = self.state.lock().map_err(|_| closed_error())?;
+
+        if let Some(kind) = state.error {
+            return Err(io::Error::new(kind, "message stream closed"));
+        }
+
+        state.pending.insert(id, sender);
+        Ok(())
+    }
+
+    #[inline]
+    fn remove(&self, id: VarInt) -> Option<oneshot::Sender<Response>> {
+        self.state.lock().ok()?.pending.remove(&id)
+    }
+
+    #[inline]
+    fn complete(&self, id: VarInt, response: Response) {
+        // the request may have already been cancelled locally
+        if let Some(sender) = self.remove(id) {
+            let _ = sender.send(response);
+        }
+    }
+
+    /// Fails all of the pending requests along with any future ones
+    #[inline]
+    fn close(&self, error: io::Error) {
+        let Ok(mut state) = self.state.lock() else {
+            return;
+        };
+
+        let kind = error.kind();
+        state.error.get_or_insert(kind);
+
+        for (_id, sender) in state.pending.drain() {
+            let _ = sender.send(Err(io::Error::new(kind, error.to_string())));
+        }
+    }
+}
+
+async fn read<R>(mut reader: frame::Reader<R>, shared: Arc<Shared>)
+where
+    R: AsyncRead + Unpin,
+{
+    let error = loop {
+        let frame = match reader.next().await {
+            Ok(Some(frame)) => frame,
+            Ok(None) => break closed_error(),
+            Err(err) => break err,
+        };
+
+        match frame {
+            frame::Frame::Response { id, payload } => shared.complete(id, Ok(payload)),
+            frame::Frame::Cancel { id } => shared.complete(id, Err(cancelled_error())),
+            frame::Frame::Request { .. } => {
+                break io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "unexpected request from the server",
+                );
+            }
+        }
+    };
+
+    tracing::debug!(%error, "message client closed");
+    shared.close(error);
+}
@@ -0,0 +1,296 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use bytes::{Buf as _, Bytes, BytesMut};
+use core::time::Duration;
+use s2n_codec::{DecoderBuffer, DecoderError, Encoder as _, EncoderBuffer};
+use s2n_quic_core::varint::VarInt;
+use std::io;
+use tokio::{
+    io::{AsyncRead, AsyncReadExt as _, AsyncWrite, AsyncWriteExt as _},
+    sync::mpsc,
+};
+
+/// The largest possible frame header
+///
+/// tag (1) + id (8) + deadline (8) + length (8)
+pub const MAX_HEADER_LEN: usize = 1 + 8 * 3;
+
+const REQUEST_TAG: u8 = 0;
+const RESPONSE_TAG: u8 = 1;
+const CANCEL_TAG: u8 = 2;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Frame {
+    /// Starts a new request
+    ///
+    /// ```text
+    /// Request {
+    ///   Tag (8) = 0,
+    ///   Id (i),
+    ///   Deadline Millis (i),
+    ///   Length (i),
+    ///   Payload (..),
+    /// }
+    /// ```
+    ///
+    /// A deadline of `0` means the request doesn't have a deadline.
+    Request {
+        id: VarInt,
+        deadline: Option<Duration>,
+        payload: Bytes,
+    },
+    /// Completes the request with the same ID
+    ///
+    /// ```text
+    /// Response {
+    ///   Tag (8) = 1,
+    ///   Id (i),
+    ///   Length (i),
+    ///   Payload (..),
+    /// }
+    /// ```
+    Response { id: VarInt, payload: Bytes },
+    /// Notifies the peer that the request with the same ID was abandoned
+    ///
+    /// ```text
+    /// Cancel {
+    ///   Tag (8) = 2,
+    ///   Id (i),
+    /// }
+    /// ```
+    Cancel { id: VarInt },
+}
+
+impl Frame {
+    #[inline]
+    pub fn id(&self) -> VarInt {
+        match self {
+            Self::Request { id, .. } | Self::Response { id, .. } | Self::Cancel { id } => *id,
+        }
+    }
+
+    #[inline]
+    pub fn payload(&self) -> &[u8] {
+        match self {
+            Self::Request { payload, .. } | Self::Response { payload, .. } => payload,
+            Self::Cancel { .. } => &[],
+        }
+    }
+
+    /// Encodes everything but the payload into `out`, returning the header length
+    #[inline]
+    pub fn encode_header(&self, out: &mut [u8; MAX_HEADER_LEN]) -> usize {
+        let mut encoder = EncoderBuffer::new(out);
+
+        match self {
+            Self::Request {
+                id,
+                deadline,
+                payload,
+            } => {
+                encoder.encode(&REQUEST_TAG);
+                encoder.encode(id);
+                encoder.encode(&encode_deadline(*deadline));
+                encoder.encode(&encode_len(payload.len()));
+            }
+            Self::Response { id, payload } => {
+                encoder.encode(&RESPONSE_TAG);
+                encoder.encode(id);
+                encoder.encode(&encode_len(payload.len()));
+            }
+            Self::Cancel { id } => {
+                encoder.encode(&CANCEL_TAG);
+                encoder.encode(id);
+            }
+        }
+
+        encoder.len()
+    }
+
+    /// Decodes a single frame from the front of `buffer`
+    ///
+    /// Returns `Ok(None)` if more bytes are needed to decode the frame.
+    #[inline]
+    pub fn decode(buffer: &mut BytesMut, max_payload_len: usize) -> io::Result<Option<Self>> {
+        let (header, remaining) = match decode_header(DecoderBuffer::new(buffer)) {
+            Ok(v) => v,
+            Err(DecoderError::UnexpectedEof(_)) => return Ok(None),
+            Err(err) => return Err(io::Error::new(io::ErrorKind::InvalidData, err.to_string())),
+        };
+
+        let payload_len = header.payload_len().as_u64();
+        if payload_len > max_payload_len as u64 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "message exceeds the maximum length",
+            ));
+        }
+        let payload_len = payload_len as usize;
+
+        if remaining < payload_len {
+            // make room for the rest of the frame
+            buffer.reserve(payload_len - remaining);
+            return Ok(None);
+        }
+
+        let header_len = buffer.len() - remaining;
+        buffer.advance(header_len);
+        let payload = buffer.split_to(payload_len).freeze();
+
+        let frame = match header {
+            Header::Request { id, deadline, .. } => Self::Request {
+                id,
+                deadline,
+                payload,
+            },
+            Header::Response { id, .. } => Self::Response { id, payload },
+            Header::Cancel { id } => Self::Cancel { id },
+        };
+
+        Ok(Some(frame))
+    }
+}
+
+enum Header {
+    Request {
+        id: VarInt,
+        deadline: Option<Duration>,
+        len: VarInt,
+    },
+    Response {
+        id: VarInt,
+        len: VarInt,
+    },
+    Cancel {
+        id: VarInt,
+    },
+}
+
+impl Header {
+    #[inline]
+    fn payload_len(&self) -> VarInt {
+        match self {
+            Self::Request { len, .. } | Self::Response { len, .. } => *len,
+            Self::Cancel { .. } => VarInt::ZERO,
+        }
+    }
+}
+
+#[inline]
+fn decode_header(buffer: DecoderBuffer) -> Result<(Header, usize), DecoderError> {
+    let (tag, buffer) = buffer.decode::<u8>()?;
+    let (id, buffer) = buffer.decode::<VarInt>()?;
+
+    let (header, buffer) = match tag {
+        REQUEST_TAG => {
+            let (deadline, buffer) = buffer.decode::<VarInt>()?;
+            let (len, buffer) = buffer.decode::<VarInt>()?;
+            let deadline = decode_deadline(deadline);
+            (Header::Request { id, deadline, len }, buffer)
+        }
+        RESPONSE_TAG => {
+            let (len, buffer) = buffer.decode::<VarInt>()?;
+            (Header::Response { id, len }, buffer)
+        }
+        CANCEL_TAG => (Header::Cancel { id }, buffer),
+        _ => {
+            return Err(DecoderError::InvariantViolation(
+                "invalid message frame tag",
+            ))
+        }
+    };
+
+    Ok((header, buffer.len()))
+}
+
+#[inline]
+fn encode_deadline(deadline: Option<Duration>) -> VarInt {
+    let Some(deadline) = deadline else {
+        return VarInt::ZERO;
+    };
+
+    // round up so short deadlines aren't confused with not having a deadline
+    let millis = ((deadline.as_nanos() + 999_999) / 1_000_000).max(1);
+    let millis = millis.min(VarInt::MAX.as_u64() as u128) as u64;
+    VarInt::new(millis).unwrap_or(VarInt::MAX)
+}
+
+#[inline]
+fn decode_deadline(value: VarInt) -> Option<Duration> {
+    if value == VarInt::ZERO {
+        None
+    } else {
+        Some(Duration::from_millis(value.as_u64()))
+    }
+}
+
+#[inline]
+fn encode_len(len: usize) -> VarInt {
+    // in-memory payloads can't exceed 2^62 bytes
+    VarInt::try_from(len).expect("payload exceeds the maximum varint")
+}
+
+/// Reads frames from a byte stream
+pub struct Reader<R> {
+    reader: R,
+    buffer: BytesMut,
+    max_payload_len: usize,
+}
+
+impl<R: AsyncRead + Unpin> Reader<R> {
+    #[inline]
+    pub fn new(reader: R, max_payload_len: usize) -> Self {
+        Self {
+            reader,
+            buffer: BytesMut::with_capacity(4096),
+            max_payload_len,
+        }
+    }
+
+    /// Returns the next frame or `None` if the peer cleanly closed the stream
+    pub async fn next(&mut self) -> io::Result<Option<Frame>> {
+        loop {
+            if let Some(frame) = Frame::decode(&mut self.buffer, self.max_payload_len)? {
+                return Ok(Some(frame));
+            }
+
+            let len = self.reader.read_buf(&mut self.buffer).await?;
+
+            if len == 0 {
+                if self.buffer.is_empty() {
+                    return Ok(None);
+                }
+
+                return Err(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    "stream closed in the middle of a message",
+                ));
+            }
+        }
+    }
+}
+
+/// Writes frames from the queue to the byte stream until all of the senders are dropped
+pub async fn write<W: AsyncWrite + Unpin>(
+    mut writer: W,
+    mut frames: mpsc::Receiver<Frame>,
+) -> io::Result<()> {
+    let mut header = [0; MAX_HEADER_LEN];
+
+    while let Some(frame) = frames.recv().await {
+        let mut frame = Some(frame);
+
+        // write all of the queued frames before flushing
+        while let Some(current) = frame.take() {
+            let len = current.encode_header(&mut header);
+            writer.write_all(&header[..len]).await?;
+            writer.write_all(current.payload()).await?;
+            frame = frames.try_recv().ok();
+        }
+
+        writer.flush().await?;
+    }
+
+    writer.shutdown().await
+}
@@ -0,0 +1,308 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{closed_error, frame, send_cancel, Builder};
+use crate::stream::application::Stream;
+use bytes::Bytes;
+use core::{
+    fmt,
+    future::{poll_fn, Future as _},
+    pin::pin,
+    sync::atomic::{AtomicBool, Ordering},
+    task::Poll,
+};
+use s2n_quic_core::varint::VarInt;
+use std::{
+    collections::HashMap,
+    io,
+    sync::{Arc, Mutex},
+    time::Instant,
+};
+use tokio::{
+    io::{AsyncRead, AsyncWrite},
+    sync::{mpsc, Notify},
+    task::JoinHandle,
+};
+
+/// Receives requests from a [`super::Client`]
+///
+/// The stream is driven by background tasks so the server must be created within a tokio runtime.
+pub struct Server {
+    requests: mpsc::Receiver<io::Result<Request>>,
+    reader: JoinHandle<()>,
+}
+
+impl Server {
+    /// Receives requests from the dc stream with the default configuration
+    #[inline]
+    pub fn new(stream: Stream) -> Self {
+        Builder::default().server(stream)
+    }
+
+    #[inline]
+    pub fn builder() -> Builder {
+        Builder::default()
+    }
+
+    pub(super) fn spawn<R, W>(builder: Builder, reader: R, writer: W) -> Self
+    where
+        R: AsyncRead + Send + Unpin + 'static,
+        W: AsyncWrite + Send + Unpin + 'static,
+    {
+        let (frames, queue) = mpsc::channel(builder.write_queue);
+        let (sender, requests) = mpsc::channel(builder.backlog);
+
+        let shared = Arc::new(Shared {
+            in_flight: Default::default(),
+            frames,
+        });
+
+        let reader = frame::Reader::new(reader, builder.max_message_len);
+        let reader = tokio::spawn(read(reader, shared, sender));
+
+        tokio::spawn(async move {
+            if let Err(error) = frame::write(writer, queue).await {
+                tracing::debug!(%error, "message server writer closed");
+            }
+        });
+
+        Self { requests, reader }
+    }
+
+    /// Waits for the next request from the client
+    ///
+    /// Returns `Ok(None)` once the client has closed the stream.
+    #[inline]
+    pub async fn accept(&mut self) -> io::Result<Option<Request>> {
+        self.requests.recv().await.transpose()
+    }
+}
+
+impl Drop for Server {
+    #[inline]
+    fn drop(&mut self) {
+        // the writer task shuts down the stream once all of the accepted requests are dropped
+        self.reader.abort();
+    }
+}
+
+/// A request received from the client
+///
+/// Dropping the request without calling [`Request::respond`] cancels the request on the client.
+pub struct Request {
+    id: VarInt,
+    payload: Bytes,
+    deadline: Option<Instant>,
+    cancel: Arc<Cancel>,
+    shared: Arc<Shared>,
+    is_complete: bool,
+}
+
+impl fmt::Debug for Request {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Request")
+            .field("id", &self.id)
+            .field("payload_len", &self.payload.len())
+            .field("deadline", &self.deadline)
+            .field("is_cancelled", &self.is_cancelled())
+            .finish()
+    }
+}
+
+impl Request {
+    #[inline]
+    pub fn id(&self) -> u64 {
+        self.id.as_u64()
+    }
+
+    #[inline]
+    pub fn payload(&self) -> &Bytes {
+        &self.payload
+    }
+
+    /// Returns the time at which the client will stop waiting for the response
+    #[inline]
+    pub fn deadline(&self) -> Option<Instant> {
+        self.deadline
+    }
+
+    /// Returns `true` if the client cancelled the request or its deadline passed
+    #[inline]
+    pub fn is_cancelled(&self) -> bool {
+        self.cancel.is_cancelled.load(Ordering::Acquire)
+            || self
+                .deadline
+                .is_some_and(|deadline| deadline <= Instant::now())
+    }
+
+    /// Waits until the client cancels the request or its deadline passes
+    ///
+    /// This can be raced against the request's work in order to stop early.
+    pub async fn cancelled(&self) {
+        let mut notified = pin!(self.cancel.notify.notified());
+        notified.as_mut().enable();
+
+        if self.cancel.is_cancelled.load(Ordering::Acquire) {
+            return;
+        }
+
+        let Some(deadline) = self.deadline else {
+            return notified.await;
+        };
+
+        let mut sleep = pin!(tokio::time::sleep_until(deadline.into()));
+
+        poll_fn(|cx| {
+            if notified.as_mut().poll(cx).is_ready() || sleep.as_mut().poll(cx).is_ready() {
+                Poll::Ready(())
+            } else {
+                Poll::Pending
+            }
+        })
+        .await
+    }
+
+    /// Sends the response to the client
+    ///
+    /// Responses to cancelled requests are discarded. Waits for capacity if the write queue is
+    /// full.
+    #[inline]
+    pub async fn respond(mut self, payload: impl Into<Bytes>) -> io::Result<()> {
+        self.is_complete = true;
+
+        if self.cancel.is_cancelled.load(Ordering::Acquire) {
+            return Ok(());
+        }
+
+        let response = frame::Frame::Response {
+            id: self.id,
+            payload: payload.into(),
+        };
+        self.shared
+            .frames
+            .send(response)
+            .await
+            .map_err(|_| closed_error())
+    }
+}
+
+impl Drop for Request {
+    #[inline]
+    fn drop(&mut self) {
+        self.shared.remove(self.id);
+
+        if self.is_complete || self.cancel.is_cancelled.load(Ordering::Acquire) {
+            return;
+        }
+
+        send_cancel(&self.shared.frames, self.id);
+    }
+}
+
+#[derive(Default)]
+struct Cancel {
+    is_cancelled: AtomicBool,
+    notify: Notify,
+}
+
+impl Cancel {
+    #[inline]
+    fn cancel(&self) {
+        self.is_cancelled.store(true, Ordering::Release);
+        self.notify.notify_waiters();
+    }
+}
+
+struct Shared {
+    in_flight: Mutex<HashMap<VarInt, Arc<Cancel>>>,
+    frames: mpsc::Sender<frame::Frame>,
+}
+
+impl Shared {
+    #[inline]
+    fn insert(&self, id: VarInt) -> io::Result<Arc<Cancel>> {
+        let mut in_flight = self.in_flight.lock().map_err(|_| closed_error())?;
+        let cancel = Arc::new(Cancel::default());
+
+        if in_flight.insert(id, cancel.clone()).is_some() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "duplicate request id",
+            ));
+        }
+
+        Ok(cancel)
+    }
+
+    #[inline]
+    fn remove(&self, id: VarInt) {
+        if let Ok(mut in_flight) = self.in_flight.lock() {
+            in_flight.remove(&id);
+        }
+    }
+
+    #[inline]
+    fn cancel(&self, id: VarInt) {
+        let Ok(in_flight) = self.in_flight.lock() else {
+            return;
+        };
+
+        // the request may have already been completed
+        if let Some(cancel) = in_flight.get(&id) {
+            cancel.cancel();
+        }
+    }
+}
+
+async fn read<R>(
+    mut reader: frame::Reader<R>,
+    shared: Arc<Shared>,
+    requests: mpsc::Sender<io::Result<Request>>,
+) where
+    R: AsyncRead + Unpin,
+{
+    let error = loop {
+        let frame = match reader.next().await {
+            Ok(Some(frame)) => frame,
+            Ok(None) => return,
+            Err(err) => break err,
+        };
+
+        match frame {
+            frame::Frame::Request {
+                id,
+                deadline,
+                payload,
+            } => {
+                let cancel = match shared.insert(id) {
+                    Ok(cancel) => cancel,
+                    Err(err) => break err,
+                };
+
+                let request = Request {
+                    id,
+                    payload,
+                    deadline: deadline.map(|deadline| Instant::now() + deadline),
+                    cancel,
+                    shared: shared.clone(),
+                    is_complete: false,
+                };
+
+                if requests.send(Ok(request)).await.is_err() {
+                    // the server was dropped
+                    return;
+                }
+            }
+            frame::Frame::Cancel { id } => shared.cancel(id),
+            frame::Frame::Response { .. } => {
+                break io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "unexpected response from the client",
+                );
+            }
+        }
+    };
+
+    tracing::debug!(%error, "message server closed");
+    let _ = requests.send(Err(error)).await;
+}
@@ -0,0 +1,314 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{frame::Frame, *};
+use bytes::{Bytes, BytesMut};
+use core::time::Duration;
+use s2n_quic_core::varint::VarInt;
+use std::sync::Arc;
+
+fn pair(builder: Builder) -> (Client, Server) {
+    let (client, server) = tokio::io::duplex(4096);
+    let (client_reader, client_writer) = tokio::io::split(client);
+    let (server_reader, server_writer) = tokio::io::split(server);
+    (
+        builder.client_with_io(client_reader, client_writer),
+        builder.server_with_io(server_reader, server_writer),
+    )
+}
+
+#[test]
+fn frame_round_trip() {
+    let frames = [
+        Frame::Request {
+            id: VarInt::from_u8(1),
+            deadline: None,
+            payload: Bytes::from_static(b"request"),
+        },
+        Frame::Request {
+            id: VarInt::from_u32(1 << 20),
+            deadline: Some(Duration::from_millis(1500)),
+            payload: Bytes::new(),
+        },
+        Frame::Response {
+            id: VarInt::from_u8(1),
+            payload: Bytes::from(vec![42; 1000]),
+        },
+        Frame::Cancel {
+            id: VarInt::from_u8(2),
+        },
+    ];
+
+    let mut encoded = vec![];
+    for frame in &frames {
+        let mut header = [0; frame::MAX_HEADER_LEN];
+        let len = frame.encode_header(&mut header);
+        encoded.extend_from_slice(&header[..len]);
+        encoded.extend_from_slice(frame.payload());
+    }
+
+    // feed the bytes one at a time to make sure partial frames are handled
+    let mut buffer = BytesMut::new();
+    let mut decoded = vec![];
+    for byte in encoded {
+        buffer.extend_from_slice(&[byte]);
+        while let Some(frame) = Frame::decode(&mut buffer, 1000).unwrap() {
+            decoded.push(frame);
+        }
+    }
+
+    assert_eq!(&decoded[..], &frames[..]);
+    assert!(buffer.is_empty());
+}
+
+#[test]
+fn frame_decode_errors() {
+    let mut buffer = BytesMut::from(&[3, 0][..]);
+    assert!(Frame::decode(&mut buffer, 1000).is_err(), "invalid tag");
+
+    let frame = Frame::Response {
+        id: VarInt::ZERO,
+        payload: Bytes::from(vec![0; 1001]),
+    };
+    let mut header = [0; frame::MAX_HEADER_LEN];
+    let len = frame.encode_header(&mut header);
+    let mut buffer = BytesMut::from(&header[..len]);
+    assert!(Frame::decode(&mut buffer, 1000).is_err(), "too long");
+}
+
+#[tokio::test]
+async fn pipelined_requests() {
+    let (client, mut server) = pair(Builder::default());
+
+    let server = tokio::spawn(async move {
+        let mut requests = vec![];
+        for _ in 0..16 {
+            requests.push(server.accept().await.unwrap().unwrap());
+        }
+
+        // respond in the opposite order to make sure responses are matched up with requests
+        for request in requests.into_iter().rev() {
+            let response = request.payload().clone();
+            request.respond(response).await.unwrap();
+        }
+
+        assert!(server.accept().await.unwrap().is_none());
+    });
+
+    let client = Arc::new(client);
+    let calls = (0..16u8).map(|i| {
+        let client = client.clone();
+        tokio::spawn(async move { client.call(vec![i; i as usize + 1], None).await })
+    });
+    let calls = calls.collect::<Vec<_>>();
+
+    for (i, call) in calls.into_iter().enumerate() {
+        let response = call.await.unwrap();
+        assert_eq!(response.unwrap(), vec![i as u8; i + 1]);
+    }
+    assert_eq!(client.pending(), 0);
+
+    drop(client);
+    server.await.unwrap();
+}
+
+#[tokio::test]
+async fn deadline_cancels_request() {
+    let (client, mut server) = pair(Builder::default());
+
+    let server = tokio::spawn(async move {
+        let request = server.accept().await.unwrap().unwrap();
+        assert!(request.deadline().is_some());
+        request.cancelled().await;
+        assert!(request.is_cancelled());
+    });
+
+    let error = client
+        .call("slow", Some(Duration::from_millis(50)))
+        .await
+        .unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::TimedOut);
+    assert_eq!(client.pending(), 0);
+
+    tokio::time::timeout(Duration::from_secs(5), server)
+        .await
+        .unwrap()
+        .unwrap();
+}
+
+#[tokio::test]
+async fn dropped_call_cancels_request() {
+    let (client, mut server) = pair(Builder::default());
+
+    let server = tokio::spawn(async move {
+        let request = server.accept().await.unwrap().unwrap();
+        assert!(request.deadline().is_none());
+        request.cancelled().await;
+        assert!(request.is_cancelled());
+        // responding to a cancelled request is a no-op
+        request.respond("late").await.unwrap();
+    });
+
+    let call = client.call("abandoned", None);
+    tokio::time::timeout(Duration::from_millis(50), call)
+        .await
+        .unwrap_err();
+    assert_eq!(client.pending(), 0);
+
+    tokio::time::timeout(Duration::from_secs(5), server)
+        .await
+        .unwrap()
+        .unwrap();
+}
+
+#[tokio::test]
+async fn dropped_request_cancels_call() {
+    let (client, mut server) = pair(Builder::default());
+
+    tokio::spawn(async move {
+        while let Ok(Some(request)) = server.accept().await {
+            if request.payload() == "drop" {
+                continue;
+            }
+            request.respond("ok").await.unwrap();
+        }
+    });
+
+    let error = client.call("drop", None).await.unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::ConnectionAborted);
+
+    // other requests on the stream are unaffected
+    assert_eq!(client.call("keep", None).await.unwrap(), "ok");
+}
+
+#[tokio::test]
+async fn bounded_write_queue() {
+    // use a small pipe so the writer blocks on the first request
+    let (client, server) = tokio::io::duplex(64);
+    let (client_reader, client_writer) = tokio::io::split(client);
+    let client = Arc::new(
+        Builder::default()
+            .with_write_queue(1)
+            .client_with_io(client_reader, client_writer),
+    );
+    let payload = Bytes::from(vec![0; 1024]);
+
+    let mut calls = vec![];
+    for _ in 0..2 {
+        let client = client.clone();
+        let payload = payload.clone();
+        calls.push(tokio::spawn(
+            async move { client.call(payload, None).await },
+        ));
+    }
+    tokio::time::sleep(Duration::from_millis(10)).await;
+
+    // the writer is blocked on one request and the other fills the queue
+    let error = client
+        .call(payload.clone(), Some(Duration::from_millis(50)))
+        .await
+        .unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::TimedOut);
+
+    // abandon the other calls while the queue is still full
+    for call in calls {
+        call.abort();
+        let _ = call.await;
+    }
+    drop(client);
+
+    let mut reader = frame::Reader::new(server, DEFAULT_MAX_MESSAGE_LEN);
+    let mut requests = vec![];
+    let mut cancels = vec![];
+    while let Some(frame) = tokio::time::timeout(Duration::from_secs(5), reader.next())
+        .await
+        .unwrap()
+        .unwrap()
+    {
+        match frame {
+            Frame::Request { id, .. } => requests.push(id.as_u64()),
+            Frame::Cancel { id } => cancels.push(id.as_u64()),
+            Frame::Response { .. } => panic!("unexpected response"),
+        }
+    }
+
+    requests.sort_unstable();
+    cancels.sort_unstable();
+    // the timed out request never made it into the queue so the peer isn't notified about it
+    assert_eq!(requests, [0, 1]);
+    // cancellations are delivered once the queue drains
+    assert_eq!(cancels, [0, 1]);
+}
+
+#[tokio::test]
+async fn max_message_len() {
+    let (client, mut server) = pair(Builder::default().with_max_message_len(16));
+
+    let error = client.call(vec![0; 17], None);
+    let (response, request) = tokio::join!(error, server.accept());
+
+    assert_eq!(request.unwrap_err().kind(), io::ErrorKind::InvalidData);
+    drop(server);
+    assert!(response.is_err());
+}
+
+#[cfg(feature = "quic")]
+#[tokio::test]
+async fn dc_stream() {
+    use crate::stream::socket::Protocol;
+    use s2n_quic::provider::tls::default as tls;
+    use s2n_quic_core::crypto::tls::testing::certificates;
+    use std::net::SocketAddr;
+
+    let localhost: SocketAddr = "127.0.0.1:0".parse().unwrap();
+
+    let tls = tls::Server::builder()
+        .with_certificate(certificates::CERT_PEM, certificates::KEY_PEM)
+        .unwrap()
+        .build()
+        .unwrap();
+    let server = crate::Server::builder()
+        .with_tls(tls)
+        .with_handshake_addr(localhost)
+        .with_acceptor_addr(localhost)
+        .with_protocol(Protocol::Udp)
+        .build()
+        .unwrap();
+
+    let tls = tls::Client::builder()
+        .with_certificate(certificates::CERT_PEM)
+        .unwrap()
+        .build()
+        .unwrap();
+    let client = crate::Client::builder()
+        .with_tls(tls)
+        .with_address(localhost)
+        .with_protocol(Protocol::Udp)
+        .build()
+        .unwrap();
+
+    let handshake_addr = server.handshake_addr();
+    let acceptor_addr = server.acceptor_addr();
+
+    tokio::spawn(async move {
+        let (stream, _peer) = server.accept().await.unwrap();
+        let mut messages = Server::new(stream);
+        while let Ok(Some(request)) = messages.accept().await {
+            let response = request.payload().clone();
+            request.respond(response).await.unwrap();
+        }
+    });
+
+    let stream = client
+        .connect(handshake_addr, acceptor_addr, "localhost".into())
+        .await
+        .unwrap();
+    let messages = Client::new(stream);
+
+    let (first, second) = tokio::join!(
+        messages.call("hello", None),
+        messages.call(vec![1; 100_000], Some(Duration::from_secs(10)))
+    );
+    assert_eq!(first.unwrap(), "hello");
+    assert_eq!(second.unwrap(), vec![1; 100_000]);
+}
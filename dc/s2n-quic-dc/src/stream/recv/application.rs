@@ -1,6 +1,8 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
+#[cfg(feature = "tokio")]
+use crate::stream::local;
 use crate::{
     clock::Timer,
     msg,
@@ -38,7 +40,13 @@ pub enum ReadMode {
     Drain,
 }
 
-pub struct Reader(ManuallyDrop<Box<Inner>>);
+pub struct Reader(Flavor);
+
+enum Flavor {
+    Dc(ManuallyDrop<Box<Inner>>),
+    #[cfg(feature = "tokio")]
+    Local(local::ReadHalf),
+}
 
 pub(crate) struct Inner {
     shared: ArcShared,
@@ -98,20 +106,40 @@ impl LocalState {
 }
 
 impl Reader {
+    #[cfg(feature = "tokio")]
+    #[inline]
+    pub(crate) fn local(reader: local::ReadHalf) -> Self {
+        Self(Flavor::Local(reader))
+    }
+
     #[inline]
     pub fn peer_addr(&self) -> io::Result<SocketAddr> {
-        self.0.shared.common.ensure_open()?;
-        Ok(self.0.shared.read_remote_addr().into())
+        match &self.0 {
+            Flavor::Dc(inner) => {
+                inner.shared.common.ensure_open()?;
+                Ok(inner.shared.read_remote_addr().into())
+            }
+            #[cfg(feature = "tokio")]
+            Flavor::Local(_) => Ok(local::ADDR),
+        }
     }
 
     #[inline]
     pub fn local_addr(&self) -> io::Result<SocketAddr> {
-        self.0.sockets.read_application().local_addr()
+        match &self.0 {
+            Flavor::Dc(inner) => inner.sockets.read_application().local_addr(),
+            #[cfg(feature = "tokio")]
+            Flavor::Local(_) => Ok(local::ADDR),
+        }
     }
 
     #[inline]
     pub fn protocol(&self) -> socket::Protocol {
-        self.0.sockets.protocol()
+        match &self.0 {
+            Flavor::Dc(inner) => inner.sockets.protocol(),
+            #[cfg(feature = "tokio")]
+            Flavor::Local(_) => socket::Protocol::Unix,
+        }
     }
 
     #[inline]
@@ -131,9 +159,15 @@ impl Reader {
     where
         S: buffer::writer::Storage,
     {
+        let inner = match &mut self.0 {
+            Flavor::Dc(inner) => inner,
+            #[cfg(feature = "tokio")]
+            Flavor::Local(reader) => return reader.poll_read_into(cx, out_buf),
+        };
+
         waker::debug_assert_contract(cx, |cx| {
             let mut out_buf = out_buf.track_write();
-            let res = self.0.poll_read_into(cx, &mut out_buf);
+            let res = inner.poll_read_into(cx, &mut out_buf);
 
             if res.is_pending() {
                 debug_assert_eq!(
@@ -145,7 +179,7 @@ impl Reader {
 
             let res = ready!(res);
             // record the first time we get `Poll::Ready`
-            self.0.local_state.on_read();
+            inner.local_state.on_read();
             res?;
 
             Ok(out_buf.written_len()).into()
@@ -240,7 +274,11 @@ impl Inner {
                 Poll::Ready(res) => res?,
                 // if we've written at least one byte then return that amount
                 Poll::Pending if out_buf.written_len() > 0 => break,
-                Poll::Pending => return Poll::Pending,
+                Poll::Pending => {
+                    // the writer half may fail the stream while we're waiting on the socket
+                    shared.receiver.application_waker.register(cx.waker());
+                    return Poll::Pending;
+                }
             }
 
             // clear the forced receive after performing it once
@@ -323,6 +361,11 @@ impl tokio::io::AsyncRead for Reader {
         cx: &mut Context<'_>,
         buf: &mut tokio::io::ReadBuf<'_>,
     ) -> Poll<io::Result<()>> {
+        // local streams can read directly into the application's buffer
+        if let Flavor::Local(reader) = &mut self.0 {
+            return reader.poll_read(cx, buf);
+        }
+
         let mut buf = buffer::writer::storage::BufMut::new(buf);
         ready!(self.poll_read_into(cx, &mut buf))?;
         Ok(()).into()
@@ -332,9 +375,12 @@ impl tokio::io::AsyncRead for Reader {
 impl Drop for Reader {
     #[inline]
     fn drop(&mut self) {
+        let Flavor::Dc(inner) = &mut self.0 else {
+            return;
+        };
         let inner = unsafe {
             // SAFETY: the inner type is only taken once
-            ManuallyDrop::take(&mut self.0)
+            ManuallyDrop::take(inner)
         };
         inner.shutdown();
     }
@@ -5,7 +5,7 @@ use crate::{
     clock::Timer,
     msg,
     stream::{
-        recv::application::{Inner, LocalState, Reader},
+        recv::application::{Flavor, Inner, LocalState, Reader},
         runtime,
         shared::ArcShared,
         socket,
@@ -53,7 +53,7 @@ impl Builder {
             endpoint::Type::Server => LocalState::Reading,
         };
 
-        Reader(ManuallyDrop::new(Box::new(Inner {
+        Reader(Flavor::Dc(ManuallyDrop::new(Box::new(Inner {
             shared,
             sockets,
             send_buffer,
@@ -62,6 +62,6 @@ impl Builder {
             timer,
             local_state,
             runtime,
-        })))
+        }))))
     }
 }
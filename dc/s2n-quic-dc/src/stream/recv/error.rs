@@ -7,7 +7,7 @@ use crate::{
     packet::{self, stream},
     stream::TransportFeatures,
 };
-use core::{fmt, panic::Location};
+use core::{fmt, panic::Location, time::Duration};
 use s2n_quic_core::{buffer, frame};
 
 #[derive(Clone, Copy)]
@@ -53,6 +53,17 @@ impl Error {
         &self.kind
     }
 
+    /// Returns how long to wait before opening another stream, if the server rejected the stream
+    /// because it was busy
+    #[inline]
+    pub fn retry_after(&self) -> Option<Duration> {
+        if let Kind::ServerBusy { retry_after } = self.kind {
+            Some(retry_after)
+        } else {
+            None
+        }
+    }
+
     #[inline]
     fn file(&self) -> &'static str {
         self.location
@@ -111,6 +122,8 @@ pub enum Kind {
     },
     #[error("unexpected packet: {packet:?}")]
     UnexpectedPacket { packet: packet::Kind },
+    #[error("the server is busy and rejected the stream (retry after {retry_after:?})")]
+    ServerBusy { retry_after: Duration },
 }
 
 impl Kind {
@@ -176,6 +189,8 @@ impl Error {
             Kind::OutOfRange => Some(transport::Error::STREAM_LIMIT_ERROR.into()),
             // we don't have working crypto keys so we can't respond
             Kind::KeyReplayPrevented | Kind::KeyReplayMaybePrevented { .. } => None,
+            // the server already discarded the stream
+            Kind::ServerBusy { .. } => None,
             Kind::ApplicationError { error } => Some((*error).into()),
         }
     }
@@ -223,8 +238,10 @@ impl From<Kind> for std::io::ErrorKind {
                 packet:
                     packet::Kind::UnknownPathSecret
                     | packet::Kind::StaleKey
-                    | packet::Kind::ReplayDetected,
+                    | packet::Kind::ReplayDetected
+                    | packet::Kind::ServerBusy,
             } => ErrorKind::ConnectionRefused,
+            Kind::ServerBusy { .. } => ErrorKind::ConnectionRefused,
             Kind::UnexpectedPacket {
                 packet: packet::Kind::Stream | packet::Kind::Control | packet::Kind::Datagram,
             } => ErrorKind::InvalidData,
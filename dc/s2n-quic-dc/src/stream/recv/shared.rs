@@ -14,6 +14,7 @@ use crate::{
     },
     task::waker::worker::Waker as WorkerWaker,
 };
+use atomic_waker::AtomicWaker;
 use core::{
     mem::ManuallyDrop,
     ops,
@@ -78,6 +79,8 @@ pub struct State {
     application_epoch: AtomicU64,
     application_state: AtomicU8,
     pub worker_waker: WorkerWaker,
+    /// Woken when the receiver is failed by the writer half of the stream
+    pub application_waker: AtomicWaker,
 }
 
 impl State {
@@ -107,6 +110,7 @@ impl State {
             application_epoch: AtomicU64::new(0),
             application_state: AtomicU8::new(0),
             worker_waker: Default::default(),
+            application_waker: AtomicWaker::new(),
         }
     }
 
@@ -155,6 +159,20 @@ impl State {
         self.worker_waker.wake();
     }
 
+    /// Fails the receiver on behalf of the writer half of the stream
+    ///
+    /// Secret control packets are routed to the writer's socket so the reader never sees them
+    /// directly.
+    #[inline]
+    pub fn on_error(&self, error: recv::Error) {
+        if let Ok(mut inner) = self.inner.lock() {
+            inner.receiver.on_error(error);
+        }
+
+        self.application_waker.wake();
+        self.worker_waker.wake();
+    }
+
     #[inline]
     pub fn worker_try_lock(&self) -> io::Result<Option<MutexGuard<Inner>>> {
         match self.inner.try_lock() {
@@ -490,6 +508,19 @@ impl Inner {
                         return;
                     }
                 }
+                Packet::ServerBusy(packet) => {
+                    // ignore busy notifications which aren't for this stream or aren't authentic
+                    ensure!(packet.credentials() == *shared.credentials(), continue);
+                    let Some(retry_after) = shared.crypto.map().handle_server_busy_packet(packet)
+                    else {
+                        continue;
+                    };
+
+                    msg.clear();
+                    self.receiver
+                        .on_error(recv::error::Kind::ServerBusy { retry_after });
+                    return;
+                }
                 other => {
                     let kind = other.kind();
                     shared.crypto.map().handle_unexpected_packet(other);
@@ -585,6 +616,17 @@ impl Inner {
                             <Result<_, recv::Error>>::Ok(())
                         });
                     }
+                    Packet::ServerBusy(packet) => {
+                        ensure!(packet.credentials() == *shared.credentials(), continue);
+                        let Some(retry_after) =
+                            shared.crypto.map().handle_server_busy_packet(&packet)
+                        else {
+                            continue;
+                        };
+
+                        self.receiver
+                            .on_error(recv::error::Kind::ServerBusy { retry_after });
+                    }
                     other => {
                         shared.crypto.map().handle_unexpected_packet(&other);
 
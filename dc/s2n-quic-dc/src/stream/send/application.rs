@@ -1,6 +1,8 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
+#[cfg(feature = "tokio")]
+use crate::stream::local;
 use crate::{
     clock, msg,
     stream::{
@@ -26,7 +28,13 @@ pub mod transmission;
 
 pub use builder::Builder;
 
-pub struct Writer(Box<Inner>);
+pub struct Writer(Flavor);
+
+enum Flavor {
+    Dc(Box<Inner>),
+    #[cfg(feature = "tokio")]
+    Local(local::WriteHalf),
+}
 
 struct Inner {
     shared: ArcShared,
@@ -47,20 +55,40 @@ impl fmt::Debug for Writer {
 }
 
 impl Writer {
+    #[cfg(feature = "tokio")]
+    #[inline]
+    pub(crate) fn local(writer: local::WriteHalf) -> Self {
+        Self(Flavor::Local(writer))
+    }
+
     #[inline]
     pub fn peer_addr(&self) -> io::Result<SocketAddr> {
-        self.0.shared.common.ensure_open()?;
-        Ok(self.0.shared.write_remote_addr().into())
+        match &self.0 {
+            Flavor::Dc(inner) => {
+                inner.shared.common.ensure_open()?;
+                Ok(inner.shared.write_remote_addr().into())
+            }
+            #[cfg(feature = "tokio")]
+            Flavor::Local(_) => Ok(local::ADDR),
+        }
     }
 
     #[inline]
     pub fn local_addr(&self) -> io::Result<SocketAddr> {
-        self.0.sockets.write_application().local_addr()
+        match &self.0 {
+            Flavor::Dc(inner) => inner.sockets.write_application().local_addr(),
+            #[cfg(feature = "tokio")]
+            Flavor::Local(_) => Ok(local::ADDR),
+        }
     }
 
     #[inline]
     pub fn protocol(&self) -> socket::Protocol {
-        self.0.sockets.protocol()
+        match &self.0 {
+            Flavor::Dc(inner) => inner.sockets.protocol(),
+            #[cfg(feature = "tokio")]
+            Flavor::Local(_) => socket::Protocol::Unix,
+        }
     }
 
     #[inline]
@@ -81,9 +109,15 @@ impl Writer {
     where
         S: buffer::reader::storage::Infallible,
     {
+        let inner = match &mut self.0 {
+            Flavor::Dc(inner) => inner,
+            #[cfg(feature = "tokio")]
+            Flavor::Local(writer) => return writer.poll_write_from(cx, buf, is_fin),
+        };
+
         waker::debug_assert_contract(cx, |cx| {
             // if we've already shut down the stream then return early
-            if !self.0.open {
+            if !inner.open {
                 ensure!(
                     buf.buffer_is_empty() && is_fin,
                     Err(io::Error::from(io::ErrorKind::BrokenPipe)).into()
@@ -91,12 +125,12 @@ impl Writer {
                 return Ok(0).into();
             }
 
-            let res = ready!(self.0.poll_write_from(cx, buf, is_fin));
+            let res = ready!(inner.poll_write_from(cx, buf, is_fin));
 
             // if we got an error then shut down the stream if needed
             if res.is_err() {
                 // use the `Drop` type so we send a RST instead
-                let _ = self.0.shutdown(ShutdownType::Drop {
+                let _ = inner.shutdown(ShutdownType::Drop {
                     is_panicking: false,
                 });
             }
@@ -107,7 +141,16 @@ impl Writer {
 
     /// Shutdown the stream for writing.
     pub fn shutdown(&mut self) -> io::Result<()> {
-        self.0.shutdown(ShutdownType::Explicit)
+        self.shutdown_with(ShutdownType::Explicit)
+    }
+
+    #[inline]
+    fn shutdown_with(&mut self, ty: ShutdownType) -> io::Result<()> {
+        match &mut self.0 {
+            Flavor::Dc(inner) => inner.shutdown(ty),
+            #[cfg(feature = "tokio")]
+            Flavor::Local(writer) => writer.shutdown(),
+        }
     }
 }
 
@@ -326,9 +369,13 @@ impl tokio::io::AsyncWrite for Writer {
     #[inline]
     fn poll_shutdown(
         mut self: Pin<&mut Self>,
-        _cx: &mut Context<'_>,
+        cx: &mut Context<'_>,
     ) -> Poll<Result<(), io::Error>> {
-        self.0.shutdown(ShutdownType::Explicit).into()
+        match &mut self.0 {
+            Flavor::Dc(inner) => inner.shutdown(ShutdownType::Explicit).into(),
+            // wait for any accepted bytes to be written before closing the socket
+            Flavor::Local(writer) => writer.poll_shutdown(cx),
+        }
     }
 
     #[inline(always)]
@@ -340,7 +387,7 @@ impl tokio::io::AsyncWrite for Writer {
 impl Drop for Writer {
     #[inline]
     fn drop(&mut self) {
-        let _ = self.0.shutdown(ShutdownType::Drop {
+        let _ = self.shutdown_with(ShutdownType::Drop {
             is_panicking: std::thread::panicking(),
         });
     }
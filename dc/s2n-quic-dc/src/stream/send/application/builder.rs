@@ -3,7 +3,7 @@
 
 use crate::stream::{
     runtime,
-    send::application::{Inner, Writer},
+    send::application::{Flavor, Inner, Writer},
     shared::ArcShared,
     socket,
 };
@@ -21,13 +21,13 @@ impl Builder {
     #[inline]
     pub fn build(self, shared: ArcShared, sockets: socket::ArcApplication) -> Writer {
         let Self { runtime } = self;
-        Writer(Box::new(Inner {
+        Writer(Flavor::Dc(Box::new(Inner {
             shared,
             sockets,
             queue: Default::default(),
             pacer: Default::default(),
             open: true,
             runtime,
-        }))
+        })))
     }
 }
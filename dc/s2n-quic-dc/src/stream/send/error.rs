@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::stream::packet_number;
-use core::{fmt, panic::Location};
+use core::{fmt, panic::Location, time::Duration};
 use s2n_quic_core::{buffer, varint::VarInt};
 
 #[derive(Clone, Copy)]
@@ -48,6 +48,17 @@ impl Error {
         &self.kind
     }
 
+    /// Returns how long to wait before opening another stream, if the server rejected the stream
+    /// because it was busy
+    #[inline]
+    pub fn retry_after(&self) -> Option<Duration> {
+        if let Kind::ServerBusy { retry_after } = self.kind {
+            Some(retry_after)
+        } else {
+            None
+        }
+    }
+
     #[inline]
     fn file(&self) -> &'static str {
         self.location
@@ -90,6 +101,8 @@ pub enum Kind {
     FrameError { decoder: s2n_codec::DecoderError },
     #[error("the stream experienced an unrecoverable error")]
     FatalError,
+    #[error("the server is busy and rejected the stream (retry after {retry_after:?})")]
+    ServerBusy { retry_after: Duration },
 }
 
 impl Kind {
@@ -124,6 +137,7 @@ impl From<Kind> for std::io::ErrorKind {
             Kind::TransportError { .. } => ErrorKind::ConnectionAborted,
             Kind::FrameError { .. } => ErrorKind::InvalidData,
             Kind::FatalError => ErrorKind::BrokenPipe,
+            Kind::ServerBusy { .. } => ErrorKind::ConnectionRefused,
         }
     }
 }
@@ -117,7 +117,12 @@ pub struct PeerActivity {
 
 impl State {
     #[inline]
-    pub fn new(stream_id: stream::Id, params: &ApplicationParams) -> Self {
+    pub fn new(
+        stream_id: stream::Id,
+        params: &ApplicationParams,
+        algorithm: congestion::Algorithm,
+        subscriber: Option<congestion::Subscriber>,
+    ) -> Self {
         let max_datagram_size = params.max_datagram_size();
         let initial_max_data = params.remote_max_data;
         let local_max_data = params.local_send_max_data;
@@ -126,7 +131,7 @@ impl State {
         let mut unacked_ranges = IntervalSet::new();
         unacked_ranges.insert(VarInt::ZERO..=VarInt::MAX).unwrap();
 
-        let cca = congestion::Controller::new(max_datagram_size);
+        let cca = congestion::Controller::with_algorithm(algorithm, max_datagram_size, subscriber);
         let max_sent_offset = VarInt::ZERO;
 
         Self {
@@ -199,7 +204,8 @@ impl State {
     pub fn send_quantum_packets(&self) -> u8 {
         // TODO use div_ceil when we're on 1.73+ MSRV
         // https://doc.rust-lang.org/std/primitive.u64.html#method.div_ceil
-        let send_quantum = (self.cca.send_quantum() as u64 + self.max_datagram_size as u64 - 1)
+        let send_quantum = (self.cca.send_quantum() as u64)
+            .saturating_add(self.max_datagram_size as u64 - 1)
             / self.max_datagram_size as u64;
         send_quantum.try_into().unwrap_or(u8::MAX)
     }
@@ -546,7 +552,7 @@ impl State {
             );
 
             self.cca.on_packet_ack(
-                cc_info.first_sent_time,
+                time_sent,
                 bytes_acked,
                 cc_info,
                 &self.rtt_estimator,
@@ -7,7 +7,7 @@ use crate::{
     msg::addr,
     packet::Packet,
     stream::{
-        pacer,
+        pacer, recv,
         send::{
             error::{self, Error},
             queue::Queue,
@@ -335,6 +335,22 @@ where
                             any_valid_packets = true;
                         }
                     }
+                    Packet::ServerBusy(packet) => {
+                        // make sure the peer rejected this stream and not a previous one
+                        ensure!(packet.credentials() == *self.shared.credentials(), continue);
+
+                        let Some(retry_after) =
+                            self.shared.crypto.map().handle_server_busy_packet(&packet)
+                        else {
+                            continue;
+                        };
+
+                        self.sender
+                            .on_error(error::Kind::ServerBusy { retry_after });
+                        self.shared
+                            .receiver
+                            .on_error(recv::Kind::ServerBusy { retry_after }.into());
+                    }
                     other => self.shared.crypto.map().handle_unexpected_packet(&other),
                 }
             }
@@ -482,7 +498,7 @@ where
             max_datagram_size: self.sender.max_datagram_size,
             next_expected_control_packet: self.sender.next_expected_control_packet,
             timeout: self.sender.next_expiration(),
-            bandwidth: self.sender.cca.bandwidth(),
+            bandwidth: self.sender.cca.bandwidth(&self.sender.rtt_estimator),
             error: self.sender.error,
         }
     }
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub mod accept;
+pub mod admission;
 pub mod stats;
 pub mod tcp;
 pub mod udp;
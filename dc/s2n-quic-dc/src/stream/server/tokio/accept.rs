@@ -1,7 +1,7 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
-use super::stats;
+use super::{admission, stats};
 use crate::{
     stream::{
         application::{Builder as StreamBuilder, Stream},
@@ -21,12 +21,17 @@ pub enum Flavor {
     Lifo,
 }
 
-pub type Sender = channel::Sender<(StreamBuilder, Timestamp)>;
-pub type Receiver = channel::Receiver<(StreamBuilder, Timestamp)>;
+/// A stream waiting to be accepted, along with the time it was queued
+///
+/// The admission permit is released once the stream is accepted or dropped.
+pub type Item = (StreamBuilder, Timestamp, admission::Permit);
+
+pub type Sender = channel::Sender<Item>;
+pub type Receiver = channel::Receiver<Item>;
 
 #[inline]
 pub async fn accept(streams: &Receiver, stats: &stats::Sender) -> io::Result<(Stream, SocketAddr)> {
-    let (stream, queue_time) = streams.recv_front().await.map_err(|_err| {
+    let (stream, queue_time, _permit) = streams.recv_front().await.map_err(|_err| {
         io::Error::new(
             io::ErrorKind::NotConnected,
             "server acceptor runtime is no longer available",
@@ -83,7 +88,7 @@ impl Pruner {
     pub async fn run(
         self,
         env: Environment,
-        channel: channel::WeakReceiver<(StreamBuilder, Timestamp)>,
+        channel: channel::WeakReceiver<Item>,
         stats: stats::Stats,
     ) {
         let Self {
@@ -115,13 +120,13 @@ impl Pruner {
 
             loop {
                 // pop off any items that have expired
-                let res = channel.pop_back_if(priority, |(_stream, queue_time)| {
+                let res = channel.pop_back_if(priority, |(_stream, queue_time, _permit)| {
                     queue_time.has_elapsed(queue_time_threshold)
                 });
 
                 match res {
                     // we pruned a stream
-                    Ok(Some((stream, queue_time))) => {
+                    Ok(Some((stream, queue_time, _permit))) => {
                         tracing::debug!(
                             event = "accept::prune",
                             credentials = ?stream.shared.credentials(),
@@ -0,0 +1,240 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Admission control for the stream acceptor
+//!
+//! Rather than queueing streams the application is unlikely to accept in time, the acceptor asks
+//! the [`Controller`] for a [`Permit`] before accepting each stream. Rejected streams are
+//! answered with an authenticated `ServerBusy` packet, which includes a hint for how long the
+//! client should back off before opening another stream.
+
+use super::stats;
+use core::{
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Duration,
+};
+use std::{
+    collections::HashMap,
+    net::IpAddr,
+    sync::{Arc, Mutex},
+};
+
+#[derive(Clone, Debug)]
+pub struct Policy {
+    /// Rejects new streams once this many are waiting to be accepted by the application
+    pub max_queue_depth: Option<usize>,
+
+    /// Rejects new streams while the smoothed sojourn time of the accept queue is above this
+    /// amount
+    pub max_sojourn_time: Option<Duration>,
+
+    /// Rejects new streams from a peer IP address once this many of its streams are waiting to be
+    /// accepted by the application
+    pub max_queued_per_peer: Option<usize>,
+
+    /// The minimum amount of time rejected clients are asked to wait before retrying
+    pub min_retry_after: Duration,
+
+    /// The maximum amount of time rejected clients are asked to wait before retrying
+    pub max_retry_after: Duration,
+}
+
+impl Default for Policy {
+    /// Admits all streams
+    fn default() -> Self {
+        Self {
+            max_queue_depth: None,
+            max_sojourn_time: None,
+            max_queued_per_peer: None,
+            min_retry_after: Duration::from_millis(10),
+            max_retry_after: Duration::from_secs(1),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Reason {
+    QueueDepth,
+    SojournTime,
+    PeerQuota,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Rejected {
+    pub reason: Reason,
+    /// The amount of time the client should wait before opening another stream
+    pub retry_after: Duration,
+}
+
+#[derive(Clone)]
+pub struct Controller {
+    policy: Arc<Policy>,
+    stats: stats::Stats,
+    state: Arc<State>,
+}
+
+#[derive(Default)]
+struct State {
+    queued: AtomicUsize,
+    peers: Mutex<HashMap<IpAddr, usize>>,
+}
+
+impl Controller {
+    #[inline]
+    pub fn new(policy: Policy, stats: stats::Stats) -> Self {
+        Self {
+            policy: Arc::new(policy),
+            stats,
+            state: Default::default(),
+        }
+    }
+
+    /// Returns the number of admitted streams which are still waiting to be accepted
+    #[inline]
+    pub fn queued(&self) -> usize {
+        self.state.queued.load(Ordering::Relaxed)
+    }
+
+    /// Checks if a new stream from `peer` can be queued
+    ///
+    /// The returned [`Permit`] should be held until the stream is accepted or dropped.
+    #[inline]
+    pub fn admit(&self, peer: IpAddr) -> Result<Permit, Rejected> {
+        let policy = &*self.policy;
+
+        if let Some(max) = policy.max_sojourn_time {
+            if self.stats.smoothed_sojourn_time() > max {
+                return Err(self.reject(Reason::SojournTime));
+            }
+        }
+
+        let queued = self.state.queued.fetch_add(1, Ordering::Relaxed);
+        // create the permit first so the count is restored on rejection
+        let mut permit = Permit {
+            state: self.state.clone(),
+            peer: None,
+        };
+
+        if let Some(max) = policy.max_queue_depth {
+            if queued >= max {
+                return Err(self.reject(Reason::QueueDepth));
+            }
+        }
+
+        if let Some(max) = policy.max_queued_per_peer {
+            let Ok(mut peers) = self.state.peers.lock() else {
+                return Err(self.reject(Reason::PeerQuota));
+            };
+            let count = peers.entry(peer).or_default();
+            if *count >= max {
+                return Err(self.reject(Reason::PeerQuota));
+            }
+            *count += 1;
+            permit.peer = Some(peer);
+        }
+
+        Ok(permit)
+    }
+
+    #[inline]
+    fn reject(&self, reason: Reason) -> Rejected {
+        let policy = &*self.policy;
+        // ask the client to wait about as long as it would have waited in the queue
+        let retry_after = self
+            .stats
+            .smoothed_sojourn_time()
+            .clamp(policy.min_retry_after, policy.max_retry_after);
+        Rejected {
+            reason,
+            retry_after,
+        }
+    }
+}
+
+/// Tracks a stream in the accept queue until it is dropped
+pub struct Permit {
+    state: Arc<State>,
+    peer: Option<IpAddr>,
+}
+
+impl Drop for Permit {
+    #[inline]
+    fn drop(&mut self) {
+        self.state.queued.fetch_sub(1, Ordering::Relaxed);
+
+        let Some(peer) = self.peer else {
+            return;
+        };
+
+        let Ok(mut peers) = self.state.peers.lock() else {
+            return;
+        };
+
+        if let Some(count) = peers.get_mut(&peer) {
+            *count -= 1;
+            if *count == 0 {
+                peers.remove(&peer);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn controller(policy: Policy) -> Controller {
+        Controller::new(policy, Default::default())
+    }
+
+    #[test]
+    fn default_admits_all() {
+        let controller = controller(Policy::default());
+        let peer = IpAddr::from([127, 0, 0, 1]);
+        let permits = (0..100)
+            .map(|_| controller.admit(peer).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(controller.queued(), 100);
+        drop(permits);
+        assert_eq!(controller.queued(), 0);
+    }
+
+    #[test]
+    fn queue_depth() {
+        let controller = controller(Policy {
+            max_queue_depth: Some(2),
+            ..Default::default()
+        });
+        let peer = IpAddr::from([127, 0, 0, 1]);
+
+        let a = controller.admit(peer).unwrap();
+        let _b = controller.admit(peer).unwrap();
+        let rejected = controller.admit(peer).err().unwrap();
+        assert_eq!(rejected.reason, Reason::QueueDepth);
+        assert_eq!(rejected.retry_after, Policy::default().min_retry_after);
+        assert_eq!(controller.queued(), 2);
+
+        // accepting a stream frees up a slot
+        drop(a);
+        assert!(controller.admit(peer).is_ok());
+    }
+
+    #[test]
+    fn peer_quota() {
+        let controller = controller(Policy {
+            max_queued_per_peer: Some(1),
+            ..Default::default()
+        });
+        let a = IpAddr::from([127, 0, 0, 1]);
+        let b = IpAddr::from([127, 0, 0, 2]);
+
+        let permit = controller.admit(a).unwrap();
+        let _other = controller.admit(b).unwrap();
+        let rejected = controller.admit(a).err().unwrap();
+        assert_eq!(rejected.reason, Reason::PeerQuota);
+
+        drop(permit);
+        assert!(controller.admit(a).is_ok());
+        assert_eq!(controller.state.peers.lock().unwrap().len(), 1);
+    }
+}
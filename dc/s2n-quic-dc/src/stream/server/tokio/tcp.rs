@@ -1,7 +1,7 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
-use super::accept;
+use super::{accept, admission};
 use crate::{
     msg,
     path::secret,
@@ -13,6 +13,7 @@ use crate::{
         },
         server,
         socket::Socket,
+        TransportFeatures,
     },
 };
 use core::{
@@ -43,6 +44,7 @@ pub struct Acceptor {
     secrets: secret::Map,
     backlog: usize,
     accept_flavor: accept::Flavor,
+    admission: admission::Controller,
 }
 
 impl Acceptor {
@@ -54,6 +56,7 @@ impl Acceptor {
         secrets: &secret::Map,
         backlog: usize,
         accept_flavor: accept::Flavor,
+        admission: &admission::Controller,
     ) -> Self {
         Self {
             sender: sender.clone(),
@@ -62,6 +65,7 @@ impl Acceptor {
             secrets: secrets.clone(),
             backlog,
             accept_flavor,
+            admission: admission.clone(),
         }
     }
 
@@ -339,6 +343,7 @@ struct WorkerContext {
     env: Environment,
     secrets: secret::Map,
     accept_flavor: accept::Flavor,
+    admission: admission::Controller,
 }
 
 impl WorkerContext {
@@ -349,6 +354,7 @@ impl WorkerContext {
             env: acceptor.env.clone(),
             secrets: acceptor.secrets.clone(),
             accept_flavor: acceptor.accept_flavor,
+            admission: acceptor.admission.clone(),
         }
     }
 }
@@ -486,10 +492,70 @@ impl WorkerState {
 
             debug!(?initial_packet);
 
+            // only authentic, non-replayed packets are subject to admission control so forged
+            // or replayed packets can't be used to trigger busy notifications for other streams
+            let authenticated = match endpoint::authenticate_stream(
+                &initial_packet,
+                recv_buffer,
+                &TransportFeatures::TCP,
+                &context.secrets,
+            ) {
+                Ok(authenticated) => authenticated,
+                Err(error) => {
+                    if !error.secret_control.is_empty() {
+                        // if we need to send an error then update the state and loop back around
+                        *self = WorkerState::Erroring {
+                            offset: 0,
+                            buffer: error.secret_control,
+                            error: error.error,
+                        };
+                        continue;
+                    }
+                    return Err(error.error).into();
+                }
+            };
+
+            let remote_addr = stream.as_ref().unwrap().peer_addr()?;
+            let permit = match context.admission.admit(remote_addr.ip()) {
+                Ok(permit) => permit,
+                Err(rejected) => {
+                    debug!(
+                        event = "accept::reject",
+                        credentials = ?initial_packet.credentials,
+                        %remote_addr,
+                        reason = ?rejected.reason,
+                        retry_after = ?rejected.retry_after,
+                    );
+
+                    let error = io::Error::new(
+                        io::ErrorKind::ConnectionRefused,
+                        "stream rejected by admission control",
+                    );
+
+                    let mut buffer = vec![];
+                    if context.secrets.server_busy_packet(
+                        &initial_packet.credentials,
+                        rejected.retry_after,
+                        &mut buffer,
+                    ) {
+                        // notify the client before closing the stream
+                        *self = WorkerState::Erroring {
+                            offset: 0,
+                            buffer,
+                            error,
+                        };
+                        continue;
+                    }
+
+                    return Err(error).into();
+                }
+            };
+
             let stream_builder = match endpoint::accept_stream(
                 &context.env,
                 env::TcpReregistered(stream.take().unwrap()),
                 &initial_packet,
+                authenticated,
                 None,
                 Some(recv_buffer),
                 &context.secrets,
@@ -519,7 +585,7 @@ impl WorkerState {
                 sojourn_time = ?now.saturating_duration_since(queue_time),
             );
 
-            let item = (stream_builder, queue_time);
+            let item = (stream_builder, queue_time, permit);
             let res = match context.accept_flavor {
                 accept::Flavor::Fifo => context.sender.send_back(item),
                 accept::Flavor::Lifo => context.sender.send_front(item),
@@ -527,7 +593,7 @@ impl WorkerState {
 
             return Poll::Ready(Ok(match res {
                 Ok(prev) => {
-                    if let Some((stream, queue_time)) = prev {
+                    if let Some((stream, queue_time, _permit)) = prev {
                         debug!(
                             event = "accept::prune",
                             credentials = ?stream.shared.credentials(),
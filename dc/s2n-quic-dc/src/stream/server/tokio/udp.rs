@@ -1,7 +1,7 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
-use super::accept;
+use super::{accept, admission};
 use crate::{
     msg,
     path::secret,
@@ -13,11 +13,12 @@ use crate::{
         },
         server,
         socket::{Ext as _, Socket},
+        TransportFeatures,
     },
 };
 use core::ops::ControlFlow;
-use s2n_quic_core::time::Clock as _;
-use std::io;
+use s2n_quic_core::{ensure, inet::SocketAddress, time::Clock as _};
+use std::{io, net::SocketAddr};
 use tracing::debug;
 
 pub struct Acceptor<S: Socket> {
@@ -28,6 +29,7 @@ pub struct Acceptor<S: Socket> {
     env: Environment,
     secrets: secret::Map,
     accept_flavor: accept::Flavor,
+    admission: admission::Controller,
 }
 
 impl<S: Socket> Acceptor<S> {
@@ -38,6 +40,7 @@ impl<S: Socket> Acceptor<S> {
         env: &Environment,
         secrets: &secret::Map,
         accept_flavor: accept::Flavor,
+        admission: &admission::Controller,
     ) -> Self {
         Self {
             sender: sender.clone(),
@@ -47,6 +50,7 @@ impl<S: Socket> Acceptor<S> {
             env: env.clone(),
             secrets: secrets.clone(),
             accept_flavor,
+            admission: admission.clone(),
         }
     }
 
@@ -75,32 +79,65 @@ impl<S: Socket> Acceptor<S> {
         };
 
         let remote_addr = self.recv_buffer.remote_address();
-        let stream = match endpoint::accept_stream(
-            &self.env,
-            env::UdpUnbound(remote_addr),
+
+        // only authentic, non-replayed packets are subject to admission control so forged or
+        // replayed packets can't be used to trigger busy notifications for other streams
+        let authenticated = match endpoint::authenticate_stream(
             &packet,
-            Some(handshake),
-            Some(&mut self.recv_buffer),
+            &mut self.recv_buffer,
+            &TransportFeatures::UDP,
             &self.secrets,
-            None,
         ) {
-            Ok(stream) => stream,
+            Ok(authenticated) => authenticated,
             Err(error) => {
-                tracing::trace!("send_start");
+                self.send_secret_control(remote_addr, &error.secret_control);
+                return Err(error.error);
+            }
+        };
 
-                let addr = msg::addr::Addr::new(remote_addr);
-                let ecn = Default::default();
-                let buffer = &[io::IoSlice::new(&error.secret_control)];
+        let permit = match self.admission.admit(SocketAddr::from(remote_addr).ip()) {
+            Ok(permit) => permit,
+            Err(rejected) => {
+                self.reject(&packet, remote_addr, rejected);
+                return Ok(ControlFlow::Continue(()));
+            }
+        };
 
-                // ignore any errors since this is just for responding to invalid connect attempts
-                let _ = self.socket.try_send(&addr, ecn, buffer);
+        let res = if self.env.has_socket_pool() {
+            endpoint::accept_stream(
+                &self.env,
+                env::UdpPooled(remote_addr),
+                &packet,
+                authenticated,
+                Some(handshake),
+                Some(&mut self.recv_buffer),
+                &self.secrets,
+                None,
+            )
+            .map_err(|error| (error.secret_control, error.error))
+        } else {
+            endpoint::accept_stream(
+                &self.env,
+                env::UdpUnbound(remote_addr),
+                &packet,
+                authenticated,
+                Some(handshake),
+                Some(&mut self.recv_buffer),
+                &self.secrets,
+                None,
+            )
+            .map_err(|error| (error.secret_control, error.error))
+        };
 
-                tracing::trace!("send_finish");
-                return Err(error.error);
+        let stream = match res {
+            Ok(stream) => stream,
+            Err((secret_control, error)) => {
+                self.send_secret_control(remote_addr, &secret_control);
+                return Err(error);
             }
         };
 
-        let item = (stream, now);
+        let item = (stream, now, permit);
         let res = match self.accept_flavor {
             accept::Flavor::Fifo => self.sender.send_back(item),
             accept::Flavor::Lifo => self.sender.send_front(item),
@@ -108,7 +145,7 @@ impl<S: Socket> Acceptor<S> {
 
         match res {
             Ok(prev) => {
-                if let Some((stream, queue_time)) = prev {
+                if let Some((stream, queue_time, _permit)) = prev {
                     debug!(
                         event = "accept::prune",
                         credentials = ?stream.shared.credentials(),
@@ -125,6 +162,64 @@ impl<S: Socket> Acceptor<S> {
         }
     }
 
+    /// Responds to an invalid connect attempt
+    fn send_secret_control(&mut self, remote_addr: SocketAddress, secret_control: &[u8]) {
+        ensure!(!secret_control.is_empty());
+
+        tracing::trace!("send_start");
+
+        let addr = msg::addr::Addr::new(remote_addr);
+        let ecn = Default::default();
+        let buffer = &[io::IoSlice::new(secret_control)];
+
+        // ignore any errors since this is just for responding to invalid connect attempts
+        let _ = self.socket.try_send(&addr, ecn, buffer);
+
+        tracing::trace!("send_finish");
+    }
+
+    /// Notifies the client that the stream was rejected so it can back off rather than waiting
+    /// for the stream to time out
+    fn reject(
+        &mut self,
+        packet: &server::InitialPacket,
+        remote_addr: SocketAddress,
+        rejected: admission::Rejected,
+    ) {
+        debug!(
+            event = "accept::reject",
+            credentials = ?packet.credentials,
+            %remote_addr,
+            reason = ?rejected.reason,
+            retry_after = ?rejected.retry_after,
+        );
+
+        let mut secret_control = vec![];
+        if !self.secrets.server_busy_packet(
+            &packet.credentials,
+            rejected.retry_after,
+            &mut secret_control,
+        ) {
+            return;
+        }
+
+        let mut addr = msg::addr::Addr::new(remote_addr);
+        let ecn = Default::default();
+        let buffer = &[io::IoSlice::new(&secret_control)];
+
+        // ignore any errors since the client will eventually time out the stream
+        let _ = self.socket.try_send(&addr, ecn, buffer);
+
+        // the client may be reading the stream on a separate socket so notify that one as well
+        if let Some(port) = packet
+            .source_stream_port
+            .filter(|port| *port != remote_addr.port())
+        {
+            addr.set_port(port);
+            let _ = self.socket.try_send(&addr, ecn, buffer);
+        }
+    }
+
     async fn recv_packet(&mut self) -> io::Result<server::InitialPacket> {
         loop {
             // discard any pending packets
@@ -7,6 +7,8 @@ pub mod application;
 pub mod fd;
 mod handle;
 #[cfg(feature = "tokio")]
+pub mod pool;
+#[cfg(feature = "tokio")]
 mod tokio;
 mod tracing;
 
@@ -22,12 +24,17 @@ pub type ArcApplication = std::sync::Arc<dyn Application>;
 pub enum Protocol {
     Tcp,
     Udp,
+    /// Same-host streams over Unix domain sockets
+    ///
+    /// See [`crate::stream::local`].
+    Unix,
     Other(&'static str),
 }
 
 impl Protocol {
     s2n_quic_core::state::is!(is_tcp, Tcp);
     s2n_quic_core::state::is!(is_udp, Udp);
+    s2n_quic_core::state::is!(is_unix, Unix);
 
     #[inline]
     pub fn is_other(&self) -> bool {
@@ -11,7 +11,7 @@ pub trait Builder: 'static + Send + Sync {
 #[cfg(feature = "tokio")]
 mod tokio_impl {
     use super::*;
-    use crate::stream::socket::{application, Tracing};
+    use crate::stream::socket::{self, application, Tracing};
     use std::{io, sync::Arc};
     use tokio::io::unix::AsyncFd;
 
@@ -56,6 +56,32 @@ mod tokio_impl {
         }
     }
 
+    impl Builder for socket::pool::Socket {
+        #[inline]
+        fn build(self: Box<Self>) -> io::Result<ArcApplication> {
+            let v = Tracing(*self);
+            let v = application::Single(v);
+            let v = Arc::new(v);
+            Ok(v)
+        }
+    }
+
+    pub struct PoolPair {
+        pub reader: socket::pool::Socket,
+        pub writer: socket::pool::Socket,
+    }
+
+    impl Builder for PoolPair {
+        #[inline]
+        fn build(self: Box<Self>) -> io::Result<ArcApplication> {
+            let read = Tracing(self.reader);
+            let write = Tracing(self.writer);
+            let v = application::Pair { read, write };
+            let v = Arc::new(v);
+            Ok(v)
+        }
+    }
+
     impl Builder for std::net::TcpStream {
         #[inline]
         fn build(self: Box<Self>) -> io::Result<ArcApplication> {
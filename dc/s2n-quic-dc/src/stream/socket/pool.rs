@@ -0,0 +1,762 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A fixed pool of UDP sockets shared between streams
+//!
+//! Instead of binding a new socket pair for every stream, each stream is assigned to one of the
+//! pooled sockets and registers a route for its [`Credentials`]. A dispatcher task per socket
+//! receives datagrams, peeks at the credentials of each packet and forwards it to the queue of
+//! the matching stream.
+//!
+//! GRO can coalesce packets from different streams into a single datagram so the dispatcher
+//! splits received datagrams into their segments and routes each of them on its own.
+//!
+//! The dispatchers exit once the pool and all of the streams registered with it are dropped.
+
+use super::{Protocol, Socket as _, TransportFeatures};
+use crate::{
+    credentials::Credentials,
+    msg::{addr::Addr, cmsg},
+    packet,
+    socket::Pair,
+};
+use core::{
+    sync::atomic::{AtomicUsize, Ordering},
+    task::{Context, Poll, Waker},
+};
+use parking_lot::Mutex;
+use s2n_codec::DecoderBufferMut;
+use s2n_quic_core::{inet::ExplicitCongestionNotification, ready};
+use std::{
+    collections::{hash_map::Entry, HashMap, VecDeque},
+    io::{self, IoSlice, IoSliceMut},
+    net::{SocketAddr, UdpSocket},
+    sync::{Arc, Weak},
+};
+use tokio::{io::unix::AsyncFd, runtime::Handle};
+
+/// The maximum number of datagrams buffered for a single stream before new ones are dropped
+const MAX_QUEUED_DATAGRAMS: usize = 1024;
+
+/// The maximum number of datagrams a dispatcher processes before yielding to the runtime
+const DISPATCH_BUDGET: usize = 64;
+
+/// The length of the authentication tag used by the stream packets
+const TAG_LEN: usize = 16;
+
+pub struct Pool {
+    slots: Box<[Slot]>,
+    next: AtomicUsize,
+}
+
+struct Slot {
+    reader: Arc<Shared>,
+    writer: Arc<Shared>,
+}
+
+impl Pool {
+    /// Opens `size` socket pairs and spawns the dispatchers onto the reader and writer runtimes
+    pub fn new(
+        options: super::Options,
+        size: usize,
+        reader_rt: &Handle,
+        writer_rt: &Handle,
+    ) -> io::Result<Self> {
+        if size == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "socket pool must contain at least one socket",
+            ));
+        }
+
+        let mut slots = Vec::with_capacity(size);
+
+        for _ in 0..size {
+            let Pair { writer, reader } = Pair::open(options.clone())?;
+
+            let reader = Shared::spawn(reader, reader_rt)?;
+            let writer = Shared::spawn(writer, writer_rt)?;
+
+            slots.push(Slot { reader, writer });
+        }
+
+        Ok(Self {
+            slots: slots.into(),
+            next: AtomicUsize::new(0),
+        })
+    }
+
+    /// Returns the local address of the pooled sockets
+    #[inline]
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.slots[0].writer.local_addr()
+    }
+
+    /// Registers the stream `credentials` with one of the pooled sockets
+    ///
+    /// Returns the `(reader, writer)` sockets for the stream. Packets are routed to the stream
+    /// until both of the returned sockets, and any of their clones, are dropped.
+    #[inline]
+    pub fn register(&self, credentials: &Credentials) -> io::Result<(Socket, Socket)> {
+        let idx = self.next.fetch_add(1, Ordering::Relaxed) % self.slots.len();
+        let slot = &self.slots[idx];
+
+        let reader = slot.reader.register(credentials)?;
+        let writer = slot.writer.register(credentials)?;
+
+        Ok((reader, writer))
+    }
+}
+
+/// State for a single pooled socket
+struct Shared {
+    socket: AsyncFd<Arc<UdpSocket>>,
+    routes: Mutex<HashMap<Credentials, Weak<Route>>>,
+    writable: Mutex<Writable>,
+}
+
+#[derive(Default)]
+struct Writable {
+    /// Streams waiting on the socket send buffer
+    senders: Vec<Waker>,
+    /// The waker for the dispatcher task
+    dispatcher: Option<Waker>,
+}
+
+impl Shared {
+    #[inline]
+    fn spawn(socket: UdpSocket, rt: &Handle) -> io::Result<Arc<Self>> {
+        let socket = {
+            let _guard = rt.enter();
+            AsyncFd::new(Arc::new(socket))?
+        };
+
+        let shared = Arc::new(Self {
+            socket,
+            routes: Default::default(),
+            writable: Default::default(),
+        });
+
+        // the dispatcher only holds a weak reference so it exits once the pool and its streams
+        // are gone
+        let dispatcher = Arc::downgrade(&shared);
+        rt.spawn(async move {
+            let mut buffer = vec![0u8; u16::MAX as usize];
+            core::future::poll_fn(|cx| {
+                let Some(dispatcher) = dispatcher.upgrade() else {
+                    return Poll::Ready(());
+                };
+                dispatcher.poll_dispatch(cx, &mut buffer)
+            })
+            .await
+        });
+
+        Ok(shared)
+    }
+
+    #[inline]
+    fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.socket.local_addr()
+    }
+
+    #[inline]
+    fn register(self: &Arc<Self>, credentials: &Credentials) -> io::Result<Socket> {
+        let route = Arc::new(Route {
+            credentials: *credentials,
+            shared: self.clone(),
+            queue: Default::default(),
+        });
+
+        match self.routes.lock().entry(*credentials) {
+            Entry::Occupied(mut entry) => {
+                // the previous stream may have been dropped without cleaning up yet
+                if entry.get().strong_count() > 0 {
+                    return Err(io::Error::new(
+                        io::ErrorKind::AlreadyExists,
+                        format!("stream is already registered: {credentials:?}"),
+                    ));
+                }
+                entry.insert(Arc::downgrade(&route));
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(Arc::downgrade(&route));
+            }
+        }
+
+        Ok(Socket { route })
+    }
+
+    fn poll_dispatch(&self, cx: &mut Context, buffer: &mut [u8]) -> Poll<()> {
+        self.poll_writable(cx);
+
+        for _ in 0..DISPATCH_BUDGET {
+            let mut addr = Addr::default();
+            let mut cmsg = cmsg::Receiver::default();
+            let mut iov = [IoSliceMut::new(buffer)];
+
+            let res = ready!(self.socket.poll_recv(cx, &mut addr, &mut cmsg, &mut iov));
+
+            match res {
+                Ok(len) => self.dispatch(&addr, &cmsg, &mut buffer[..len]),
+                Err(err) => {
+                    // errors on UDP sockets are transient so keep receiving
+                    tracing::debug!(pool_recv_error = %err);
+                }
+            }
+        }
+
+        // yield to the runtime so other tasks can make progress
+        cx.waker().wake_by_ref();
+        Poll::Pending
+    }
+
+    #[inline]
+    fn dispatch(&self, addr: &Addr, cmsg: &cmsg::Receiver, payload: &mut [u8]) {
+        let segment_len = match cmsg.segment_len() as usize {
+            0 => payload.len(),
+            len => len,
+        };
+
+        // GRO may have coalesced packets for different streams so route each segment separately
+        for segment in payload.chunks_mut(segment_len) {
+            self.dispatch_segment(addr, cmsg.ecn(), segment);
+        }
+    }
+
+    #[inline]
+    fn dispatch_segment(
+        &self,
+        addr: &Addr,
+        ecn: ExplicitCongestionNotification,
+        segment: &mut [u8],
+    ) {
+        let decoder = DecoderBufferMut::new(segment);
+        let target = match decoder.decode_parameterized(TAG_LEN) {
+            Ok((packet::Packet::Stream(packet), _)) => Target::Stream(*packet.credentials()),
+            Ok((packet::Packet::Control(packet), _)) => Target::Stream(*packet.credentials()),
+            Ok((packet::Packet::Datagram(packet), _)) => Target::Stream(*packet.credentials()),
+            Ok((packet::Packet::StaleKey(packet), _)) => Target::Path(*packet.credential_id()),
+            Ok((packet::Packet::ReplayDetected(packet), _)) => {
+                Target::Path(*packet.credential_id())
+            }
+            Ok((packet::Packet::UnknownPathSecret(packet), _)) => {
+                Target::Path(*packet.credential_id())
+            }
+            // busy packets reject a single stream so route them to it directly
+            Ok((packet::Packet::ServerBusy(packet), _)) => Target::Stream(packet.credentials()),
+            Err(err) => {
+                tracing::trace!(pool_decoder_error = %err, remote_addr = %addr);
+                return;
+            }
+        };
+
+        let routes: Vec<_> = {
+            let routes = self.routes.lock();
+            match target {
+                Target::Stream(credentials) => routes
+                    .get(&credentials)
+                    .and_then(Weak::upgrade)
+                    .into_iter()
+                    .collect(),
+                // secret control packets only carry the path secret id so deliver them to all of
+                // the streams using that path
+                Target::Path(id) => routes
+                    .iter()
+                    .filter(|(credentials, _)| credentials.id == id)
+                    .filter_map(|(_, route)| route.upgrade())
+                    .collect(),
+            }
+        };
+
+        if routes.is_empty() {
+            tracing::trace!(pool_unroutable = ?target, remote_addr = %addr);
+            return;
+        }
+
+        for route in routes {
+            route.push(Datagram {
+                remote_addr: addr.get(),
+                ecn,
+                payload: segment.to_vec(),
+            });
+        }
+    }
+
+    /// Wakes any streams blocked on sending once the socket becomes writable
+    #[inline]
+    fn poll_writable(&self, cx: &mut Context) {
+        {
+            let mut writable = self.writable.lock();
+
+            if !writable
+                .dispatcher
+                .as_ref()
+                .is_some_and(|waker| waker.will_wake(cx.waker()))
+            {
+                writable.dispatcher = Some(cx.waker().clone());
+            }
+
+            if writable.senders.is_empty() {
+                return;
+            }
+        }
+
+        let Poll::Ready(res) = self.socket.poll_write_ready(cx) else {
+            return;
+        };
+
+        // the senders observed a full send buffer so clear the readiness before waking them.
+        // Any sender that fails after this point will register again and wait for the next
+        // readiness event.
+        if let Ok(mut guard) = res {
+            guard.clear_ready();
+        }
+
+        let senders = core::mem::take(&mut self.writable.lock().senders);
+        for waker in senders {
+            waker.wake();
+        }
+    }
+
+    #[inline]
+    fn register_sender(&self, waker: &Waker) {
+        let dispatcher = {
+            let mut writable = self.writable.lock();
+            if !writable.senders.iter().any(|w| w.will_wake(waker)) {
+                writable.senders.push(waker.clone());
+            }
+            writable.dispatcher.clone()
+        };
+
+        if let Some(dispatcher) = dispatcher {
+            dispatcher.wake();
+        }
+    }
+}
+
+impl Drop for Shared {
+    #[inline]
+    fn drop(&mut self) {
+        // wake the dispatcher so it notices that it should exit
+        if let Some(dispatcher) = self.writable.get_mut().dispatcher.take() {
+            dispatcher.wake();
+        }
+    }
+}
+
+#[derive(Debug)]
+enum Target {
+    Stream(Credentials),
+    Path(crate::credentials::Id),
+}
+
+struct Datagram {
+    remote_addr: s2n_quic_core::inet::SocketAddress,
+    ecn: ExplicitCongestionNotification,
+    payload: Vec<u8>,
+}
+
+#[derive(Default)]
+struct Queue {
+    datagrams: VecDeque<Datagram>,
+    /// Both the worker and the application may be reading from the same route
+    wakers: Vec<Waker>,
+}
+
+impl Queue {
+    #[inline]
+    fn register(&mut self, waker: &Waker) {
+        if !self.wakers.iter().any(|w| w.will_wake(waker)) {
+            self.wakers.push(waker.clone());
+        }
+    }
+}
+
+struct Route {
+    credentials: Credentials,
+    shared: Arc<Shared>,
+    queue: Mutex<Queue>,
+}
+
+impl Route {
+    #[inline]
+    fn push(&self, datagram: Datagram) {
+        let wakers = {
+            let mut queue = self.queue.lock();
+
+            if queue.datagrams.len() >= MAX_QUEUED_DATAGRAMS {
+                tracing::debug!(pool_queue_full = ?self.credentials);
+                return;
+            }
+
+            queue.datagrams.push_back(datagram);
+            core::mem::take(&mut queue.wakers)
+        };
+
+        for waker in wakers {
+            waker.wake();
+        }
+    }
+}
+
+impl Drop for Route {
+    #[inline]
+    fn drop(&mut self) {
+        let mut routes = self.shared.routes.lock();
+        if let Entry::Occupied(entry) = routes.entry(self.credentials) {
+            // only remove the route if it hasn't been replaced by another stream
+            if core::ptr::eq(entry.get().as_ptr(), self) {
+                entry.remove();
+            }
+        }
+    }
+}
+
+/// A stream's view of a pooled socket
+///
+/// Receiving only returns datagrams routed to the stream's credentials while sending goes
+/// directly to the shared socket.
+#[derive(Clone)]
+pub struct Socket {
+    route: Arc<Route>,
+}
+
+impl Socket {
+    #[inline]
+    fn socket(&self) -> &AsyncFd<Arc<UdpSocket>> {
+        &self.route.shared.socket
+    }
+}
+
+impl super::Socket for Socket {
+    #[inline]
+    fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.socket().local_addr()
+    }
+
+    #[inline]
+    fn protocol(&self) -> Protocol {
+        Protocol::Udp
+    }
+
+    #[inline]
+    fn features(&self) -> TransportFeatures {
+        TransportFeatures::UDP
+    }
+
+    #[inline]
+    fn poll_peek_len(&self, cx: &mut Context) -> Poll<io::Result<usize>> {
+        let mut queue = self.route.queue.lock();
+
+        if let Some(datagram) = queue.datagrams.front() {
+            return Ok(datagram.payload.len()).into();
+        }
+
+        queue.register(cx.waker());
+        Poll::Pending
+    }
+
+    #[inline]
+    fn poll_recv(
+        &self,
+        cx: &mut Context,
+        addr: &mut Addr,
+        cmsg: &mut cmsg::Receiver,
+        buffer: &mut [IoSliceMut],
+    ) -> Poll<io::Result<usize>> {
+        let datagram = {
+            let mut queue = self.route.queue.lock();
+
+            let Some(datagram) = queue.datagrams.pop_front() else {
+                queue.register(cx.waker());
+                return Poll::Pending;
+            };
+
+            datagram
+        };
+
+        addr.set(datagram.remote_addr);
+        cmsg.set_ecn(datagram.ecn);
+        // each datagram holds a single segment
+        cmsg.set_segment_len(0);
+
+        // copy as much of the payload as fits, truncating the rest like a UDP socket would
+        let mut payload = &datagram.payload[..];
+        let mut len = 0;
+        for segment in buffer.iter_mut() {
+            let n = segment.len().min(payload.len());
+            segment[..n].copy_from_slice(&payload[..n]);
+            payload = &payload[n..];
+            len += n;
+        }
+
+        Ok(len).into()
+    }
+
+    #[inline]
+    fn try_send(
+        &self,
+        addr: &Addr,
+        ecn: ExplicitCongestionNotification,
+        buffer: &[IoSlice],
+    ) -> io::Result<usize> {
+        self.socket().try_send(addr, ecn, buffer)
+    }
+
+    #[inline]
+    fn poll_send(
+        &self,
+        cx: &mut Context,
+        addr: &Addr,
+        ecn: ExplicitCongestionNotification,
+        buffer: &[IoSlice],
+    ) -> Poll<io::Result<usize>> {
+        match self.try_send(addr, ecn, buffer) {
+            Err(err) if err.kind() == io::ErrorKind::WouldBlock => {}
+            res => return res.into(),
+        }
+
+        // the socket is shared between many streams so the dispatcher waits for the socket
+        // to become writable on our behalf
+        self.route.shared.register_sender(cx.waker());
+
+        // try again in case the socket became writable before we registered
+        match self.try_send(addr, ecn, buffer) {
+            Err(err) if err.kind() == io::ErrorKind::WouldBlock => Poll::Pending,
+            res => res.into(),
+        }
+    }
+
+    #[inline]
+    fn send_finish(&self) -> io::Result<()> {
+        // UDP sockets don't need a shut down
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        credentials::testing,
+        crypto::awslc::{seal, AES_128_GCM},
+        packet::stream,
+        stream::socket::Ext as _,
+    };
+    use s2n_codec::EncoderBuffer;
+    use s2n_quic_core::{buffer::reader::Complete, varint::VarInt};
+    use std::time::Duration;
+
+    fn stream_packet(credentials: &Credentials, mut payload: &[u8]) -> Vec<u8> {
+        let crypto = seal::Application::new(&[1; 16], [2; 12], &AES_128_GCM);
+        let stream_id = stream::Id {
+            key_id: credentials.key_id,
+            is_reliable: true,
+            is_bidirectional: true,
+        };
+
+        let mut payload = Complete::new(&mut payload).unwrap();
+        let mut buffer = vec![0u8; 1500];
+        let len = stream::encoder::encode(
+            EncoderBuffer::new(&mut buffer),
+            1234,
+            None,
+            stream_id,
+            VarInt::ZERO,
+            VarInt::ZERO,
+            VarInt::ZERO,
+            &mut &[][..],
+            VarInt::ZERO,
+            &(),
+            &mut payload,
+            &crypto,
+            credentials,
+        );
+        buffer.truncate(len);
+        buffer
+    }
+
+    #[tokio::test]
+    async fn routing_test() {
+        let rt = Handle::current();
+        let options = super::super::Options::new("127.0.0.1:0".parse().unwrap());
+        let pool = Pool::new(options, 1, &rt, &rt).unwrap();
+        let pool_addr = pool.local_addr().unwrap();
+
+        let a = testing::new(1, 1);
+        let b = testing::new(1, 2);
+
+        let (a_reader, _a_writer) = pool.register(&a).unwrap();
+        let (b_reader, _b_writer) = pool.register(&b).unwrap();
+
+        // the same credentials can't be registered twice
+        assert_eq!(
+            pool.register(&a).err().map(|err| err.kind()),
+            Some(io::ErrorKind::AlreadyExists)
+        );
+
+        let peer = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let peer_addr = peer.local_addr().unwrap();
+
+        let packet = stream_packet(&b, b"hello");
+        peer.send_to(&packet, pool_addr).unwrap();
+
+        let mut msg = crate::msg::recv::Message::new(9000.try_into().unwrap());
+        let len = tokio::time::timeout(Duration::from_secs(1), b_reader.recv_buffer(&mut msg))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(len, packet.len());
+        assert_eq!(msg.peek(), &packet[..]);
+        assert_eq!(msg.remote_address(), peer_addr.into());
+
+        // the packet should only be routed to the matching stream
+        let mut msg = crate::msg::recv::Message::new(9000.try_into().unwrap());
+        let res =
+            tokio::time::timeout(Duration::from_millis(50), a_reader.recv_buffer(&mut msg)).await;
+        assert!(res.is_err());
+
+        // sending goes directly out of the shared socket
+        let addr = Addr::new(peer_addr.into());
+        let len = core::future::poll_fn(|cx| {
+            a_reader.poll_send(cx, &addr, Default::default(), &[IoSlice::new(b"ping")])
+        })
+        .await
+        .unwrap();
+        assert_eq!(len, 4);
+
+        let mut buffer = [0u8; 16];
+        let (len, from) = peer.recv_from(&mut buffer).unwrap();
+        assert_eq!(&buffer[..len], b"ping");
+        assert_eq!(from, pool_addr);
+    }
+
+    fn stale_key_packet(credentials: &Credentials) -> Vec<u8> {
+        let crypto = seal::control::Secret::new(&[1; 16], &aws_lc_rs::hmac::HMAC_SHA256);
+        let mut buffer = vec![0u8; packet::secret_control::MAX_PACKET_SIZE];
+        let len = packet::secret_control::StaleKey {
+            wire_version: packet::WireVersion::ZERO,
+            credential_id: credentials.id,
+            min_key_id: VarInt::from_u8(1),
+        }
+        .encode(EncoderBuffer::new(&mut buffer), &crypto);
+        buffer.truncate(len);
+        buffer
+    }
+
+    async fn recv(socket: &Socket) -> Option<Vec<u8>> {
+        let mut msg = crate::msg::recv::Message::new(9000.try_into().unwrap());
+        tokio::time::timeout(Duration::from_millis(50), socket.recv_buffer(&mut msg))
+            .await
+            .ok()?
+            .unwrap();
+        Some(msg.peek().to_vec())
+    }
+
+    #[tokio::test]
+    async fn gro_segments_test() {
+        let rt = Handle::current();
+        let options = super::super::Options::new("127.0.0.1:0".parse().unwrap());
+        let pool = Pool::new(options, 1, &rt, &rt).unwrap();
+
+        let a = testing::new(3, 1);
+        let b = testing::new(3, 2);
+        let (a_reader, _a_writer) = pool.register(&a).unwrap();
+        let (b_reader, _b_writer) = pool.register(&b).unwrap();
+
+        let a_packet = stream_packet(&a, b"hello");
+        let b_packet = stream_packet(&b, b"world");
+        assert_eq!(a_packet.len(), b_packet.len());
+
+        // simulate GRO coalescing packets for different streams into a single datagram
+        let mut payload = [&a_packet[..], &b_packet[..], &a_packet[..]].concat();
+        let mut cmsg = cmsg::Receiver::default();
+        cmsg.set_segment_len(a_packet.len() as _);
+        let addr = Addr::new("127.0.0.1:1234".parse::<SocketAddr>().unwrap().into());
+        a_reader.route.shared.dispatch(&addr, &cmsg, &mut payload);
+
+        assert_eq!(recv(&a_reader).await.unwrap(), a_packet);
+        assert_eq!(recv(&a_reader).await.unwrap(), a_packet);
+        assert!(recv(&a_reader).await.is_none());
+
+        assert_eq!(recv(&b_reader).await.unwrap(), b_packet);
+        assert!(recv(&b_reader).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn path_packets_test() {
+        let rt = Handle::current();
+        let options = super::super::Options::new("127.0.0.1:0".parse().unwrap());
+        let pool = Pool::new(options, 1, &rt, &rt).unwrap();
+        let pool_addr = pool.local_addr().unwrap();
+
+        // two streams on the same path and one on another path
+        let a = testing::new(4, 1);
+        let b = testing::new(4, 2);
+        let other = testing::new(5, 1);
+        let (_a_reader, a_writer) = pool.register(&a).unwrap();
+        let (_b_reader, b_writer) = pool.register(&b).unwrap();
+        let (_other_reader, other_writer) = pool.register(&other).unwrap();
+
+        let packet = stale_key_packet(&a);
+        let peer = UdpSocket::bind("127.0.0.1:0").unwrap();
+        peer.send_to(&packet, pool_addr).unwrap();
+
+        // every stream on the path is notified
+        assert_eq!(recv(&a_writer).await.unwrap(), packet);
+        assert_eq!(recv(&b_writer).await.unwrap(), packet);
+        assert!(recv(&other_writer).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn dispatcher_exit_test() {
+        let rt = Handle::current();
+        let options = super::super::Options::new("127.0.0.1:0".parse().unwrap());
+        let pool = Pool::new(options, 1, &rt, &rt).unwrap();
+
+        let (reader, writer) = pool.register(&testing::new(6, 1)).unwrap();
+        let shared = Arc::downgrade(&reader.route.shared);
+        drop(pool);
+
+        // the dispatcher keeps running for the registered streams
+        tokio::task::yield_now().await;
+        assert!(shared.upgrade().is_some());
+
+        drop(reader);
+        drop(writer);
+
+        // let the dispatchers observe that the pool is gone
+        for _ in 0..10 {
+            tokio::task::yield_now().await;
+        }
+        assert!(shared.upgrade().is_none());
+    }
+
+    #[tokio::test]
+    async fn unregister_test() {
+        let rt = Handle::current();
+        let options = super::super::Options::new("127.0.0.1:0".parse().unwrap());
+        let pool = Pool::new(options, 2, &rt, &rt).unwrap();
+
+        let credentials = testing::new(2, 1);
+
+        let (reader, writer) = pool.register(&credentials).unwrap();
+        let clone = reader.clone();
+        drop(reader);
+        drop(writer);
+
+        // the route is kept while any clones are still alive
+        assert_eq!(
+            clone.route.shared.routes.lock().len(),
+            1,
+            "route should still be registered"
+        );
+
+        let shared = clone.route.shared.clone();
+        drop(clone);
+        assert!(shared.routes.lock().is_empty());
+
+        // the credentials can be reused once the previous stream is gone
+        pool.register(&credentials).unwrap();
+    }
+}
@@ -58,6 +58,8 @@ OPTIONS=(
   --allowlist-type 'port_type'
   --opaque-type 'GSList'
   --allowlist-type 'GSList'
+  --opaque-type 'pref_module'
+  --allowlist-type 'module_t'
   --opaque-type 'GHashTable'
   --allowlist-type 'GHashTable'
   --opaque-type 'wtap_pseudo_header'
@@ -108,6 +110,9 @@ bindgen \
   --allowlist-function 'col_clear' \
   --allowlist-function 'find_or_create_conversation' \
   --allowlist-function 'conversation_set_dissector' \
+  --allowlist-function 'prefs_register_protocol' \
+  --allowlist-function 'prefs_register_filename_preference' \
+  --allowlist-function 'report_failure' \
   wrapper.h \
   -o src/wireshark_sys/full.rs \
   -- ${INCLUDES[@]}
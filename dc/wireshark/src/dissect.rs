@@ -4,6 +4,7 @@
 use crate::{
     buffer::Buffer,
     field::Registration,
+    keys::{Keys, Opened},
     value::Parsed,
     wireshark::{Info, Item, Node},
 };
@@ -18,10 +19,12 @@ pub enum Protocol {
     Udp,
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn segment<T: Node>(
     tree: &mut T,
     root: &mut impl Item,
     fields: &Registration,
+    keys: Option<&Keys>,
     ptag: Parsed<packet::Tag>,
     buffer: &mut Buffer,
     info: &mut impl Info,
@@ -31,7 +34,7 @@ pub fn segment<T: Node>(
         packet::Tag::Stream(tag) => {
             root.append_text(c" Stream");
             let tag = ptag.map(|_| tag);
-            stream(tree, fields, tag, buffer, info)
+            stream(tree, fields, keys, tag, buffer, info)
         }
         packet::Tag::Control(tag) => {
             match protocol {
@@ -39,7 +42,7 @@ pub fn segment<T: Node>(
                 Protocol::Udp => root.append_text(c" Control"),
             }
             let tag = ptag.map(|_| tag);
-            control(tree, fields, tag, buffer, info)
+            control(tree, fields, keys, tag, buffer, info)
         }
         packet::Tag::Datagram(tag) => {
             match protocol {
@@ -47,11 +50,11 @@ pub fn segment<T: Node>(
                 Protocol::Udp => root.append_text(c" Datagram"),
             }
             let tag = ptag.map(|_| tag);
-            datagram(tree, fields, tag, buffer, info)
+            datagram(tree, fields, keys, tag, buffer, info)
         }
         _ => {
             root.append_text(c" Secret Control");
-            secret_control(tree, fields, ptag, buffer, info)
+            secret_control(tree, fields, keys, ptag, buffer, info)
         }
     }
 }
@@ -59,10 +62,12 @@ pub fn segment<T: Node>(
 pub fn stream<T: Node>(
     tree: &mut T,
     fields: &Registration,
+    keys: Option<&Keys>,
     tag: Parsed<stream::Tag>,
     buffer: &mut Buffer,
     info: &mut impl Info,
 ) -> Option<()> {
+    let start = tag.offset;
     let tag_item = tag.record(buffer, tree, fields.tag);
 
     let mut tag_tree = tree.add_subtree(tag_item, fields.tag_subtree);
@@ -151,6 +156,11 @@ pub fn stream<T: Node>(
     let auth_tag = buffer.consume_bytes(16)?;
     auth_tag.record(buffer, tree, fields.auth_tag);
 
+    if let Some(keys) = keys {
+        let opened = keys.open_stream(&buffer.packet[start..buffer.offset]);
+        record_opened(tree, fields, buffer, Some(payload), auth_tag, opened);
+    }
+
     info.append_delim(" ");
     info.append(format_args!(
         "Stream(ID={}, PN={},{control_info} LEN={})",
@@ -163,10 +173,12 @@ pub fn stream<T: Node>(
 pub fn control<T: Node>(
     tree: &mut T,
     fields: &Registration,
+    keys: Option<&Keys>,
     tag: Parsed<packet::control::Tag>,
     buffer: &mut Buffer,
     info: &mut impl Info,
 ) -> Option<()> {
+    let start = tag.offset;
     let tag_item = tag.record(buffer, tree, fields.tag);
 
     let mut tag_tree = tree.add_subtree(tag_item, fields.tag_subtree);
@@ -219,6 +231,11 @@ pub fn control<T: Node>(
     let auth_tag = buffer.consume_bytes(16)?;
     auth_tag.record(buffer, tree, fields.auth_tag);
 
+    if let Some(keys) = keys {
+        let opened = keys.open_control(&buffer.packet[start..buffer.offset]);
+        record_opened(tree, fields, buffer, None, auth_tag, opened);
+    }
+
     info.append_delim(" ");
     info.append(format_args!(
         "Control(ID={}, PN={},{control_info})",
@@ -401,10 +418,12 @@ fn record_stream_id<T: Node>(
 pub fn datagram<T: Node>(
     tree: &mut T,
     fields: &Registration,
+    keys: Option<&Keys>,
     tag: Parsed<packet::datagram::Tag>,
     buffer: &mut Buffer,
     info: &mut impl Info,
 ) -> Option<()> {
+    let start = tag.offset;
     let tag_item = tag.record(buffer, tree, fields.tag);
 
     let mut tag_tree = tree.add_subtree(tag_item, fields.tag_subtree);
@@ -476,6 +495,11 @@ pub fn datagram<T: Node>(
     let auth_tag = buffer.consume_bytes(16)?;
     auth_tag.record(buffer, tree, fields.auth_tag);
 
+    if let Some(keys) = keys {
+        let opened = keys.open_datagram(&buffer.packet[start..buffer.offset]);
+        record_opened(tree, fields, buffer, Some(payload), auth_tag, opened);
+    }
+
     info.append_delim(" ");
     if let Some(pn) = packet_number {
         info.append(format_args!(
@@ -495,10 +519,12 @@ pub fn datagram<T: Node>(
 pub fn secret_control<T: Node>(
     tree: &mut T,
     fields: &Registration,
+    keys: Option<&Keys>,
     tag: Parsed<packet::Tag>,
     buffer: &mut Buffer,
     info: &mut impl Info,
 ) -> Option<()> {
+    let start = tag.offset;
     let mut item = tag.record(buffer, tree, fields.tag);
 
     match tag.value {
@@ -534,6 +560,12 @@ pub fn secret_control<T: Node>(
             let auth_tag = buffer.consume_bytes(16)?;
             auth_tag.record(buffer, tree, fields.auth_tag);
 
+            if let Some(opened) =
+                keys.and_then(|keys| keys.open_secret_control(&buffer.packet[start..buffer.offset]))
+            {
+                record_opened(tree, fields, buffer, None, auth_tag, opened);
+            }
+
             info.append_delim(" ");
             info.append_str("StaleKey");
 
@@ -554,11 +586,74 @@ pub fn secret_control<T: Node>(
             let auth_tag = buffer.consume_bytes(16)?;
             auth_tag.record(buffer, tree, fields.auth_tag);
 
+            if let Some(opened) =
+                keys.and_then(|keys| keys.open_secret_control(&buffer.packet[start..buffer.offset]))
+            {
+                record_opened(tree, fields, buffer, None, auth_tag, opened);
+            }
+
             info.append_delim(" ");
             info.append_str("ReplayDetected");
 
             Some(())
         }
+        packet::Tag::ServerBusy(_) => {
+            item.append_text(c" (ServerBusy)");
+
+            let path_secret_id = buffer.consume_bytes(16)?;
+            path_secret_id.record(buffer, tree, fields.path_secret_id);
+
+            let wire_version = buffer.consume::<WireVersion>()?;
+            wire_version.record(buffer, tree, fields.wire_version);
+
+            let rejected_key_id = buffer.consume::<VarInt>()?;
+            rejected_key_id.record(buffer, tree, fields.rejected_key_id);
+
+            let retry_after_ms = buffer.consume::<VarInt>()?;
+            retry_after_ms.record(buffer, tree, fields.retry_after_ms);
+
+            let auth_tag = buffer.consume_bytes(16)?;
+            auth_tag.record(buffer, tree, fields.auth_tag);
+
+            if let Some(opened) =
+                keys.and_then(|keys| keys.open_secret_control(&buffer.packet[start..buffer.offset]))
+            {
+                record_opened(tree, fields, buffer, None, auth_tag, opened);
+            }
+
+            info.append_delim(" ");
+            info.append_str("ServerBusy");
+
+            Some(())
+        }
         _ => None,
     }
 }
+
+/// Records the result of decrypting and authenticating a packet with the key log
+fn record_opened<T: Node>(
+    tree: &mut T,
+    fields: &Registration,
+    buffer: &Buffer,
+    payload: Option<Parsed<&[u8]>>,
+    auth_tag: Parsed<&[u8]>,
+    opened: Opened,
+) {
+    let Some(is_valid) = opened.is_valid() else {
+        // the key log doesn't have the path secret for this packet
+        return;
+    };
+
+    tree.add_boolean(buffer, fields.auth_tag_valid, auth_tag.with(is_valid));
+
+    if let (Some(payload), Opened::Valid(decrypted)) = (payload, opened) {
+        if decrypted.is_empty() {
+            return;
+        }
+
+        // the decrypted payload has the same length as the encrypted one so point at its location
+        payload
+            .with(&decrypted[..])
+            .record(buffer, tree, fields.decrypted_payload);
+    }
+}
@@ -46,6 +46,8 @@ pub struct Registration {
     pub control_data: i32,
     pub payload: i32,
     pub auth_tag: i32,
+    pub decrypted_payload: i32,
+    pub auth_tag_valid: i32,
 
     pub is_bidirectional: i32,
     pub is_reliable: i32,
@@ -68,6 +70,7 @@ pub struct Registration {
 
     pub min_key_id: i32,
     pub rejected_key_id: i32,
+    pub retry_after_ms: i32,
 }
 
 #[cfg_attr(test, allow(unused))]
@@ -394,6 +397,24 @@ fn init() -> Registration {
                 c"",
             )
             .register(),
+        decrypted_payload: protocol
+            .field(
+                c"Decrypted Payload",
+                c"dcquic.decrypted_payload",
+                BYTES,
+                BASE_NONE,
+                c"Payload decrypted with the path secret from the key log",
+            )
+            .register(),
+        auth_tag_valid: protocol
+            .field(
+                c"Is authentication tag valid?",
+                c"dcquic.auth_tag.valid",
+                BOOLEAN,
+                BASE_NONE,
+                c"Was the packet authenticated with the path secret from the key log?",
+            )
+            .register(),
         is_bidirectional: protocol
             .field(
                 c"Is bidirectional?",
@@ -544,7 +565,16 @@ fn init() -> Registration {
                 c"dcquic.secret.rejected_key_id",
                 UINT64,
                 BASE_DEC,
-                c"KeyId rejected due to definitively observing replay",
+                c"KeyId rejected due to definitively observing replay or the server being busy",
+            )
+            .register(),
+        retry_after_ms: protocol
+            .field(
+                c"Retry After (ms)",
+                c"dcquic.secret.retry_after_ms",
+                UINT64,
+                BASE_DEC,
+                c"Time the client should wait before opening another stream",
             )
             .register(),
     }
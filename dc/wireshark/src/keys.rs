@@ -0,0 +1,305 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Path secrets loaded from a dcQUIC key log, which are used to decrypt and authenticate packets.
+
+#![cfg_attr(test, allow(dead_code))]
+
+use s2n_codec::DecoderBufferMut;
+use s2n_quic_core::{endpoint, packet::KeyPhase};
+use s2n_quic_dc::{
+    credentials,
+    crypto::{self, awslc, open::Control as _, UninitSlice},
+    packet,
+    path::secret::{
+        key_log,
+        schedule::{Initiator, Secret},
+    },
+};
+use std::{
+    collections::HashMap,
+    fs, io,
+    path::Path,
+    sync::{Arc, RwLock},
+};
+
+/// The maximum number of key updates tried when opening stream packets
+///
+/// The key phase bit only tells us the parity of the key generation so later generations need to
+/// be tried one after the other.
+const MAX_KEY_UPDATES: usize = 16;
+
+/// All dcQUIC packets currently use 16 byte authentication tags
+const TAG_LEN: usize = 16;
+
+static KEYS: RwLock<Option<Arc<Keys>>> = RwLock::new(None);
+
+/// Returns the currently loaded keys, if any
+pub fn get() -> Option<Arc<Keys>> {
+    KEYS.read().ok()?.clone()
+}
+
+/// Replaces the currently loaded keys
+pub fn set(keys: Option<Keys>) {
+    if let Ok(mut current) = KEYS.write() {
+        *current = keys.map(Arc::new);
+    }
+}
+
+/// The result of trying to open a packet with the loaded keys
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Opened {
+    /// The key log doesn't contain the path secret for the packet
+    MissingSecret,
+    /// None of the keys derived from the path secret authenticated the packet
+    Invalid,
+    /// The packet was authenticated, along with its decrypted payload
+    Valid(Vec<u8>),
+}
+
+impl Opened {
+    pub fn is_valid(&self) -> Option<bool> {
+        match self {
+            Self::MissingSecret => None,
+            Self::Invalid => Some(false),
+            Self::Valid(_) => Some(true),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct Keys {
+    // each path secret is derived for both endpoints since the keys depend on which side sent the
+    // packet
+    secrets: HashMap<credentials::Id, [Secret; 2]>,
+}
+
+impl Keys {
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let file = fs::File::open(path)?;
+        let entries = key_log::read(io::BufReader::new(file))?;
+        Ok(entries.into_iter().collect())
+    }
+
+    /// Decrypts a stream packet, starting at its tag
+    pub fn open_stream(&self, packet: &[u8]) -> Opened {
+        let Some(credentials) = decode_stream(&mut packet.to_vec()) else {
+            return Opened::Invalid;
+        };
+        let Some(secrets) = self.secrets.get(&credentials.id) else {
+            return Opened::MissingSecret;
+        };
+
+        for secret in secrets {
+            for initiator in [Initiator::Local, Initiator::Remote] {
+                let (_sealer, _sealer_ku, mut key, mut key_update) =
+                    secret.application_pair(credentials.key_id, initiator);
+                let (_sealer, control) = secret.control_pair(credentials.key_id, initiator);
+
+                for generation in 0..MAX_KEY_UPDATES {
+                    if generation > 0 {
+                        (key, key_update) = key_update.next();
+                    }
+
+                    let mut buffer = packet.to_vec();
+                    let Some(Ok(payload)) = decrypt_stream(&mut buffer, generation, &key, &control)
+                    else {
+                        continue;
+                    };
+                    return Opened::Valid(payload);
+                }
+            }
+        }
+
+        Opened::Invalid
+    }
+
+    /// Decrypts a datagram packet, starting at its tag
+    pub fn open_datagram(&self, packet: &[u8]) -> Opened {
+        let mut buffer = packet.to_vec();
+        let Ok((packet, _)) = packet::datagram::decoder::Packet::decode(
+            DecoderBufferMut::new(&mut buffer),
+            (),
+            TAG_LEN,
+        ) else {
+            return Opened::Invalid;
+        };
+        let credentials = packet.credentials();
+        let Some(secrets) = self.secrets.get(&credentials.id) else {
+            return Opened::MissingSecret;
+        };
+
+        for secret in secrets {
+            let key = secret.application_opener(credentials.key_id);
+            let mut payload = vec![0; packet.payload().len()];
+            let res = crypto::open::Application::decrypt(
+                &key,
+                packet.tag().key_phase(),
+                packet.crypto_nonce(),
+                packet.header(),
+                packet.payload(),
+                packet.auth_tag(),
+                UninitSlice::new(&mut payload),
+            );
+            if res.is_ok() {
+                return Opened::Valid(payload);
+            }
+        }
+
+        Opened::Invalid
+    }
+
+    /// Authenticates a control packet, starting at its tag
+    pub fn open_control(&self, packet: &[u8]) -> Opened {
+        let mut buffer = packet.to_vec();
+        let Ok((packet, _)) = packet::control::decoder::Packet::decode(
+            DecoderBufferMut::new(&mut buffer),
+            (),
+            TAG_LEN,
+        ) else {
+            return Opened::Invalid;
+        };
+        let credentials = packet.credentials();
+        let Some(secrets) = self.secrets.get(&credentials.id) else {
+            return Opened::MissingSecret;
+        };
+
+        for secret in secrets {
+            for initiator in [Initiator::Local, Initiator::Remote] {
+                let (_sealer, key) = secret.control_pair(credentials.key_id, initiator);
+                if key.verify(packet.header(), packet.auth_tag()).is_ok() {
+                    // control packets don't have an encrypted payload
+                    return Opened::Valid(vec![]);
+                }
+            }
+        }
+
+        Opened::Invalid
+    }
+
+    /// Authenticates a secret control packet, starting at its tag
+    ///
+    /// Returns `None` for packets which can't be authenticated with the path secret.
+    pub fn open_secret_control(&self, packet: &[u8]) -> Option<Opened> {
+        let mut buffer = packet.to_vec();
+        let Ok((packet, _)) =
+            packet::secret_control::Packet::decode(DecoderBufferMut::new(&mut buffer))
+        else {
+            return Some(Opened::Invalid);
+        };
+        let Some(secrets) = self.secrets.get(packet.credential_id()) else {
+            return Some(Opened::MissingSecret);
+        };
+
+        for secret in secrets {
+            let key = secret.control_opener();
+            let is_valid = match &packet {
+                // these are authenticated with the stateless reset token, which isn't logged
+                packet::secret_control::Packet::UnknownPathSecret(_) => return None,
+                packet::secret_control::Packet::StaleKey(packet) => {
+                    packet.authenticate(&key).is_some()
+                }
+                packet::secret_control::Packet::ReplayDetected(packet) => {
+                    packet.authenticate(&key).is_some()
+                }
+                packet::secret_control::Packet::ServerBusy(packet) => {
+                    packet.authenticate(&key).is_some()
+                }
+            };
+            if is_valid {
+                return Some(Opened::Valid(vec![]));
+            }
+        }
+
+        Some(Opened::Invalid)
+    }
+}
+
+impl FromIterator<key_log::Entry> for Keys {
+    fn from_iter<T: IntoIterator<Item = key_log::Entry>>(iter: T) -> Self {
+        let version = s2n_quic_dc::SUPPORTED_VERSIONS[0];
+        let secrets = iter
+            .into_iter()
+            .map(|entry| {
+                let secrets = [
+                    entry.secret(version, endpoint::Type::Client),
+                    entry.secret(version, endpoint::Type::Server),
+                ];
+                (entry.id, secrets)
+            })
+            .collect();
+        Self { secrets }
+    }
+}
+
+fn decode_stream(buffer: &mut [u8]) -> Option<credentials::Credentials> {
+    let (packet, _) =
+        packet::stream::decoder::Packet::decode(DecoderBufferMut::new(buffer), (), TAG_LEN).ok()?;
+    Some(*packet.credentials())
+}
+
+fn decrypt_stream(
+    buffer: &mut [u8],
+    generation: usize,
+    key: &awslc::open::Application,
+    control: &awslc::open::control::Stream,
+) -> Option<Result<Vec<u8>, crypto::open::Error>> {
+    let (mut packet, _) =
+        packet::stream::decoder::Packet::decode(DecoderBufferMut::new(buffer), (), TAG_LEN).ok()?;
+
+    let key_phase = if generation % 2 == 0 {
+        KeyPhase::Zero
+    } else {
+        KeyPhase::One
+    };
+
+    // skip the generations which don't match the key phase bit
+    if packet.tag().key_phase() != key_phase {
+        return None;
+    }
+
+    let key = Generation(key);
+    let mut payload = vec![0; packet.payload().len()];
+    let res = packet.decrypt(&key, control, UninitSlice::new(&mut payload));
+    Some(res.map(|()| payload))
+}
+
+/// Opens packets for a specific key generation, regardless of the key phase
+struct Generation<'a>(&'a awslc::open::Application);
+
+impl crypto::open::Application for Generation<'_> {
+    fn tag_len(&self) -> usize {
+        self.0.tag_len()
+    }
+
+    fn decrypt(
+        &self,
+        _key_phase: KeyPhase,
+        packet_number: u64,
+        header: &[u8],
+        payload_in: &[u8],
+        tag: &[u8],
+        payload_out: &mut UninitSlice,
+    ) -> crypto::open::Result {
+        // the underlying key doesn't perform rotation
+        self.0.decrypt(
+            KeyPhase::Zero,
+            packet_number,
+            header,
+            payload_in,
+            tag,
+            payload_out,
+        )
+    }
+
+    fn decrypt_in_place(
+        &self,
+        _key_phase: KeyPhase,
+        packet_number: u64,
+        header: &[u8],
+        payload_and_tag: &mut [u8],
+    ) -> crypto::open::Result {
+        self.0
+            .decrypt_in_place(KeyPhase::Zero, packet_number, header, payload_and_tag)
+    }
+}
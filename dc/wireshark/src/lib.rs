@@ -4,6 +4,7 @@
 mod buffer;
 mod dissect;
 mod field;
+mod keys;
 #[cfg(not(test))]
 mod plugin;
 mod value;
@@ -1,8 +1,15 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{buffer::Buffer, dissect, field, field::Registration, wireshark::Item, wireshark_sys};
-use std::{ffi::CStr, sync::OnceLock};
+use crate::{
+    buffer::Buffer, dissect, field, field::Registration, keys, wireshark::Item, wireshark_sys,
+};
+use std::{
+    ffi::{c_char, CStr, CString},
+    path::Path,
+    ptr,
+    sync::OnceLock,
+};
 
 #[no_mangle]
 #[used]
@@ -35,7 +42,7 @@ static plugin_want_minor: std::ffi::c_int = env_version!("PLUGIN_MINOR_VERSION")
 #[no_mangle]
 pub extern "C" fn plugin_register() {
     static PLUGIN: wireshark_sys::proto_plugin = wireshark_sys::proto_plugin {
-        register_protoinfo: Some(field::proto_register),
+        register_protoinfo: Some(proto_register),
         register_handoff: Some(proto_reg_handoff),
     };
 
@@ -44,6 +51,43 @@ pub extern "C" fn plugin_register() {
     }
 }
 
+// Wireshark owns the string and updates the pointer when the preference changes.
+static mut KEY_LOG_PATH: *const c_char = ptr::null();
+
+unsafe extern "C" fn proto_register() {
+    field::proto_register();
+
+    let module = wireshark_sys::prefs_register_protocol(field::get().protocol, Some(prefs_apply));
+    wireshark_sys::prefs_register_filename_preference(
+        module,
+        c"key_log".as_ptr(),
+        c"Key log filename".as_ptr(),
+        c"Path secret key log used to decrypt payloads and verify authentication tags".as_ptr(),
+        ptr::addr_of_mut!(KEY_LOG_PATH),
+        0,
+    );
+}
+
+unsafe extern "C" fn prefs_apply() {
+    let path = KEY_LOG_PATH;
+    if path.is_null() || *path == 0 {
+        keys::set(None);
+        return;
+    }
+
+    let path = CStr::from_ptr(path).to_string_lossy();
+    match keys::Keys::load(Path::new(&*path)) {
+        Ok(keys) => keys::set(Some(keys)),
+        Err(err) => {
+            // the message is passed as an argument so it isn't interpreted as a format string
+            let message = format!("dcQUIC: failed to load key log {path:?}: {err}");
+            let message = CString::new(message).unwrap_or_default();
+            wireshark_sys::report_failure(c"%s".as_ptr(), message.as_ptr());
+            keys::set(None);
+        }
+    }
+}
+
 static STREAM_DISSECTOR: OnceLock<DissectorHandle> = OnceLock::new();
 
 struct DissectorHandle(wireshark_sys::dissector_handle_t);
@@ -101,6 +145,7 @@ unsafe extern "C" fn dissect_heur_udp<Ret: From<bool>>(
     _: *mut std::ffi::c_void,
 ) -> Ret {
     let fields = field::get();
+    let keys = keys::get();
 
     let packet = copy_to_rust(tvb);
     let mut buffer = Buffer::new(tvb, &packet);
@@ -117,6 +162,7 @@ unsafe extern "C" fn dissect_heur_udp<Ret: From<bool>>(
             &mut tree,
             &mut root,
             fields,
+            keys.as_deref(),
             tag,
             &mut buffer,
             &mut info,
@@ -152,6 +198,7 @@ unsafe extern "C" fn dissect_heur_tcp<Ret: From<bool>>(
     _: *mut std::ffi::c_void,
 ) -> Ret {
     let fields = field::get();
+    let keys = keys::get();
 
     let packet = copy_to_rust(tvb);
     let mut buffer = Buffer::new(tvb, &packet);
@@ -170,6 +217,7 @@ unsafe extern "C" fn dissect_heur_tcp<Ret: From<bool>>(
             &mut tree,
             &mut root,
             fields,
+            keys.as_deref(),
             tag,
             &mut buffer,
             &mut info,
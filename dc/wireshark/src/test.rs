@@ -1,17 +1,23 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{buffer::Buffer, dissect, value::Parsed};
+use crate::{buffer::Buffer, dissect, keys::Keys, value::Parsed};
 use s2n_codec::EncoderBuffer;
 use s2n_quic_core::{
     buffer::{reader::Storage, Reader},
+    endpoint,
     packet::KeyPhase,
     stream::testing::Data,
     varint::VarInt,
 };
 use s2n_quic_dc::{
     credentials,
+    crypto::awslc,
     packet::{self, stream, WireVersion},
+    path::secret::{
+        key_log,
+        schedule::{Ciphersuite, Initiator, Secret},
+    },
 };
 use std::{collections::HashMap, num::NonZeroU16, ptr, time::Duration};
 
@@ -70,7 +76,9 @@ fn check_stream_parse() {
 
             let mut buffer = unsafe { Buffer::new(ptr::null_mut(), &buffer[..length]) };
             let tag: Parsed<packet::stream::Tag> = buffer.consume().unwrap();
-            assert!(dissect::stream(&mut tracker, fields, tag, &mut buffer, &mut ()).is_some());
+            assert!(
+                dissect::stream(&mut tracker, fields, None, tag, &mut buffer, &mut ()).is_some()
+            );
             let tag: Parsed<u8> = tag.map(|v| v.into());
 
             assert_eq!(tracker.remove(fields.tag), Field::Integer(tag.value as u64));
@@ -241,7 +249,9 @@ fn check_datagram_parse() {
 
             let mut buffer = unsafe { Buffer::new(ptr::null_mut(), &buffer[..length]) };
             let tag: Parsed<packet::datagram::Tag> = buffer.consume().unwrap();
-            assert!(dissect::datagram(&mut tracker, fields, tag, &mut buffer, &mut ()).is_some());
+            assert!(
+                dissect::datagram(&mut tracker, fields, None, tag, &mut buffer, &mut ()).is_some()
+            );
             let tag: Parsed<u8> = tag.map(|v| v.into());
 
             assert_eq!(tracker.remove(fields.tag), Field::Integer(tag.value as u64));
@@ -368,7 +378,9 @@ fn check_control_parse() {
 
             let mut buffer = unsafe { Buffer::new(ptr::null_mut(), &buffer[..length]) };
             let tag: Parsed<packet::control::Tag> = buffer.consume().unwrap();
-            assert!(dissect::control(&mut tracker, fields, tag, &mut buffer, &mut ()).is_some());
+            assert!(
+                dissect::control(&mut tracker, fields, None, tag, &mut buffer, &mut ()).is_some()
+            );
             let tag: Parsed<u8> = tag.map(|v| v.into());
 
             assert_eq!(tracker.remove(fields.tag), Field::Integer(tag.value as u64));
@@ -430,6 +442,11 @@ enum SecretControlPacket {
         id: credentials::Id,
         key_id: VarInt,
     },
+    ServerBusy {
+        id: credentials::Id,
+        key_id: VarInt,
+        retry_after_ms: VarInt,
+    },
 }
 
 #[test]
@@ -467,6 +484,17 @@ fn check_secret_control_parse() {
                     }
                     .encode(EncoderBuffer::new(&mut buffer), &key)
                 }
+                SecretControlPacket::ServerBusy {
+                    id,
+                    key_id,
+                    retry_after_ms,
+                } => s2n_quic_dc::packet::secret_control::ServerBusy {
+                    wire_version: WireVersion::ZERO,
+                    credential_id: *id,
+                    rejected_key_id: *key_id,
+                    retry_after_ms: *retry_after_ms,
+                }
+                .encode(EncoderBuffer::new(&mut buffer), &key),
             };
 
             let fields = crate::field::get();
@@ -475,7 +503,8 @@ fn check_secret_control_parse() {
             let mut buffer = unsafe { Buffer::new(ptr::null_mut(), &buffer[..length]) };
             let tag = buffer.consume().unwrap();
             assert!(
-                dissect::secret_control(&mut tracker, fields, tag, &mut buffer, &mut ()).is_some()
+                dissect::secret_control(&mut tracker, fields, None, tag, &mut buffer, &mut ())
+                    .is_some()
             );
 
             match packet {
@@ -519,6 +548,27 @@ fn check_secret_control_parse() {
                     // FIXME: Deeper check?
                     assert!(tracker.take(fields.auth_tag).is_some());
                 }
+                SecretControlPacket::ServerBusy {
+                    id,
+                    key_id,
+                    retry_after_ms,
+                } => {
+                    assert_eq!(tracker.remove(fields.tag), Field::Integer(0b0110_0011));
+                    assert_eq!(tracker.remove(fields.wire_version), Field::Integer(0));
+                    assert_eq!(
+                        tracker.remove(fields.path_secret_id),
+                        Field::Slice(id.to_vec())
+                    );
+                    assert_eq!(
+                        tracker.remove(fields.rejected_key_id),
+                        Field::Integer(key_id.as_u64())
+                    );
+                    assert_eq!(
+                        tracker.remove(fields.retry_after_ms),
+                        Field::Integer(retry_after_ms.as_u64())
+                    );
+                    assert!(tracker.take(fields.auth_tag).is_some());
+                }
             };
 
             assert_eq!(
@@ -544,7 +594,7 @@ fn random_stream_packets() {
             return;
         };
         // May fail to parse, but shouldn't panic.
-        let _ = dissect::stream(&mut tracker, fields, tag, &mut buffer, &mut ());
+        let _ = dissect::stream(&mut tracker, fields, None, tag, &mut buffer, &mut ());
     });
 }
 
@@ -565,6 +615,7 @@ fn random_segments() {
             &mut tracker,
             &mut (),
             fields,
+            None,
             tag,
             &mut buffer,
             &mut (),
@@ -586,7 +637,7 @@ fn random_datagram_packets() {
             return;
         };
         // May fail to parse, but shouldn't panic.
-        let _ = dissect::datagram(&mut tracker, fields, tag, &mut buffer, &mut ());
+        let _ = dissect::datagram(&mut tracker, fields, None, tag, &mut buffer, &mut ());
     });
 }
 
@@ -603,10 +654,308 @@ fn random_control_packets() {
             return;
         };
         // May fail to parse, but shouldn't panic.
-        let _ = dissect::control(&mut tracker, fields, tag, &mut buffer, &mut ());
+        let _ = dissect::control(&mut tracker, fields, None, tag, &mut buffer, &mut ());
     });
 }
 
+/// Derives a client path secret along with the keys loaded from its key log entry
+fn test_keys(ciphersuite: Ciphersuite, export_secret: [u8; 32]) -> (Secret, Keys) {
+    let entry = key_log::Entry {
+        id: Default::default(),
+        ciphersuite,
+        export_secret: export_secret.into(),
+    };
+    let secret = entry.secret(s2n_quic_dc::SUPPORTED_VERSIONS[0], endpoint::Type::Client);
+
+    // round trip the secret through the key log format
+    let log = key_log::Entry {
+        id: *secret.id(),
+        ..entry
+    }
+    .to_string();
+    let keys = key_log::read(log.as_bytes()).unwrap().into_iter().collect();
+
+    (secret, keys)
+}
+
+#[derive(Clone, Debug, bolero::TypeGenerator)]
+struct EncryptedStreamPacket {
+    ciphersuite: Ciphersuite,
+    export_secret: [u8; 32],
+    packet: StreamPacket,
+    #[generator(0..4)]
+    key_updates: u8,
+    is_remote_initiator: bool,
+}
+
+#[test]
+fn check_stream_decrypt() {
+    // Initialize field IDs.
+    let _ = crate::field::get();
+
+    bolero::check!()
+        .with_type()
+        .for_each(|packet: &EncryptedStreamPacket| {
+            let (secret, keys) = test_keys(packet.ciphersuite, packet.export_secret);
+            let initiator = if packet.is_remote_initiator {
+                Initiator::Remote
+            } else {
+                Initiator::Local
+            };
+
+            let mut packet_info = packet.packet.clone();
+            packet_info.credentials.id = *secret.id();
+            let credentials = packet_info.credentials;
+
+            let (mut sealer, mut sealer_ku, _opener, _opener_ku) =
+                secret.application_pair(credentials.key_id, initiator);
+            let mut key_phase = KeyPhase::Zero;
+            for _ in 0..packet.key_updates {
+                (sealer, sealer_ku) = sealer_ku.next();
+                key_phase = key_phase.next_phase();
+            }
+            let key = PhasedKey(sealer, key_phase);
+
+            let sent_payload = packet_info.payload;
+            let mut buffer = vec![
+                0;
+                sent_payload.buffered_len()
+                    + packet_info.application_header.buffered_len()
+                    + s2n_quic_dc::packet::stream::encoder::MAX_HEADER_LEN
+                    + s2n_quic_dc::packet::stream::encoder::MAX_RETRANSMISSION_HEADER_LEN
+            ];
+            let length = s2n_quic_dc::packet::stream::encoder::encode(
+                EncoderBuffer::new(&mut buffer),
+                packet_info.source_control_port.get(),
+                packet_info.source_stream_port.map(NonZeroU16::get),
+                packet_info.stream_id,
+                packet_info.packet_number,
+                packet_info.next_expected_control_packet,
+                VarInt::new(packet_info.application_header.buffered_len() as u64).unwrap(),
+                &mut packet_info.application_header,
+                VarInt::ZERO,
+                &(),
+                &mut packet_info.payload,
+                &key,
+                &credentials,
+            );
+
+            let expected_payload =
+                Data::send_one_at(sent_payload.offset(), sent_payload.buffered_len());
+
+            let fields = crate::field::get();
+
+            // the packet is decrypted with the key log
+            let mut tracker = dissect_packet(&buffer[..length], |t, b| {
+                let tag = b.consume().unwrap();
+                dissect::stream(t, fields, Some(&keys), tag, b, &mut ())
+            });
+            assert_eq!(tracker.take(fields.auth_tag_valid), Some(Field::Integer(1)));
+            let decrypted = tracker.take(fields.decrypted_payload);
+            if expected_payload.is_empty() {
+                assert!(decrypted.is_none());
+            } else {
+                assert_eq!(decrypted, Some(Field::Slice(expected_payload.to_vec())));
+            }
+
+            // corrupting the packet fails authentication
+            let mut corrupted = buffer[..length].to_vec();
+            *corrupted.last_mut().unwrap() ^= 1;
+            let mut tracker = dissect_packet(&corrupted, |t, b| {
+                let tag = b.consume().unwrap();
+                dissect::stream(t, fields, Some(&keys), tag, b, &mut ())
+            });
+            assert_eq!(tracker.take(fields.auth_tag_valid), Some(Field::Integer(0)));
+            assert_eq!(tracker.take(fields.decrypted_payload), None);
+
+            // packets for other path secrets are left alone
+            let (_, other_keys) = test_keys(packet.ciphersuite, [0xff; 32]);
+            let mut tracker = dissect_packet(&buffer[..length], |t, b| {
+                let tag = b.consume().unwrap();
+                dissect::stream(t, fields, Some(&other_keys), tag, b, &mut ())
+            });
+            assert_eq!(tracker.take(fields.auth_tag_valid), None);
+        });
+}
+
+#[derive(Clone, Debug, bolero::TypeGenerator)]
+struct EncryptedDatagramPacket {
+    ciphersuite: Ciphersuite,
+    export_secret: [u8; 32],
+    packet: DatagramPacket,
+}
+
+#[test]
+fn check_datagram_decrypt() {
+    // Initialize field IDs.
+    let _ = crate::field::get();
+
+    bolero::check!()
+        .with_type()
+        .for_each(|packet: &EncryptedDatagramPacket| {
+            let (secret, keys) = test_keys(packet.ciphersuite, packet.export_secret);
+
+            let mut packet_info = packet.packet.clone();
+            if packet_info.next_expected_control_packet.is_some()
+                && packet_info.packet_number.is_none()
+            {
+                packet_info.packet_number = Some(Default::default());
+            }
+            packet_info.credentials.id = *secret.id();
+            let credentials = packet_info.credentials;
+            let key = secret.application_sealer(credentials.key_id);
+
+            let sent_payload = packet_info.payload;
+            let mut buffer = vec![
+                0;
+                sent_payload.buffered_len()
+                    + packet_info.application_header.buffered_len()
+                    + s2n_quic_dc::packet::stream::encoder::MAX_HEADER_LEN
+                    + s2n_quic_dc::packet::stream::encoder::MAX_RETRANSMISSION_HEADER_LEN
+            ];
+            let length = s2n_quic_dc::packet::datagram::encoder::encode(
+                EncoderBuffer::new(&mut buffer),
+                packet_info.source_control_port.get(),
+                packet_info.packet_number,
+                packet_info.next_expected_control_packet,
+                VarInt::new(packet_info.application_header.buffered_len() as u64).unwrap(),
+                &mut packet_info.application_header,
+                &(),
+                VarInt::new(packet_info.payload.buffered_len() as u64).unwrap(),
+                &mut packet_info.payload,
+                &key,
+                &credentials,
+            );
+
+            let expected_payload =
+                Data::send_one_at(sent_payload.offset(), sent_payload.buffered_len());
+
+            let fields = crate::field::get();
+            let mut tracker = dissect_packet(&buffer[..length], |t, b| {
+                let tag = b.consume().unwrap();
+                dissect::datagram(t, fields, Some(&keys), tag, b, &mut ())
+            });
+            assert_eq!(tracker.take(fields.auth_tag_valid), Some(Field::Integer(1)));
+            let decrypted = tracker.take(fields.decrypted_payload);
+            if expected_payload.is_empty() {
+                assert!(decrypted.is_none());
+            } else {
+                assert_eq!(decrypted, Some(Field::Slice(expected_payload.to_vec())));
+            }
+
+            let mut corrupted = buffer[..length].to_vec();
+            *corrupted.last_mut().unwrap() ^= 1;
+            let mut tracker = dissect_packet(&corrupted, |t, b| {
+                let tag = b.consume().unwrap();
+                dissect::datagram(t, fields, Some(&keys), tag, b, &mut ())
+            });
+            assert_eq!(tracker.take(fields.auth_tag_valid), Some(Field::Integer(0)));
+        });
+}
+
+#[derive(Clone, Debug, bolero::TypeGenerator)]
+struct AuthenticatedControlPacket {
+    ciphersuite: Ciphersuite,
+    export_secret: [u8; 32],
+    packet: ControlPacket,
+    is_remote_initiator: bool,
+}
+
+#[test]
+fn check_control_authenticate() {
+    // Initialize field IDs.
+    let _ = crate::field::get();
+
+    bolero::check!()
+        .with_type()
+        .for_each(|packet: &AuthenticatedControlPacket| {
+            let (secret, keys) = test_keys(packet.ciphersuite, packet.export_secret);
+            let initiator = if packet.is_remote_initiator {
+                Initiator::Remote
+            } else {
+                Initiator::Local
+            };
+
+            let mut packet_info = packet.packet.clone();
+            packet_info.credentials.id = *secret.id();
+            let credentials = packet_info.credentials;
+            let (key, _opener) = secret.control_pair(credentials.key_id, initiator);
+
+            let mut buffer = vec![
+                0;
+                packet_info.control_data.buffered_len()
+                    + packet_info.application_header.buffered_len()
+                    + s2n_quic_dc::packet::stream::encoder::MAX_HEADER_LEN
+                    + s2n_quic_dc::packet::stream::encoder::MAX_RETRANSMISSION_HEADER_LEN
+            ];
+            let length = s2n_quic_dc::packet::control::encoder::encode(
+                EncoderBuffer::new(&mut buffer),
+                packet_info.source_control_port.get(),
+                packet_info.stream_id,
+                packet_info.packet_number,
+                VarInt::new(packet_info.application_header.buffered_len() as u64).unwrap(),
+                &mut packet_info.application_header,
+                VarInt::new(packet_info.control_data.buffered_len() as u64).unwrap(),
+                &&packet_info.control_data.read_chunk(usize::MAX).unwrap()[..],
+                &key,
+                &credentials,
+            );
+
+            let fields = crate::field::get();
+            let mut tracker = dissect_packet(&buffer[..length], |t, b| {
+                let tag = b.consume().unwrap();
+                dissect::control(t, fields, Some(&keys), tag, b, &mut ())
+            });
+            assert_eq!(tracker.take(fields.auth_tag_valid), Some(Field::Integer(1)));
+
+            let mut corrupted = buffer[..length].to_vec();
+            *corrupted.last_mut().unwrap() ^= 1;
+            let mut tracker = dissect_packet(&corrupted, |t, b| {
+                let tag = b.consume().unwrap();
+                dissect::control(t, fields, Some(&keys), tag, b, &mut ())
+            });
+            assert_eq!(tracker.take(fields.auth_tag_valid), Some(Field::Integer(0)));
+        });
+}
+
+#[test]
+fn check_secret_control_authenticate() {
+    // Initialize field IDs.
+    let _ = crate::field::get();
+
+    bolero::check!().with_type().for_each(
+        |(ciphersuite, export_secret, key_id): &(Ciphersuite, [u8; 32], VarInt)| {
+            let (secret, keys) = test_keys(*ciphersuite, *export_secret);
+            let key = secret.control_sealer();
+
+            let mut buffer = vec![0; s2n_quic_dc::packet::secret_control::MAX_PACKET_SIZE];
+            let length = s2n_quic_dc::packet::secret_control::StaleKey {
+                wire_version: WireVersion::ZERO,
+                credential_id: *secret.id(),
+                min_key_id: *key_id,
+            }
+            .encode(EncoderBuffer::new(&mut buffer), &key);
+
+            let fields = crate::field::get();
+            let mut tracker = dissect_packet(&buffer[..length], |t, b| {
+                let tag = b.consume().unwrap();
+                dissect::secret_control(t, fields, Some(&keys), tag, b, &mut ())
+            });
+            assert_eq!(tracker.take(fields.auth_tag_valid), Some(Field::Integer(1)));
+        },
+    );
+}
+
+fn dissect_packet(
+    packet: &[u8],
+    dissect: impl FnOnce(&mut Tracker, &mut Buffer) -> Option<()>,
+) -> Tracker {
+    let mut tracker = Tracker::default();
+    let mut buffer = unsafe { Buffer::new(ptr::null_mut(), packet) };
+    assert!(dissect(&mut tracker, &mut buffer).is_some());
+    tracker
+}
+
 #[derive(Default, Clone)]
 struct Tracker {
     seen_fields: std::rc::Rc<std::cell::RefCell<HashMap<i32, Field>>>,
@@ -762,3 +1111,27 @@ impl s2n_quic_dc::crypto::seal::control::Stream for TestKey {
 }
 
 impl s2n_quic_dc::crypto::seal::control::Secret for TestKey {}
+
+/// Encrypts packets with a key which has been updated to the given key phase
+struct PhasedKey(awslc::seal::Application, KeyPhase);
+
+impl s2n_quic_dc::crypto::seal::Application for PhasedKey {
+    fn key_phase(&self) -> KeyPhase {
+        self.1
+    }
+
+    fn tag_len(&self) -> usize {
+        self.0.tag_len()
+    }
+
+    fn encrypt(
+        &self,
+        packet_number: u64,
+        header: &[u8],
+        extra_payload: Option<&[u8]>,
+        payload_and_tag: &mut [u8],
+    ) {
+        self.0
+            .encrypt(packet_number, header, extra_payload, payload_and_tag)
+    }
+}
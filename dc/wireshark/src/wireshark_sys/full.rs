@@ -3839,6 +3839,31 @@ extern "C" {
         handle: dissector_handle_t,
     );
 }
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct pref_module {
+    _unused: [u8; 0],
+}
+pub type module_t = pref_module;
+extern "C" {
+    pub fn prefs_register_protocol(
+        id: ::std::os::raw::c_int,
+        apply_cb: ::std::option::Option<unsafe extern "C" fn()>,
+    ) -> *mut module_t;
+}
+extern "C" {
+    pub fn prefs_register_filename_preference(
+        module: *mut module_t,
+        name: *const ::std::os::raw::c_char,
+        title: *const ::std::os::raw::c_char,
+        description: *const ::std::os::raw::c_char,
+        var: *mut *const ::std::os::raw::c_char,
+        for_writing: gboolean,
+    );
+}
+extern "C" {
+    pub fn report_failure(msg_format: *const ::std::os::raw::c_char, ...);
+}
 pub type __builtin_va_list = [__va_list_tag; 1usize];
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
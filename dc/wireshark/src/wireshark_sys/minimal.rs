@@ -1529,3 +1529,9 @@ pub struct conversation_addr_port_endpoints {
 pub union wtap_pseudo_header {
     pub _address: u8,
 }
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct pref_module {
+    _unused: [u8; 0],
+}
+pub type module_t = pref_module;
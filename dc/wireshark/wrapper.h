@@ -1,2 +1,4 @@
 #include "epan/packet_info.h"
 #include "epan/conversation.h"
+#include "epan/prefs.h"
+#include "wsutil/report_message.h"
@@ -28,3 +28,15 @@ openssl pkcs8 -topk8 -nocrypt -outform DER -in key.pem -out key.der
 # 'untrusted' here means that the cert will be untrusted by other certificates above
 echo "generating a cert/key pair to test 'untrusted' behavior"
 openssl req -new -newkey rsa:2048 -x509 -nodes -out untrusted_cert.pem -keyout untrusted_key.pem -days 65536 -config config/ca.cnf
+
+# raw public keys (RFC 7250) aren't wrapped in a certificate
+echo "generating raw public key pairs"
+mkdir -p rpk
+openssl genpkey -algorithm ed25519 -out rpk/server-key.pem
+openssl pkey -in rpk/server-key.pem -pubout -out rpk/server-public-key.pem
+openssl pkey -in rpk/server-key.pem -pubout -outform DER -out rpk/server-public-key.der
+openssl genpkey -algorithm EC -pkeyopt ec_paramgen_curve:prime256v1 -out rpk/client-key.pem
+openssl pkey -in rpk/client-key.pem -pubout -out rpk/client-public-key.pem
+openssl pkey -in rpk/client-key.pem -pubout -outform DER -out rpk/client-public-key.der
+openssl genpkey -algorithm ed25519 -out rpk/untrusted-key.pem
+openssl pkey -in rpk/untrusted-key.pem -pubout -out rpk/untrusted-public-key.pem
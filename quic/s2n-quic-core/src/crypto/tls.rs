@@ -11,6 +11,9 @@ use zerocopy::{AsBytes, FromBytes, FromZeroes, Unaligned};
 mod error;
 pub use error::Error;
 
+pub mod keylog;
+pub use keylog::KeyLog;
+
 #[cfg(any(test, feature = "testing"))]
 pub mod testing;
 
@@ -63,6 +66,15 @@ pub trait TlsSession: Send {
 
     #[cfg(feature = "alloc")]
     fn peer_cert_chain_der(&self) -> Result<Vec<Vec<u8>>, ChainError>;
+
+    /// Returns the DER-encoded SubjectPublicKeyInfo of the peer
+    ///
+    /// This is only available if the peer authenticated with a raw public key
+    /// (<https://www.rfc-editor.org/rfc/rfc7250>) instead of a certificate chain.
+    #[cfg(feature = "alloc")]
+    fn peer_public_key_der(&self) -> Result<Vec<u8>, ChainError> {
+        Err(ChainError::failure())
+    }
 }
 
 //= https://www.rfc-editor.org/rfc/rfc9000#section-4
@@ -123,6 +135,21 @@ pub trait Context<Crypto: crate::crypto::CryptoSuite> {
         application_protocol: Bytes,
     ) -> Result<(), crate::transport::Error>;
 
+    /// Called on clients once the server accepts or rejects an Encrypted Client Hello (ECH)
+    ///
+    /// If the offer was rejected, `retry_configs` contains the `ECHConfigList` the server
+    /// provided for retrying the connection, if any.
+    #[inline]
+    fn on_ech_status(
+        &mut self,
+        status: EchStatus,
+        retry_configs: Option<&[u8]>,
+    ) -> Result<(), crate::transport::Error> {
+        let _ = status;
+        let _ = retry_configs;
+        Ok(())
+    }
+
     //= https://www.rfc-editor.org/rfc/rfc9001#section-4.1.1
     //# The TLS handshake is considered complete when the
     //# TLS stack has reported that the handshake is complete.  This happens
@@ -163,6 +190,20 @@ pub trait Context<Crypto: crate::crypto::CryptoSuite> {
     fn send_application(&mut self, transmission: Bytes);
 
     fn waker(&self) -> &core::task::Waker;
+
+    /// Returns the internal id of the connection which owns the session, if any
+    ///
+    /// This is passed to [`KeyLog`] implementations to identify the connection.
+    #[inline]
+    fn connection_id(&self) -> Option<u64> {
+        None
+    }
+
+    /// Returns the [`keylog::Context`] for the connection which owns the session
+    #[inline]
+    fn key_log_context(&self) -> keylog::Context {
+        keylog::Context::new(self.connection_id())
+    }
 }
 
 #[cfg(feature = "alloc")]
@@ -321,6 +362,26 @@ impl crate::event::IntoEvent<crate::event::api::CipherSuite> for CipherSuite {
     }
 }
 
+/// The outcome of an Encrypted Client Hello (ECH) offer
+///
+/// See <https://datatracker.ietf.org/doc/draft-ietf-tls-esni/>
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EchStatus {
+    Accepted,
+    Rejected,
+}
+
+impl crate::event::IntoEvent<crate::event::builder::EchStatus> for EchStatus {
+    #[inline]
+    fn into_event(self) -> crate::event::builder::EchStatus {
+        use crate::event::builder::EchStatus::*;
+        match self {
+            Self::Accepted => Accepted {},
+            Self::Rejected => Rejected {},
+        }
+    }
+}
+
 macro_rules! handshake_type {
     ($($variant:ident($value:literal)),* $(,)?) => {
         #[derive(Clone, Copy, Debug, PartialEq, Eq, AsBytes, Unaligned)]
@@ -0,0 +1,304 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Exports TLS secrets for debugging tools, such as Wireshark
+//!
+//! Secrets are identified by the label of the secret and the `client_random` value from the
+//! ClientHello, which uniquely identifies the handshake. See
+//! <https://firefox-source-docs.mozilla.org/security/nss/legacy/key_log_format/index.html>.
+//!
+//! Each secret is accompanied by a [`Context`] which identifies the connection that derived it.
+
+use core::fmt;
+
+/// Identifies the connection which derived a secret
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Context {
+    /// The internal id of the connection
+    ///
+    /// This matches the id reported in `Subject::Connection` for events. It is `None` if the
+    /// TLS provider was driven outside of a connection.
+    pub connection_id: Option<u64>,
+}
+
+impl Context {
+    #[inline]
+    pub fn new(connection_id: Option<u64>) -> Self {
+        Self { connection_id }
+    }
+}
+
+/// A sink for TLS secrets
+///
+/// **WARNING**: the logged secrets can be used to decrypt all traffic on a connection and
+/// should only be enabled for debugging.
+pub trait KeyLog: 'static + Send + Sync {
+    /// Called for each secret derived during a handshake
+    fn log(&self, context: &Context, label: &str, client_random: &[u8], secret: &[u8]);
+
+    /// Returns `true` if secrets with the given label should be logged
+    #[inline]
+    fn will_log(&self, label: &str) -> bool {
+        let _ = label;
+        true
+    }
+
+    /// Called when the TLS provider emits a line which is not in the NSS key log format
+    #[inline]
+    fn on_malformed_line(&self, context: &Context, line: &[u8]) {
+        let _ = context;
+        let _ = line;
+    }
+}
+
+impl<F> KeyLog for F
+where
+    F: 'static + Fn(&Context, &str, &[u8], &[u8]) + Send + Sync,
+{
+    #[inline]
+    fn log(&self, context: &Context, label: &str, client_random: &[u8], secret: &[u8]) {
+        (self)(context, label, client_random, secret)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: KeyLog> KeyLog for alloc::sync::Arc<T> {
+    #[inline]
+    fn log(&self, context: &Context, label: &str, client_random: &[u8], secret: &[u8]) {
+        (**self).log(context, label, client_random, secret)
+    }
+
+    #[inline]
+    fn will_log(&self, label: &str) -> bool {
+        (**self).will_log(label)
+    }
+
+    #[inline]
+    fn on_malformed_line(&self, context: &Context, line: &[u8]) {
+        (**self).on_malformed_line(context, line)
+    }
+}
+
+#[cfg(feature = "std")]
+std::thread_local! {
+    static CURRENT: core::cell::Cell<Context> = const { core::cell::Cell::new(Context { connection_id: None }) };
+}
+
+/// Sets the [`Context`] returned by [`current`] for the duration of `f`
+///
+/// TLS providers call this around any calls into the TLS library which may derive secrets,
+/// since the library key log callbacks don't have access to the connection.
+#[cfg(feature = "std")]
+pub fn scope<R, F: FnOnce() -> R>(context: Context, f: F) -> R {
+    struct Restore(Context);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            CURRENT.with(|current| current.set(self.0));
+        }
+    }
+
+    let _restore = Restore(CURRENT.with(|current| current.replace(context)));
+    f()
+}
+
+/// Returns the [`Context`] of the connection currently being driven on this thread
+#[cfg(feature = "std")]
+pub fn current() -> Context {
+    CURRENT.with(|current| current.get())
+}
+
+/// A single line in the NSS key log format, without the trailing newline
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Line<'a> {
+    pub label: &'a str,
+    pub client_random: &'a [u8],
+    pub secret: &'a [u8],
+}
+
+impl fmt::Display for Line<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ", self.label)?;
+        for byte in self.client_random {
+            write!(f, "{byte:02x}")?;
+        }
+        f.write_str(" ")?;
+        for byte in self.secret {
+            write!(f, "{byte:02x}")?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a> Line<'a> {
+    /// The maximum length of a decoded `client_random` or secret
+    pub const MAX_VALUE_LEN: usize = 64;
+
+    /// Parses a line in the NSS key log format and passes it to `f`
+    ///
+    /// Returns `None` if the line is malformed.
+    pub fn parse<R, F: FnOnce(Line) -> R>(line: &'a [u8], f: F) -> Option<R> {
+        let line = core::str::from_utf8(line).ok()?.trim_end();
+        let mut parts = line.split(' ');
+        let label = parts.next()?;
+
+        let mut client_random = [0u8; Self::MAX_VALUE_LEN];
+        let client_random = decode_hex(parts.next()?, &mut client_random)?;
+        let mut secret = [0u8; Self::MAX_VALUE_LEN];
+        let secret = decode_hex(parts.next()?, &mut secret)?;
+
+        if parts.next().is_some() {
+            return None;
+        }
+
+        Some(f(Line {
+            label,
+            client_random,
+            secret,
+        }))
+    }
+}
+
+fn decode_hex<'a>(value: &str, out: &'a mut [u8]) -> Option<&'a [u8]> {
+    let value = value.as_bytes();
+    if value.len() % 2 != 0 || value.len() / 2 > out.len() {
+        return None;
+    }
+
+    let nibble = |c: u8| (c as char).to_digit(16).map(|v| v as u8);
+
+    let out = &mut out[..value.len() / 2];
+    for (byte, pair) in out.iter_mut().zip(value.chunks_exact(2)) {
+        *byte = (nibble(pair[0])? << 4) | nibble(pair[1])?;
+    }
+
+    Some(out)
+}
+
+/// Collects secrets in memory
+///
+/// This can be used to attach secrets to packet captures of specific connections.
+#[cfg(feature = "std")]
+#[derive(Debug, Default)]
+pub struct Buffer {
+    lines: std::sync::Mutex<alloc::vec::Vec<(Context, alloc::string::String)>>,
+    malformed_lines: core::sync::atomic::AtomicU64,
+}
+
+#[cfg(feature = "std")]
+impl Buffer {
+    /// Returns all logged secrets in the NSS key log format and clears the buffer
+    pub fn take(&self) -> alloc::string::String {
+        self.take_if(|_| true)
+    }
+
+    /// Returns the secrets logged by the given connection in the NSS key log format and
+    /// removes them from the buffer
+    pub fn take_connection(&self, connection_id: u64) -> alloc::string::String {
+        self.take_if(|context| context.connection_id == Some(connection_id))
+    }
+
+    /// Returns the number of malformed lines which were reported by the TLS provider
+    pub fn malformed_lines(&self) -> u64 {
+        self.malformed_lines
+            .load(core::sync::atomic::Ordering::Relaxed)
+    }
+
+    fn take_if<F: Fn(&Context) -> bool>(&self, f: F) -> alloc::string::String {
+        let mut out = alloc::string::String::new();
+        self.lock().retain(|(context, line)| {
+            if !f(context) {
+                return true;
+            }
+            out.push_str(line);
+            out.push('\n');
+            false
+        });
+        out
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<alloc::vec::Vec<(Context, alloc::string::String)>> {
+        self.lines.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+#[cfg(feature = "std")]
+impl KeyLog for Buffer {
+    fn log(&self, context: &Context, label: &str, client_random: &[u8], secret: &[u8]) {
+        let line = Line {
+            label,
+            client_random,
+            secret,
+        };
+        self.lock().push((*context, line.to_string()));
+    }
+
+    fn on_malformed_line(&self, _context: &Context, _line: &[u8]) {
+        self.malformed_lines
+            .fetch_add(1, core::sync::atomic::Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip_test() {
+        let line = Line {
+            label: "CLIENT_HANDSHAKE_TRAFFIC_SECRET",
+            client_random: &[0xab; 32],
+            secret: &[0x01, 0x23, 0x45],
+        };
+        let formatted = line.to_string();
+        assert_eq!(
+            formatted,
+            format!("CLIENT_HANDSHAKE_TRAFFIC_SECRET {} 012345", "ab".repeat(32))
+        );
+
+        assert_eq!(Line::parse(formatted.as_bytes(), |l| l == line), Some(true));
+        assert_eq!(Line::parse(b"LABEL 0 00", |_| ()), None);
+        assert_eq!(Line::parse(b"LABEL zz 00", |_| ()), None);
+        assert_eq!(Line::parse(b"LABEL 00", |_| ()), None);
+        assert_eq!(Line::parse(b"LABEL 00 00 00", |_| ()), None);
+    }
+
+    #[test]
+    fn buffer_test() {
+        let buffer = Buffer::default();
+        buffer.log(&Context::default(), "LABEL", &[1], &[2]);
+        buffer.log(&Context::default(), "LABEL", &[3], &[4]);
+        assert_eq!(buffer.take(), "LABEL 01 02\nLABEL 03 04\n");
+        assert_eq!(buffer.take(), "");
+
+        buffer.on_malformed_line(&Context::default(), b"LABEL 0 00");
+        assert_eq!(buffer.malformed_lines(), 1);
+    }
+
+    #[test]
+    fn buffer_connection_test() {
+        let buffer = Buffer::default();
+        buffer.log(&Context::new(Some(1)), "LABEL", &[1], &[2]);
+        buffer.log(&Context::new(Some(2)), "LABEL", &[3], &[4]);
+        buffer.log(&Context::new(Some(1)), "LABEL", &[5], &[6]);
+        assert_eq!(buffer.take_connection(1), "LABEL 01 02\nLABEL 05 06\n");
+        assert_eq!(buffer.take_connection(1), "");
+        assert_eq!(buffer.take(), "LABEL 03 04\n");
+    }
+
+    #[test]
+    fn scope_test() {
+        assert_eq!(current(), Context::default());
+
+        let inner = scope(Context::new(Some(1)), || {
+            let nested = scope(Context::new(Some(2)), current);
+            (nested, current())
+        });
+        assert_eq!(inner, (Context::new(Some(2)), Context::new(Some(1))));
+
+        // the previous context is restored after a panic
+        let _ = std::panic::catch_unwind(|| scope(Context::new(Some(3)), || panic!()));
+        assert_eq!(current(), Context::default());
+    }
+}
@@ -58,6 +58,19 @@ pub mod certificates {
     pem!(MTLS_SERVER_KEY, "mtls/server-key.pem");
     pem!(MTLS_CLIENT_CERT, "mtls/client-cert.pem");
     pem!(MTLS_CLIENT_KEY, "mtls/client-key.pem");
+
+    // Keys used for testing raw public key (RFC 7250) authentication, which aren't wrapped
+    // in a certificate.
+    //
+    // The server uses an Ed25519 key and the client uses an ECDSA P-256 key.
+    pem!(RPK_SERVER_KEY, "rpk/server-key.pem");
+    pem!(RPK_SERVER_PUBLIC_KEY, "rpk/server-public-key.pem");
+    der!(RPK_SERVER_PUBLIC_KEY_DER, "rpk/server-public-key.der");
+    pem!(RPK_CLIENT_KEY, "rpk/client-key.pem");
+    pem!(RPK_CLIENT_PUBLIC_KEY, "rpk/client-public-key.pem");
+    der!(RPK_CLIENT_PUBLIC_KEY_DER, "rpk/client-public-key.der");
+    pem!(RPK_UNTRUSTED_KEY, "rpk/untrusted-key.pem");
+    pem!(RPK_UNTRUSTED_PUBLIC_KEY, "rpk/untrusted-public-key.pem");
 }
 
 #[derive(Debug)]
@@ -344,6 +357,32 @@ impl<S: tls::Session, C: tls::Session> Pair<S, C> {
     }
 }
 
+impl<S: tls::Session + TlsSession, C: tls::Session + TlsSession> Pair<S, C> {
+    /// Asserts that the endpoints authenticated each other with the given raw public keys
+    ///
+    /// The keys are DER-encoded SubjectPublicKeyInfo structures. If `client_public_key` is
+    /// `None`, the server must not have received a raw public key from the client.
+    pub fn assert_peer_public_keys(
+        &self,
+        server_public_key: &[u8],
+        client_public_key: Option<&[u8]>,
+    ) {
+        let actual = self
+            .client
+            .session
+            .peer_public_key_der()
+            .expect("client did not receive the server public key");
+        assert_eq!(actual, server_public_key, "unexpected server public key");
+
+        let actual = self.server.session.peer_public_key_der().ok();
+        assert_eq!(
+            actual.as_deref(),
+            client_public_key,
+            "unexpected client public key"
+        );
+    }
+}
+
 #[derive(Debug)]
 pub enum ClientState {
     ClientHelloSent,
@@ -402,7 +441,10 @@ where
     pub handshake_complete: bool,
     pub server_name: Option<Bytes>,
     pub application_protocol: Option<Bytes>,
+    pub ech_status: Option<tls::EchStatus>,
+    pub ech_retry_configs: Option<Bytes>,
     pub transport_parameters: Option<Bytes>,
+    pub connection_id: Option<u64>,
     endpoint: endpoint::Type,
     pub state: State,
     waker: Waker,
@@ -422,7 +464,10 @@ where
             .field("handshake_complete", &self.handshake_complete)
             .field("sni", &self.server_name)
             .field("application_protocol", &self.application_protocol)
+            .field("ech_status", &self.ech_status)
+            .field("ech_retry_configs", &self.ech_retry_configs)
             .field("transport_parameters", &self.transport_parameters)
+            .field("connection_id", &self.connection_id)
             .field("endpoint", &self.endpoint)
             .finish()
     }
@@ -442,7 +487,10 @@ where
             handshake_complete: false,
             server_name: None,
             application_protocol: None,
+            ech_status: None,
+            ech_retry_configs: None,
             transport_parameters: None,
+            connection_id: None,
             endpoint,
             state,
             waker,
@@ -734,6 +782,25 @@ where
         Ok(())
     }
 
+    fn on_ech_status(
+        &mut self,
+        status: tls::EchStatus,
+        retry_configs: Option<&[u8]>,
+    ) -> Result<(), transport::Error> {
+        self.log("ech status");
+        assert!(
+            self.endpoint.is_client(),
+            "only clients should report the ECH status"
+        );
+        assert!(
+            self.ech_status.is_none(),
+            "ech status called multiple times"
+        );
+        self.ech_status = Some(status);
+        self.ech_retry_configs = retry_configs.map(Bytes::copy_from_slice);
+        Ok(())
+    }
+
     fn on_handshake_complete(&mut self) -> Result<(), transport::Error> {
         assert!(
             !self.handshake_complete,
@@ -808,4 +875,8 @@ where
     fn waker(&self) -> &Waker {
         &self.waker
     }
+
+    fn connection_id(&self) -> Option<u64> {
+        self.connection_id
+    }
 }
@@ -5,7 +5,9 @@
 
 use crate::{
     connection,
-    datagram::{ConnectionInfo, Packet, PreConnectionInfo, ReceiveContext},
+    datagram::{CongestionInfo, ConnectionInfo, Packet, PreConnectionInfo, ReceiveContext},
+    packet::number::{PacketNumber, PacketNumberRange},
+    time::Timestamp,
     transport::parameters::MaxDatagramFrameSize,
 };
 use alloc::collections::VecDeque;
@@ -13,6 +15,7 @@ use bytes::Bytes;
 use core::{
     fmt,
     task::{Context, Poll, Waker},
+    time::Duration,
 };
 
 /// Handles configuring unreliable datagram support.
@@ -232,25 +235,44 @@ impl ReceiverBuilder {
 
 /// A struct to handle sending unreliable datagrams.
 ///
-/// The Sender struct contains the queue of unreliable datagrams to be sent.
+/// The Sender struct contains the queues of unreliable datagrams to be sent.
 /// During transmission time, we alternate between sending datagrams and sending stream
 /// data. This is to ensure there is a balance between the amount of reliable
 /// and unreliable data getting sent.
 ///
-/// Datagrams are written to the packet in the order they are added to the queue.
+/// Each datagram belongs to a [`Priority`] class. Datagrams of a higher class are always
+/// written before datagrams of a lower class, and datagrams within a class are written in
+/// the order they were added to the queue. All classes share the configured queue capacity.
+///
 /// A datagram that is too large to fit in the packet will be dropped, unless the
 /// packet already contains written datagrams. This attempts to prevent
 /// the case where all datagrams are dropped because only a small amount of packet
 /// space remains.
 ///
-/// Note that there is currently no expiration date for datagrams to live on the queue.
-/// Implement the [`Sender`](s2n-quic-core::datagram::traits::Sender) trait if
-/// this behavior is necessary for your use-case.
+/// Datagrams enqueued with a deadline (see [`SendOptions`]) are dropped instead of
+/// transmitted once the deadline has passed. Deadlines are compared against the endpoint
+/// clock rather than the system clock. Datagrams enqueued with tracking enabled
+/// produce a [`DatagramEvent`] once the packet carrying them is acknowledged or declared
+/// lost, or once they are dropped without being transmitted. Tracked datagrams which are
+/// still pending when the connection closes are reported as lost or dropped. These events
+/// can be consumed with [`Sender::poll_event`].
+///
+/// The Sender is informed of the congestion state of the connection. Producers can use
+/// [`Sender::poll_send_datagram_paced`] to wait until congestion control would admit a
+/// datagram, or [`Sender::sending_rate`] to adapt how much data they produce.
 ///
 #[derive(Debug)]
 pub struct Sender {
-    queue: VecDeque<Datagram>,
+    queues: [VecDeque<Entry>; Priority::COUNT],
+    len: usize,
+    queued_bytes: usize,
+    congestion: Option<CongestionInfo>,
     capacity: usize,
+    next_id: u64,
+    in_flight: VecDeque<(PacketNumber, DatagramId)>,
+    events: Events,
+    now: Option<Timestamp>,
+    pending_ttl: usize,
     min_packet_space: usize,
     max_packet_space: usize,
     smoothed_packet_size: f64,
@@ -263,6 +285,161 @@ pub struct Sender {
 #[derive(Debug, PartialEq)]
 pub struct Datagram {
     pub data: Bytes,
+    /// The identifier assigned to the datagram when it was enqueued
+    pub id: DatagramId,
+    /// The priority class of the datagram
+    pub priority: Priority,
+    /// The time after which the datagram will be dropped instead of transmitted
+    ///
+    /// A datagram enqueued with a time-to-live has its deadline assigned once the
+    /// sender next observes the endpoint clock.
+    pub deadline: Option<Timestamp>,
+    /// Whether [`DatagramEvent`] notifications are produced for the datagram
+    pub track: bool,
+}
+
+/// A datagram on the send queue
+#[derive(Debug)]
+struct Entry {
+    datagram: Datagram,
+    /// The time-to-live of the datagram, until a deadline is assigned from it
+    ttl: Option<Duration>,
+}
+
+/// The priority class of a datagram
+///
+/// Datagrams of a higher class are always transmitted before datagrams of a lower class.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Priority {
+    High,
+    #[default]
+    Normal,
+    Low,
+}
+
+impl Priority {
+    const COUNT: usize = 3;
+
+    #[inline]
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// An identifier assigned to each datagram enqueued on the [`Sender`]
+///
+/// Identifiers are assigned in increasing order for the lifetime of the connection.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DatagramId(u64);
+
+impl DatagramId {
+    #[inline]
+    pub fn as_u64(self) -> u64 {
+        self.0
+    }
+}
+
+/// Options for enqueuing a datagram with [`Sender::send_datagram_with`]
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SendOptions {
+    pub priority: Priority,
+    pub deadline: Option<Timestamp>,
+    pub ttl: Option<Duration>,
+    pub track: bool,
+}
+
+impl SendOptions {
+    /// Sets the priority class of the datagram
+    pub fn with_priority(mut self, priority: Priority) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Sets the time, according to the endpoint clock, after which the datagram is dropped
+    /// instead of transmitted
+    pub fn with_deadline(mut self, deadline: Timestamp) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Sets how long the datagram may wait on the queue
+    ///
+    /// The time is measured from when the sender next observes the endpoint clock after
+    /// the datagram is enqueued. If a deadline is also set, the earlier of the two applies.
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+
+    /// Enables [`DatagramEvent`] notifications for the datagram
+    ///
+    /// Acknowledgements and losses can only be reported if the packet carrying the
+    /// datagram exposes its [`Packet::packet_number`].
+    pub fn with_tracking(mut self, enabled: bool) -> Self {
+        self.track = enabled;
+        self
+    }
+}
+
+/// A notification about the outcome of a tracked datagram
+///
+/// See [`SendOptions::with_tracking`].
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DatagramEvent {
+    /// The packet carrying the datagram was acknowledged by the peer
+    #[non_exhaustive]
+    Acked { id: DatagramId },
+    /// The packet carrying the datagram was declared lost
+    #[non_exhaustive]
+    Lost { id: DatagramId },
+    /// The datagram's deadline passed before it could be transmitted
+    #[non_exhaustive]
+    Expired { id: DatagramId },
+    /// The datagram was removed from the queue without being transmitted
+    #[non_exhaustive]
+    Dropped { id: DatagramId },
+}
+
+impl DatagramEvent {
+    /// Returns the identifier of the datagram the event refers to
+    pub fn id(&self) -> DatagramId {
+        match self {
+            Self::Acked { id }
+            | Self::Lost { id }
+            | Self::Expired { id }
+            | Self::Dropped { id } => *id,
+        }
+    }
+}
+
+/// The queue of pending datagram notifications
+///
+/// The oldest notification is discarded if the application does not keep up.
+#[derive(Debug)]
+struct Events {
+    queue: VecDeque<DatagramEvent>,
+    capacity: usize,
+    waker: Option<Waker>,
+}
+
+impl Events {
+    fn push(&mut self, event: DatagramEvent) {
+        if self.queue.len() >= self.capacity.max(1) {
+            self.queue.pop_front();
+        }
+        self.queue.push_back(event);
+        if let Some(w) = self.waker.take() {
+            w.wake();
+        }
+    }
+
+    fn on_dropped(&mut self, datagram: &Datagram) {
+        if datagram.track {
+            self.push(DatagramEvent::Dropped { id: datagram.id });
+        }
+    }
 }
 
 #[non_exhaustive]
@@ -276,6 +453,9 @@ pub enum DatagramError {
     ConnectionError { error: connection::Error },
 }
 
+#[cfg(feature = "std")]
+impl std::error::Error for DatagramError {}
+
 impl fmt::Display for DatagramError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
@@ -317,32 +497,56 @@ impl Sender {
         data: &mut bytes::Bytes,
         cx: &mut Context,
     ) -> Poll<Result<(), DatagramError>> {
-        if data.len() as u64 > self.max_datagram_payload {
-            return Poll::Ready(Err(DatagramError::ExceedsPeerTransportLimits));
-        }
+        self.check_datagram(data)?;
 
-        // If there was some connection-level error the user is not allowed to add
-        // datagrams to the queue as they will never be sent.
-        if let Some(err) = self.error {
-            return Poll::Ready(Err(DatagramError::ConnectionError { error: err }));
+        if self.len == self.capacity {
+            self.waker = Some(cx.waker().clone());
+            return Poll::Pending;
         }
 
-        if self.queue.len() == self.capacity {
+        let data = core::mem::replace(data, bytes::Bytes::new());
+        self.push(data, SendOptions::default());
+        Poll::Ready(Ok(()))
+    }
+
+    /// Enqueues a datagram once congestion control would admit it.
+    ///
+    /// Unlike [`Sender::poll_send_datagram`], this waits until the datagram and
+    /// everything already on the queue fit in the available congestion window of the
+    /// active path, instead of only waiting for queue capacity. Before the first
+    /// congestion update is received only the queue capacity is considered.
+    ///
+    /// # Return value
+    ///
+    /// - `Poll::Pending` if the queue is at capacity or congestion control would not
+    ///   currently admit the datagram. In this case, the caller should retry sending
+    ///   after the [`Waker`](core::task::Waker) on the provided
+    ///   [`Context`](core::task::Context) is notified.
+    /// - `Poll::Ready(Ok(DatagramId))` if the datagram was enqueued for sending.
+    /// - `Poll::Ready(Err(DatagramError))` if an error occurred while trying
+    ///   to send the datagram.
+    pub fn poll_send_datagram_paced(
+        &mut self,
+        data: &mut bytes::Bytes,
+        options: SendOptions,
+        cx: &mut Context,
+    ) -> Poll<Result<DatagramId, DatagramError>> {
+        self.check_datagram(data)?;
+
+        if self.len == self.capacity || !self.is_admitted(data.len()) {
             self.waker = Some(cx.waker().clone());
             return Poll::Pending;
         }
 
-        let datagram = Datagram {
-            data: core::mem::replace(data, bytes::Bytes::new()),
-        };
-        self.queue.push_back(datagram);
-        Poll::Ready(Ok(()))
+        let data = core::mem::replace(data, bytes::Bytes::new());
+        Poll::Ready(Ok(self.push(data, options)))
     }
 
     /// Adds datagrams on the queue to be sent
     ///
-    /// If the datagram queue is at capacity the oldest datagram will be popped
-    /// off the queue and returned to make space for the newest datagram.
+    /// If the datagram queue is at capacity the oldest datagram of the lowest
+    /// non-empty priority class will be popped off the queue and returned to make
+    /// space for the newest datagram.
     ///
     /// # Return value
     /// - `Ok(None)` if the datagram was enqueued for sending
@@ -352,24 +556,15 @@ impl Sender {
         &mut self,
         data: bytes::Bytes,
     ) -> Result<Option<Bytes>, DatagramError> {
-        if data.len() as u64 > self.max_datagram_payload {
-            return Err(DatagramError::ExceedsPeerTransportLimits);
-        }
-
-        // If there was some connection-level error the user is not allowed to add
-        // datagrams to the queue as they will never be sent.
-        if let Some(err) = self.error {
-            return Err(DatagramError::ConnectionError { error: err });
-        }
+        self.check_datagram(&data)?;
 
         // Pop oldest datagram off the queue if it is at capacity
         let mut oldest = None;
-        if self.queue.len() == self.capacity {
-            oldest = self.queue.pop_front();
+        if self.len == self.capacity {
+            oldest = self.evict();
         }
 
-        let datagram = Datagram { data };
-        self.queue.push_back(datagram);
+        self.push(data, SendOptions::default());
 
         match oldest {
             Some(datagram) => Ok(Some(datagram.data)),
@@ -385,6 +580,113 @@ impl Sender {
     /// - `Ok()` if the datagram was enqueued for sending
     /// - `Err(DatagramError)` if some error occurred
     pub fn send_datagram(&mut self, data: bytes::Bytes) -> Result<(), DatagramError> {
+        self.send_datagram_with(data, SendOptions::default())?;
+        Ok(())
+    }
+
+    /// Adds a datagram on the queue to be sent with the provided options
+    ///
+    /// If the queue is full the newest datagram is not added and an error is returned.
+    ///
+    /// # Return value
+    /// - `Ok(DatagramId)` if the datagram was enqueued for sending
+    /// - `Err(DatagramError)` if some error occurred
+    pub fn send_datagram_with(
+        &mut self,
+        data: bytes::Bytes,
+        options: SendOptions,
+    ) -> Result<DatagramId, DatagramError> {
+        self.check_datagram(&data)?;
+
+        if self.len == self.capacity {
+            return Err(DatagramError::QueueAtCapacity);
+        }
+
+        Ok(self.push(data, options))
+    }
+
+    /// Filter through the datagrams in the send queue and only keep those that
+    /// match a predicate
+    ///
+    /// A [`DatagramEvent::Dropped`] notification is emitted for each removed datagram
+    /// that has tracking enabled.
+    pub fn retain_datagrams<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&Datagram) -> bool,
+    {
+        let events = &mut self.events;
+        for queue in self.queues.iter_mut() {
+            queue.retain(|entry| {
+                let keep = f(&entry.datagram);
+                if !keep {
+                    events.on_dropped(&entry.datagram);
+                }
+                keep
+            });
+        }
+        self.len = self.queues.iter().map(VecDeque::len).sum();
+        self.queued_bytes = self
+            .queues
+            .iter()
+            .flatten()
+            .map(|entry| entry.datagram.data.len())
+            .sum();
+        self.pending_ttl = self
+            .queues
+            .iter()
+            .flatten()
+            .filter(|entry| entry.ttl.is_some())
+            .count();
+    }
+
+    /// Returns the most recent congestion state of the active path, if known
+    pub fn congestion_info(&self) -> Option<CongestionInfo> {
+        self.congestion
+    }
+
+    /// Returns the estimated rate, in bytes per second, at which the active path can
+    /// currently send, if known
+    ///
+    /// Producers can use this to adapt the amount of data they generate rather than
+    /// relying on the queue to drop datagrams.
+    pub fn sending_rate(&self) -> Option<u64> {
+        self.congestion.as_ref().map(CongestionInfo::sending_rate)
+    }
+
+    /// Returns the number of bytes that can be enqueued before congestion control
+    /// would stop admitting datagrams, if known
+    pub fn available_send_window(&self) -> Option<usize> {
+        let info = self.congestion.as_ref()?;
+        Some((info.available_window() as usize).saturating_sub(self.queued_bytes))
+    }
+
+    /// Returns the next notification for datagrams sent with tracking enabled
+    pub fn next_event(&mut self) -> Option<DatagramEvent> {
+        self.events.queue.pop_front()
+    }
+
+    /// Dequeues a notification for datagrams sent with tracking enabled.
+    ///
+    /// # Return value
+    ///
+    /// - `Poll::Pending` if there are no notifications available. In this case, the
+    ///   caller should retry after the [`Waker`](core::task::Waker) on the provided
+    ///   [`Context`](core::task::Context) is notified.
+    /// - `Poll::Ready(Ok(DatagramEvent))` if a notification is available.
+    /// - `Poll::Ready(Err(DatagramError))` if a connection error occurred and no more
+    ///   notifications will be produced.
+    pub fn poll_event(&mut self, cx: &mut Context) -> Poll<Result<DatagramEvent, DatagramError>> {
+        if let Some(event) = self.events.queue.pop_front() {
+            Poll::Ready(Ok(event))
+        } else if let Some(err) = self.error {
+            Poll::Ready(Err(DatagramError::ConnectionError { error: err }))
+        } else {
+            self.events.waker = Some(cx.waker().clone());
+            Poll::Pending
+        }
+    }
+
+    fn check_datagram(&self, data: &Bytes) -> Result<(), DatagramError> {
         if data.len() as u64 > self.max_datagram_payload {
             return Err(DatagramError::ExceedsPeerTransportLimits);
         }
@@ -395,22 +697,108 @@ impl Sender {
             return Err(DatagramError::ConnectionError { error: err });
         }
 
-        if self.queue.len() == self.capacity {
-            return Err(DatagramError::QueueAtCapacity);
+        Ok(())
+    }
+
+    fn push(&mut self, data: Bytes, options: SendOptions) -> DatagramId {
+        let id = DatagramId(self.next_id);
+        self.next_id += 1;
+
+        let entry = Entry {
+            datagram: Datagram {
+                data,
+                id,
+                priority: options.priority,
+                deadline: options.deadline,
+                track: options.track,
+            },
+            ttl: options.ttl,
+        };
+        if entry.ttl.is_some() {
+            self.pending_ttl += 1;
         }
+        self.queued_bytes += entry.datagram.data.len();
+        self.queues[options.priority.index()].push_back(entry);
+        self.len += 1;
+        id
+    }
 
-        let datagram = Datagram { data };
-        self.queue.push_back(datagram);
-        Ok(())
+    /// Records the current time of the endpoint
+    ///
+    /// Any datagrams enqueued with a time-to-live since the last observation are assigned
+    /// a deadline relative to `now`.
+    fn on_timestamp(&mut self, now: Timestamp) {
+        self.now = Some(self.now.map_or(now, |prev| prev.max(now)));
+
+        if self.pending_ttl == 0 {
+            return;
+        }
+
+        for entry in self.queues.iter_mut().flatten() {
+            if let Some(ttl) = entry.ttl.take() {
+                let deadline = now + ttl;
+                entry.datagram.deadline = Some(
+                    entry
+                        .datagram
+                        .deadline
+                        .map_or(deadline, |prev| prev.min(deadline)),
+                );
+            }
+        }
+        self.pending_ttl = 0;
     }
 
-    /// Filter through the datagrams in the send queue and only keep those that
-    /// match a predicate
-    pub fn retain_datagrams<F>(&mut self, f: F)
-    where
-        F: FnMut(&Datagram) -> bool,
-    {
-        self.queue.retain(f);
+    #[inline]
+    fn is_admitted(&self, len: usize) -> bool {
+        self.available_send_window()
+            .map_or(true, |available| len <= available)
+    }
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Removes the next datagram to be transmitted
+    fn pop_front(&mut self) -> Option<Entry> {
+        let entry = self.queues.iter_mut().find_map(VecDeque::pop_front)?;
+        self.on_removed(&entry);
+        Some(entry)
+    }
+
+    /// Returns a datagram to the front of its priority class
+    fn push_front(&mut self, entry: Entry) {
+        self.len += 1;
+        self.queued_bytes += entry.datagram.data.len();
+        if entry.ttl.is_some() {
+            self.pending_ttl += 1;
+        }
+        self.queues[entry.datagram.priority.index()].push_front(entry);
+    }
+
+    /// Removes the oldest datagram of the lowest non-empty priority class
+    fn evict(&mut self) -> Option<Datagram> {
+        let entry = self.queues.iter_mut().rev().find_map(VecDeque::pop_front)?;
+        self.on_removed(&entry);
+        self.events.on_dropped(&entry.datagram);
+        Some(entry.datagram)
+    }
+
+    #[inline]
+    fn on_removed(&mut self, entry: &Entry) {
+        self.len -= 1;
+        self.queued_bytes -= entry.datagram.data.len();
+        if entry.ttl.is_some() {
+            self.pending_ttl -= 1;
+        }
+    }
+
+    #[inline]
+    fn is_expired(datagram: &Datagram, now: Option<Timestamp>) -> bool {
+        match (datagram.deadline, now) {
+            (Some(deadline), Some(now)) => deadline <= now,
+            _ => false,
+        }
     }
 
     fn record_capacity_stats(&mut self, capacity: usize) {
@@ -458,48 +846,120 @@ impl super::Sender for Sender {
             return;
         }
         self.record_capacity_stats(packet.remaining_capacity());
+        if let Some(now) = packet.current_time() {
+            self.on_timestamp(now);
+        }
+        let now = self.now;
+        let prev_len = self.len;
         let mut has_written = false;
         while packet.remaining_capacity() > 0 {
-            if let Some(datagram) = self.queue.pop_front() {
-                // Ensure there is enough space in the packet to send a datagram
-                if packet.remaining_capacity() >= datagram.data.len() {
-                    match packet.write_datagram(&datagram.data) {
-                        Ok(()) => has_written = true,
-                        Err(_error) => {
-                            continue;
-                        }
-                    }
-                    // Since a datagram was popped off the queue, wake the
-                    // stored waker if we have one to let the application know
-                    // that there is space on the queue for more datagrams.
-                    if let Some(w) = self.waker.take() {
-                        w.wake();
+            let Some(entry) = self.pop_front() else {
+                // If there are no datagrams on the queue we stop
+                break;
+            };
+            let datagram = &entry.datagram;
+
+            // Drop datagrams that are no longer useful to the application
+            if Self::is_expired(datagram, now) {
+                if datagram.track {
+                    self.events.push(DatagramEvent::Expired { id: datagram.id });
+                }
+                continue;
+            }
+
+            // Ensure there is enough space in the packet to send a datagram
+            if packet.remaining_capacity() >= datagram.data.len() {
+                match packet.write_datagram(&datagram.data) {
+                    Ok(()) => has_written = true,
+                    Err(_error) => {
+                        self.events.on_dropped(datagram);
+                        continue;
                     }
-                } else {
-                    // This check keeps us from popping all the datagrams off the
-                    // queue when packet space remaining is smaller than the datagram.
-                    if has_written {
-                        self.queue.push_front(datagram);
-                        return;
+                }
+                if datagram.track {
+                    if let Some(packet_number) = packet.packet_number() {
+                        self.in_flight.push_back((packet_number, datagram.id));
                     }
                 }
             } else {
-                // If there are no datagrams on the queue we return
-                return;
+                // This check keeps us from popping all the datagrams off the
+                // queue when packet space remaining is smaller than the datagram.
+                if has_written {
+                    self.push_front(entry);
+                    break;
+                }
+                self.events.on_dropped(datagram);
+            }
+        }
+
+        // If datagrams were removed from the queue, wake the stored waker
+        // if we have one to let the application know that there is space
+        // on the queue for more datagrams.
+        if self.len < prev_len {
+            if let Some(w) = self.waker.take() {
+                w.wake();
             }
         }
     }
 
     #[inline]
     fn has_transmission_interest(&self) -> bool {
-        !self.queue.is_empty()
+        !self.is_empty()
     }
 
     fn on_connection_error(&mut self, error: connection::Error) {
         self.error = Some(error);
+
+        // Report the final outcome of any tracked datagrams, since no more
+        // acknowledgements will be received and the queue will never be transmitted
+        for (_packet_number, id) in core::mem::take(&mut self.in_flight) {
+            self.events.push(DatagramEvent::Lost { id });
+        }
+        while let Some(entry) = self.pop_front() {
+            self.events.on_dropped(&entry.datagram);
+        }
+
         if let Some(w) = self.waker.take() {
             w.wake();
         }
+        if let Some(w) = self.events.waker.take() {
+            w.wake();
+        }
+    }
+
+    fn on_congestion_update(&mut self, info: &CongestionInfo) {
+        self.congestion = Some(*info);
+        self.on_timestamp(info.timestamp);
+        // Let a paced sender know that the congestion window has room again
+        if self.len < self.capacity && self.available_send_window() > Some(0) {
+            if let Some(w) = self.waker.take() {
+                w.wake();
+            }
+        }
+    }
+
+    fn on_packet_ack(&mut self, packet_number_range: &PacketNumberRange) {
+        let events = &mut self.events;
+        self.in_flight.retain(|(packet_number, id)| {
+            if packet_number_range.contains(*packet_number) {
+                events.push(DatagramEvent::Acked { id: *id });
+                false
+            } else {
+                true
+            }
+        });
+    }
+
+    fn on_packet_loss(&mut self, packet_number_range: &PacketNumberRange) {
+        let events = &mut self.events;
+        self.in_flight.retain(|(packet_number, id)| {
+            if packet_number_range.contains(*packet_number) {
+                events.push(DatagramEvent::Lost { id: *id });
+                false
+            } else {
+                true
+            }
+        });
     }
 }
 
@@ -537,8 +997,20 @@ impl SenderBuilder {
     /// Builds the datagram sender into a provider
     pub fn build(self) -> Result<Sender, core::convert::Infallible> {
         Ok(Sender {
-            queue: VecDeque::with_capacity(self.queue_capacity),
+            queues: Default::default(),
+            len: 0,
+            queued_bytes: 0,
+            congestion: None,
             capacity: self.queue_capacity,
+            next_id: 0,
+            in_flight: VecDeque::new(),
+            now: None,
+            pending_ttl: 0,
+            events: Events {
+                queue: VecDeque::new(),
+                capacity: self.queue_capacity,
+                waker: None,
+            },
             max_datagram_payload: self.max_datagram_payload,
             max_packet_space: 0,
             min_packet_space: 0,
@@ -552,7 +1024,7 @@ impl SenderBuilder {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::datagram::WriteError;
+    use crate::{datagram::WriteError, packet::number::PacketNumberSpace, varint::VarInt};
     use core::task::{Context, Poll};
     use futures_test::task::{new_count_waker, noop_waker};
 
@@ -579,11 +1051,11 @@ mod tests {
 
         // Oldest datagram has been bumped off the queue and the newest two datagrams
         // are there
-        let second = default_sender.queue.pop_front().unwrap();
-        assert_eq!(second.data[..], [4, 5, 6]);
-        let third = default_sender.queue.pop_front().unwrap();
-        assert_eq!(third.data[..], [7, 8, 9]);
-        assert!(default_sender.queue.is_empty());
+        let second = default_sender.pop_front().unwrap();
+        assert_eq!(second.datagram.data[..], [4, 5, 6]);
+        let third = default_sender.pop_front().unwrap();
+        assert_eq!(third.datagram.data[..], [7, 8, 9]);
+        assert!(default_sender.is_empty());
 
         // Connection-level error means new datagrams are not added to the queue
         let conn_err = connection::Error::closed(crate::endpoint::Location::Remote);
@@ -592,7 +1064,7 @@ mod tests {
             default_sender.send_datagram_forced(bytes::Bytes::from_static(&[7, 8, 9])),
             Err(DatagramError::ConnectionError { error: conn_err })
         );
-        assert!(default_sender.queue.is_empty());
+        assert!(default_sender.is_empty());
     }
 
     #[test]
@@ -620,11 +1092,11 @@ mod tests {
         );
 
         // Check that the first two datagrams are still there
-        let first = default_sender.queue.pop_front().unwrap();
-        assert_eq!(first.data[..], [1, 2, 3]);
-        let second = default_sender.queue.pop_front().unwrap();
-        assert_eq!(second.data[..], [4, 5, 6]);
-        assert!(default_sender.queue.is_empty());
+        let first = default_sender.pop_front().unwrap();
+        assert_eq!(first.datagram.data[..], [1, 2, 3]);
+        let second = default_sender.pop_front().unwrap();
+        assert_eq!(second.datagram.data[..], [4, 5, 6]);
+        assert!(default_sender.is_empty());
 
         // Connection-level error means new datagrams are not added to the queue
         let conn_err = connection::Error::closed(crate::endpoint::Location::Remote);
@@ -633,7 +1105,7 @@ mod tests {
             default_sender.send_datagram(bytes::Bytes::from_static(&[7, 8, 9])),
             Err(DatagramError::ConnectionError { error: conn_err })
         );
-        assert!(default_sender.queue.is_empty());
+        assert!(default_sender.is_empty());
     }
 
     #[test]
@@ -678,6 +1150,8 @@ mod tests {
             remaining_capacity: 10,
             has_pending_streams: false,
             datagrams_prioritized: false,
+            packet_number: 0,
+            current_time: None,
         };
         crate::datagram::Sender::on_transmit(&mut default_sender, &mut packet);
 
@@ -692,9 +1166,9 @@ mod tests {
         );
 
         // Check that all datagrams we expect are on the queue
-        let datagram = default_sender.queue.pop_front().unwrap();
-        assert_eq!(datagram.data[..], [10, 11, 12]);
-        assert!(default_sender.queue.is_empty());
+        let datagram = default_sender.pop_front().unwrap();
+        assert_eq!(datagram.datagram.data[..], [10, 11, 12]);
+        assert!(default_sender.is_empty());
 
         // Connection-level error means new datagrams are not added to the queue
         let conn_err = connection::Error::closed(crate::endpoint::Location::Remote);
@@ -703,7 +1177,7 @@ mod tests {
             default_sender.poll_send_datagram(&mut bytes::Bytes::from_static(&[7, 8, 9]), &mut cx),
             Poll::Ready(Err(DatagramError::ConnectionError { error: conn_err }))
         );
-        assert!(default_sender.queue.is_empty());
+        assert!(default_sender.is_empty());
     }
 
     #[test]
@@ -726,9 +1200,9 @@ mod tests {
 
         // Keep only the third datagram
         default_sender.retain_datagrams(|datagram| datagram.data[..] == [7, 8, 9]);
-        let first = default_sender.queue.pop_front().unwrap();
-        assert_eq!(first.data[..], [7, 8, 9]);
-        assert!(default_sender.queue.is_empty());
+        let first = default_sender.pop_front().unwrap();
+        assert_eq!(first.datagram.data[..], [7, 8, 9]);
+        assert!(default_sender.is_empty());
     }
 
     #[test]
@@ -771,13 +1245,297 @@ mod tests {
             remaining_capacity: 5,
             has_pending_streams: false,
             datagrams_prioritized: false,
+            packet_number: 0,
+            current_time: None,
         };
         crate::datagram::Sender::on_transmit(&mut default_sender, &mut packet);
 
         // Packet still has capacity to write datagrams
         assert!(packet.remaining_capacity > 0);
         // Send queue is not completely depleted
-        assert!(!default_sender.queue.is_empty());
+        assert!(!default_sender.is_empty());
+    }
+
+    #[test]
+    fn priority_ordering() {
+        let conn_info = ConnectionInfo::new(100, noop_waker());
+        let mut default_sender = Sender::builder()
+            .with_capacity(3)
+            .with_connection_info(&conn_info)
+            .build()
+            .unwrap();
+        let low = SendOptions::default().with_priority(Priority::Low);
+        let high = SendOptions::default().with_priority(Priority::High);
+        default_sender
+            .send_datagram_with(bytes::Bytes::from_static(&[1]), low)
+            .unwrap();
+        default_sender
+            .send_datagram(bytes::Bytes::from_static(&[2]))
+            .unwrap();
+        default_sender
+            .send_datagram_with(bytes::Bytes::from_static(&[3]), high)
+            .unwrap();
+
+        // Forcing a new datagram evicts from the lowest priority class
+        assert_eq!(
+            default_sender.send_datagram_forced(bytes::Bytes::from_static(&[4])),
+            Ok(Some(bytes::Bytes::from_static(&[1])))
+        );
+
+        // Higher priority classes are transmitted first
+        assert_eq!(default_sender.pop_front().unwrap().datagram.data[..], [3]);
+        assert_eq!(default_sender.pop_front().unwrap().datagram.data[..], [2]);
+        assert_eq!(default_sender.pop_front().unwrap().datagram.data[..], [4]);
+        assert!(default_sender.is_empty());
+    }
+
+    #[test]
+    fn expired_datagrams() {
+        let conn_info = ConnectionInfo::new(100, noop_waker());
+        let mut default_sender = Sender::builder()
+            .with_connection_info(&conn_info)
+            .build()
+            .unwrap();
+        let now = crate::time::testing::now();
+        let expired = SendOptions::default()
+            .with_deadline(now)
+            .with_tracking(true);
+        let id = default_sender
+            .send_datagram_with(bytes::Bytes::from_static(&[1, 2, 3]), expired)
+            .unwrap();
+        let fresh = SendOptions::default().with_ttl(Duration::from_secs(60));
+        default_sender
+            .send_datagram_with(bytes::Bytes::from_static(&[4, 5, 6]), fresh)
+            .unwrap();
+
+        let mut packet = MockPacket {
+            remaining_capacity: 10,
+            has_pending_streams: false,
+            datagrams_prioritized: false,
+            packet_number: 0,
+            current_time: Some(now),
+        };
+        crate::datagram::Sender::on_transmit(&mut default_sender, &mut packet);
+
+        // Only the unexpired datagram was written
+        assert_eq!(packet.remaining_capacity, 7);
+        assert!(default_sender.is_empty());
+        assert_eq!(
+            default_sender.next_event(),
+            Some(DatagramEvent::Expired { id })
+        );
+        assert_eq!(default_sender.next_event(), None);
+    }
+
+    #[test]
+    fn ttl_datagrams() {
+        let conn_info = ConnectionInfo::new(100, noop_waker());
+        let mut default_sender = Sender::builder()
+            .with_connection_info(&conn_info)
+            .build()
+            .unwrap();
+        let now = crate::time::testing::now();
+        let options = SendOptions::default()
+            .with_ttl(Duration::from_secs(1))
+            .with_tracking(true);
+        let id = default_sender
+            .send_datagram_with(bytes::Bytes::from_static(&[1, 2, 3]), options)
+            .unwrap();
+
+        // The deadline is assigned once the endpoint clock is observed
+        let info = CongestionInfo::new(0, 0, Duration::from_millis(100), now);
+        crate::datagram::Sender::on_congestion_update(&mut default_sender, &info);
+        default_sender.retain_datagrams(|datagram| {
+            assert_eq!(datagram.deadline, Some(now + Duration::from_secs(1)));
+            true
+        });
+
+        // The datagram is dropped once the endpoint clock passes the deadline
+        let mut packet = MockPacket {
+            remaining_capacity: 10,
+            has_pending_streams: false,
+            datagrams_prioritized: false,
+            packet_number: 0,
+            current_time: Some(now + Duration::from_secs(1)),
+        };
+        crate::datagram::Sender::on_transmit(&mut default_sender, &mut packet);
+        assert_eq!(packet.remaining_capacity, 10);
+        assert!(default_sender.is_empty());
+        assert_eq!(
+            default_sender.next_event(),
+            Some(DatagramEvent::Expired { id })
+        );
+    }
+
+    #[test]
+    fn close_events() {
+        let conn_info = ConnectionInfo::new(100, noop_waker());
+        let mut default_sender = Sender::builder()
+            .with_connection_info(&conn_info)
+            .build()
+            .unwrap();
+        let tracked = SendOptions::default().with_tracking(true);
+
+        let in_flight = default_sender
+            .send_datagram_with(bytes::Bytes::from_static(&[1, 2, 3]), tracked)
+            .unwrap();
+        let mut packet = MockPacket {
+            remaining_capacity: 3,
+            has_pending_streams: false,
+            datagrams_prioritized: false,
+            packet_number: 1,
+            current_time: None,
+        };
+        crate::datagram::Sender::on_transmit(&mut default_sender, &mut packet);
+        let queued = default_sender
+            .send_datagram_with(bytes::Bytes::from_static(&[4, 5, 6]), tracked)
+            .unwrap();
+        default_sender
+            .send_datagram(bytes::Bytes::from_static(&[7, 8, 9]))
+            .unwrap();
+
+        // Pending datagrams are reported once the connection closes
+        let conn_err = connection::Error::closed(crate::endpoint::Location::Remote);
+        crate::datagram::Sender::on_connection_error(&mut default_sender, conn_err);
+        assert_eq!(
+            default_sender.next_event(),
+            Some(DatagramEvent::Lost { id: in_flight })
+        );
+        assert_eq!(
+            default_sender.next_event(),
+            Some(DatagramEvent::Dropped { id: queued })
+        );
+        assert_eq!(default_sender.next_event(), None);
+        assert!(default_sender.is_empty());
+        assert!(default_sender.in_flight.is_empty());
+    }
+
+    #[test]
+    fn ack_and_loss_events() {
+        let conn_info = ConnectionInfo::new(100, noop_waker());
+        let mut default_sender = Sender::builder()
+            .with_connection_info(&conn_info)
+            .build()
+            .unwrap();
+        let tracked = SendOptions::default().with_tracking(true);
+
+        let transmit = |sender: &mut Sender, data: &'static [u8], pn: u8| {
+            let id = sender
+                .send_datagram_with(bytes::Bytes::from_static(data), tracked)
+                .unwrap();
+            let mut packet = MockPacket {
+                remaining_capacity: 10,
+                has_pending_streams: false,
+                datagrams_prioritized: false,
+                packet_number: pn,
+                current_time: None,
+            };
+            crate::datagram::Sender::on_transmit(sender, &mut packet);
+            id
+        };
+        let id_0 = transmit(&mut default_sender, &[1, 2, 3], 1);
+        let id_1 = transmit(&mut default_sender, &[4, 5, 6], 2);
+        // Untracked datagrams don't produce notifications
+        default_sender
+            .send_datagram(bytes::Bytes::from_static(&[7, 8, 9]))
+            .unwrap();
+        let id_2 = transmit(&mut default_sender, &[10, 11, 12], 3);
+
+        let (waker, wake_count) = new_count_waker();
+        let mut cx = Context::from_waker(&waker);
+        assert_eq!(default_sender.poll_event(&mut cx), Poll::Pending);
+
+        crate::datagram::Sender::on_packet_ack(
+            &mut default_sender,
+            &PacketNumberRange::new(packet_number(1), packet_number(1)),
+        );
+        crate::datagram::Sender::on_packet_loss(
+            &mut default_sender,
+            &PacketNumberRange::new(packet_number(2), packet_number(3)),
+        );
+        assert_eq!(wake_count, 1);
+
+        assert_eq!(
+            default_sender.poll_event(&mut cx),
+            Poll::Ready(Ok(DatagramEvent::Acked { id: id_0 }))
+        );
+        assert_eq!(
+            default_sender.poll_event(&mut cx),
+            Poll::Ready(Ok(DatagramEvent::Lost { id: id_1 }))
+        );
+        assert_eq!(
+            default_sender.poll_event(&mut cx),
+            Poll::Ready(Ok(DatagramEvent::Lost { id: id_2 }))
+        );
+        assert!(default_sender.in_flight.is_empty());
+
+        // Dropping a tracked datagram from the queue is reported
+        let id_3 = default_sender
+            .send_datagram_with(bytes::Bytes::from_static(&[13]), tracked)
+            .unwrap();
+        default_sender.retain_datagrams(|_| false);
+        assert_eq!(
+            default_sender.next_event(),
+            Some(DatagramEvent::Dropped { id: id_3 })
+        );
+
+        // Once notifications are drained the connection error is returned
+        let conn_err = connection::Error::closed(crate::endpoint::Location::Remote);
+        crate::datagram::Sender::on_connection_error(&mut default_sender, conn_err);
+        assert_eq!(
+            default_sender.poll_event(&mut cx),
+            Poll::Ready(Err(DatagramError::ConnectionError { error: conn_err }))
+        );
+    }
+
+    #[test]
+    fn poll_send_datagram_paced() {
+        let conn_info = ConnectionInfo::new(100, noop_waker());
+        let mut default_sender = Sender::builder()
+            .with_connection_info(&conn_info)
+            .build()
+            .unwrap();
+        let (waker, wake_count) = new_count_waker();
+        let mut cx = Context::from_waker(&waker);
+        let options = SendOptions::default();
+
+        // Without any congestion information only the queue capacity applies
+        assert!(default_sender.sending_rate().is_none());
+        let mut datagram_0 = bytes::Bytes::from_static(&[1, 2, 3]);
+        assert!(default_sender
+            .poll_send_datagram_paced(&mut datagram_0, options, &mut cx)
+            .is_ready());
+
+        let info = CongestionInfo::new(
+            10,
+            5,
+            Duration::from_millis(100),
+            crate::time::testing::now(),
+        );
+        crate::datagram::Sender::on_congestion_update(&mut default_sender, &info);
+        assert_eq!(default_sender.sending_rate(), Some(100));
+        assert_eq!(default_sender.available_send_window(), Some(2));
+
+        // The window only has room for 2 more bytes
+        let mut datagram_1 = bytes::Bytes::from_static(&[4, 5, 6]);
+        assert_eq!(
+            default_sender.poll_send_datagram_paced(&mut datagram_1, options, &mut cx),
+            Poll::Pending
+        );
+
+        // Acknowledged data opens up the window and wakes the sender
+        let info = CongestionInfo::new(
+            10,
+            0,
+            Duration::from_millis(100),
+            crate::time::testing::now(),
+        );
+        crate::datagram::Sender::on_congestion_update(&mut default_sender, &info);
+        assert_eq!(wake_count, 1);
+        assert!(default_sender
+            .poll_send_datagram_paced(&mut datagram_1, options, &mut cx)
+            .is_ready());
+        assert_eq!(default_sender.available_send_window(), Some(4));
     }
 
     fn fake_receive_context() -> crate::datagram::ReceiveContext<'static> {
@@ -906,6 +1664,8 @@ mod tests {
         has_pending_streams: bool,
         datagrams_prioritized: bool,
         remaining_capacity: usize,
+        packet_number: u8,
+        current_time: Option<Timestamp>,
     }
 
     impl crate::datagram::Packet for MockPacket {
@@ -933,5 +1693,17 @@ mod tests {
         fn datagrams_prioritized(&self) -> bool {
             self.datagrams_prioritized
         }
+
+        fn packet_number(&self) -> Option<PacketNumber> {
+            Some(packet_number(self.packet_number))
+        }
+
+        fn current_time(&self) -> Option<Timestamp> {
+            self.current_time
+        }
+    }
+
+    fn packet_number(value: u8) -> PacketNumber {
+        PacketNumberSpace::ApplicationData.new_packet_number(VarInt::from_u8(value))
     }
 }
@@ -1,9 +1,13 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
-use core::task::Waker;
+use core::{task::Waker, time::Duration};
 
-use crate::connection;
+use crate::{
+    connection,
+    packet::number::{PacketNumber, PacketNumberRange},
+    time::Timestamp,
+};
 
 /// The datagram endpoint trait provides a way to implement custom unreliable datagram
 /// sending and receiving logic. The Sender type should be implemented for custom
@@ -95,6 +99,83 @@ pub trait Sender: 'static + Send {
 
     /// A callback used to notify the application in the case of a connection error
     fn on_connection_error(&mut self, error: connection::Error);
+
+    /// A callback invoked when a range of packets has been acknowledged by the peer
+    ///
+    /// Compare against the values returned by [`Packet::packet_number`] during
+    /// `on_transmit` to determine which datagrams were delivered.
+    #[inline]
+    fn on_packet_ack(&mut self, packet_number_range: &PacketNumberRange) {
+        let _ = packet_number_range;
+    }
+
+    /// A callback invoked when a range of packets has been declared lost
+    ///
+    /// Compare against the values returned by [`Packet::packet_number`] during
+    /// `on_transmit` to determine which datagrams were lost.
+    #[inline]
+    fn on_packet_loss(&mut self, packet_number_range: &PacketNumberRange) {
+        let _ = packet_number_range;
+    }
+
+    /// A callback invoked when the congestion state of the active path changes
+    ///
+    /// This is called after packets are sent, acknowledged or declared lost, and can
+    /// be used to limit the rate at which datagrams are enqueued.
+    #[inline]
+    fn on_congestion_update(&mut self, info: &CongestionInfo) {
+        let _ = info;
+    }
+}
+
+/// The congestion state of the active path
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CongestionInfo {
+    /// The number of bytes the congestion controller allows in flight
+    pub congestion_window: u32,
+
+    /// The number of bytes currently in flight
+    pub bytes_in_flight: u32,
+
+    /// The smoothed round trip time of the path
+    pub smoothed_rtt: Duration,
+
+    /// The time of the endpoint when the update was produced
+    pub timestamp: Timestamp,
+}
+
+impl CongestionInfo {
+    #[doc(hidden)]
+    pub fn new(
+        congestion_window: u32,
+        bytes_in_flight: u32,
+        smoothed_rtt: Duration,
+        timestamp: Timestamp,
+    ) -> Self {
+        Self {
+            congestion_window,
+            bytes_in_flight,
+            smoothed_rtt,
+            timestamp,
+        }
+    }
+
+    /// Returns the number of bytes congestion control would currently admit
+    #[inline]
+    pub fn available_window(&self) -> u32 {
+        self.congestion_window.saturating_sub(self.bytes_in_flight)
+    }
+
+    /// Returns the estimated sending rate of the path in bytes per second
+    ///
+    /// The estimate is one congestion window per smoothed round trip time.
+    #[inline]
+    pub fn sending_rate(&self) -> u64 {
+        let rtt = self.smoothed_rtt.as_micros().max(1);
+        let rate = self.congestion_window as u128 * 1_000_000 / rtt;
+        rate.try_into().unwrap_or(u64::MAX)
+    }
 }
 
 /// A packet will be available during the on_transmit callback. Use the methods
@@ -121,6 +202,24 @@ pub trait Packet {
     /// Datagrams get prioritized every other packet, which gives the application the best
     /// chance to send a large datagram.
     fn datagrams_prioritized(&self) -> bool;
+
+    /// Returns the packet number of the packet being written, if known.
+    ///
+    /// The peer's acknowledgement or loss of this packet is reported through
+    /// [`Sender::on_packet_ack`] and [`Sender::on_packet_loss`].
+    #[inline]
+    fn packet_number(&self) -> Option<PacketNumber> {
+        None
+    }
+
+    /// Returns the current time of the endpoint, if known.
+    ///
+    /// This uses the endpoint clock, which should be preferred over the system clock
+    /// when comparing against datagram deadlines.
+    #[inline]
+    fn current_time(&self) -> Option<Timestamp> {
+        None
+    }
 }
 
 #[non_exhaustive]
@@ -49,6 +49,7 @@ impl Type {
 
 /// Enumerates endpoint locations
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
 pub enum Location {
     /// The local endpoint
     Local,
@@ -7,14 +7,27 @@ use alloc::vec::Vec;
 use core::{ops::RangeInclusive, time::Duration};
 
 mod generated;
+#[cfg(feature = "std")]
+pub mod log;
 pub mod metrics;
 pub use generated::*;
 
 /// All event types which can be emitted from this library.
-pub trait Event: core::fmt::Debug {
+pub trait Event: core::fmt::Debug + Serialize {
     const NAME: &'static str;
 }
 
+/// Implemented by all event types when the `event-serde` feature is enabled
+#[cfg(feature = "event-serde")]
+pub use serde::Serialize;
+
+/// Implemented by all types when the `event-serde` feature is disabled
+#[cfg(not(feature = "event-serde"))]
+pub trait Serialize {}
+
+#[cfg(not(feature = "event-serde"))]
+impl<T: ?Sized> Serialize for T {}
+
 pub trait IntoEvent<Target> {
     fn into_event(self) -> Target;
 }
@@ -131,6 +144,14 @@ impl IntoEvent<Timestamp> for crate::time::Timestamp {
     }
 }
 
+/// Serialized as the [`Duration`] since the start of the process
+#[cfg(feature = "event-serde")]
+impl serde::Serialize for Timestamp {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.duration_since_start().serialize(serializer)
+    }
+}
+
 #[derive(Clone)]
 pub struct TlsSession<'a> {
     session: &'a dyn crate::crypto::tls::TlsSession,
@@ -158,6 +179,13 @@ impl<'a> TlsSession<'a> {
         self.session.peer_cert_chain_der()
     }
 
+    // Currently intended only for unstable usage
+    #[doc(hidden)]
+    #[cfg(feature = "alloc")]
+    pub fn peer_public_key_der(&self) -> Result<Vec<u8>, crate::crypto::tls::ChainError> {
+        self.session.peer_public_key_der()
+    }
+
     pub fn cipher_suite(&self) -> crate::event::api::CipherSuite {
         self.session.cipher_suite().into_event()
     }
@@ -175,3 +203,11 @@ impl core::fmt::Debug for TlsSession<'_> {
         f.debug_struct("TlsSession").finish_non_exhaustive()
     }
 }
+
+#[cfg(feature = "event-serde")]
+impl serde::Serialize for TlsSession<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+        serializer.serialize_struct("TlsSession", 0)?.end()
+    }
+}
@@ -12,6 +12,7 @@ pub mod api {
     pub use traits::Subscriber;
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
     pub struct ConnectionMeta {
         pub endpoint_type: EndpointType,
         pub id: u64,
@@ -19,15 +20,18 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
     pub struct EndpointMeta {
         pub endpoint_type: EndpointType,
         pub timestamp: crate::event::Timestamp,
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
     pub struct ConnectionInfo {}
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
     pub struct TransportParameters<'a> {
         pub original_destination_connection_id: Option<ConnectionId<'a>>,
         pub initial_source_connection_id: Option<ConnectionId<'a>>,
@@ -50,6 +54,7 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
     pub struct PreferredAddress<'a> {
         pub ipv4_address: Option<SocketAddress<'a>>,
         pub ipv6_address: Option<SocketAddress<'a>>,
@@ -58,6 +63,7 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
     pub struct Path<'a> {
         pub local_addr: SocketAddress<'a>,
         pub local_cid: ConnectionId<'a>,
@@ -67,12 +73,14 @@ pub mod api {
         pub is_active: bool,
     }
     #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
     #[derive(Clone)]
     pub struct ConnectionId<'a> {
         pub bytes: &'a [u8],
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
     pub struct EcnCounts {
         #[doc = " A variable-length integer representing the total number of packets"]
         #[doc = " received with the ECT(0) codepoint."]
@@ -86,6 +94,7 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
     pub struct MtuConfig {
         pub initial_mtu: u16,
         pub base_mtu: u16,
@@ -93,6 +102,7 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
     #[doc = " A bandwidth delivery rate estimate with associated metadata"]
     pub struct RateSample {
         #[doc = " The length of the sampling interval"]
@@ -117,6 +127,7 @@ pub mod api {
         pub delivery_rate_bytes_per_second: u64,
     }
     #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
     #[derive(Clone)]
     pub enum SocketAddress<'a> {
         #[non_exhaustive]
@@ -126,6 +137,7 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
     pub enum DuplicatePacketError {
         #[non_exhaustive]
         #[doc = " The packet number was already received and is a duplicate."]
@@ -141,6 +153,7 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
     pub enum Frame {
         #[non_exhaustive]
         Padding {},
@@ -215,6 +228,7 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
     pub enum StreamType {
         #[non_exhaustive]
         Bidirectional {},
@@ -223,6 +237,7 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
     pub enum PacketHeader {
         #[non_exhaustive]
         Initial { number: u64, version: u32 },
@@ -241,6 +256,7 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
     pub enum PacketType {
         #[non_exhaustive]
         Initial {},
@@ -259,6 +275,7 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
     pub enum KeyType {
         #[non_exhaustive]
         Initial {},
@@ -271,6 +288,7 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
     #[doc = " A context from which the event is being emitted"]
     #[doc = ""]
     #[doc = " An event can occur in the context of an Endpoint or Connection"]
@@ -282,6 +300,7 @@ pub mod api {
         Connection { id: u64 },
     }
     #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
     #[doc = " An endpoint may be either a Server or a Client"]
     pub enum EndpointType {
         #[non_exhaustive]
@@ -291,6 +310,7 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
     pub enum DatagramDropReason {
         #[non_exhaustive]
         #[doc = " There was an error while attempting to decode the datagram."]
@@ -342,6 +362,7 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
     pub enum KeySpace {
         #[non_exhaustive]
         Initial {},
@@ -354,6 +375,7 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
     pub enum PacketSkipReason {
         #[non_exhaustive]
         #[doc = " Skipped a packet number to elicit a quicker PTO acknowledgment"]
@@ -364,6 +386,7 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
     pub enum PacketDropReason<'a> {
         #[non_exhaustive]
         #[doc = " A connection error occurred and is no longer able to process packets."]
@@ -422,6 +445,7 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
     #[deprecated(note = "use on_rx_ack_range_dropped event instead")]
     pub enum AckAction {
         #[non_exhaustive]
@@ -444,6 +468,7 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
     pub enum RetryDiscardReason<'a> {
         #[non_exhaustive]
         #[doc = " Received a Retry packet with SCID field equal to DCID field."]
@@ -461,6 +486,7 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
     pub enum MigrationDenyReason {
         #[non_exhaustive]
         BlockedPort {},
@@ -473,6 +499,7 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
     #[doc = " The current state of the ECN controller for the path"]
     pub enum EcnState {
         #[non_exhaustive]
@@ -490,6 +517,7 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
     #[doc = " Events tracking the progress of handshake status"]
     pub enum HandshakeStatus {
         #[non_exhaustive]
@@ -514,6 +542,7 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
     #[doc = " The source that caused a congestion event"]
     pub enum CongestionSource {
         #[non_exhaustive]
@@ -524,6 +553,7 @@ pub mod api {
         PacketLoss {},
     }
     #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
     #[derive(Copy, Clone, Debug, Eq, PartialEq)]
     #[allow(non_camel_case_types)]
     pub enum CipherSuite {
@@ -538,6 +568,7 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
     pub enum PathChallengeStatus {
         #[non_exhaustive]
         Validated {},
@@ -546,6 +577,19 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
+    #[doc = " The outcome of an Encrypted Client Hello (ECH) offer"]
+    pub enum EchStatus {
+        #[non_exhaustive]
+        #[doc = " The server decrypted the ClientHello and used the inner ClientHello"]
+        Accepted {},
+        #[non_exhaustive]
+        #[doc = " The server used the outer ClientHello, which only contains the public name"]
+        Rejected {},
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
     #[doc = " The reason the slow start congestion controller state has been exited"]
     pub enum SlowStartExitCause {
         #[non_exhaustive]
@@ -567,6 +611,7 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
     #[doc = " The reason the MTU was updated"]
     pub enum MtuUpdatedCause {
         #[non_exhaustive]
@@ -590,6 +635,7 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
     pub enum BbrState {
         #[non_exhaustive]
         Startup {},
@@ -608,6 +654,7 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
     pub enum DcState {
         #[non_exhaustive]
         VersionNegotiated { version: u32 },
@@ -620,6 +667,7 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
     #[doc = " Application level protocol"]
     pub struct ApplicationProtocolInformation<'a> {
         pub chosen_application_protocol: &'a [u8],
@@ -629,6 +677,7 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
     #[doc = " Server Name was negotiated for the connection"]
     pub struct ServerNameInformation<'a> {
         pub chosen_server_name: &'a str,
@@ -638,6 +687,7 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
     #[doc = " Packet was skipped with a given reason"]
     pub struct PacketSkipped {
         pub number: u64,
@@ -649,6 +699,7 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
     #[doc = " Packet was sent by a connection"]
     pub struct PacketSent {
         pub packet_header: PacketHeader,
@@ -659,6 +710,7 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
     #[doc = " Packet was received by a connection"]
     pub struct PacketReceived {
         pub packet_header: PacketHeader,
@@ -668,6 +720,7 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
     #[doc = " Active path was updated"]
     pub struct ActivePathUpdated<'a> {
         pub previous: Path<'a>,
@@ -678,6 +731,7 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
     #[doc = " A new path was created"]
     pub struct PathCreated<'a> {
         pub active: Path<'a>,
@@ -688,6 +742,7 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
     #[doc = " Frame was sent"]
     pub struct FrameSent {
         pub packet_header: PacketHeader,
@@ -699,6 +754,7 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
     #[doc = " Frame was received"]
     pub struct FrameReceived<'a> {
         pub packet_header: PacketHeader,
@@ -710,6 +766,7 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
     #[doc = " Packet was lost"]
     pub struct PacketLost<'a> {
         pub packet_header: PacketHeader,
@@ -722,6 +779,7 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
     #[doc = " Recovery metrics updated"]
     pub struct RecoveryMetrics<'a> {
         pub path: Path<'a>,
@@ -740,6 +798,7 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
     #[doc = " Congestion (ECN or packet loss) has occurred"]
     pub struct Congestion<'a> {
         pub path: Path<'a>,
@@ -750,6 +809,7 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
     #[doc = " Events related to ACK processing"]
     #[deprecated(note = "use on_rx_ack_range_dropped event instead")]
     #[allow(deprecated)]
@@ -763,6 +823,7 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
     #[doc = " Ack range for received packets was dropped due to space constraints"]
     #[doc = ""]
     #[doc = " For the purpose of processing Acks, RX packet numbers are stored as"]
@@ -785,6 +846,7 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
     #[doc = " ACK range was received"]
     pub struct AckRangeReceived<'a> {
         pub packet_header: PacketHeader,
@@ -796,6 +858,7 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
     #[doc = " ACK range was sent"]
     pub struct AckRangeSent {
         pub packet_header: PacketHeader,
@@ -807,6 +870,7 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
     #[doc = " Packet was dropped with the given reason"]
     pub struct PacketDropped<'a> {
         pub reason: PacketDropReason<'a>,
@@ -816,6 +880,7 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
     #[doc = " Crypto key updated"]
     pub struct KeyUpdate {
         pub key_type: KeyType,
@@ -826,6 +891,7 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
     pub struct KeySpaceDiscarded {
         pub space: KeySpace,
     }
@@ -834,6 +900,7 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
     #[doc = " Connection started"]
     pub struct ConnectionStarted<'a> {
         pub path: Path<'a>,
@@ -843,6 +910,7 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
     #[doc = " Connection closed"]
     pub struct ConnectionClosed {
         pub error: crate::connection::Error,
@@ -852,6 +920,7 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
     #[doc = " Duplicate packet received"]
     pub struct DuplicatePacket<'a> {
         pub packet_header: PacketHeader,
@@ -863,6 +932,7 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
     #[doc = " Transport parameters received by connection"]
     pub struct TransportParametersReceived<'a> {
         pub transport_parameters: TransportParameters<'a>,
@@ -872,6 +942,7 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
     #[doc = " Datagram sent by a connection"]
     pub struct DatagramSent {
         pub len: u16,
@@ -888,6 +959,7 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
     #[doc = " Datagram received by a connection"]
     pub struct DatagramReceived {
         pub len: u16,
@@ -897,6 +969,7 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
     #[doc = " Datagram dropped by a connection"]
     pub struct DatagramDropped {
         pub len: u16,
@@ -907,6 +980,7 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
     #[doc = " ConnectionId updated"]
     pub struct ConnectionIdUpdated<'a> {
         pub path_id: u64,
@@ -920,6 +994,7 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
     pub struct EcnStateChanged<'a> {
         pub path: Path<'a>,
         pub state: EcnState,
@@ -929,6 +1004,7 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
     pub struct ConnectionMigrationDenied {
         pub reason: MigrationDenyReason,
     }
@@ -937,6 +1013,7 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
     pub struct HandshakeStatusUpdated {
         pub status: HandshakeStatus,
     }
@@ -945,6 +1022,7 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
     pub struct TlsExporterReady<'a> {
         pub session: crate::event::TlsSession<'a>,
     }
@@ -953,6 +1031,7 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
     #[doc = " Path challenge updated"]
     pub struct PathChallengeUpdated<'a> {
         pub path_challenge_status: PathChallengeStatus,
@@ -964,6 +1043,7 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
     pub struct TlsClientHello<'a> {
         pub payload: &'a [&'a [u8]],
     }
@@ -972,6 +1052,7 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
     pub struct TlsServerHello<'a> {
         pub payload: &'a [&'a [u8]],
     }
@@ -980,6 +1061,21 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
+    #[doc = " The server accepted or rejected the Encrypted Client Hello (ECH) offered by the client"]
+    pub struct TlsEchStatus<'a> {
+        pub status: EchStatus,
+        #[doc = " The `ECHConfigList` provided by the server for retrying a rejected offer"]
+        #[doc = ""]
+        #[doc = " Applications can use the list to establish a new connection with ECH."]
+        pub retry_configs: Option<&'a [u8]>,
+    }
+    impl<'a> Event for TlsEchStatus<'a> {
+        const NAME: &'static str = "tls:ech_status";
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
     pub struct RxStreamProgress {
         pub bytes: usize,
     }
@@ -988,6 +1084,7 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
     pub struct TxStreamProgress {
         pub bytes: usize,
     }
@@ -996,6 +1093,7 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
     pub struct KeepAliveTimerExpired {
         pub timeout: Duration,
     }
@@ -1004,6 +1102,7 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
     #[doc = " The maximum transmission unit (MTU) and/or MTU probing status for the path has changed"]
     pub struct MtuUpdated {
         pub path_id: u64,
@@ -1018,6 +1117,7 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
     #[doc = " The slow start congestion controller state has been exited"]
     pub struct SlowStartExited {
         pub path_id: u64,
@@ -1029,6 +1129,7 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
     #[doc = " A new delivery rate sample has been generated"]
     #[doc = " Note: This event is only recorded for congestion controllers that support"]
     #[doc = "       bandwidth estimates, such as BBR"]
@@ -1041,6 +1142,7 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
     #[doc = " The pacing rate has been updated"]
     pub struct PacingRateUpdated {
         pub path_id: u64,
@@ -1053,6 +1155,7 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
     #[doc = " The BBR state has changed"]
     pub struct BbrStateChanged {
         pub path_id: u64,
@@ -1063,6 +1166,7 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
     #[doc = " The DC state has changed"]
     pub struct DcStateChanged {
         pub state: DcState,
@@ -1072,6 +1176,7 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
     #[doc = " QUIC version"]
     pub struct VersionInformation<'a> {
         pub server_versions: &'a [u32],
@@ -1083,6 +1188,7 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
     #[doc = " Packet was sent by the endpoint"]
     pub struct EndpointPacketSent {
         pub packet_header: PacketHeader,
@@ -1092,6 +1198,7 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
     #[doc = " Packet was received by the endpoint"]
     pub struct EndpointPacketReceived {
         pub packet_header: PacketHeader,
@@ -1101,6 +1208,7 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
     #[doc = " Datagram sent by the endpoint"]
     pub struct EndpointDatagramSent {
         pub len: u16,
@@ -1117,6 +1225,7 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
     #[doc = " Datagram received by the endpoint"]
     pub struct EndpointDatagramReceived {
         pub len: u16,
@@ -1126,6 +1235,7 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
     #[doc = " Datagram dropped by the endpoint"]
     pub struct EndpointDatagramDropped {
         pub len: u16,
@@ -1136,6 +1246,7 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
     pub struct EndpointConnectionAttemptFailed {
         pub error: crate::connection::Error,
     }
@@ -1144,6 +1255,7 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
     #[doc = " Emitted when the platform sends at least one packet"]
     pub struct PlatformTx {
         #[doc = " The number of packets sent"]
@@ -1154,6 +1266,7 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
     #[doc = " Emitted when the platform returns an error while sending datagrams"]
     pub struct PlatformTxError {
         #[doc = " The error code returned by the platform"]
@@ -1164,6 +1277,7 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
     #[doc = " Emitted when the platform receives at least one packet"]
     pub struct PlatformRx {
         #[doc = " The number of packets received"]
@@ -1174,6 +1288,7 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
     #[doc = " Emitted when the platform returns an error while receiving datagrams"]
     pub struct PlatformRxError {
         #[doc = " The error code returned by the platform"]
@@ -1184,6 +1299,7 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
     #[doc = " Emitted when a platform feature is configured"]
     pub struct PlatformFeatureConfigured {
         pub configuration: PlatformFeatureConfiguration,
@@ -1193,6 +1309,7 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
     pub struct PlatformEventLoopWakeup {
         pub timeout_expired: bool,
         pub rx_ready: bool,
@@ -1204,6 +1321,7 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
     pub struct PlatformEventLoopSleep {
         #[doc = " The next time at which the event loop will wake"]
         pub timeout: Option<core::time::Duration>,
@@ -1215,6 +1333,7 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
     pub enum PlatformFeatureConfiguration {
         #[non_exhaustive]
         #[doc = " Emitted when segment offload was configured"]
@@ -2181,6 +2300,20 @@ pub mod tracing {
             tracing :: event ! (target : "tls_server_hello" , parent : id , tracing :: Level :: DEBUG , payload = tracing :: field :: debug (payload));
         }
         #[inline]
+        fn on_tls_ech_status(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            _meta: &api::ConnectionMeta,
+            event: &api::TlsEchStatus,
+        ) {
+            let id = context.id();
+            let api::TlsEchStatus {
+                status,
+                retry_configs,
+            } = event;
+            tracing :: event ! (target : "tls_ech_status" , parent : id , tracing :: Level :: DEBUG , status = tracing :: field :: debug (status) , retry_configs = tracing :: field :: debug (retry_configs));
+        }
+        #[inline]
         fn on_rx_stream_progress(
             &mut self,
             context: &mut Self::ConnectionContext,
@@ -3492,6 +3625,24 @@ pub mod builder {
         }
     }
     #[derive(Clone, Debug)]
+    #[doc = " The outcome of an Encrypted Client Hello (ECH) offer"]
+    pub enum EchStatus {
+        #[doc = " The server decrypted the ClientHello and used the inner ClientHello"]
+        Accepted,
+        #[doc = " The server used the outer ClientHello, which only contains the public name"]
+        Rejected,
+    }
+    impl IntoEvent<api::EchStatus> for EchStatus {
+        #[inline]
+        fn into_event(self) -> api::EchStatus {
+            use api::EchStatus::*;
+            match self {
+                Self::Accepted => Accepted {},
+                Self::Rejected => Rejected {},
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
     #[doc = " The reason the slow start congestion controller state has been exited"]
     pub enum SlowStartExitCause {
         #[doc = " A packet was determined lost"]
@@ -4229,6 +4380,28 @@ pub mod builder {
         }
     }
     #[derive(Clone, Debug)]
+    #[doc = " The server accepted or rejected the Encrypted Client Hello (ECH) offered by the client"]
+    pub struct TlsEchStatus<'a> {
+        pub status: EchStatus,
+        #[doc = " The `ECHConfigList` provided by the server for retrying a rejected offer"]
+        #[doc = ""]
+        #[doc = " Applications can use the list to establish a new connection with ECH."]
+        pub retry_configs: Option<&'a [u8]>,
+    }
+    impl<'a> IntoEvent<api::TlsEchStatus<'a>> for TlsEchStatus<'a> {
+        #[inline]
+        fn into_event(self) -> api::TlsEchStatus<'a> {
+            let TlsEchStatus {
+                status,
+                retry_configs,
+            } = self;
+            api::TlsEchStatus {
+                status: status.into_event(),
+                retry_configs: retry_configs.into_event(),
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
     pub struct RxStreamProgress {
         pub bytes: usize,
     }
@@ -4731,7 +4904,7 @@ mod traits {
     use api::*;
     use core::fmt;
     #[doc = r" Provides metadata related to an event"]
-    pub trait Meta: fmt::Debug {
+    pub trait Meta: fmt::Debug + crate::event::Serialize {
         #[doc = r" Returns whether the local endpoint is a Client or Server"]
         fn endpoint_type(&self) -> &EndpointType;
         #[doc = r" A context from which the event is being emitted"]
@@ -5258,6 +5431,18 @@ mod traits {
             let _ = meta;
             let _ = event;
         }
+        #[doc = "Called when the `TlsEchStatus` event is triggered"]
+        #[inline]
+        fn on_tls_ech_status(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &TlsEchStatus,
+        ) {
+            let _ = context;
+            let _ = meta;
+            let _ = event;
+        }
         #[doc = "Called when the `RxStreamProgress` event is triggered"]
         #[inline]
         fn on_rx_stream_progress(
@@ -5918,6 +6103,16 @@ mod traits {
             (self.1).on_tls_server_hello(&mut context.1, meta, event);
         }
         #[inline]
+        fn on_tls_ech_status(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &TlsEchStatus,
+        ) {
+            (self.0).on_tls_ech_status(&mut context.0, meta, event);
+            (self.1).on_tls_ech_status(&mut context.1, meta, event);
+        }
+        #[inline]
         fn on_rx_stream_progress(
             &mut self,
             context: &mut Self::ConnectionContext,
@@ -6376,6 +6571,8 @@ mod traits {
         fn on_tls_client_hello(&mut self, event: builder::TlsClientHello);
         #[doc = "Publishes a `TlsServerHello` event to the publisher's subscriber"]
         fn on_tls_server_hello(&mut self, event: builder::TlsServerHello);
+        #[doc = "Publishes a `TlsEchStatus` event to the publisher's subscriber"]
+        fn on_tls_ech_status(&mut self, event: builder::TlsEchStatus);
         #[doc = "Publishes a `RxStreamProgress` event to the publisher's subscriber"]
         fn on_rx_stream_progress(&mut self, event: builder::RxStreamProgress);
         #[doc = "Publishes a `TxStreamProgress` event to the publisher's subscriber"]
@@ -6744,6 +6941,15 @@ mod traits {
             self.subscriber.on_event(&self.meta, &event);
         }
         #[inline]
+        fn on_tls_ech_status(&mut self, event: builder::TlsEchStatus) {
+            let event = event.into_event();
+            self.subscriber
+                .on_tls_ech_status(self.context, &self.meta, &event);
+            self.subscriber
+                .on_connection_event(self.context, &self.meta, &event);
+            self.subscriber.on_event(&self.meta, &event);
+        }
+        #[inline]
         fn on_rx_stream_progress(&mut self, event: builder::RxStreamProgress) {
             let event = event.into_event();
             self.subscriber
@@ -6888,6 +7094,7 @@ pub mod metrics {
         path_challenge_updated: u32,
         tls_client_hello: u32,
         tls_server_hello: u32,
+        tls_ech_status: u32,
         rx_stream_progress: u32,
         tx_stream_progress: u32,
         keep_alive_timer_expired: u32,
@@ -6944,6 +7151,7 @@ pub mod metrics {
                 path_challenge_updated: 0,
                 tls_client_hello: 0,
                 tls_server_hello: 0,
+                tls_ech_status: 0,
                 rx_stream_progress: 0,
                 tx_stream_progress: 0,
                 keep_alive_timer_expired: 0,
@@ -7331,6 +7539,17 @@ pub mod metrics {
                 .on_tls_server_hello(&mut context.recorder, meta, event);
         }
         #[inline]
+        fn on_tls_ech_status(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::TlsEchStatus,
+        ) {
+            context.tls_ech_status += 1;
+            self.subscriber
+                .on_tls_ech_status(&mut context.recorder, meta, event);
+        }
+        #[inline]
         fn on_rx_stream_progress(
             &mut self,
             context: &mut Self::ConnectionContext,
@@ -7508,6 +7727,8 @@ pub mod metrics {
                 .increment_counter("tls_client_hello", self.tls_client_hello as _);
             self.recorder
                 .increment_counter("tls_server_hello", self.tls_server_hello as _);
+            self.recorder
+                .increment_counter("tls_ech_status", self.tls_ech_status as _);
             self.recorder
                 .increment_counter("rx_stream_progress", self.rx_stream_progress as _);
             self.recorder
@@ -7531,185 +7752,2678 @@ pub mod metrics {
         }
     }
 }
-#[cfg(any(test, feature = "testing"))]
-pub mod testing {
+pub mod sample {
+    #![doc = r" Forwards events to a subscriber for a sample of connections"]
+    #![doc = r""]
+    #![doc = r" The sampling decision is made once, when the connection context is created, so"]
+    #![doc = r" a subscriber either sees all of a connection's events or none of them."]
+    #![doc = r" Endpoint events are always forwarded."]
     use super::*;
-    #[derive(Clone, Debug)]
-    pub struct Subscriber {
-        location: Option<Location>,
-        output: Vec<String>,
-        pub application_protocol_information: u32,
-        pub server_name_information: u32,
-        pub packet_skipped: u32,
-        pub packet_sent: u32,
-        pub packet_received: u32,
-        pub active_path_updated: u32,
-        pub path_created: u32,
-        pub frame_sent: u32,
-        pub frame_received: u32,
-        pub packet_lost: u32,
-        pub recovery_metrics: u32,
-        pub congestion: u32,
-        pub ack_processed: u32,
-        pub rx_ack_range_dropped: u32,
-        pub ack_range_received: u32,
-        pub ack_range_sent: u32,
-        pub packet_dropped: u32,
-        pub key_update: u32,
-        pub key_space_discarded: u32,
-        pub connection_started: u32,
-        pub connection_closed: u32,
-        pub duplicate_packet: u32,
-        pub transport_parameters_received: u32,
-        pub datagram_sent: u32,
-        pub datagram_received: u32,
-        pub datagram_dropped: u32,
-        pub connection_id_updated: u32,
-        pub ecn_state_changed: u32,
-        pub connection_migration_denied: u32,
-        pub handshake_status_updated: u32,
-        pub tls_exporter_ready: u32,
-        pub path_challenge_updated: u32,
-        pub tls_client_hello: u32,
-        pub tls_server_hello: u32,
-        pub rx_stream_progress: u32,
-        pub tx_stream_progress: u32,
-        pub keep_alive_timer_expired: u32,
-        pub mtu_updated: u32,
-        pub slow_start_exited: u32,
-        pub delivery_rate_sampled: u32,
-        pub pacing_rate_updated: u32,
-        pub bbr_state_changed: u32,
-        pub dc_state_changed: u32,
-        pub version_information: u32,
-        pub endpoint_packet_sent: u32,
-        pub endpoint_packet_received: u32,
-        pub endpoint_datagram_sent: u32,
-        pub endpoint_datagram_received: u32,
-        pub endpoint_datagram_dropped: u32,
-        pub endpoint_connection_attempt_failed: u32,
-        pub platform_tx: u32,
-        pub platform_tx_error: u32,
-        pub platform_rx: u32,
-        pub platform_rx_error: u32,
-        pub platform_feature_configured: u32,
-        pub platform_event_loop_wakeup: u32,
-        pub platform_event_loop_sleep: u32,
+    use crate::query;
+    #[doc = r" Decides if a connection's events should be forwarded to the subscriber"]
+    pub trait Sampler: 'static + Send + Sync {
+        fn sample(&self, meta: &api::ConnectionMeta, info: &api::ConnectionInfo) -> bool;
     }
-    impl Drop for Subscriber {
-        fn drop(&mut self) {
-            if std::thread::panicking() {
-                return;
-            }
-            if let Some(location) = self.location.as_ref() {
-                location.snapshot(&self.output);
-            }
+    impl<F> Sampler for F
+    where
+        F: 'static + Send + Sync + Fn(&api::ConnectionMeta, &api::ConnectionInfo) -> bool,
+    {
+        #[inline]
+        fn sample(&self, meta: &api::ConnectionMeta, info: &api::ConnectionInfo) -> bool {
+            (self)(meta, info)
         }
     }
-    impl Subscriber {
-        #[doc = r" Creates a subscriber with snapshot assertions enabled"]
-        #[track_caller]
-        pub fn snapshot() -> Self {
-            let mut sub = Self::no_snapshot();
-            sub.location = Location::try_new();
-            sub
+    #[doc = r" Samples one in every `n` connections, based on the connection id"]
+    #[derive(Clone, Copy, Debug)]
+    pub struct OneIn(u64);
+    impl OneIn {
+        #[doc = r" Creates a sampler which samples one in every `n` connections"]
+        #[doc = r""]
+        #[doc = r" A value of `0` disables sampling of all connections."]
+        pub fn new(n: u64) -> Self {
+            Self(n)
         }
-        #[doc = r" Creates a subscriber with snapshot assertions disabled"]
-        pub fn no_snapshot() -> Self {
+    }
+    impl Sampler for OneIn {
+        #[inline]
+        fn sample(&self, meta: &api::ConnectionMeta, _info: &api::ConnectionInfo) -> bool {
+            self.0 != 0 && meta.id % self.0 == 0
+        }
+    }
+    #[derive(Clone, Debug)]
+    pub struct Subscriber<S: super::Subscriber, F: Sampler> {
+        subscriber: S,
+        sampler: F,
+    }
+    impl<S: super::Subscriber, F: Sampler> Subscriber<S, F> {
+        #[doc = r" Forwards events for the connections accepted by the `sampler`"]
+        pub fn new(subscriber: S, sampler: F) -> Self {
             Self {
-                location: None,
-                output: Default::default(),
-                application_protocol_information: 0,
-                server_name_information: 0,
-                packet_skipped: 0,
-                packet_sent: 0,
-                packet_received: 0,
-                active_path_updated: 0,
-                path_created: 0,
-                frame_sent: 0,
-                frame_received: 0,
-                packet_lost: 0,
-                recovery_metrics: 0,
-                congestion: 0,
-                ack_processed: 0,
-                rx_ack_range_dropped: 0,
-                ack_range_received: 0,
-                ack_range_sent: 0,
-                packet_dropped: 0,
-                key_update: 0,
-                key_space_discarded: 0,
-                connection_started: 0,
-                connection_closed: 0,
-                duplicate_packet: 0,
-                transport_parameters_received: 0,
-                datagram_sent: 0,
-                datagram_received: 0,
-                datagram_dropped: 0,
-                connection_id_updated: 0,
-                ecn_state_changed: 0,
-                connection_migration_denied: 0,
-                handshake_status_updated: 0,
-                tls_exporter_ready: 0,
-                path_challenge_updated: 0,
-                tls_client_hello: 0,
-                tls_server_hello: 0,
-                rx_stream_progress: 0,
-                tx_stream_progress: 0,
-                keep_alive_timer_expired: 0,
-                mtu_updated: 0,
-                slow_start_exited: 0,
-                delivery_rate_sampled: 0,
-                pacing_rate_updated: 0,
-                bbr_state_changed: 0,
-                dc_state_changed: 0,
-                version_information: 0,
-                endpoint_packet_sent: 0,
-                endpoint_packet_received: 0,
-                endpoint_datagram_sent: 0,
-                endpoint_datagram_received: 0,
-                endpoint_datagram_dropped: 0,
-                endpoint_connection_attempt_failed: 0,
-                platform_tx: 0,
-                platform_tx_error: 0,
-                platform_rx: 0,
-                platform_rx_error: 0,
-                platform_feature_configured: 0,
-                platform_event_loop_wakeup: 0,
-                platform_event_loop_sleep: 0,
+                subscriber,
+                sampler,
             }
         }
     }
-    impl super::Subscriber for Subscriber {
-        type ConnectionContext = ();
+    impl<S: super::Subscriber> Subscriber<S, OneIn> {
+        #[doc = r" Forwards events for one in every `n` connections"]
+        pub fn one_in(subscriber: S, n: u64) -> Self {
+            Self::new(subscriber, OneIn::new(n))
+        }
+    }
+    impl<S: super::Subscriber, F: Sampler> super::Subscriber for Subscriber<S, F> {
+        type ConnectionContext = Option<S::ConnectionContext>;
+        #[inline]
         fn create_connection_context(
             &mut self,
-            _meta: &api::ConnectionMeta,
-            _info: &api::ConnectionInfo,
+            meta: &api::ConnectionMeta,
+            info: &api::ConnectionInfo,
         ) -> Self::ConnectionContext {
+            if self.sampler.sample(meta, info) {
+                Some(self.subscriber.create_connection_context(meta, info))
+            } else {
+                None
+            }
         }
-        fn on_application_protocol_information(
+        #[inline]
+        fn supervisor_timeout(
             &mut self,
-            _context: &mut Self::ConnectionContext,
+            conn_context: &mut Self::ConnectionContext,
             meta: &api::ConnectionMeta,
-            event: &api::ApplicationProtocolInformation,
-        ) {
-            self.application_protocol_information += 1;
-            if self.location.is_some() {
-                self.output.push(format!("{meta:?} {event:?}"));
-            }
+            context: &supervisor::Context,
+        ) -> Option<Duration> {
+            let conn_context = conn_context.as_mut()?;
+            self.subscriber
+                .supervisor_timeout(conn_context, meta, context)
         }
-        fn on_server_name_information(
+        #[inline]
+        fn on_supervisor_timeout(
             &mut self,
-            _context: &mut Self::ConnectionContext,
+            conn_context: &mut Self::ConnectionContext,
             meta: &api::ConnectionMeta,
-            event: &api::ServerNameInformation,
-        ) {
-            self.server_name_information += 1;
-            if self.location.is_some() {
-                self.output.push(format!("{meta:?} {event:?}"));
+            context: &supervisor::Context,
+        ) -> supervisor::Outcome {
+            if let Some(conn_context) = conn_context {
+                self.subscriber
+                    .on_supervisor_timeout(conn_context, meta, context)
+            } else {
+                supervisor::Outcome::default()
             }
         }
-        fn on_packet_skipped(
+        #[inline]
+        fn query_mut(
+            context: &mut Self::ConnectionContext,
+            query: &mut dyn query::QueryMut,
+        ) -> query::ControlFlow {
+            query.execute_mut(context).and_then(|| match context {
+                Some(context) => S::query_mut(context, query),
+                None => query::ControlFlow::Continue,
+            })
+        }
+        #[inline]
+        fn query(
+            context: &Self::ConnectionContext,
+            query: &mut dyn query::Query,
+        ) -> query::ControlFlow {
+            query.execute(context).and_then(|| match context {
+                Some(context) => S::query(context, query),
+                None => query::ControlFlow::Continue,
+            })
+        }
+        #[inline]
+        fn on_application_protocol_information(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::ApplicationProtocolInformation,
+        ) {
+            if let Some(context) = context {
+                self.subscriber
+                    .on_application_protocol_information(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_server_name_information(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::ServerNameInformation,
+        ) {
+            if let Some(context) = context {
+                self.subscriber
+                    .on_server_name_information(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_packet_skipped(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::PacketSkipped,
+        ) {
+            if let Some(context) = context {
+                self.subscriber.on_packet_skipped(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_packet_sent(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::PacketSent,
+        ) {
+            if let Some(context) = context {
+                self.subscriber.on_packet_sent(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_packet_received(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::PacketReceived,
+        ) {
+            if let Some(context) = context {
+                self.subscriber.on_packet_received(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_active_path_updated(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::ActivePathUpdated,
+        ) {
+            if let Some(context) = context {
+                self.subscriber.on_active_path_updated(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_path_created(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::PathCreated,
+        ) {
+            if let Some(context) = context {
+                self.subscriber.on_path_created(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_frame_sent(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::FrameSent,
+        ) {
+            if let Some(context) = context {
+                self.subscriber.on_frame_sent(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_frame_received(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::FrameReceived,
+        ) {
+            if let Some(context) = context {
+                self.subscriber.on_frame_received(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_packet_lost(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::PacketLost,
+        ) {
+            if let Some(context) = context {
+                self.subscriber.on_packet_lost(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_recovery_metrics(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::RecoveryMetrics,
+        ) {
+            if let Some(context) = context {
+                self.subscriber.on_recovery_metrics(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_congestion(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::Congestion,
+        ) {
+            if let Some(context) = context {
+                self.subscriber.on_congestion(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        #[allow(deprecated)]
+        fn on_ack_processed(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::AckProcessed,
+        ) {
+            if let Some(context) = context {
+                self.subscriber.on_ack_processed(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_rx_ack_range_dropped(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::RxAckRangeDropped,
+        ) {
+            if let Some(context) = context {
+                self.subscriber
+                    .on_rx_ack_range_dropped(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_ack_range_received(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::AckRangeReceived,
+        ) {
+            if let Some(context) = context {
+                self.subscriber.on_ack_range_received(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_ack_range_sent(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::AckRangeSent,
+        ) {
+            if let Some(context) = context {
+                self.subscriber.on_ack_range_sent(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_packet_dropped(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::PacketDropped,
+        ) {
+            if let Some(context) = context {
+                self.subscriber.on_packet_dropped(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_key_update(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::KeyUpdate,
+        ) {
+            if let Some(context) = context {
+                self.subscriber.on_key_update(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_key_space_discarded(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::KeySpaceDiscarded,
+        ) {
+            if let Some(context) = context {
+                self.subscriber.on_key_space_discarded(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_connection_started(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::ConnectionStarted,
+        ) {
+            if let Some(context) = context {
+                self.subscriber.on_connection_started(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_connection_closed(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::ConnectionClosed,
+        ) {
+            if let Some(context) = context {
+                self.subscriber.on_connection_closed(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_duplicate_packet(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::DuplicatePacket,
+        ) {
+            if let Some(context) = context {
+                self.subscriber.on_duplicate_packet(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_transport_parameters_received(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::TransportParametersReceived,
+        ) {
+            if let Some(context) = context {
+                self.subscriber
+                    .on_transport_parameters_received(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_datagram_sent(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::DatagramSent,
+        ) {
+            if let Some(context) = context {
+                self.subscriber.on_datagram_sent(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_datagram_received(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::DatagramReceived,
+        ) {
+            if let Some(context) = context {
+                self.subscriber.on_datagram_received(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_datagram_dropped(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::DatagramDropped,
+        ) {
+            if let Some(context) = context {
+                self.subscriber.on_datagram_dropped(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_connection_id_updated(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::ConnectionIdUpdated,
+        ) {
+            if let Some(context) = context {
+                self.subscriber
+                    .on_connection_id_updated(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_ecn_state_changed(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::EcnStateChanged,
+        ) {
+            if let Some(context) = context {
+                self.subscriber.on_ecn_state_changed(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_connection_migration_denied(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::ConnectionMigrationDenied,
+        ) {
+            if let Some(context) = context {
+                self.subscriber
+                    .on_connection_migration_denied(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_handshake_status_updated(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::HandshakeStatusUpdated,
+        ) {
+            if let Some(context) = context {
+                self.subscriber
+                    .on_handshake_status_updated(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_tls_exporter_ready(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::TlsExporterReady,
+        ) {
+            if let Some(context) = context {
+                self.subscriber.on_tls_exporter_ready(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_path_challenge_updated(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::PathChallengeUpdated,
+        ) {
+            if let Some(context) = context {
+                self.subscriber
+                    .on_path_challenge_updated(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_tls_client_hello(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::TlsClientHello,
+        ) {
+            if let Some(context) = context {
+                self.subscriber.on_tls_client_hello(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_tls_server_hello(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::TlsServerHello,
+        ) {
+            if let Some(context) = context {
+                self.subscriber.on_tls_server_hello(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_tls_ech_status(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::TlsEchStatus,
+        ) {
+            if let Some(context) = context {
+                self.subscriber.on_tls_ech_status(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_rx_stream_progress(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::RxStreamProgress,
+        ) {
+            if let Some(context) = context {
+                self.subscriber.on_rx_stream_progress(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_tx_stream_progress(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::TxStreamProgress,
+        ) {
+            if let Some(context) = context {
+                self.subscriber.on_tx_stream_progress(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_keep_alive_timer_expired(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::KeepAliveTimerExpired,
+        ) {
+            if let Some(context) = context {
+                self.subscriber
+                    .on_keep_alive_timer_expired(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_mtu_updated(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::MtuUpdated,
+        ) {
+            if let Some(context) = context {
+                self.subscriber.on_mtu_updated(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_slow_start_exited(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::SlowStartExited,
+        ) {
+            if let Some(context) = context {
+                self.subscriber.on_slow_start_exited(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_delivery_rate_sampled(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::DeliveryRateSampled,
+        ) {
+            if let Some(context) = context {
+                self.subscriber
+                    .on_delivery_rate_sampled(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_pacing_rate_updated(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::PacingRateUpdated,
+        ) {
+            if let Some(context) = context {
+                self.subscriber.on_pacing_rate_updated(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_bbr_state_changed(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::BbrStateChanged,
+        ) {
+            if let Some(context) = context {
+                self.subscriber.on_bbr_state_changed(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_dc_state_changed(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::DcStateChanged,
+        ) {
+            if let Some(context) = context {
+                self.subscriber.on_dc_state_changed(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_version_information(
+            &mut self,
+            meta: &api::EndpointMeta,
+            event: &api::VersionInformation,
+        ) {
+            self.subscriber.on_version_information(meta, event);
+            self.subscriber.on_event(meta, event);
+        }
+        #[inline]
+        fn on_endpoint_packet_sent(
+            &mut self,
+            meta: &api::EndpointMeta,
+            event: &api::EndpointPacketSent,
+        ) {
+            self.subscriber.on_endpoint_packet_sent(meta, event);
+            self.subscriber.on_event(meta, event);
+        }
+        #[inline]
+        fn on_endpoint_packet_received(
+            &mut self,
+            meta: &api::EndpointMeta,
+            event: &api::EndpointPacketReceived,
+        ) {
+            self.subscriber.on_endpoint_packet_received(meta, event);
+            self.subscriber.on_event(meta, event);
+        }
+        #[inline]
+        fn on_endpoint_datagram_sent(
+            &mut self,
+            meta: &api::EndpointMeta,
+            event: &api::EndpointDatagramSent,
+        ) {
+            self.subscriber.on_endpoint_datagram_sent(meta, event);
+            self.subscriber.on_event(meta, event);
+        }
+        #[inline]
+        fn on_endpoint_datagram_received(
+            &mut self,
+            meta: &api::EndpointMeta,
+            event: &api::EndpointDatagramReceived,
+        ) {
+            self.subscriber.on_endpoint_datagram_received(meta, event);
+            self.subscriber.on_event(meta, event);
+        }
+        #[inline]
+        fn on_endpoint_datagram_dropped(
+            &mut self,
+            meta: &api::EndpointMeta,
+            event: &api::EndpointDatagramDropped,
+        ) {
+            self.subscriber.on_endpoint_datagram_dropped(meta, event);
+            self.subscriber.on_event(meta, event);
+        }
+        #[inline]
+        fn on_endpoint_connection_attempt_failed(
+            &mut self,
+            meta: &api::EndpointMeta,
+            event: &api::EndpointConnectionAttemptFailed,
+        ) {
+            self.subscriber
+                .on_endpoint_connection_attempt_failed(meta, event);
+            self.subscriber.on_event(meta, event);
+        }
+        #[inline]
+        fn on_platform_tx(&mut self, meta: &api::EndpointMeta, event: &api::PlatformTx) {
+            self.subscriber.on_platform_tx(meta, event);
+            self.subscriber.on_event(meta, event);
+        }
+        #[inline]
+        fn on_platform_tx_error(&mut self, meta: &api::EndpointMeta, event: &api::PlatformTxError) {
+            self.subscriber.on_platform_tx_error(meta, event);
+            self.subscriber.on_event(meta, event);
+        }
+        #[inline]
+        fn on_platform_rx(&mut self, meta: &api::EndpointMeta, event: &api::PlatformRx) {
+            self.subscriber.on_platform_rx(meta, event);
+            self.subscriber.on_event(meta, event);
+        }
+        #[inline]
+        fn on_platform_rx_error(&mut self, meta: &api::EndpointMeta, event: &api::PlatformRxError) {
+            self.subscriber.on_platform_rx_error(meta, event);
+            self.subscriber.on_event(meta, event);
+        }
+        #[inline]
+        fn on_platform_feature_configured(
+            &mut self,
+            meta: &api::EndpointMeta,
+            event: &api::PlatformFeatureConfigured,
+        ) {
+            self.subscriber.on_platform_feature_configured(meta, event);
+            self.subscriber.on_event(meta, event);
+        }
+        #[inline]
+        fn on_platform_event_loop_wakeup(
+            &mut self,
+            meta: &api::EndpointMeta,
+            event: &api::PlatformEventLoopWakeup,
+        ) {
+            self.subscriber.on_platform_event_loop_wakeup(meta, event);
+            self.subscriber.on_event(meta, event);
+        }
+        #[inline]
+        fn on_platform_event_loop_sleep(
+            &mut self,
+            meta: &api::EndpointMeta,
+            event: &api::PlatformEventLoopSleep,
+        ) {
+            self.subscriber.on_platform_event_loop_sleep(meta, event);
+            self.subscriber.on_event(meta, event);
+        }
+    }
+}
+pub mod filter {
+    #![doc = r" Forwards events to a subscriber based on the event type"]
+    #![doc = r""]
+    #![doc = r" Events are matched by their [`Event::NAME`](crate::event::Event::NAME), e.g."]
+    #![doc = r#" `"transport:packet_sent"`."#]
+    use super::*;
+    use crate::query;
+    #[doc = r" The names of all of the events, in the order of their filter index"]
+    const NAMES: [&str; 58usize] = [
+        "transport:application_protocol_information",
+        "transport:server_name_information",
+        "transport:packet_skipped",
+        "transport:packet_sent",
+        "transport:packet_received",
+        "connectivity:active_path_updated",
+        "transport:path_created",
+        "transport:frame_sent",
+        "transport:frame_received",
+        "recovery:packet_lost",
+        "recovery:metrics_updated",
+        "recovery:congestion",
+        "recovery:ack_processed",
+        "recovery:rx_ack_range_dropped",
+        "recovery:ack_range_received",
+        "recovery:ack_range_sent",
+        "transport:packet_dropped",
+        "security:key_update",
+        "security:key_space_discarded",
+        "connectivity:connection_started",
+        "connectivity:connection_closed",
+        "transport:duplicate_packet",
+        "transport:transport_parameters_received",
+        "transport:datagram_sent",
+        "transport:datagram_received",
+        "transport:datagram_dropped",
+        "connectivity:connection_id_updated",
+        "recovery:ecn_state_changed",
+        "connectivity:connection_migration_denied",
+        "connectivity:handshake_status_updated",
+        "connectivity:tls_exporter_ready",
+        "connectivity:path_challenge_updated",
+        "tls:client_hello",
+        "tls:server_hello",
+        "tls:ech_status",
+        "transport:rx_stream_progress",
+        "transport:tx_stream_progress",
+        "connectivity::keep_alive_timer_expired",
+        "connectivity:mtu_updated",
+        "recovery:slow_start_exited",
+        "recovery:delivery_rate_sampled",
+        "recovery:pacing_rate_updated",
+        "recovery:bbr_state_changed",
+        "transport:dc_state_changed",
+        "transport::version_information",
+        "transport:packet_sent",
+        "transport:packet_received",
+        "transport:datagram_sent",
+        "transport:datagram_received",
+        "transport:datagram_dropped",
+        "transport:connection_attempt_failed",
+        "platform:tx",
+        "platform:tx_error",
+        "platform:rx",
+        "platform:rx_error",
+        "platform:feature_configured",
+        "platform:event_loop_wakeup",
+        "platform:event_loop_sleep",
+    ];
+    #[derive(Clone, Debug)]
+    pub struct Subscriber<S: super::Subscriber> {
+        subscriber: S,
+        enabled: [bool; 58usize],
+    }
+    impl<S: super::Subscriber> Subscriber<S> {
+        #[doc = r" Forwards the events for which `predicate` returns `true`"]
+        pub fn new<F: FnMut(&'static str) -> bool>(subscriber: S, mut predicate: F) -> Self {
+            let enabled = core::array::from_fn(|index| predicate(NAMES[index]));
+            Self {
+                subscriber,
+                enabled,
+            }
+        }
+        #[doc = r" Only forwards the events with the given names"]
+        pub fn allow(subscriber: S, names: &[&str]) -> Self {
+            Self::new(subscriber, |name| names.contains(&name))
+        }
+        #[doc = r" Forwards all of the events except for the ones with the given names"]
+        pub fn deny(subscriber: S, names: &[&str]) -> Self {
+            Self::new(subscriber, |name| !names.contains(&name))
+        }
+    }
+    impl<S: super::Subscriber> super::Subscriber for Subscriber<S> {
+        type ConnectionContext = S::ConnectionContext;
+        #[inline]
+        fn create_connection_context(
+            &mut self,
+            meta: &api::ConnectionMeta,
+            info: &api::ConnectionInfo,
+        ) -> Self::ConnectionContext {
+            self.subscriber.create_connection_context(meta, info)
+        }
+        #[inline]
+        fn supervisor_timeout(
+            &mut self,
+            conn_context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            context: &supervisor::Context,
+        ) -> Option<Duration> {
+            self.subscriber
+                .supervisor_timeout(conn_context, meta, context)
+        }
+        #[inline]
+        fn on_supervisor_timeout(
+            &mut self,
+            conn_context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            context: &supervisor::Context,
+        ) -> supervisor::Outcome {
+            self.subscriber
+                .on_supervisor_timeout(conn_context, meta, context)
+        }
+        #[inline]
+        fn query_mut(
+            context: &mut Self::ConnectionContext,
+            query: &mut dyn query::QueryMut,
+        ) -> query::ControlFlow {
+            S::query_mut(context, query)
+        }
+        #[inline]
+        fn query(
+            context: &Self::ConnectionContext,
+            query: &mut dyn query::Query,
+        ) -> query::ControlFlow {
+            S::query(context, query)
+        }
+        #[inline]
+        fn on_application_protocol_information(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::ApplicationProtocolInformation,
+        ) {
+            if self.enabled[0] {
+                self.subscriber
+                    .on_application_protocol_information(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_server_name_information(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::ServerNameInformation,
+        ) {
+            if self.enabled[1] {
+                self.subscriber
+                    .on_server_name_information(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_packet_skipped(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::PacketSkipped,
+        ) {
+            if self.enabled[2] {
+                self.subscriber.on_packet_skipped(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_packet_sent(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::PacketSent,
+        ) {
+            if self.enabled[3] {
+                self.subscriber.on_packet_sent(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_packet_received(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::PacketReceived,
+        ) {
+            if self.enabled[4] {
+                self.subscriber.on_packet_received(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_active_path_updated(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::ActivePathUpdated,
+        ) {
+            if self.enabled[5] {
+                self.subscriber.on_active_path_updated(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_path_created(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::PathCreated,
+        ) {
+            if self.enabled[6] {
+                self.subscriber.on_path_created(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_frame_sent(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::FrameSent,
+        ) {
+            if self.enabled[7] {
+                self.subscriber.on_frame_sent(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_frame_received(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::FrameReceived,
+        ) {
+            if self.enabled[8] {
+                self.subscriber.on_frame_received(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_packet_lost(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::PacketLost,
+        ) {
+            if self.enabled[9] {
+                self.subscriber.on_packet_lost(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_recovery_metrics(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::RecoveryMetrics,
+        ) {
+            if self.enabled[10] {
+                self.subscriber.on_recovery_metrics(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_congestion(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::Congestion,
+        ) {
+            if self.enabled[11] {
+                self.subscriber.on_congestion(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        #[allow(deprecated)]
+        fn on_ack_processed(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::AckProcessed,
+        ) {
+            if self.enabled[12] {
+                self.subscriber.on_ack_processed(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_rx_ack_range_dropped(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::RxAckRangeDropped,
+        ) {
+            if self.enabled[13] {
+                self.subscriber
+                    .on_rx_ack_range_dropped(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_ack_range_received(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::AckRangeReceived,
+        ) {
+            if self.enabled[14] {
+                self.subscriber.on_ack_range_received(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_ack_range_sent(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::AckRangeSent,
+        ) {
+            if self.enabled[15] {
+                self.subscriber.on_ack_range_sent(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_packet_dropped(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::PacketDropped,
+        ) {
+            if self.enabled[16] {
+                self.subscriber.on_packet_dropped(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_key_update(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::KeyUpdate,
+        ) {
+            if self.enabled[17] {
+                self.subscriber.on_key_update(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_key_space_discarded(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::KeySpaceDiscarded,
+        ) {
+            if self.enabled[18] {
+                self.subscriber.on_key_space_discarded(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_connection_started(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::ConnectionStarted,
+        ) {
+            if self.enabled[19] {
+                self.subscriber.on_connection_started(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_connection_closed(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::ConnectionClosed,
+        ) {
+            if self.enabled[20] {
+                self.subscriber.on_connection_closed(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_duplicate_packet(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::DuplicatePacket,
+        ) {
+            if self.enabled[21] {
+                self.subscriber.on_duplicate_packet(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_transport_parameters_received(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::TransportParametersReceived,
+        ) {
+            if self.enabled[22] {
+                self.subscriber
+                    .on_transport_parameters_received(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_datagram_sent(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::DatagramSent,
+        ) {
+            if self.enabled[23] {
+                self.subscriber.on_datagram_sent(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_datagram_received(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::DatagramReceived,
+        ) {
+            if self.enabled[24] {
+                self.subscriber.on_datagram_received(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_datagram_dropped(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::DatagramDropped,
+        ) {
+            if self.enabled[25] {
+                self.subscriber.on_datagram_dropped(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_connection_id_updated(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::ConnectionIdUpdated,
+        ) {
+            if self.enabled[26] {
+                self.subscriber
+                    .on_connection_id_updated(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_ecn_state_changed(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::EcnStateChanged,
+        ) {
+            if self.enabled[27] {
+                self.subscriber.on_ecn_state_changed(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_connection_migration_denied(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::ConnectionMigrationDenied,
+        ) {
+            if self.enabled[28] {
+                self.subscriber
+                    .on_connection_migration_denied(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_handshake_status_updated(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::HandshakeStatusUpdated,
+        ) {
+            if self.enabled[29] {
+                self.subscriber
+                    .on_handshake_status_updated(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_tls_exporter_ready(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::TlsExporterReady,
+        ) {
+            if self.enabled[30] {
+                self.subscriber.on_tls_exporter_ready(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_path_challenge_updated(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::PathChallengeUpdated,
+        ) {
+            if self.enabled[31] {
+                self.subscriber
+                    .on_path_challenge_updated(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_tls_client_hello(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::TlsClientHello,
+        ) {
+            if self.enabled[32] {
+                self.subscriber.on_tls_client_hello(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_tls_server_hello(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::TlsServerHello,
+        ) {
+            if self.enabled[33] {
+                self.subscriber.on_tls_server_hello(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_tls_ech_status(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::TlsEchStatus,
+        ) {
+            if self.enabled[34] {
+                self.subscriber.on_tls_ech_status(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_rx_stream_progress(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::RxStreamProgress,
+        ) {
+            if self.enabled[35] {
+                self.subscriber.on_rx_stream_progress(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_tx_stream_progress(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::TxStreamProgress,
+        ) {
+            if self.enabled[36] {
+                self.subscriber.on_tx_stream_progress(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_keep_alive_timer_expired(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::KeepAliveTimerExpired,
+        ) {
+            if self.enabled[37] {
+                self.subscriber
+                    .on_keep_alive_timer_expired(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_mtu_updated(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::MtuUpdated,
+        ) {
+            if self.enabled[38] {
+                self.subscriber.on_mtu_updated(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_slow_start_exited(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::SlowStartExited,
+        ) {
+            if self.enabled[39] {
+                self.subscriber.on_slow_start_exited(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_delivery_rate_sampled(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::DeliveryRateSampled,
+        ) {
+            if self.enabled[40] {
+                self.subscriber
+                    .on_delivery_rate_sampled(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_pacing_rate_updated(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::PacingRateUpdated,
+        ) {
+            if self.enabled[41] {
+                self.subscriber.on_pacing_rate_updated(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_bbr_state_changed(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::BbrStateChanged,
+        ) {
+            if self.enabled[42] {
+                self.subscriber.on_bbr_state_changed(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_dc_state_changed(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::DcStateChanged,
+        ) {
+            if self.enabled[43] {
+                self.subscriber.on_dc_state_changed(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_version_information(
+            &mut self,
+            meta: &api::EndpointMeta,
+            event: &api::VersionInformation,
+        ) {
+            if self.enabled[44] {
+                self.subscriber.on_version_information(meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_endpoint_packet_sent(
+            &mut self,
+            meta: &api::EndpointMeta,
+            event: &api::EndpointPacketSent,
+        ) {
+            if self.enabled[45] {
+                self.subscriber.on_endpoint_packet_sent(meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_endpoint_packet_received(
+            &mut self,
+            meta: &api::EndpointMeta,
+            event: &api::EndpointPacketReceived,
+        ) {
+            if self.enabled[46] {
+                self.subscriber.on_endpoint_packet_received(meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_endpoint_datagram_sent(
+            &mut self,
+            meta: &api::EndpointMeta,
+            event: &api::EndpointDatagramSent,
+        ) {
+            if self.enabled[47] {
+                self.subscriber.on_endpoint_datagram_sent(meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_endpoint_datagram_received(
+            &mut self,
+            meta: &api::EndpointMeta,
+            event: &api::EndpointDatagramReceived,
+        ) {
+            if self.enabled[48] {
+                self.subscriber.on_endpoint_datagram_received(meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_endpoint_datagram_dropped(
+            &mut self,
+            meta: &api::EndpointMeta,
+            event: &api::EndpointDatagramDropped,
+        ) {
+            if self.enabled[49] {
+                self.subscriber.on_endpoint_datagram_dropped(meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_endpoint_connection_attempt_failed(
+            &mut self,
+            meta: &api::EndpointMeta,
+            event: &api::EndpointConnectionAttemptFailed,
+        ) {
+            if self.enabled[50] {
+                self.subscriber
+                    .on_endpoint_connection_attempt_failed(meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_platform_tx(&mut self, meta: &api::EndpointMeta, event: &api::PlatformTx) {
+            if self.enabled[51] {
+                self.subscriber.on_platform_tx(meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_platform_tx_error(&mut self, meta: &api::EndpointMeta, event: &api::PlatformTxError) {
+            if self.enabled[52] {
+                self.subscriber.on_platform_tx_error(meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_platform_rx(&mut self, meta: &api::EndpointMeta, event: &api::PlatformRx) {
+            if self.enabled[53] {
+                self.subscriber.on_platform_rx(meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_platform_rx_error(&mut self, meta: &api::EndpointMeta, event: &api::PlatformRxError) {
+            if self.enabled[54] {
+                self.subscriber.on_platform_rx_error(meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_platform_feature_configured(
+            &mut self,
+            meta: &api::EndpointMeta,
+            event: &api::PlatformFeatureConfigured,
+        ) {
+            if self.enabled[55] {
+                self.subscriber.on_platform_feature_configured(meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_platform_event_loop_wakeup(
+            &mut self,
+            meta: &api::EndpointMeta,
+            event: &api::PlatformEventLoopWakeup,
+        ) {
+            if self.enabled[56] {
+                self.subscriber.on_platform_event_loop_wakeup(meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_platform_event_loop_sleep(
+            &mut self,
+            meta: &api::EndpointMeta,
+            event: &api::PlatformEventLoopSleep,
+        ) {
+            if self.enabled[57] {
+                self.subscriber.on_platform_event_loop_sleep(meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+    }
+}
+pub mod rate_limit {
+    #![doc = r" Limits the rate at which events are forwarded to a subscriber"]
+    #![doc = r""]
+    #![doc = r" Each event type is limited separately, so frequent events such as"]
+    #![doc = r" `PacketSent` don't crowd out infrequent ones. The limits are shared by all of"]
+    #![doc = r" the connections on the endpoint. Events over the limit are dropped."]
+    use super::*;
+    use crate::query;
+    use core::time::Duration;
+    #[derive(Clone, Debug)]
+    pub struct Subscriber<S: super::Subscriber> {
+        subscriber: S,
+        limit: u32,
+        interval: u64,
+        windows: [Window; 58usize],
+    }
+    impl<S: super::Subscriber> Subscriber<S> {
+        #[doc = r" Forwards at most `limit` events of each type per `interval`"]
+        pub fn new(subscriber: S, limit: u32, interval: Duration) -> Self {
+            Self {
+                subscriber,
+                limit,
+                interval: interval.as_nanos().try_into().unwrap_or(u64::MAX),
+                windows: core::array::from_fn(|_| Window::default()),
+            }
+        }
+        #[inline]
+        fn try_acquire<M: Meta>(&mut self, index: usize, meta: &M) -> bool {
+            let now = meta.timestamp().duration_since_start().as_nanos() as u64;
+            self.windows[index].try_acquire(now, self.interval, self.limit)
+        }
+    }
+    #[derive(Clone, Copy, Debug, Default)]
+    struct Window {
+        start: u64,
+        count: u32,
+    }
+    impl Window {
+        #[inline]
+        fn try_acquire(&mut self, now: u64, interval: u64, limit: u32) -> bool {
+            if now.saturating_sub(self.start) >= interval {
+                self.start = now;
+                self.count = 0;
+            }
+            if self.count >= limit {
+                return false;
+            }
+            self.count += 1;
+            true
+        }
+    }
+    impl<S: super::Subscriber> super::Subscriber for Subscriber<S> {
+        type ConnectionContext = S::ConnectionContext;
+        #[inline]
+        fn create_connection_context(
+            &mut self,
+            meta: &api::ConnectionMeta,
+            info: &api::ConnectionInfo,
+        ) -> Self::ConnectionContext {
+            self.subscriber.create_connection_context(meta, info)
+        }
+        #[inline]
+        fn supervisor_timeout(
+            &mut self,
+            conn_context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            context: &supervisor::Context,
+        ) -> Option<Duration> {
+            self.subscriber
+                .supervisor_timeout(conn_context, meta, context)
+        }
+        #[inline]
+        fn on_supervisor_timeout(
+            &mut self,
+            conn_context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            context: &supervisor::Context,
+        ) -> supervisor::Outcome {
+            self.subscriber
+                .on_supervisor_timeout(conn_context, meta, context)
+        }
+        #[inline]
+        fn query_mut(
+            context: &mut Self::ConnectionContext,
+            query: &mut dyn query::QueryMut,
+        ) -> query::ControlFlow {
+            S::query_mut(context, query)
+        }
+        #[inline]
+        fn query(
+            context: &Self::ConnectionContext,
+            query: &mut dyn query::Query,
+        ) -> query::ControlFlow {
+            S::query(context, query)
+        }
+        #[inline]
+        fn on_application_protocol_information(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::ApplicationProtocolInformation,
+        ) {
+            if self.try_acquire(0, meta) {
+                self.subscriber
+                    .on_application_protocol_information(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_server_name_information(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::ServerNameInformation,
+        ) {
+            if self.try_acquire(1, meta) {
+                self.subscriber
+                    .on_server_name_information(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_packet_skipped(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::PacketSkipped,
+        ) {
+            if self.try_acquire(2, meta) {
+                self.subscriber.on_packet_skipped(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_packet_sent(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::PacketSent,
+        ) {
+            if self.try_acquire(3, meta) {
+                self.subscriber.on_packet_sent(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_packet_received(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::PacketReceived,
+        ) {
+            if self.try_acquire(4, meta) {
+                self.subscriber.on_packet_received(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_active_path_updated(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::ActivePathUpdated,
+        ) {
+            if self.try_acquire(5, meta) {
+                self.subscriber.on_active_path_updated(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_path_created(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::PathCreated,
+        ) {
+            if self.try_acquire(6, meta) {
+                self.subscriber.on_path_created(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_frame_sent(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::FrameSent,
+        ) {
+            if self.try_acquire(7, meta) {
+                self.subscriber.on_frame_sent(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_frame_received(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::FrameReceived,
+        ) {
+            if self.try_acquire(8, meta) {
+                self.subscriber.on_frame_received(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_packet_lost(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::PacketLost,
+        ) {
+            if self.try_acquire(9, meta) {
+                self.subscriber.on_packet_lost(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_recovery_metrics(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::RecoveryMetrics,
+        ) {
+            if self.try_acquire(10, meta) {
+                self.subscriber.on_recovery_metrics(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_congestion(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::Congestion,
+        ) {
+            if self.try_acquire(11, meta) {
+                self.subscriber.on_congestion(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        #[allow(deprecated)]
+        fn on_ack_processed(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::AckProcessed,
+        ) {
+            if self.try_acquire(12, meta) {
+                self.subscriber.on_ack_processed(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_rx_ack_range_dropped(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::RxAckRangeDropped,
+        ) {
+            if self.try_acquire(13, meta) {
+                self.subscriber
+                    .on_rx_ack_range_dropped(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_ack_range_received(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::AckRangeReceived,
+        ) {
+            if self.try_acquire(14, meta) {
+                self.subscriber.on_ack_range_received(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_ack_range_sent(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::AckRangeSent,
+        ) {
+            if self.try_acquire(15, meta) {
+                self.subscriber.on_ack_range_sent(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_packet_dropped(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::PacketDropped,
+        ) {
+            if self.try_acquire(16, meta) {
+                self.subscriber.on_packet_dropped(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_key_update(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::KeyUpdate,
+        ) {
+            if self.try_acquire(17, meta) {
+                self.subscriber.on_key_update(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_key_space_discarded(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::KeySpaceDiscarded,
+        ) {
+            if self.try_acquire(18, meta) {
+                self.subscriber.on_key_space_discarded(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_connection_started(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::ConnectionStarted,
+        ) {
+            if self.try_acquire(19, meta) {
+                self.subscriber.on_connection_started(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_connection_closed(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::ConnectionClosed,
+        ) {
+            if self.try_acquire(20, meta) {
+                self.subscriber.on_connection_closed(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_duplicate_packet(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::DuplicatePacket,
+        ) {
+            if self.try_acquire(21, meta) {
+                self.subscriber.on_duplicate_packet(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_transport_parameters_received(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::TransportParametersReceived,
+        ) {
+            if self.try_acquire(22, meta) {
+                self.subscriber
+                    .on_transport_parameters_received(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_datagram_sent(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::DatagramSent,
+        ) {
+            if self.try_acquire(23, meta) {
+                self.subscriber.on_datagram_sent(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_datagram_received(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::DatagramReceived,
+        ) {
+            if self.try_acquire(24, meta) {
+                self.subscriber.on_datagram_received(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_datagram_dropped(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::DatagramDropped,
+        ) {
+            if self.try_acquire(25, meta) {
+                self.subscriber.on_datagram_dropped(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_connection_id_updated(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::ConnectionIdUpdated,
+        ) {
+            if self.try_acquire(26, meta) {
+                self.subscriber
+                    .on_connection_id_updated(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_ecn_state_changed(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::EcnStateChanged,
+        ) {
+            if self.try_acquire(27, meta) {
+                self.subscriber.on_ecn_state_changed(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_connection_migration_denied(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::ConnectionMigrationDenied,
+        ) {
+            if self.try_acquire(28, meta) {
+                self.subscriber
+                    .on_connection_migration_denied(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_handshake_status_updated(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::HandshakeStatusUpdated,
+        ) {
+            if self.try_acquire(29, meta) {
+                self.subscriber
+                    .on_handshake_status_updated(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_tls_exporter_ready(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::TlsExporterReady,
+        ) {
+            if self.try_acquire(30, meta) {
+                self.subscriber.on_tls_exporter_ready(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_path_challenge_updated(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::PathChallengeUpdated,
+        ) {
+            if self.try_acquire(31, meta) {
+                self.subscriber
+                    .on_path_challenge_updated(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_tls_client_hello(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::TlsClientHello,
+        ) {
+            if self.try_acquire(32, meta) {
+                self.subscriber.on_tls_client_hello(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_tls_server_hello(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::TlsServerHello,
+        ) {
+            if self.try_acquire(33, meta) {
+                self.subscriber.on_tls_server_hello(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_tls_ech_status(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::TlsEchStatus,
+        ) {
+            if self.try_acquire(34, meta) {
+                self.subscriber.on_tls_ech_status(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_rx_stream_progress(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::RxStreamProgress,
+        ) {
+            if self.try_acquire(35, meta) {
+                self.subscriber.on_rx_stream_progress(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_tx_stream_progress(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::TxStreamProgress,
+        ) {
+            if self.try_acquire(36, meta) {
+                self.subscriber.on_tx_stream_progress(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_keep_alive_timer_expired(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::KeepAliveTimerExpired,
+        ) {
+            if self.try_acquire(37, meta) {
+                self.subscriber
+                    .on_keep_alive_timer_expired(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_mtu_updated(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::MtuUpdated,
+        ) {
+            if self.try_acquire(38, meta) {
+                self.subscriber.on_mtu_updated(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_slow_start_exited(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::SlowStartExited,
+        ) {
+            if self.try_acquire(39, meta) {
+                self.subscriber.on_slow_start_exited(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_delivery_rate_sampled(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::DeliveryRateSampled,
+        ) {
+            if self.try_acquire(40, meta) {
+                self.subscriber
+                    .on_delivery_rate_sampled(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_pacing_rate_updated(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::PacingRateUpdated,
+        ) {
+            if self.try_acquire(41, meta) {
+                self.subscriber.on_pacing_rate_updated(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_bbr_state_changed(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::BbrStateChanged,
+        ) {
+            if self.try_acquire(42, meta) {
+                self.subscriber.on_bbr_state_changed(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_dc_state_changed(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::DcStateChanged,
+        ) {
+            if self.try_acquire(43, meta) {
+                self.subscriber.on_dc_state_changed(context, meta, event);
+                self.subscriber.on_connection_event(context, meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_version_information(
+            &mut self,
+            meta: &api::EndpointMeta,
+            event: &api::VersionInformation,
+        ) {
+            if self.try_acquire(44, meta) {
+                self.subscriber.on_version_information(meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_endpoint_packet_sent(
+            &mut self,
+            meta: &api::EndpointMeta,
+            event: &api::EndpointPacketSent,
+        ) {
+            if self.try_acquire(45, meta) {
+                self.subscriber.on_endpoint_packet_sent(meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_endpoint_packet_received(
+            &mut self,
+            meta: &api::EndpointMeta,
+            event: &api::EndpointPacketReceived,
+        ) {
+            if self.try_acquire(46, meta) {
+                self.subscriber.on_endpoint_packet_received(meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_endpoint_datagram_sent(
+            &mut self,
+            meta: &api::EndpointMeta,
+            event: &api::EndpointDatagramSent,
+        ) {
+            if self.try_acquire(47, meta) {
+                self.subscriber.on_endpoint_datagram_sent(meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_endpoint_datagram_received(
+            &mut self,
+            meta: &api::EndpointMeta,
+            event: &api::EndpointDatagramReceived,
+        ) {
+            if self.try_acquire(48, meta) {
+                self.subscriber.on_endpoint_datagram_received(meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_endpoint_datagram_dropped(
+            &mut self,
+            meta: &api::EndpointMeta,
+            event: &api::EndpointDatagramDropped,
+        ) {
+            if self.try_acquire(49, meta) {
+                self.subscriber.on_endpoint_datagram_dropped(meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_endpoint_connection_attempt_failed(
+            &mut self,
+            meta: &api::EndpointMeta,
+            event: &api::EndpointConnectionAttemptFailed,
+        ) {
+            if self.try_acquire(50, meta) {
+                self.subscriber
+                    .on_endpoint_connection_attempt_failed(meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_platform_tx(&mut self, meta: &api::EndpointMeta, event: &api::PlatformTx) {
+            if self.try_acquire(51, meta) {
+                self.subscriber.on_platform_tx(meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_platform_tx_error(&mut self, meta: &api::EndpointMeta, event: &api::PlatformTxError) {
+            if self.try_acquire(52, meta) {
+                self.subscriber.on_platform_tx_error(meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_platform_rx(&mut self, meta: &api::EndpointMeta, event: &api::PlatformRx) {
+            if self.try_acquire(53, meta) {
+                self.subscriber.on_platform_rx(meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_platform_rx_error(&mut self, meta: &api::EndpointMeta, event: &api::PlatformRxError) {
+            if self.try_acquire(54, meta) {
+                self.subscriber.on_platform_rx_error(meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_platform_feature_configured(
+            &mut self,
+            meta: &api::EndpointMeta,
+            event: &api::PlatformFeatureConfigured,
+        ) {
+            if self.try_acquire(55, meta) {
+                self.subscriber.on_platform_feature_configured(meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_platform_event_loop_wakeup(
+            &mut self,
+            meta: &api::EndpointMeta,
+            event: &api::PlatformEventLoopWakeup,
+        ) {
+            if self.try_acquire(56, meta) {
+                self.subscriber.on_platform_event_loop_wakeup(meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_platform_event_loop_sleep(
+            &mut self,
+            meta: &api::EndpointMeta,
+            event: &api::PlatformEventLoopSleep,
+        ) {
+            if self.try_acquire(57, meta) {
+                self.subscriber.on_platform_event_loop_sleep(meta, event);
+                self.subscriber.on_event(meta, event);
+            }
+        }
+    }
+}
+#[cfg(any(test, feature = "testing"))]
+pub mod testing {
+    use super::*;
+    #[derive(Clone, Debug)]
+    pub struct Subscriber {
+        location: Option<Location>,
+        output: Vec<String>,
+        pub application_protocol_information: u32,
+        pub server_name_information: u32,
+        pub packet_skipped: u32,
+        pub packet_sent: u32,
+        pub packet_received: u32,
+        pub active_path_updated: u32,
+        pub path_created: u32,
+        pub frame_sent: u32,
+        pub frame_received: u32,
+        pub packet_lost: u32,
+        pub recovery_metrics: u32,
+        pub congestion: u32,
+        pub ack_processed: u32,
+        pub rx_ack_range_dropped: u32,
+        pub ack_range_received: u32,
+        pub ack_range_sent: u32,
+        pub packet_dropped: u32,
+        pub key_update: u32,
+        pub key_space_discarded: u32,
+        pub connection_started: u32,
+        pub connection_closed: u32,
+        pub duplicate_packet: u32,
+        pub transport_parameters_received: u32,
+        pub datagram_sent: u32,
+        pub datagram_received: u32,
+        pub datagram_dropped: u32,
+        pub connection_id_updated: u32,
+        pub ecn_state_changed: u32,
+        pub connection_migration_denied: u32,
+        pub handshake_status_updated: u32,
+        pub tls_exporter_ready: u32,
+        pub path_challenge_updated: u32,
+        pub tls_client_hello: u32,
+        pub tls_server_hello: u32,
+        pub tls_ech_status: u32,
+        pub rx_stream_progress: u32,
+        pub tx_stream_progress: u32,
+        pub keep_alive_timer_expired: u32,
+        pub mtu_updated: u32,
+        pub slow_start_exited: u32,
+        pub delivery_rate_sampled: u32,
+        pub pacing_rate_updated: u32,
+        pub bbr_state_changed: u32,
+        pub dc_state_changed: u32,
+        pub version_information: u32,
+        pub endpoint_packet_sent: u32,
+        pub endpoint_packet_received: u32,
+        pub endpoint_datagram_sent: u32,
+        pub endpoint_datagram_received: u32,
+        pub endpoint_datagram_dropped: u32,
+        pub endpoint_connection_attempt_failed: u32,
+        pub platform_tx: u32,
+        pub platform_tx_error: u32,
+        pub platform_rx: u32,
+        pub platform_rx_error: u32,
+        pub platform_feature_configured: u32,
+        pub platform_event_loop_wakeup: u32,
+        pub platform_event_loop_sleep: u32,
+    }
+    impl Drop for Subscriber {
+        fn drop(&mut self) {
+            if std::thread::panicking() {
+                return;
+            }
+            if let Some(location) = self.location.as_ref() {
+                location.snapshot(&self.output);
+            }
+        }
+    }
+    impl Subscriber {
+        #[doc = r" Creates a subscriber with snapshot assertions enabled"]
+        #[track_caller]
+        pub fn snapshot() -> Self {
+            let mut sub = Self::no_snapshot();
+            sub.location = Location::try_new();
+            sub
+        }
+        #[doc = r" Creates a subscriber with snapshot assertions disabled"]
+        pub fn no_snapshot() -> Self {
+            Self {
+                location: None,
+                output: Default::default(),
+                application_protocol_information: 0,
+                server_name_information: 0,
+                packet_skipped: 0,
+                packet_sent: 0,
+                packet_received: 0,
+                active_path_updated: 0,
+                path_created: 0,
+                frame_sent: 0,
+                frame_received: 0,
+                packet_lost: 0,
+                recovery_metrics: 0,
+                congestion: 0,
+                ack_processed: 0,
+                rx_ack_range_dropped: 0,
+                ack_range_received: 0,
+                ack_range_sent: 0,
+                packet_dropped: 0,
+                key_update: 0,
+                key_space_discarded: 0,
+                connection_started: 0,
+                connection_closed: 0,
+                duplicate_packet: 0,
+                transport_parameters_received: 0,
+                datagram_sent: 0,
+                datagram_received: 0,
+                datagram_dropped: 0,
+                connection_id_updated: 0,
+                ecn_state_changed: 0,
+                connection_migration_denied: 0,
+                handshake_status_updated: 0,
+                tls_exporter_ready: 0,
+                path_challenge_updated: 0,
+                tls_client_hello: 0,
+                tls_server_hello: 0,
+                tls_ech_status: 0,
+                rx_stream_progress: 0,
+                tx_stream_progress: 0,
+                keep_alive_timer_expired: 0,
+                mtu_updated: 0,
+                slow_start_exited: 0,
+                delivery_rate_sampled: 0,
+                pacing_rate_updated: 0,
+                bbr_state_changed: 0,
+                dc_state_changed: 0,
+                version_information: 0,
+                endpoint_packet_sent: 0,
+                endpoint_packet_received: 0,
+                endpoint_datagram_sent: 0,
+                endpoint_datagram_received: 0,
+                endpoint_datagram_dropped: 0,
+                endpoint_connection_attempt_failed: 0,
+                platform_tx: 0,
+                platform_tx_error: 0,
+                platform_rx: 0,
+                platform_rx_error: 0,
+                platform_feature_configured: 0,
+                platform_event_loop_wakeup: 0,
+                platform_event_loop_sleep: 0,
+            }
+        }
+    }
+    impl super::Subscriber for Subscriber {
+        type ConnectionContext = ();
+        fn create_connection_context(
+            &mut self,
+            _meta: &api::ConnectionMeta,
+            _info: &api::ConnectionInfo,
+        ) -> Self::ConnectionContext {
+        }
+        fn on_application_protocol_information(
+            &mut self,
+            _context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::ApplicationProtocolInformation,
+        ) {
+            self.application_protocol_information += 1;
+            if self.location.is_some() {
+                self.output.push(format!("{meta:?} {event:?}"));
+            }
+        }
+        fn on_server_name_information(
+            &mut self,
+            _context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::ServerNameInformation,
+        ) {
+            self.server_name_information += 1;
+            if self.location.is_some() {
+                self.output.push(format!("{meta:?} {event:?}"));
+            }
+        }
+        fn on_packet_skipped(
             &mut self,
             _context: &mut Self::ConnectionContext,
             meta: &api::ConnectionMeta,
@@ -8062,6 +10776,17 @@ pub mod testing {
                 self.output.push(format!("{meta:?} {event:?}"));
             }
         }
+        fn on_tls_ech_status(
+            &mut self,
+            _context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::TlsEchStatus,
+        ) {
+            self.tls_ech_status += 1;
+            if self.location.is_some() {
+                self.output.push(format!("{meta:?} {event:?}"));
+            }
+        }
         fn on_rx_stream_progress(
             &mut self,
             _context: &mut Self::ConnectionContext,
@@ -8296,6 +11021,7 @@ pub mod testing {
         pub path_challenge_updated: u32,
         pub tls_client_hello: u32,
         pub tls_server_hello: u32,
+        pub tls_ech_status: u32,
         pub rx_stream_progress: u32,
         pub tx_stream_progress: u32,
         pub keep_alive_timer_expired: u32,
@@ -8367,6 +11093,7 @@ pub mod testing {
                 path_challenge_updated: 0,
                 tls_client_hello: 0,
                 tls_server_hello: 0,
+                tls_ech_status: 0,
                 rx_stream_progress: 0,
                 tx_stream_progress: 0,
                 keep_alive_timer_expired: 0,
@@ -8717,6 +11444,13 @@ pub mod testing {
                 self.output.push(format!("{event:?}"));
             }
         }
+        fn on_tls_ech_status(&mut self, event: builder::TlsEchStatus) {
+            self.tls_ech_status += 1;
+            let event = event.into_event();
+            if self.location.is_some() {
+                self.output.push(format!("{event:?}"));
+            }
+        }
         fn on_rx_stream_progress(&mut self, event: builder::RxStreamProgress) {
             self.rx_stream_progress += 1;
             let event = event.into_event();
@@ -0,0 +1,1046 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A compact binary log of connection events
+//!
+//! The log starts with a header containing [`MAGIC`] and the format [`VERSION`], followed by a
+//! sequence of records. Each record is prefixed with a tag and its length, which allows readers to
+//! skip records added in later versions of the format.
+//!
+//! The [`Subscriber`] writes the log for an endpoint and the [`Reader`] decodes it.
+
+use crate::{endpoint, event::api};
+use core::{fmt, time::Duration};
+use s2n_codec::{DecoderBuffer, DecoderError, Encoder, EncoderBuffer, EncoderValue};
+use std::{io, time::SystemTime};
+
+/// The bytes at the start of every log
+pub const MAGIC: [u8; 8] = *b"s2nqlog\0";
+
+/// The version of the log format written by this library
+///
+/// The version is incremented when the encoding of an existing record changes. Adding a new
+/// record type does not require a new version.
+///
+/// Version 2 added the wall-clock start time to [`Data::ConnectionStarted`].
+pub const VERSION: u16 = 2;
+
+/// Reasons longer than this are truncated to keep records small
+const MAX_REASON_LEN: usize = 1024;
+
+/// A single entry in the log
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Record {
+    /// The connection which emitted the event
+    pub connection_id: u64,
+    /// The time the event occurred, relative to the start of the process
+    pub timestamp: Duration,
+    pub data: Data,
+}
+
+/// The event-specific contents of a [`Record`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Data {
+    ConnectionStarted {
+        endpoint_type: endpoint::Type,
+        /// The wall-clock time the connection started, relative to the Unix epoch
+        ///
+        /// This anchors the process-relative record timestamps. It is `None` for logs written
+        /// with version 1 of the format.
+        unix_time: Option<Duration>,
+    },
+    HandshakeStatusUpdated {
+        status: HandshakeStatus,
+    },
+    RecoveryMetrics {
+        path_id: u64,
+        min_rtt: Duration,
+        smoothed_rtt: Duration,
+        latest_rtt: Duration,
+        rtt_variance: Duration,
+        pto_count: u32,
+        congestion_window: u32,
+        bytes_in_flight: u32,
+    },
+    PacketLost {
+        path_id: u64,
+        space: PacketSpace,
+        packet_number: u64,
+        bytes_lost: u16,
+    },
+    ConnectionClosed {
+        /// The formatted [`connection::Error`](crate::connection::Error)
+        reason: String,
+    },
+    PacketSent {
+        space: PacketSpace,
+        packet_number: u64,
+        packet_len: u32,
+    },
+    PacketReceived {
+        space: PacketSpace,
+        packet_number: u64,
+    },
+    PacketDropped {
+        /// The name of the drop reason
+        reason: String,
+    },
+    MtuUpdated {
+        path_id: u64,
+        mtu: u16,
+        /// The name of the update cause
+        cause: String,
+        search_complete: bool,
+    },
+    KeySpaceDiscarded {
+        space: KeySpace,
+    },
+    ActivePathUpdated {
+        path_id: u64,
+    },
+}
+
+/// Progress of the connection handshake
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HandshakeStatus {
+    Complete,
+    Confirmed,
+    HandshakeDoneAcked,
+    HandshakeDoneLost,
+}
+
+/// The packet number space of a packet
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PacketSpace {
+    Initial,
+    Handshake,
+    ApplicationData,
+}
+
+/// The keys which were discarded
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KeySpace {
+    Initial,
+    Handshake,
+    ZeroRtt,
+    OneRtt,
+}
+
+macro_rules! codes {
+    ($ty:ident { $($variant:ident = $code:literal),* $(,)? }) => {
+        impl $ty {
+            fn code(self) -> u8 {
+                match self {
+                    $(Self::$variant => $code,)*
+                }
+            }
+
+            fn from_code(code: u8) -> Result<Self, DecoderError> {
+                match code {
+                    $($code => Ok(Self::$variant),)*
+                    _ => Err(DecoderError::InvariantViolation(concat!("invalid ", stringify!($ty)))),
+                }
+            }
+        }
+    };
+}
+
+codes!(HandshakeStatus {
+    Complete = 0,
+    Confirmed = 1,
+    HandshakeDoneAcked = 2,
+    HandshakeDoneLost = 3,
+});
+
+codes!(PacketSpace {
+    Initial = 0,
+    Handshake = 1,
+    ApplicationData = 2,
+});
+
+codes!(KeySpace {
+    Initial = 0,
+    Handshake = 1,
+    ZeroRtt = 2,
+    OneRtt = 3,
+});
+
+mod tag {
+    pub const CONNECTION_STARTED: u8 = 0;
+    pub const HANDSHAKE_STATUS_UPDATED: u8 = 1;
+    pub const RECOVERY_METRICS: u8 = 2;
+    pub const PACKET_LOST: u8 = 3;
+    pub const CONNECTION_CLOSED: u8 = 4;
+    pub const PACKET_SENT: u8 = 5;
+    pub const PACKET_RECEIVED: u8 = 6;
+    pub const PACKET_DROPPED: u8 = 7;
+    pub const MTU_UPDATED: u8 = 8;
+    pub const KEY_SPACE_DISCARDED: u8 = 9;
+    pub const ACTIVE_PATH_UPDATED: u8 = 10;
+
+    /// The last tag known to this version of the library
+    pub const LAST: u8 = ACTIVE_PATH_UPDATED;
+}
+
+#[inline]
+fn nanos(duration: Duration) -> u64 {
+    duration.as_nanos().try_into().unwrap_or(u64::MAX)
+}
+
+/// Returns the name of an event enum variant, without its fields
+fn variant_name<T: fmt::Debug>(value: &T) -> String {
+    let value = format!("{value:?}");
+    let len = value
+        .find(|c: char| !c.is_alphanumeric() && c != '_')
+        .unwrap_or(value.len());
+    value[..len].to_string()
+}
+
+/// Encodes a string with a length prefix, truncated to [`MAX_REASON_LEN`]
+fn encode_str<E: Encoder>(encoder: &mut E, value: &str) {
+    let mut len = value.len().min(MAX_REASON_LEN);
+    while !value.is_char_boundary(len) {
+        len -= 1;
+    }
+    encoder.encode_with_len_prefix::<u16, _>(&&value.as_bytes()[..len]);
+}
+
+fn decode_str(buffer: DecoderBuffer) -> Result<(String, DecoderBuffer), DecoderError> {
+    let (value, buffer) = buffer.decode_slice_with_len_prefix::<u16>()?;
+    let value = String::from_utf8_lossy(value.into_less_safe_slice()).into_owned();
+    Ok((value, buffer))
+}
+
+/// Returns the packet number space and packet number for packets which have one
+fn packet_number(header: &api::PacketHeader) -> Option<(PacketSpace, u64)> {
+    Some(match *header {
+        api::PacketHeader::Initial { number, .. } => (PacketSpace::Initial, number),
+        api::PacketHeader::Handshake { number, .. } => (PacketSpace::Handshake, number),
+        api::PacketHeader::ZeroRtt { number, .. } | api::PacketHeader::OneRtt { number } => {
+            (PacketSpace::ApplicationData, number)
+        }
+        _ => return None,
+    })
+}
+
+impl Data {
+    fn tag(&self) -> u8 {
+        match self {
+            Self::ConnectionStarted { .. } => tag::CONNECTION_STARTED,
+            Self::HandshakeStatusUpdated { .. } => tag::HANDSHAKE_STATUS_UPDATED,
+            Self::RecoveryMetrics { .. } => tag::RECOVERY_METRICS,
+            Self::PacketLost { .. } => tag::PACKET_LOST,
+            Self::ConnectionClosed { .. } => tag::CONNECTION_CLOSED,
+            Self::PacketSent { .. } => tag::PACKET_SENT,
+            Self::PacketReceived { .. } => tag::PACKET_RECEIVED,
+            Self::PacketDropped { .. } => tag::PACKET_DROPPED,
+            Self::MtuUpdated { .. } => tag::MTU_UPDATED,
+            Self::KeySpaceDiscarded { .. } => tag::KEY_SPACE_DISCARDED,
+            Self::ActivePathUpdated { .. } => tag::ACTIVE_PATH_UPDATED,
+        }
+    }
+}
+
+impl Record {
+    /// Decodes the body of a record with the given `tag`
+    ///
+    /// Returns `None` if the tag is unknown to this version of the library.
+    pub fn decode_body(tag: u8, buffer: DecoderBuffer) -> Result<Option<Self>, DecoderError> {
+        // records added in later versions may not share the same layout
+        if !(tag::CONNECTION_STARTED..=tag::LAST).contains(&tag) {
+            return Ok(None);
+        }
+
+        let (connection_id, buffer) = buffer.decode::<u64>()?;
+        let (timestamp, buffer) = buffer.decode::<u64>()?;
+        let timestamp = Duration::from_nanos(timestamp);
+
+        macro_rules! decode {
+            ($buffer:ident, $($name:ident: $ty:ty),*) => {
+                $(
+                    let ($name, $buffer) = $buffer.decode::<$ty>()?;
+                )*
+            };
+        }
+
+        macro_rules! duration {
+            ($buffer:ident, $($name:ident),*) => {
+                $(
+                    let ($name, $buffer) = $buffer.decode::<u64>()?;
+                    let $name = Duration::from_nanos($name);
+                )*
+            };
+        }
+
+        let data = match tag {
+            tag::CONNECTION_STARTED => {
+                decode!(buffer, endpoint_type: u8);
+                let endpoint_type = match endpoint_type {
+                    0 => endpoint::Type::Client,
+                    1 => endpoint::Type::Server,
+                    _ => return Err(DecoderError::InvariantViolation("invalid endpoint type")),
+                };
+                // version 1 logs don't include the start time
+                let unix_time = if buffer.is_empty() {
+                    None
+                } else {
+                    duration!(buffer, unix_time);
+                    buffer.ensure_empty()?;
+                    Some(unix_time)
+                };
+                Data::ConnectionStarted {
+                    endpoint_type,
+                    unix_time,
+                }
+            }
+            tag::HANDSHAKE_STATUS_UPDATED => {
+                decode!(buffer, status: u8);
+                buffer.ensure_empty()?;
+                Data::HandshakeStatusUpdated {
+                    status: HandshakeStatus::from_code(status)?,
+                }
+            }
+            tag::RECOVERY_METRICS => {
+                decode!(buffer, path_id: u64);
+                duration!(buffer, min_rtt, smoothed_rtt, latest_rtt, rtt_variance);
+                decode!(buffer, pto_count: u32, congestion_window: u32, bytes_in_flight: u32);
+                buffer.ensure_empty()?;
+                Data::RecoveryMetrics {
+                    path_id,
+                    min_rtt,
+                    smoothed_rtt,
+                    latest_rtt,
+                    rtt_variance,
+                    pto_count,
+                    congestion_window,
+                    bytes_in_flight,
+                }
+            }
+            tag::PACKET_LOST => {
+                decode!(buffer, path_id: u64, space: u8, packet_number: u64, bytes_lost: u16);
+                buffer.ensure_empty()?;
+                Data::PacketLost {
+                    path_id,
+                    space: PacketSpace::from_code(space)?,
+                    packet_number,
+                    bytes_lost,
+                }
+            }
+            tag::CONNECTION_CLOSED => {
+                let (reason, buffer) = decode_str(buffer)?;
+                buffer.ensure_empty()?;
+                Data::ConnectionClosed { reason }
+            }
+            tag::PACKET_SENT => {
+                decode!(buffer, space: u8, packet_number: u64, packet_len: u32);
+                buffer.ensure_empty()?;
+                Data::PacketSent {
+                    space: PacketSpace::from_code(space)?,
+                    packet_number,
+                    packet_len,
+                }
+            }
+            tag::PACKET_RECEIVED => {
+                decode!(buffer, space: u8, packet_number: u64);
+                buffer.ensure_empty()?;
+                Data::PacketReceived {
+                    space: PacketSpace::from_code(space)?,
+                    packet_number,
+                }
+            }
+            tag::PACKET_DROPPED => {
+                let (reason, buffer) = decode_str(buffer)?;
+                buffer.ensure_empty()?;
+                Data::PacketDropped { reason }
+            }
+            tag::MTU_UPDATED => {
+                decode!(buffer, path_id: u64, mtu: u16);
+                let (cause, buffer) = decode_str(buffer)?;
+                decode!(buffer, search_complete: u8);
+                buffer.ensure_empty()?;
+                Data::MtuUpdated {
+                    path_id,
+                    mtu,
+                    cause,
+                    search_complete: search_complete != 0,
+                }
+            }
+            tag::KEY_SPACE_DISCARDED => {
+                decode!(buffer, space: u8);
+                buffer.ensure_empty()?;
+                Data::KeySpaceDiscarded {
+                    space: KeySpace::from_code(space)?,
+                }
+            }
+            tag::ACTIVE_PATH_UPDATED => {
+                decode!(buffer, path_id: u64);
+                buffer.ensure_empty()?;
+                Data::ActivePathUpdated { path_id }
+            }
+            _ => return Ok(None),
+        };
+
+        Ok(Some(Self {
+            connection_id,
+            timestamp,
+            data,
+        }))
+    }
+}
+
+/// Encodes the record body, without the tag and length prefix
+struct Body<'a>(&'a Record);
+
+impl EncoderValue for Body<'_> {
+    fn encode<E: Encoder>(&self, encoder: &mut E) {
+        let record = self.0;
+        encoder.encode(&record.connection_id);
+        encoder.encode(&nanos(record.timestamp));
+
+        match &record.data {
+            Data::ConnectionStarted {
+                endpoint_type,
+                unix_time,
+            } => {
+                let endpoint_type: u8 = match endpoint_type {
+                    endpoint::Type::Client => 0,
+                    endpoint::Type::Server => 1,
+                };
+                encoder.encode(&endpoint_type);
+                if let Some(unix_time) = unix_time {
+                    encoder.encode(&nanos(*unix_time));
+                }
+            }
+            Data::HandshakeStatusUpdated { status } => {
+                encoder.encode(&status.code());
+            }
+            Data::RecoveryMetrics {
+                path_id,
+                min_rtt,
+                smoothed_rtt,
+                latest_rtt,
+                rtt_variance,
+                pto_count,
+                congestion_window,
+                bytes_in_flight,
+            } => {
+                encoder.encode(path_id);
+                for rtt in [min_rtt, smoothed_rtt, latest_rtt, rtt_variance] {
+                    encoder.encode(&nanos(*rtt));
+                }
+                encoder.encode(pto_count);
+                encoder.encode(congestion_window);
+                encoder.encode(bytes_in_flight);
+            }
+            Data::PacketLost {
+                path_id,
+                space,
+                packet_number,
+                bytes_lost,
+            } => {
+                encoder.encode(path_id);
+                encoder.encode(&space.code());
+                encoder.encode(packet_number);
+                encoder.encode(bytes_lost);
+            }
+            Data::ConnectionClosed { reason } => {
+                encode_str(encoder, reason);
+            }
+            Data::PacketSent {
+                space,
+                packet_number,
+                packet_len,
+            } => {
+                encoder.encode(&space.code());
+                encoder.encode(packet_number);
+                encoder.encode(packet_len);
+            }
+            Data::PacketReceived {
+                space,
+                packet_number,
+            } => {
+                encoder.encode(&space.code());
+                encoder.encode(packet_number);
+            }
+            Data::PacketDropped { reason } => {
+                encode_str(encoder, reason);
+            }
+            Data::MtuUpdated {
+                path_id,
+                mtu,
+                cause,
+                search_complete,
+            } => {
+                encoder.encode(path_id);
+                encoder.encode(mtu);
+                encode_str(encoder, cause);
+                encoder.encode(&(*search_complete as u8));
+            }
+            Data::KeySpaceDiscarded { space } => {
+                encoder.encode(&space.code());
+            }
+            Data::ActivePathUpdated { path_id } => {
+                encoder.encode(path_id);
+            }
+        }
+    }
+}
+
+impl EncoderValue for Record {
+    fn encode<E: Encoder>(&self, encoder: &mut E) {
+        encoder.encode(&self.data.tag());
+        encoder.encode_with_len_prefix::<u16, _>(&Body(self));
+    }
+}
+
+/// Writes the log header and records to an [`io::Write`]
+#[derive(Debug)]
+pub struct Writer<W: io::Write> {
+    inner: W,
+    buffer: Vec<u8>,
+}
+
+impl<W: io::Write> Writer<W> {
+    /// Writes the log header to `inner`
+    pub fn new(mut inner: W) -> io::Result<Self> {
+        inner.write_all(&MAGIC)?;
+        inner.write_all(&VERSION.to_be_bytes())?;
+        Ok(Self {
+            inner,
+            buffer: Vec::new(),
+        })
+    }
+
+    pub fn write(&mut self, record: &Record) -> io::Result<()> {
+        self.buffer.clear();
+        self.buffer.resize(record.encoding_size(), 0);
+        let mut encoder = EncoderBuffer::new(&mut self.buffer);
+        encoder.encode(record);
+        self.inner.write_all(&self.buffer)
+    }
+
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+/// Reads records from a log written by a [`Writer`]
+#[derive(Debug)]
+pub struct Reader<R: io::Read> {
+    inner: R,
+    version: u16,
+    buffer: Vec<u8>,
+}
+
+impl<R: io::Read> Reader<R> {
+    /// Reads and validates the log header from `inner`
+    pub fn new(mut inner: R) -> io::Result<Self> {
+        let mut header = [0u8; MAGIC.len() + 2];
+        inner.read_exact(&mut header)?;
+
+        if header[..MAGIC.len()] != MAGIC {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "not an s2n-quic event log",
+            ));
+        }
+
+        let version = u16::from_be_bytes([header[MAGIC.len()], header[MAGIC.len() + 1]]);
+        if version > VERSION {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("unsupported event log version {version}"),
+            ));
+        }
+
+        Ok(Self {
+            inner,
+            version,
+            buffer: Vec::new(),
+        })
+    }
+
+    /// The version of the format the log was written with
+    pub fn version(&self) -> u16 {
+        self.version
+    }
+
+    /// Reads the next record
+    ///
+    /// If the log ends partway through a record, for example because the process writing it
+    /// was killed, an error of kind [`io::ErrorKind::UnexpectedEof`] is returned. All of the
+    /// records before it are complete.
+    fn read_record(&mut self) -> io::Result<Option<Record>> {
+        let truncated = |err: io::Error| {
+            if err.kind() == io::ErrorKind::UnexpectedEof {
+                io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    "the log ends with a truncated record",
+                )
+            } else {
+                err
+            }
+        };
+
+        loop {
+            let mut prefix = [0u8; 3];
+
+            // a clean end of the log
+            if self.inner.read(&mut prefix[..1])? == 0 {
+                return Ok(None);
+            }
+            self.inner.read_exact(&mut prefix[1..]).map_err(truncated)?;
+
+            let tag = prefix[0];
+            let len = u16::from_be_bytes([prefix[1], prefix[2]]) as usize;
+
+            self.buffer.resize(len, 0);
+            self.inner.read_exact(&mut self.buffer).map_err(truncated)?;
+
+            let buffer = DecoderBuffer::new(&self.buffer);
+            let record = Record::decode_body(tag, buffer)
+                .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err.to_string()))?;
+
+            // skip records from newer versions of the format
+            if let Some(record) = record {
+                return Ok(Some(record));
+            }
+        }
+    }
+}
+
+impl<R: io::Read> Iterator for Reader<R> {
+    type Item = io::Result<Record>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_record().transpose()
+    }
+}
+
+/// Records connection events to a binary log
+///
+/// Each record is written to `W` as the event is emitted, so it's recommended to wrap files and
+/// sockets in an [`io::BufWriter`]. The writer is flushed when a connection closes. If writing
+/// fails, recording stops for the remaining lifetime of the endpoint.
+#[derive(Debug)]
+pub struct Subscriber<W: io::Write> {
+    writer: Option<Writer<W>>,
+}
+
+impl<W: io::Write> Subscriber<W> {
+    /// Writes the log header to `inner` and records all subsequent events to it
+    pub fn new(inner: W) -> io::Result<Self> {
+        let writer = Some(Writer::new(inner)?);
+        Ok(Self { writer })
+    }
+
+    fn record(&mut self, meta: &api::ConnectionMeta, data: Data) {
+        let Some(writer) = self.writer.as_mut() else {
+            return;
+        };
+
+        let record = Record {
+            connection_id: meta.id,
+            timestamp: meta.timestamp.duration_since_start(),
+            data,
+        };
+
+        if writer.write(&record).is_err() {
+            self.writer = None;
+        }
+    }
+}
+
+impl<W: io::Write> Drop for Subscriber<W> {
+    fn drop(&mut self) {
+        if let Some(writer) = self.writer.as_mut() {
+            let _ = writer.flush();
+        }
+    }
+}
+
+impl<W: 'static + Send + io::Write> super::Subscriber for Subscriber<W> {
+    type ConnectionContext = ();
+
+    fn create_connection_context(
+        &mut self,
+        meta: &api::ConnectionMeta,
+        _info: &api::ConnectionInfo,
+    ) -> Self::ConnectionContext {
+        let endpoint_type = match meta.endpoint_type {
+            api::EndpointType::Client {} => endpoint::Type::Client,
+            api::EndpointType::Server {} => endpoint::Type::Server,
+        };
+        // sampled alongside the event timestamp to anchor it to the wall clock
+        let unix_time = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .ok();
+        self.record(
+            meta,
+            Data::ConnectionStarted {
+                endpoint_type,
+                unix_time,
+            },
+        );
+    }
+
+    fn on_handshake_status_updated(
+        &mut self,
+        _context: &mut Self::ConnectionContext,
+        meta: &api::ConnectionMeta,
+        event: &api::HandshakeStatusUpdated,
+    ) {
+        let status = match event.status {
+            api::HandshakeStatus::Complete {} => HandshakeStatus::Complete,
+            api::HandshakeStatus::Confirmed {} => HandshakeStatus::Confirmed,
+            api::HandshakeStatus::HandshakeDoneAcked {} => HandshakeStatus::HandshakeDoneAcked,
+            api::HandshakeStatus::HandshakeDoneLost {} => HandshakeStatus::HandshakeDoneLost,
+        };
+        self.record(meta, Data::HandshakeStatusUpdated { status });
+    }
+
+    fn on_recovery_metrics(
+        &mut self,
+        _context: &mut Self::ConnectionContext,
+        meta: &api::ConnectionMeta,
+        event: &api::RecoveryMetrics,
+    ) {
+        self.record(
+            meta,
+            Data::RecoveryMetrics {
+                path_id: event.path.id,
+                min_rtt: event.min_rtt,
+                smoothed_rtt: event.smoothed_rtt,
+                latest_rtt: event.latest_rtt,
+                rtt_variance: event.rtt_variance,
+                pto_count: event.pto_count,
+                congestion_window: event.congestion_window,
+                bytes_in_flight: event.bytes_in_flight,
+            },
+        );
+    }
+
+    fn on_packet_lost(
+        &mut self,
+        _context: &mut Self::ConnectionContext,
+        meta: &api::ConnectionMeta,
+        event: &api::PacketLost,
+    ) {
+        let Some((space, packet_number)) = packet_number(&event.packet_header) else {
+            return;
+        };
+        self.record(
+            meta,
+            Data::PacketLost {
+                path_id: event.path.id,
+                space,
+                packet_number,
+                bytes_lost: event.bytes_lost,
+            },
+        );
+    }
+
+    fn on_packet_sent(
+        &mut self,
+        _context: &mut Self::ConnectionContext,
+        meta: &api::ConnectionMeta,
+        event: &api::PacketSent,
+    ) {
+        let Some((space, packet_number)) = packet_number(&event.packet_header) else {
+            return;
+        };
+        self.record(
+            meta,
+            Data::PacketSent {
+                space,
+                packet_number,
+                packet_len: event.packet_len.try_into().unwrap_or(u32::MAX),
+            },
+        );
+    }
+
+    fn on_packet_received(
+        &mut self,
+        _context: &mut Self::ConnectionContext,
+        meta: &api::ConnectionMeta,
+        event: &api::PacketReceived,
+    ) {
+        let Some((space, packet_number)) = packet_number(&event.packet_header) else {
+            return;
+        };
+        self.record(
+            meta,
+            Data::PacketReceived {
+                space,
+                packet_number,
+            },
+        );
+    }
+
+    fn on_packet_dropped(
+        &mut self,
+        _context: &mut Self::ConnectionContext,
+        meta: &api::ConnectionMeta,
+        event: &api::PacketDropped,
+    ) {
+        let reason = variant_name(&event.reason);
+        self.record(meta, Data::PacketDropped { reason });
+    }
+
+    fn on_mtu_updated(
+        &mut self,
+        _context: &mut Self::ConnectionContext,
+        meta: &api::ConnectionMeta,
+        event: &api::MtuUpdated,
+    ) {
+        self.record(
+            meta,
+            Data::MtuUpdated {
+                path_id: event.path_id,
+                mtu: event.mtu,
+                cause: variant_name(&event.cause),
+                search_complete: event.search_complete,
+            },
+        );
+    }
+
+    fn on_key_space_discarded(
+        &mut self,
+        _context: &mut Self::ConnectionContext,
+        meta: &api::ConnectionMeta,
+        event: &api::KeySpaceDiscarded,
+    ) {
+        let space = match event.space {
+            api::KeySpace::Initial {} => KeySpace::Initial,
+            api::KeySpace::Handshake {} => KeySpace::Handshake,
+            api::KeySpace::ZeroRtt {} => KeySpace::ZeroRtt,
+            api::KeySpace::OneRtt {} => KeySpace::OneRtt,
+        };
+        self.record(meta, Data::KeySpaceDiscarded { space });
+    }
+
+    fn on_active_path_updated(
+        &mut self,
+        _context: &mut Self::ConnectionContext,
+        meta: &api::ConnectionMeta,
+        event: &api::ActivePathUpdated,
+    ) {
+        self.record(
+            meta,
+            Data::ActivePathUpdated {
+                path_id: event.active.id,
+            },
+        );
+    }
+
+    fn on_connection_closed(
+        &mut self,
+        _context: &mut Self::ConnectionContext,
+        meta: &api::ConnectionMeta,
+        event: &api::ConnectionClosed,
+    ) {
+        let reason = event.error.to_string();
+        self.record(meta, Data::ConnectionClosed { reason });
+
+        if let Some(writer) = self.writer.as_mut() {
+            if writer.flush().is_err() {
+                self.writer = None;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn records() -> Vec<Record> {
+        let record = |ms: u64, data: Data| Record {
+            connection_id: 7,
+            timestamp: Duration::from_millis(ms),
+            data,
+        };
+
+        vec![
+            record(
+                1,
+                Data::ConnectionStarted {
+                    endpoint_type: endpoint::Type::Client,
+                    unix_time: Some(Duration::from_secs(1_700_000_000)),
+                },
+            ),
+            record(
+                2,
+                Data::HandshakeStatusUpdated {
+                    status: HandshakeStatus::Confirmed,
+                },
+            ),
+            record(
+                3,
+                Data::RecoveryMetrics {
+                    path_id: 0,
+                    min_rtt: Duration::from_millis(10),
+                    smoothed_rtt: Duration::from_millis(12),
+                    latest_rtt: Duration::from_millis(11),
+                    rtt_variance: Duration::from_millis(2),
+                    pto_count: 1,
+                    congestion_window: 12_000,
+                    bytes_in_flight: 1_200,
+                },
+            ),
+            record(
+                4,
+                Data::PacketLost {
+                    path_id: 0,
+                    space: PacketSpace::ApplicationData,
+                    packet_number: 42,
+                    bytes_lost: 1_200,
+                },
+            ),
+            record(
+                5,
+                Data::PacketSent {
+                    space: PacketSpace::Handshake,
+                    packet_number: 3,
+                    packet_len: 1_250,
+                },
+            ),
+            record(
+                6,
+                Data::PacketReceived {
+                    space: PacketSpace::Initial,
+                    packet_number: 0,
+                },
+            ),
+            record(
+                7,
+                Data::PacketDropped {
+                    reason: "DecryptionFailed".into(),
+                },
+            ),
+            record(
+                8,
+                Data::MtuUpdated {
+                    path_id: 0,
+                    mtu: 1_472,
+                    cause: "ProbeAcknowledged".into(),
+                    search_complete: true,
+                },
+            ),
+            record(
+                9,
+                Data::KeySpaceDiscarded {
+                    space: KeySpace::Handshake,
+                },
+            ),
+            record(10, Data::ActivePathUpdated { path_id: 1 }),
+            record(
+                11,
+                Data::ConnectionClosed {
+                    reason: "idle timeout".into(),
+                },
+            ),
+        ]
+    }
+
+    #[test]
+    fn round_trip() {
+        let expected = records();
+
+        let mut writer = Writer::new(vec![]).unwrap();
+        for record in &expected {
+            writer.write(record).unwrap();
+        }
+        let log = writer.into_inner();
+
+        let reader = Reader::new(&log[..]).unwrap();
+        assert_eq!(reader.version(), VERSION);
+        let actual: Vec<_> = reader.collect::<io::Result<_>>().unwrap();
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn unknown_records_are_skipped() {
+        let expected = records();
+
+        let mut writer = Writer::new(vec![]).unwrap();
+        writer.write(&expected[0]).unwrap();
+        let mut log = writer.into_inner();
+        // a record from a future version of the format
+        log.extend_from_slice(&[u8::MAX, 0, 3, 1, 2, 3]);
+        let mut writer = Writer {
+            inner: log,
+            buffer: vec![],
+        };
+        writer.write(&expected[1]).unwrap();
+        let log = writer.into_inner();
+
+        let actual: Vec<_> = Reader::new(&log[..])
+            .unwrap()
+            .collect::<io::Result<_>>()
+            .unwrap();
+        assert_eq!(&expected[..2], &actual[..]);
+    }
+
+    #[test]
+    fn invalid_header() {
+        assert!(Reader::new(&b"not a log at all"[..]).is_err());
+
+        let mut log = MAGIC.to_vec();
+        log.extend_from_slice(&(VERSION + 1).to_be_bytes());
+        assert!(Reader::new(&log[..]).is_err());
+    }
+
+    #[test]
+    fn truncated_record() {
+        let expected = records();
+
+        let mut writer = Writer::new(vec![]).unwrap();
+        writer.write(&expected[0]).unwrap();
+        writer.write(&expected[2]).unwrap();
+        let mut log = writer.into_inner();
+        log.pop();
+
+        // the complete records are still returned
+        let mut reader = Reader::new(&log[..]).unwrap();
+        assert_eq!(reader.next().unwrap().unwrap(), expected[0]);
+        let error = reader.next().unwrap().unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::UnexpectedEof);
+    }
+
+    #[test]
+    fn version_1_connection_started() {
+        // the version 1 encoding didn't include the start time
+        let mut log = MAGIC.to_vec();
+        log.extend_from_slice(&1u16.to_be_bytes());
+        log.extend_from_slice(&[tag::CONNECTION_STARTED, 0, 17]);
+        log.extend_from_slice(&7u64.to_be_bytes());
+        log.extend_from_slice(&1_000u64.to_be_bytes());
+        log.push(1);
+
+        let mut reader = Reader::new(&log[..]).unwrap();
+        assert_eq!(reader.version(), 1);
+        assert_eq!(
+            reader.next().unwrap().unwrap().data,
+            Data::ConnectionStarted {
+                endpoint_type: endpoint::Type::Server,
+                unix_time: None,
+            }
+        );
+        assert!(reader.next().is_none());
+    }
+
+    #[test]
+    fn variant_name_test() {
+        assert_eq!(
+            variant_name(&api::MtuUpdatedCause::ProbeAcknowledged {}),
+            "ProbeAcknowledged"
+        );
+        assert_eq!(variant_name(&api::KeySpace::OneRtt {}), "OneRtt");
+    }
+}
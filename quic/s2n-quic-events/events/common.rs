@@ -937,6 +937,14 @@ enum PathChallengeStatus {
     Abandoned,
 }
 
+/// The outcome of an Encrypted Client Hello (ECH) offer
+enum EchStatus {
+    /// The server decrypted the ClientHello and used the inner ClientHello
+    Accepted,
+    /// The server used the outer ClientHello, which only contains the public name
+    Rejected,
+}
+
 /// The reason the slow start congestion controller state has been exited
 enum SlowStartExitCause {
     /// A packet was determined lost
@@ -281,6 +281,16 @@ struct TlsServerHello<'a> {
     payload: &'a [&'a [u8]],
 }
 
+#[event("tls:ech_status")]
+/// The server accepted or rejected the Encrypted Client Hello (ECH) offered by the client
+struct TlsEchStatus<'a> {
+    status: EchStatus,
+    /// The `ECHConfigList` provided by the server for retrying a rejected offer
+    ///
+    /// Applications can use the list to establish a new connection with ECH.
+    retry_configs: Option<&'a [u8]>,
+}
+
 #[event("transport:rx_stream_progress")]
 struct RxStreamProgress {
     bytes: usize,
@@ -291,6 +291,161 @@ impl OutputMode {
     }
 }
 
+impl OutputMode {
+    fn supervisor_timeout_sample(&self) -> TokenStream {
+        match self {
+            OutputMode::Ref => quote!(),
+            OutputMode::Mut => quote!(
+                #[inline]
+                fn supervisor_timeout(
+                    &mut self,
+                    conn_context: &mut Self::ConnectionContext,
+                    meta: &api::ConnectionMeta,
+                    context: &supervisor::Context,
+                ) -> Option<Duration> {
+                    let conn_context = conn_context.as_mut()?;
+                    self.subscriber
+                        .supervisor_timeout(conn_context, meta, context)
+                }
+
+                #[inline]
+                fn on_supervisor_timeout(
+                    &mut self,
+                    conn_context: &mut Self::ConnectionContext,
+                    meta: &api::ConnectionMeta,
+                    context: &supervisor::Context,
+                ) -> supervisor::Outcome {
+                    if let Some(conn_context) = conn_context {
+                        self.subscriber
+                            .on_supervisor_timeout(conn_context, meta, context)
+                    } else {
+                        supervisor::Outcome::default()
+                    }
+                }
+
+                #[inline]
+                fn query_mut(
+                    context: &mut Self::ConnectionContext,
+                    query: &mut dyn query::QueryMut,
+                ) -> query::ControlFlow {
+                    query.execute_mut(context).and_then(|| match context {
+                        Some(context) => S::query_mut(context, query),
+                        None => query::ControlFlow::Continue,
+                    })
+                }
+            ),
+        }
+    }
+
+    fn supervisor_timeout_forward(&self) -> TokenStream {
+        match self {
+            OutputMode::Ref => quote!(),
+            OutputMode::Mut => quote!(
+                #[inline]
+                fn supervisor_timeout(
+                    &mut self,
+                    conn_context: &mut Self::ConnectionContext,
+                    meta: &api::ConnectionMeta,
+                    context: &supervisor::Context,
+                ) -> Option<Duration> {
+                    self.subscriber
+                        .supervisor_timeout(conn_context, meta, context)
+                }
+
+                #[inline]
+                fn on_supervisor_timeout(
+                    &mut self,
+                    conn_context: &mut Self::ConnectionContext,
+                    meta: &api::ConnectionMeta,
+                    context: &supervisor::Context,
+                ) -> supervisor::Outcome {
+                    self.subscriber
+                        .on_supervisor_timeout(conn_context, meta, context)
+                }
+
+                #[inline]
+                fn query_mut(
+                    context: &mut Self::ConnectionContext,
+                    query: &mut dyn query::QueryMut,
+                ) -> query::ControlFlow {
+                    S::query_mut(context, query)
+                }
+            ),
+        }
+    }
+
+    fn rate_limit_window(&self) -> TokenStream {
+        match self {
+            OutputMode::Ref => quote!(
+                #[derive(Debug, Default)]
+                struct Window {
+                    start: core::sync::atomic::AtomicU64,
+                    count: AtomicU32,
+                }
+
+                impl Window {
+                    #[inline]
+                    fn try_acquire(&self, now: u64, interval: u64, limit: u32) -> bool {
+                        let start = self.start.load(Ordering::Relaxed);
+                        if now.saturating_sub(start) >= interval
+                            && self
+                                .start
+                                .compare_exchange(start, now, Ordering::Relaxed, Ordering::Relaxed)
+                                .is_ok()
+                        {
+                            self.count.store(0, Ordering::Relaxed);
+                        }
+
+                        self.count
+                            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |count| {
+                                count.checked_add(1).filter(|count| *count <= limit)
+                            })
+                            .is_ok()
+                    }
+                }
+            ),
+            OutputMode::Mut => quote!(
+                #[derive(Clone, Copy, Debug, Default)]
+                struct Window {
+                    start: u64,
+                    count: u32,
+                }
+
+                impl Window {
+                    #[inline]
+                    fn try_acquire(&mut self, now: u64, interval: u64, limit: u32) -> bool {
+                        if now.saturating_sub(self.start) >= interval {
+                            self.start = now;
+                            self.count = 0;
+                        }
+
+                        if self.count >= limit {
+                            return false;
+                        }
+
+                        self.count += 1;
+                        true
+                    }
+                }
+            ),
+        }
+    }
+
+    fn rate_limit_derive(&self) -> TokenStream {
+        match self {
+            OutputMode::Ref => quote!(#[derive(Debug)]),
+            OutputMode::Mut => quote!(#[derive(Clone, Debug)]),
+        }
+    }
+
+    fn testing_derive(&self) -> TokenStream {
+        match self {
+            OutputMode::Ref => quote!(#[derive(Debug)]),
+            OutputMode::Mut => quote!(#[derive(Clone, Debug)]),
+        }
+    }
+}
+
 impl ToTokens for OutputMode {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         tokens.extend(self.receiver());
@@ -317,6 +472,11 @@ struct Output {
     pub metrics_fields_init: TokenStream,
     pub metrics_record: TokenStream,
     pub subscriber_metrics: TokenStream,
+    pub sample_subscriber: TokenStream,
+    pub filter_subscriber: TokenStream,
+    pub rate_limit_subscriber: TokenStream,
+    pub event_names: TokenStream,
+    pub event_count: usize,
     pub extra: TokenStream,
     pub mode: OutputMode,
     pub s2n_quic_core_path: TokenStream,
@@ -343,6 +503,11 @@ impl ToTokens for Output {
             metrics_fields_init,
             metrics_record,
             subscriber_metrics,
+            sample_subscriber,
+            filter_subscriber,
+            rate_limit_subscriber,
+            event_names,
+            event_count,
             extra,
             mode,
             s2n_quic_core_path,
@@ -358,6 +523,11 @@ impl ToTokens for Output {
         let supervisor_timeout_tuple = self.mode.supervisor_timeout_tuple();
         let query_mut = self.mode.query_mut();
         let query_mut_tuple = self.mode.query_mut_tuple();
+        let supervisor_timeout_sample = self.mode.supervisor_timeout_sample();
+        let supervisor_timeout_forward = self.mode.supervisor_timeout_forward();
+        let rate_limit_window = self.mode.rate_limit_window();
+        let rate_limit_derive = self.mode.rate_limit_derive();
+        let testing_derive = self.mode.testing_derive();
 
         tokens.extend(quote!(
             use super::*;
@@ -430,10 +600,10 @@ impl ToTokens for Output {
                 use super::*;
                 use api::*;
                 use core::fmt;
-                use crate::query;
+                use #s2n_quic_core_path::query;
 
                 /// Provides metadata related to an event
-                pub trait Meta: fmt::Debug {
+                pub trait Meta: fmt::Debug + crate::event::Serialize {
                     /// Returns whether the local endpoint is a Client or Server
                     fn endpoint_type(&self) -> &EndpointType;
 
@@ -740,13 +910,215 @@ impl ToTokens for Output {
                 }
             }
 
+            pub mod sample {
+                //! Forwards events to a subscriber for a sample of connections
+                //!
+                //! The sampling decision is made once, when the connection context is created, so
+                //! a subscriber either sees all of a connection's events or none of them.
+                //! Endpoint events are always forwarded.
+                use super::*;
+                use #s2n_quic_core_path::query;
+
+                /// Decides if a connection's events should be forwarded to the subscriber
+                pub trait Sampler: 'static + Send + Sync {
+                    fn sample(&self, meta: &api::ConnectionMeta, info: &api::ConnectionInfo) -> bool;
+                }
+
+                impl<F> Sampler for F
+                where
+                    F: 'static + Send + Sync + Fn(&api::ConnectionMeta, &api::ConnectionInfo) -> bool,
+                {
+                    #[inline]
+                    fn sample(&self, meta: &api::ConnectionMeta, info: &api::ConnectionInfo) -> bool {
+                        (self)(meta, info)
+                    }
+                }
+
+                /// Samples one in every `n` connections, based on the connection id
+                #[derive(Clone, Copy, Debug)]
+                pub struct OneIn(u64);
+
+                impl OneIn {
+                    /// Creates a sampler which samples one in every `n` connections
+                    ///
+                    /// A value of `0` disables sampling of all connections.
+                    pub fn new(n: u64) -> Self {
+                        Self(n)
+                    }
+                }
+
+                impl Sampler for OneIn {
+                    #[inline]
+                    fn sample(&self, meta: &api::ConnectionMeta, _info: &api::ConnectionInfo) -> bool {
+                        self.0 != 0 && meta.id % self.0 == 0
+                    }
+                }
+
+                #[derive(Clone, Debug)]
+                pub struct Subscriber<S: super::Subscriber, F: Sampler> {
+                    subscriber: S,
+                    sampler: F,
+                }
+
+                impl<S: super::Subscriber, F: Sampler> Subscriber<S, F> {
+                    /// Forwards events for the connections accepted by the `sampler`
+                    pub fn new(subscriber: S, sampler: F) -> Self {
+                        Self { subscriber, sampler }
+                    }
+                }
+
+                impl<S: super::Subscriber> Subscriber<S, OneIn> {
+                    /// Forwards events for one in every `n` connections
+                    pub fn one_in(subscriber: S, n: u64) -> Self {
+                        Self::new(subscriber, OneIn::new(n))
+                    }
+                }
+
+                impl<S: super::Subscriber, F: Sampler> super::Subscriber for Subscriber<S, F> {
+                    type ConnectionContext = Option<S::ConnectionContext>;
+
+                    #[inline]
+                    fn create_connection_context(&#mode self, meta: &api::ConnectionMeta, info: &api::ConnectionInfo) -> Self::ConnectionContext {
+                        if self.sampler.sample(meta, info) {
+                            Some(self.subscriber.create_connection_context(meta, info))
+                        } else {
+                            None
+                        }
+                    }
+
+                    #supervisor_timeout_sample
+
+                    #[inline]
+                    fn query(context: &Self::ConnectionContext, query: &mut dyn query::Query) -> query::ControlFlow {
+                        query.execute(context).and_then(|| match context {
+                            Some(context) => S::query(context, query),
+                            None => query::ControlFlow::Continue,
+                        })
+                    }
+
+                    #sample_subscriber
+                }
+            }
+
+            pub mod filter {
+                //! Forwards events to a subscriber based on the event type
+                //!
+                //! Events are matched by their [`Event::NAME`](crate::event::Event::NAME), e.g.
+                //! `"transport:packet_sent"`.
+                use super::*;
+                use #s2n_quic_core_path::query;
+
+                /// The names of all of the events, in the order of their filter index
+                const NAMES: [&str; #event_count] = [#event_names];
+
+                #[derive(Clone, Debug)]
+                pub struct Subscriber<S: super::Subscriber> {
+                    subscriber: S,
+                    enabled: [bool; #event_count],
+                }
+
+                impl<S: super::Subscriber> Subscriber<S> {
+                    /// Forwards the events for which `predicate` returns `true`
+                    pub fn new<F: FnMut(&'static str) -> bool>(subscriber: S, mut predicate: F) -> Self {
+                        let enabled = core::array::from_fn(|index| predicate(NAMES[index]));
+                        Self { subscriber, enabled }
+                    }
+
+                    /// Only forwards the events with the given names
+                    pub fn allow(subscriber: S, names: &[&str]) -> Self {
+                        Self::new(subscriber, |name| names.contains(&name))
+                    }
+
+                    /// Forwards all of the events except for the ones with the given names
+                    pub fn deny(subscriber: S, names: &[&str]) -> Self {
+                        Self::new(subscriber, |name| !names.contains(&name))
+                    }
+                }
+
+                impl<S: super::Subscriber> super::Subscriber for Subscriber<S> {
+                    type ConnectionContext = S::ConnectionContext;
+
+                    #[inline]
+                    fn create_connection_context(&#mode self, meta: &api::ConnectionMeta, info: &api::ConnectionInfo) -> Self::ConnectionContext {
+                        self.subscriber.create_connection_context(meta, info)
+                    }
+
+                    #supervisor_timeout_forward
+
+                    #[inline]
+                    fn query(context: &Self::ConnectionContext, query: &mut dyn query::Query) -> query::ControlFlow {
+                        S::query(context, query)
+                    }
+
+                    #filter_subscriber
+                }
+            }
+
+            pub mod rate_limit {
+                //! Limits the rate at which events are forwarded to a subscriber
+                //!
+                //! Each event type is limited separately, so frequent events such as
+                //! `PacketSent` don't crowd out infrequent ones. The limits are shared by all of
+                //! the connections on the endpoint. Events over the limit are dropped.
+                use super::*;
+                use #s2n_quic_core_path::query;
+                use core::time::Duration;
+                #imports
+
+                #rate_limit_derive
+                pub struct Subscriber<S: super::Subscriber> {
+                    subscriber: S,
+                    limit: u32,
+                    interval: u64,
+                    windows: [Window; #event_count],
+                }
+
+                impl<S: super::Subscriber> Subscriber<S> {
+                    /// Forwards at most `limit` events of each type per `interval`
+                    pub fn new(subscriber: S, limit: u32, interval: Duration) -> Self {
+                        Self {
+                            subscriber,
+                            limit,
+                            interval: interval.as_nanos().try_into().unwrap_or(u64::MAX),
+                            windows: core::array::from_fn(|_| Window::default()),
+                        }
+                    }
+
+                    #[inline]
+                    fn try_acquire<M: Meta>(&#mode self, index: usize, meta: &M) -> bool {
+                        let now = meta.timestamp().duration_since_start().as_nanos() as u64;
+                        self.windows[index].try_acquire(now, self.interval, self.limit)
+                    }
+                }
+
+                #rate_limit_window
+
+                impl<S: super::Subscriber> super::Subscriber for Subscriber<S> {
+                    type ConnectionContext = S::ConnectionContext;
+
+                    #[inline]
+                    fn create_connection_context(&#mode self, meta: &api::ConnectionMeta, info: &api::ConnectionInfo) -> Self::ConnectionContext {
+                        self.subscriber.create_connection_context(meta, info)
+                    }
+
+                    #supervisor_timeout_forward
+
+                    #[inline]
+                    fn query(context: &Self::ConnectionContext, query: &mut dyn query::Query) -> query::ControlFlow {
+                        S::query(context, query)
+                    }
+
+                    #rate_limit_subscriber
+                }
+            }
+
             #[cfg(any(test, feature = "testing"))]
             pub mod testing {
                 use super::*;
                 #imports
                 #mutex
 
-                #[derive(Clone, Debug)]
+                #testing_derive
                 pub struct Subscriber {
                     location: Option<Location>,
                     output: #testing_output_type,
@@ -793,7 +1165,7 @@ impl ToTokens for Output {
                     #subscriber_testing
                 }
 
-                #[derive(Clone, Debug)]
+                #testing_derive
                 pub struct Publisher {
                     location: Option<Location>,
                     output: #testing_output_type,
@@ -120,6 +120,10 @@ impl Struct {
             output.api.extend(quote!(#[non_exhaustive]));
         }
 
+        output.api.extend(quote!(
+            #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
+        ));
+
         output.api.extend(quote!(
             #derive_attrs
             #extra_attrs
@@ -164,6 +168,11 @@ impl Struct {
             let counter_increment = output.mode.counter_increment();
             let lock = output.mode.lock();
 
+            // each event is assigned an index for the filter and rate limit combinators
+            let index = proc_macro2::Literal::usize_unsuffixed(output.event_count);
+            output.event_count += 1;
+            output.event_names.extend(quote!(#event_name,));
+
             match attrs.subject {
                 Subject::Endpoint => {
                     output.subscriber.extend(quote!(
@@ -226,6 +235,39 @@ impl Struct {
                         }
                     ));
 
+                    let forward = quote!(
+                        self.subscriber.#function(meta, event);
+                        self.subscriber.on_event(meta, event);
+                    );
+
+                    output.sample_subscriber.extend(quote!(
+                        #[inline]
+                        #allow_deprecated
+                        fn #function(&#receiver self, meta: &api::EndpointMeta, event: &api::#ident) {
+                            #forward
+                        }
+                    ));
+
+                    output.filter_subscriber.extend(quote!(
+                        #[inline]
+                        #allow_deprecated
+                        fn #function(&#receiver self, meta: &api::EndpointMeta, event: &api::#ident) {
+                            if self.enabled[#index] {
+                                #forward
+                            }
+                        }
+                    ));
+
+                    output.rate_limit_subscriber.extend(quote!(
+                        #[inline]
+                        #allow_deprecated
+                        fn #function(&#receiver self, meta: &api::EndpointMeta, event: &api::#ident) {
+                            if self.try_acquire(#index, meta) {
+                                #forward
+                            }
+                        }
+                    ));
+
                     output.endpoint_publisher_testing.extend(quote!(
                         #allow_deprecated
                         fn #function(&#receiver self, event: builder::#ident) {
@@ -300,7 +342,7 @@ impl Struct {
                         #allow_deprecated
                         fn #function(&#receiver self, context: &#receiver Self::ConnectionContext, meta: &api::ConnectionMeta, event: &api::#ident) {
                             context.#counter #counter_increment;
-                            self.subscriber.#function(&mut context.recorder, meta, event);
+                            self.subscriber.#function(&#receiver context.recorder, meta, event);
                         }
                     ));
 
@@ -314,6 +356,42 @@ impl Struct {
                         }
                     ));
 
+                    let forward = quote!(
+                        self.subscriber.#function(context, meta, event);
+                        self.subscriber.on_connection_event(context, meta, event);
+                        self.subscriber.on_event(meta, event);
+                    );
+
+                    output.sample_subscriber.extend(quote!(
+                        #[inline]
+                        #allow_deprecated
+                        fn #function(&#receiver self, context: &#receiver Self::ConnectionContext, meta: &api::ConnectionMeta, event: &api::#ident) {
+                            if let Some(context) = context {
+                                #forward
+                            }
+                        }
+                    ));
+
+                    output.filter_subscriber.extend(quote!(
+                        #[inline]
+                        #allow_deprecated
+                        fn #function(&#receiver self, context: &#receiver Self::ConnectionContext, meta: &api::ConnectionMeta, event: &api::#ident) {
+                            if self.enabled[#index] {
+                                #forward
+                            }
+                        }
+                    ));
+
+                    output.rate_limit_subscriber.extend(quote!(
+                        #[inline]
+                        #allow_deprecated
+                        fn #function(&#receiver self, context: &#receiver Self::ConnectionContext, meta: &api::ConnectionMeta, event: &api::#ident) {
+                            if self.try_acquire(#index, meta) {
+                                #forward
+                            }
+                        }
+                    ));
+
                     output.connection_publisher_testing.extend(quote!(
                         #allow_deprecated
                         fn #function(&#receiver self, event: builder::#ident) {
@@ -406,6 +484,10 @@ impl Enum {
             output.api.extend(quote!(#[non_exhaustive]));
         }
 
+        output.api.extend(quote!(
+            #[cfg_attr(feature = "event-serde", derive(serde::Serialize))]
+        ));
+
         output.api.extend(quote!(
             #derive_attrs
             #extra_attrs
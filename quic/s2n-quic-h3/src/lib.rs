@@ -2,6 +2,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
 mod s2n_quic;
+pub mod webtransport;
+
+#[cfg(test)]
+mod tests;
 
 pub use self::s2n_quic::*;
 pub use h3;
@@ -1,11 +1,15 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
-use bytes::{Buf, Bytes};
+use bytes::{Buf, Bytes, BytesMut};
 use core::task::ready;
-use h3::quic::{self, Error, StreamId, WriteBuf};
+use h3::{
+    ext::Datagram,
+    quic::{self, Error, StreamId, WriteBuf},
+};
 use s2n_quic::{
     application,
+    provider::datagram::default::{self as datagram, Receiver, Sender},
     stream::{BidirectionalStream, ReceiveStream},
 };
 use std::{
@@ -37,6 +41,18 @@ impl Connection {
     }
 }
 
+/// Returns an HTTP/3 server builder with datagrams and extended CONNECT enabled
+///
+/// This advertises `SETTINGS_H3_DATAGRAM` ([RFC 9297](https://www.rfc-editor.org/rfc/rfc9297))
+/// and `SETTINGS_ENABLE_CONNECT_PROTOCOL` ([RFC 9220](https://www.rfc-editor.org/rfc/rfc9220))
+/// to the peer. The `s2n_quic::Server` must also be configured with the default datagram provider
+/// in order for datagrams to be exchanged.
+pub fn server_builder() -> h3::server::Builder {
+    let mut builder = h3::server::builder();
+    builder.enable_datagram(true).enable_connect(true);
+    builder
+}
+
 #[derive(Debug)]
 pub struct ConnectionError(s2n_quic::connection::Error);
 
@@ -145,6 +161,50 @@ where
     }
 }
 
+impl<B> quic::SendDatagramExt<B> for Connection
+where
+    B: Buf,
+{
+    type Error = DatagramError;
+
+    #[cfg_attr(feature = "tracing", instrument(skip_all, level = "trace"))]
+    fn send_datagram(&mut self, data: Datagram<B>) -> Result<(), Self::Error> {
+        let mut buf = BytesMut::new();
+        data.encode(&mut buf);
+        let buf = buf.freeze();
+
+        self.conn
+            .datagram_mut(|sender: &mut Sender| sender.send_datagram(buf))??;
+
+        Ok(())
+    }
+}
+
+impl quic::RecvDatagramExt for Connection {
+    type Buf = Bytes;
+    type Error = DatagramError;
+
+    #[cfg_attr(feature = "tracing", instrument(skip_all, level = "trace"))]
+    fn poll_accept_datagram(
+        &mut self,
+        cx: &mut task::Context<'_>,
+    ) -> Poll<Result<Option<Self::Buf>, Self::Error>> {
+        let res = self
+            .conn
+            .datagram_mut(|receiver: &mut Receiver| receiver.poll_recv_datagram(cx))?;
+
+        match ready!(res) {
+            Ok(datagram) => Poll::Ready(Ok(Some(datagram))),
+            // the connection was closed without an error so no more datagrams will arrive
+            Err(datagram::DatagramError::ConnectionError {
+                error: s2n_quic::connection::Error::Closed { .. },
+                ..
+            }) => Poll::Ready(Ok(None)),
+            Err(err) => Poll::Ready(Err(err.into())),
+        }
+    }
+}
+
 pub struct OpenStreams {
     conn: s2n_quic::connection::Handle,
 }
@@ -263,6 +323,19 @@ where
     }
 }
 
+impl<B> quic::SendStreamUnframed<B> for BidiStream<B>
+where
+    B: Buf,
+{
+    fn poll_send<D: Buf>(
+        &mut self,
+        cx: &mut task::Context<'_>,
+        buf: &mut D,
+    ) -> Poll<Result<usize, Self::Error>> {
+        self.send.poll_send(cx, buf)
+    }
+}
+
 impl<B> From<BidirectionalStream> for BidiStream<B>
 where
     B: Buf,
@@ -342,6 +415,12 @@ impl From<s2n_quic::stream::Error> for ReadError {
     }
 }
 
+impl From<ReadError> for std::io::Error {
+    fn from(e: ReadError) -> Self {
+        e.0.into()
+    }
+}
+
 impl Error for ReadError {
     fn is_timeout(&self) -> bool {
         matches!(
@@ -472,6 +551,28 @@ where
     }
 }
 
+impl<B> quic::SendStreamUnframed<B> for SendStream<B>
+where
+    B: Buf,
+{
+    #[cfg_attr(feature = "tracing", instrument(skip_all, level = "trace"))]
+    fn poll_send<D: Buf>(
+        &mut self,
+        cx: &mut task::Context<'_>,
+        buf: &mut D,
+    ) -> Poll<Result<usize, Self::Error>> {
+        // flush any data previously provided with `send_data`
+        ready!(quic::SendStream::poll_ready(self, cx))?;
+
+        let available = ready!(self.stream.poll_send_ready(cx))?;
+        let len = available.min(buf.chunk().len());
+        let chunk = buf.copy_to_bytes(len);
+        self.stream.send_data(chunk)?;
+
+        Ok(len).into()
+    }
+}
+
 impl<B> From<s2n_quic::stream::SendStream> for SendStream<B>
 where
     B: Buf,
@@ -501,6 +602,15 @@ impl From<s2n_quic::stream::Error> for SendStreamError {
     }
 }
 
+impl From<SendStreamError> for std::io::Error {
+    fn from(e: SendStreamError) -> Self {
+        match e {
+            SendStreamError::Write(err) => err.into(),
+            SendStreamError::NotReady => std::io::ErrorKind::WouldBlock.into(),
+        }
+    }
+}
+
 impl Error for SendStreamError {
     fn is_timeout(&self) -> bool {
         matches!(
@@ -531,3 +641,62 @@ impl From<SendStreamError> for Arc<dyn Error> {
         Arc::new(e)
     }
 }
+
+#[derive(Debug)]
+pub enum DatagramError {
+    /// The connection was not configured with the default datagram provider
+    /// or the connection is no longer available
+    Query(s2n_quic_core::query::Error),
+    Datagram(datagram::DatagramError),
+}
+
+impl std::error::Error for DatagramError {}
+
+impl Display for DatagramError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Query(err) => err.fmt(f),
+            Self::Datagram(err) => err.fmt(f),
+        }
+    }
+}
+
+impl From<s2n_quic_core::query::Error> for DatagramError {
+    fn from(e: s2n_quic_core::query::Error) -> Self {
+        Self::Query(e)
+    }
+}
+
+impl From<datagram::DatagramError> for DatagramError {
+    fn from(e: datagram::DatagramError) -> Self {
+        Self::Datagram(e)
+    }
+}
+
+impl Error for DatagramError {
+    fn is_timeout(&self) -> bool {
+        matches!(
+            self,
+            Self::Datagram(datagram::DatagramError::ConnectionError {
+                error: s2n_quic::connection::Error::IdleTimerExpired { .. },
+                ..
+            })
+        )
+    }
+
+    fn err_code(&self) -> Option<u64> {
+        match self {
+            Self::Datagram(datagram::DatagramError::ConnectionError {
+                error: s2n_quic::connection::Error::Application { error, .. },
+                ..
+            }) => Some((*error).into()),
+            _ => None,
+        }
+    }
+}
+
+impl From<DatagramError> for Arc<dyn Error> {
+    fn from(e: DatagramError) -> Self {
+        Arc::new(e)
+    }
+}
@@ -0,0 +1,204 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! End-to-end tests over an s2n-quic client and server on localhost
+
+use bytes::{Buf, Bytes, BytesMut};
+use s2n_codec::{DecoderBuffer, DecoderError, EncoderValue};
+use s2n_quic::{
+    client::Connect, provider::datagram::default::Endpoint as Datagrams,
+    stream::BidirectionalStream, Client, Server,
+};
+use s2n_quic_core::{
+    crypto::tls::testing::certificates::{CERT_PEM, KEY_PEM},
+    varint::VarInt,
+};
+use std::time::Duration;
+
+mod datagram;
+mod webtransport;
+
+/// The maximum amount of time a test waits on the peer
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+fn datagrams() -> Datagrams {
+    Datagrams::builder()
+        .with_send_capacity(16)
+        .unwrap()
+        .with_recv_capacity(16)
+        .unwrap()
+        .build()
+        .unwrap()
+}
+
+/// Starts a server on localhost with datagrams enabled
+fn server() -> Server {
+    Server::builder()
+        .with_tls((CERT_PEM, KEY_PEM))
+        .unwrap()
+        .with_io("127.0.0.1:0")
+        .unwrap()
+        .with_datagram(datagrams())
+        .unwrap()
+        .start()
+        .unwrap()
+}
+
+/// Connects a new client to the server
+///
+/// The client is returned along with the connection to keep its endpoint running.
+async fn connect(server: &Server) -> (Client, s2n_quic::Connection) {
+    let client = Client::builder()
+        .with_tls(CERT_PEM)
+        .unwrap()
+        .with_io("0.0.0.0:0")
+        .unwrap()
+        .with_datagram(datagrams())
+        .unwrap()
+        .start()
+        .unwrap();
+
+    let connect = Connect::new(server.local_addr().unwrap()).with_server_name("localhost");
+    let connection = client.connect(connect).await.unwrap();
+
+    (client, connection)
+}
+
+/// Waits for `future` to complete, failing the test if the peer doesn't respond in time
+async fn timeout<F: core::future::Future>(future: F) -> F::Output {
+    tokio::time::timeout(TIMEOUT, future)
+        .await
+        .expect("the peer did not respond in time")
+}
+
+fn encode_varint(value: u64, out: &mut Vec<u8>) {
+    out.extend(VarInt::new(value).unwrap().encode_to_vec());
+}
+
+/// Encodes an HTTP/3 frame
+fn frame(ty: u64, payload: &[u8]) -> Vec<u8> {
+    let mut out = vec![];
+    encode_varint(ty, &mut out);
+    encode_varint(payload.len() as u64, &mut out);
+    out.extend_from_slice(payload);
+    out
+}
+
+/// Encodes a QPACK field section without using the dynamic table
+///
+/// Every field is encoded as a literal field line with a literal name.
+///
+/// See <https://www.rfc-editor.org/rfc/rfc9204#section-4.5.6>
+fn qpack(fields: &[(&str, &str)]) -> Vec<u8> {
+    fn prefixed(out: &mut Vec<u8>, first: u8, prefix_bits: u32, mut value: usize) {
+        let max = (1 << prefix_bits) - 1;
+        if value < max {
+            out.push(first | value as u8);
+            return;
+        }
+        out.push(first | max as u8);
+        value -= max;
+        while value >= 0x80 {
+            out.push(0x80 | (value & 0x7f) as u8);
+            value >>= 7;
+        }
+        out.push(value as u8);
+    }
+
+    // Required Insert Count and Delta Base are both 0
+    let mut out = vec![0, 0];
+    for (name, value) in fields {
+        prefixed(&mut out, 0b0010_0000, 3, name.len());
+        out.extend_from_slice(name.as_bytes());
+        prefixed(&mut out, 0, 7, value.len());
+        out.extend_from_slice(value.as_bytes());
+    }
+    out
+}
+
+/// A raw QUIC stream which reads HTTP/3 frames
+struct Stream {
+    stream: BidirectionalStream,
+    buffer: BytesMut,
+}
+
+impl Stream {
+    fn new(stream: BidirectionalStream) -> Self {
+        Self {
+            stream,
+            buffer: BytesMut::new(),
+        }
+    }
+
+    fn id(&self) -> u64 {
+        self.stream.id()
+    }
+
+    async fn send(&mut self, data: Vec<u8>) {
+        self.stream.send(data.into()).await.unwrap();
+    }
+
+    fn finish(&mut self) {
+        self.stream.finish().unwrap();
+    }
+
+    /// Reads more data into the buffer, returning `false` if the peer finished the stream
+    async fn fill(&mut self) -> bool {
+        match timeout(self.stream.receive()).await.unwrap() {
+            Some(chunk) => {
+                self.buffer.extend_from_slice(&chunk);
+                true
+            }
+            None => false,
+        }
+    }
+
+    async fn varint(&mut self) -> Option<u64> {
+        loop {
+            match DecoderBuffer::new(&self.buffer).decode::<VarInt>() {
+                Ok((value, remaining)) => {
+                    let len = self.buffer.len() - remaining.len();
+                    self.buffer.advance(len);
+                    return Some(value.as_u64());
+                }
+                Err(DecoderError::UnexpectedEof(_)) => {
+                    if !self.fill().await {
+                        assert!(self.buffer.is_empty(), "truncated varint");
+                        return None;
+                    }
+                }
+                Err(err) => panic!("{err}"),
+            }
+        }
+    }
+
+    async fn bytes(&mut self, len: usize) -> Bytes {
+        while self.buffer.len() < len {
+            assert!(self.fill().await, "truncated stream");
+        }
+        self.buffer.split_to(len).freeze()
+    }
+
+    /// Reads the next HTTP/3 frame, returning `None` if the peer finished the stream
+    ///
+    /// Reserved frame types used for greasing are skipped.
+    async fn frame(&mut self) -> Option<(u64, Bytes)> {
+        loop {
+            let ty = self.varint().await?;
+            let len = self.varint().await.expect("truncated frame");
+            let payload = self.bytes(len as usize).await;
+
+            // https://www.rfc-editor.org/rfc/rfc9114#section-7.2.8
+            if ty >= 0x21 && (ty - 0x21) % 0x1f == 0 {
+                continue;
+            }
+
+            return Some((ty, payload));
+        }
+    }
+
+    /// Returns `true` once the peer has finished the stream without sending anything else
+    async fn is_finished(&mut self) -> bool {
+        self.buffer.is_empty() && !self.fill().await
+    }
+}
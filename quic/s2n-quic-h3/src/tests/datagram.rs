@@ -0,0 +1,139 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::DatagramError;
+use futures::future::poll_fn;
+use h3::{
+    connection::ConnectionState as _,
+    ext::Datagram,
+    quic::{RecvDatagramExt as _, SendDatagramExt as _, StreamId},
+};
+use s2n_quic::provider::datagram::default::DatagramError as QueueError;
+use std::task::Poll;
+
+type H3Connection = h3::server::Connection<crate::Connection, Bytes>;
+
+async fn h3(connection: s2n_quic::Connection) -> H3Connection {
+    crate::server_builder()
+        .build(crate::Connection::new(connection))
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn round_trip() {
+    let mut server = server();
+    let (_client, client) = connect(&server).await;
+    let server_conn = server.accept().await.unwrap();
+    let (mut client, mut server_conn) = tokio::join!(h3(client), h3(server_conn));
+
+    let stream_id = StreamId::try_from(0).unwrap();
+    client
+        .send_datagram(stream_id, Bytes::from_static(b"ping"))
+        .unwrap();
+
+    let datagram = timeout(server_conn.read_datagram()).await.unwrap().unwrap();
+    assert_eq!(datagram.stream_id(), stream_id);
+    assert_eq!(datagram.payload(), "ping");
+
+    server_conn
+        .send_datagram(stream_id, Bytes::from_static(b"pong"))
+        .unwrap();
+
+    let datagram = timeout(client.read_datagram()).await.unwrap().unwrap();
+    assert_eq!(datagram.stream_id(), stream_id);
+    assert_eq!(datagram.payload(), "pong");
+
+    // both endpoints advertise SETTINGS_H3_DATAGRAM and SETTINGS_ENABLE_CONNECT_PROTOCOL
+    for conn in [&mut client, &mut server_conn] {
+        // the peer's SETTINGS are processed while polling for requests
+        timeout(poll_fn(|cx| {
+            let _ = conn.poll_accept_request(cx);
+            let state = conn.shared_state().read("test");
+            if state.peer_config.enable_datagram() {
+                Poll::Ready(())
+            } else {
+                Poll::Pending
+            }
+        }))
+        .await;
+
+        let state = conn.shared_state().read("test");
+        assert!(state.peer_config.enable_extended_connect());
+    }
+}
+
+#[tokio::test]
+async fn datagram_errors() {
+    let mut server = server();
+    let (_client, client) = connect(&server).await;
+    let server_conn = server.accept().await.unwrap();
+    let mut client = crate::Connection::new(client);
+
+    let stream_id = StreamId::try_from(0).unwrap();
+
+    // datagrams larger than the peer's max_datagram_frame_size are rejected
+    let large = Bytes::from(vec![0; u16::MAX as usize]);
+    let error = client
+        .send_datagram(Datagram::new(stream_id, large))
+        .unwrap_err();
+    assert!(matches!(
+        error,
+        DatagramError::Datagram(QueueError::ExceedsPeerTransportLimits { .. })
+    ));
+
+    // the queue only holds a limited number of datagrams
+    let error = loop {
+        let datagram = Datagram::new(stream_id, Bytes::from_static(b"ping"));
+        if let Err(error) = client.send_datagram(datagram) {
+            break error;
+        }
+    };
+    assert!(matches!(
+        error,
+        DatagramError::Datagram(QueueError::QueueAtCapacity { .. })
+    ));
+
+    // the peer closing the connection ends the stream of received datagrams
+    drop(server_conn);
+    let datagram = timeout(poll_fn(|cx| client.poll_accept_datagram(cx))).await;
+    assert!(matches!(datagram, Ok(None)));
+
+    let error = client
+        .send_datagram(Datagram::new(stream_id, Bytes::from_static(b"ping")))
+        .unwrap_err();
+    assert!(matches!(
+        error,
+        DatagramError::Datagram(QueueError::ConnectionError { .. })
+    ));
+}
+
+#[tokio::test]
+async fn datagrams_disabled() {
+    let mut server = server();
+
+    // the client isn't configured with the default datagram provider
+    let client = Client::builder()
+        .with_tls(CERT_PEM)
+        .unwrap()
+        .with_io("0.0.0.0:0")
+        .unwrap()
+        .start()
+        .unwrap();
+    let connect = Connect::new(server.local_addr().unwrap()).with_server_name("localhost");
+    let connection = client.connect(connect).await.unwrap();
+    let _server_conn = server.accept().await.unwrap();
+    let mut connection = crate::Connection::new(connection);
+
+    let stream_id = StreamId::try_from(0).unwrap();
+    let error = connection
+        .send_datagram(Datagram::new(stream_id, Bytes::from_static(b"ping")))
+        .unwrap_err();
+    assert!(matches!(error, DatagramError::Query(_)));
+
+    let error = timeout(poll_fn(|cx| connection.poll_accept_datagram(cx)))
+        .await
+        .unwrap_err();
+    assert!(matches!(error, DatagramError::Query(_)));
+}
@@ -0,0 +1,253 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::webtransport::{self as wt, AcceptedBi, CloseReason, Session};
+use h3::quic::StreamId;
+use std::sync::Arc;
+use tokio::{
+    io::{AsyncReadExt as _, AsyncWriteExt as _},
+    sync::oneshot,
+};
+
+const HEADERS: u64 = 0x01;
+const DATA: u64 = 0x00;
+const WEBTRANSPORT_BIDI_STREAM: u64 = 0x41;
+const WEBTRANSPORT_UNI_STREAM: u64 = 0x54;
+const CLOSE_WEBTRANSPORT_SESSION: u64 = 0x2843;
+
+/// QPACK encoding of `:status 200` from the static table
+const STATUS_OK: u8 = 0xc0 | 25;
+
+type H3Connection = h3::server::Connection<crate::Connection, Bytes>;
+
+/// The client side of a WebTransport session
+///
+/// The `h3` 0.0.6 client can't advertise datagram or WebTransport support, so the server
+/// connection driver is used to exchange SETTINGS and datagrams while requests and streams are
+/// written directly to the QUIC connection.
+struct Peer {
+    _client: Client,
+    h3: H3Connection,
+    handle: s2n_quic::connection::Handle,
+}
+
+impl Peer {
+    async fn new(server: &Server) -> Self {
+        let (client, connection) = connect(server).await;
+        let handle = connection.handle();
+        let h3 = wt::server_builder()
+            .build(crate::Connection::new(connection))
+            .await
+            .unwrap();
+
+        Self {
+            _client: client,
+            h3,
+            handle,
+        }
+    }
+
+    async fn open(&mut self) -> Stream {
+        Stream::new(self.handle.open_bidirectional_stream().await.unwrap())
+    }
+
+    /// Sends an extended CONNECT request and returns the CONNECT stream with the response headers
+    async fn connect(&mut self) -> (Stream, Bytes) {
+        let mut stream = self.open().await;
+        let headers = qpack(&[
+            (":method", "CONNECT"),
+            (":protocol", "webtransport"),
+            (":scheme", "https"),
+            (":authority", "localhost"),
+            (":path", "/session"),
+        ]);
+        stream.send(frame(HEADERS, &headers)).await;
+
+        let (ty, response) = stream.frame().await.unwrap();
+        assert_eq!(ty, HEADERS);
+
+        (stream, response)
+    }
+}
+
+/// Accepts a WebTransport session on the next connection
+async fn accept(server: &mut Server) -> Arc<Session> {
+    let connection = server.accept().await.unwrap();
+    let mut conn = wt::server_builder()
+        .build(crate::Connection::new(connection))
+        .await
+        .unwrap();
+    let (request, stream) = conn.accept().await.unwrap().unwrap();
+    assert!(wt::is_connect(&request));
+    assert_eq!(request.uri().path(), "/session");
+
+    Arc::new(Session::accept(request, stream, conn).await.unwrap())
+}
+
+/// Echoes the session's bidirectional streams and datagrams back to the peer
+fn echo(session: &Arc<Session>) {
+    tokio::spawn({
+        let session = session.clone();
+        async move {
+            while let Some(accepted) = session.accept_bi().await.unwrap() {
+                let AcceptedBi::BidiStream(stream) = accepted else {
+                    panic!("unexpected request");
+                };
+                let (mut recv, mut send) = tokio::io::split(stream);
+                tokio::io::copy(&mut recv, &mut send).await.unwrap();
+                send.shutdown().await.unwrap();
+            }
+        }
+    });
+
+    tokio::spawn({
+        let session = session.clone();
+        async move {
+            while let Some(datagram) = session.accept_datagram().await.unwrap() {
+                session.send_datagram(datagram).unwrap();
+            }
+        }
+    });
+}
+
+#[tokio::test]
+async fn session() {
+    let mut server = server();
+    let mut peer = Peer::new(&server).await;
+
+    let (session, (mut connect, response)) = tokio::join!(accept(&mut server), peer.connect());
+    assert_eq!(response[2], STATUS_OK);
+    echo(&session);
+
+    // the session id is the id of the CONNECT stream
+    let session_id = connect.id();
+    assert_eq!(
+        session.session_id(),
+        wt::SessionId::try_from(session_id).unwrap()
+    );
+
+    // bidirectional streams are associated with the session by their header
+    let mut stream = peer.open().await;
+    let mut header = vec![];
+    encode_varint(WEBTRANSPORT_BIDI_STREAM, &mut header);
+    encode_varint(session_id, &mut header);
+    header.extend_from_slice(b"hello stream");
+    stream.send(header).await;
+    stream.finish();
+    assert_eq!(stream.bytes(12).await, "hello stream");
+    assert!(stream.is_finished().await);
+
+    // datagrams are associated with the session by the CONNECT stream id
+    let connect_stream_id = StreamId::try_from(session_id).unwrap();
+    peer.h3
+        .send_datagram(connect_stream_id, Bytes::from_static(b"hello datagram"))
+        .unwrap();
+    let datagram = timeout(peer.h3.read_datagram()).await.unwrap().unwrap();
+    assert_eq!(datagram.stream_id(), connect_stream_id);
+    assert_eq!(datagram.payload(), "hello datagram");
+
+    // close the session with a CLOSE_WEBTRANSPORT_SESSION capsule
+    let mut capsule = vec![];
+    encode_varint(CLOSE_WEBTRANSPORT_SESSION, &mut capsule);
+    encode_varint(4 + 4, &mut capsule);
+    capsule.extend_from_slice(&42u32.to_be_bytes());
+    capsule.extend_from_slice(b"done");
+    connect.send(frame(DATA, &capsule)).await;
+    connect.finish();
+
+    let reason = timeout(session.closed()).await.unwrap();
+    assert_eq!(
+        reason,
+        CloseReason {
+            code: 42,
+            reason: "done".to_string(),
+        }
+    );
+}
+
+#[tokio::test]
+async fn uni_streams() {
+    let mut server = server();
+    let mut peer = Peer::new(&server).await;
+
+    let (session, (connect, response)) = tokio::join!(accept(&mut server), peer.connect());
+    assert_eq!(response[2], STATUS_OK);
+    let session_id = connect.id();
+
+    // `accept_bi` also drives the unidirectional streams into the session's queue
+    echo(&session);
+
+    // open several streams before the session accepts any of them
+    let mut streams = vec![];
+    for _ in 0..3 {
+        let mut stream = peer.handle.open_send_stream().await.unwrap();
+        let mut header = vec![];
+        encode_varint(WEBTRANSPORT_UNI_STREAM, &mut header);
+        stream.send(header.into()).await.unwrap();
+        streams.push(stream);
+    }
+    // `h3` needs the session id to arrive separately from the stream type
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    for (i, stream) in streams.iter_mut().enumerate() {
+        let mut data = vec![];
+        encode_varint(session_id, &mut data);
+        data.push(i as u8);
+        stream.send(data.into()).await.unwrap();
+        stream.finish().unwrap();
+    }
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let mut received = vec![];
+    for _ in 0..3 {
+        let mut stream = timeout(session.accept_uni()).await.unwrap().unwrap();
+        timeout(stream.read_to_end(&mut received)).await.unwrap();
+    }
+    received.sort_unstable();
+    assert_eq!(received, [0, 1, 2]);
+
+    // the session stops accepting streams once the connection is closed
+    drop(connect);
+    drop(peer);
+    assert!(timeout(session.accept_uni()).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn single_session_per_connection() {
+    let mut server = server();
+    let mut peer = Peer::new(&server).await;
+
+    let (session, (mut connect, response)) = tokio::join!(accept(&mut server), peer.connect());
+    assert_eq!(response[2], STATUS_OK);
+
+    let (close, closed) = oneshot::channel();
+    let server_task = tokio::spawn({
+        let session = session.clone();
+        async move {
+            tokio::select! {
+                accepted = session.accept_bi() => panic!("unexpected stream: {:?}", accepted.is_ok()),
+                _ = closed => {}
+            }
+            session.close(7, "goodbye").await.unwrap();
+        }
+    });
+
+    // a second session on the same connection is rejected
+    let (mut second, response) = peer.connect().await;
+    assert_ne!(response[2], STATUS_OK);
+    assert!(second.frame().await.is_none());
+
+    // the first session is still usable and can be closed by the server
+    close.send(()).unwrap();
+    timeout(server_task).await.unwrap();
+
+    let (ty, payload) = connect.frame().await.unwrap();
+    assert_eq!(ty, DATA);
+    let mut expected = vec![];
+    encode_varint(CLOSE_WEBTRANSPORT_SESSION, &mut expected);
+    encode_varint(4 + 7, &mut expected);
+    expected.extend_from_slice(&7u32.to_be_bytes());
+    expected.extend_from_slice(b"goodbye");
+    assert_eq!(payload, expected);
+    assert!(connect.frame().await.is_none());
+}
@@ -0,0 +1,556 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! WebTransport over HTTP/3 sessions
+//!
+//! See <https://datatracker.ietf.org/doc/html/draft-ietf-webtrans-http3>
+//!
+//! # Limitations
+//!
+//! A [`Session`] takes ownership of the HTTP/3 connection it was accepted on, so each connection
+//! carries at most one session. [`server_builder`] advertises
+//! `SETTINGS_WEBTRANSPORT_MAX_SESSIONS = 1` to the peer, and any further WebTransport CONNECT
+//! requests on the connection are rejected with `429 Too Many Requests`. Clients that need
+//! several sessions must open a connection per session.
+//!
+//! `h3` 0.0.6 only reads the session id of a unidirectional stream after receiving more data
+//! following the stream type, so a peer sending the whole stream in a single packet causes a
+//! connection error.
+//!
+//! `h3` 0.0.6 does not have a public WebTransport API. Sessions are built on the stream, frame
+//! and connection modules that `h3` exposes to third-party QUIC backends, which this crate
+//! already opts into for its [`crate::Connection`] adapter. These modules can change in any `h3`
+//! release, so upgrading `h3` requires revisiting this module.
+
+use crate::s2n_quic as adapter;
+use bytes::{Buf, Bytes, BytesMut};
+use core::task::ready;
+use futures::future::poll_fn;
+use h3::{
+    connection::ConnectionState,
+    error::{Code, ErrorLevel, Kind},
+    ext::Protocol,
+    frame::FrameStream,
+    proto::frame::Frame,
+    quic::{self, SendStreamUnframed as _, StreamId},
+    server::RequestStream,
+    stream::{BidiStreamHeader, BufRecvStream, UniStreamHeader, WriteBuf},
+    Error,
+};
+use http::{Method, Request, Response, StatusCode};
+use s2n_codec::{DecoderBuffer, DecoderError, Encoder, EncoderBuffer, EncoderValue};
+use s2n_quic_core::varint::VarInt;
+use std::{fmt, future::Future as _, pin::Pin, sync::Mutex, task::Poll};
+
+pub use h3::webtransport::SessionId;
+
+/// A unidirectional stream received from the peer
+pub type RecvStream = BufRecvStream<adapter::RecvStream, Bytes>;
+/// A unidirectional stream opened towards the peer
+pub type SendStream = BufRecvStream<adapter::SendStream<Bytes>, Bytes>;
+/// A bidirectional stream associated with a session
+pub type BidiStream = BufRecvStream<adapter::BidiStream<Bytes>, Bytes>;
+
+type Connection = h3::server::Connection<adapter::Connection, Bytes>;
+type ConnectStream<S> = RequestStream<S, Bytes>;
+
+// See https://datatracker.ietf.org/doc/html/draft-ietf-webtrans-http3#section-5
+//
+// CLOSE_WEBTRANSPORT_SESSION Capsule {
+//   Type (i) = CLOSE_WEBTRANSPORT_SESSION,
+//   Length (i),
+//   Application Error Code (32),
+//   Application Error Message (..8192),
+// }
+const CLOSE_WEBTRANSPORT_SESSION: u64 = 0x2843;
+
+/// The maximum length of the error message sent in a `CLOSE_WEBTRANSPORT_SESSION` capsule
+pub const MAX_CLOSE_REASON_LEN: usize = 1024;
+
+// The range of HTTP/3 error codes reserved for WebTransport application errors
+//
+// See https://datatracker.ietf.org/doc/html/draft-ietf-webtrans-http3#section-4.3
+const FIRST_ERROR_CODE: u64 = 0x52e4a40fa8db;
+const LAST_ERROR_CODE: u64 = 0x52e5ac983162;
+
+/// The HTTP/3 error code used to reject a stream for an unknown session
+const WEBTRANSPORT_SESSION_GONE: u64 = 0x170d7b68;
+
+/// Maps a WebTransport application error code to the HTTP/3 error code used to reset streams
+pub fn error_code(code: u32) -> u64 {
+    let code = code as u64;
+    FIRST_ERROR_CODE + code + code / 0x1e
+}
+
+/// Maps an HTTP/3 stream error code back into a WebTransport application error code
+///
+/// Returns `None` if the code is outside of the range reserved for WebTransport.
+pub fn from_error_code(code: u64) -> Option<u32> {
+    if !(FIRST_ERROR_CODE..=LAST_ERROR_CODE).contains(&code) || (code - 0x21) % 0x1f == 0 {
+        return None;
+    }
+    let shifted = code - FIRST_ERROR_CODE;
+    (shifted - shifted / 0x1f).try_into().ok()
+}
+
+/// Returns an HTTP/3 server builder with the settings required to accept WebTransport sessions
+pub fn server_builder() -> h3::server::Builder {
+    let mut builder = crate::server_builder();
+    builder
+        .enable_webtransport(true)
+        // a session owns the connection so only one can be established per connection
+        .max_webtransport_sessions(1);
+    builder
+}
+
+/// Returns `true` if the request is an extended CONNECT for a WebTransport session
+pub fn is_connect(request: &Request<()>) -> bool {
+    let protocol = request.extensions().get::<Protocol>();
+    matches!((request.method(), protocol), (&Method::CONNECT, Some(p)) if p == &Protocol::WEB_TRANSPORT)
+}
+
+/// The reason a session was closed by the peer
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CloseReason {
+    pub code: u32,
+    pub reason: String,
+}
+
+impl fmt::Display for CloseReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "session closed with code {}: {:?}",
+            self.code, self.reason
+        )
+    }
+}
+
+/// An incoming bidirectional stream accepted on a session
+pub enum AcceptedBi {
+    /// A bidirectional stream associated with the session
+    BidiStream(BidiStream),
+    /// An HTTP/3 request received on the same connection
+    Request(Request<()>, ConnectStream<adapter::BidiStream<Bytes>>),
+}
+
+/// A WebTransport session established on an HTTP/3 connection
+///
+/// The session takes ownership of the HTTP/3 connection, which limits each connection to a
+/// single session. See the [module documentation](self#limitations) for details.
+pub struct Session {
+    session_id: SessionId,
+    connect_stream_id: StreamId,
+    conn: Mutex<Connection>,
+    opener: Mutex<adapter::OpenStreams>,
+    send: tokio::sync::Mutex<Option<ConnectStream<adapter::SendStream<Bytes>>>>,
+    recv: tokio::sync::Mutex<CapsuleReader>,
+}
+
+impl Session {
+    /// Accepts an extended CONNECT request and establishes a WebTransport session
+    pub async fn accept(
+        request: Request<()>,
+        mut stream: ConnectStream<adapter::BidiStream<Bytes>>,
+        mut conn: Connection,
+    ) -> Result<Self, Error> {
+        {
+            let shared = conn.shared_state().read("read peer settings");
+
+            if !shared.peer_config.enable_webtransport() {
+                drop(shared);
+                return Err(conn.close(
+                    Code::H3_SETTINGS_ERROR,
+                    "webtransport is not supported by the peer",
+                ));
+            }
+
+            if !shared.peer_config.enable_datagram() {
+                drop(shared);
+                return Err(conn.close(
+                    Code::H3_SETTINGS_ERROR,
+                    "datagrams are not supported by the peer",
+                ));
+            }
+        }
+
+        if !is_connect(&request) {
+            let response = Response::builder()
+                .status(StatusCode::BAD_REQUEST)
+                .body(())
+                .unwrap();
+            stream.send_response(response).await?;
+            stream.finish().await?;
+            return Err(Code::H3_REQUEST_REJECTED.into());
+        }
+
+        let response = Response::builder()
+            // browsers still negotiate the draft version with this header
+            .header("sec-webtransport-http3-draft", "draft02")
+            .status(StatusCode::OK)
+            .body(())
+            .unwrap();
+        stream.send_response(response).await?;
+
+        let connect_stream_id = stream.id();
+        // `SessionId::from(StreamId)` uses the stream index, while the draft uses the stream id
+        let session_id = u64::from(h3::proto::varint::VarInt::from(connect_stream_id));
+        let session_id = SessionId::try_from(session_id).expect("stream ids are valid varints");
+        let opener = quic::Connection::<Bytes>::opener(&conn.inner.conn);
+        let (send, recv) = stream.split();
+
+        Ok(Self {
+            session_id,
+            connect_stream_id,
+            conn: Mutex::new(conn),
+            opener: Mutex::new(opener),
+            send: tokio::sync::Mutex::new(Some(send)),
+            recv: tokio::sync::Mutex::new(CapsuleReader {
+                stream: recv,
+                buffer: BytesMut::new(),
+            }),
+        })
+    }
+
+    /// Returns the session id
+    pub fn session_id(&self) -> SessionId {
+        self.session_id
+    }
+
+    /// Sends a datagram associated with the session
+    pub fn send_datagram(&self, data: Bytes) -> Result<(), Error> {
+        self.conn
+            .lock()
+            .unwrap()
+            .send_datagram(self.connect_stream_id, data)
+    }
+
+    /// Receives the next datagram associated with the session
+    pub async fn accept_datagram(&self) -> Result<Option<Bytes>, Error> {
+        poll_fn(|cx| loop {
+            let mut conn = self.conn.lock().unwrap();
+            // the future doesn't hold any state so it can be recreated on each poll
+            let datagram = match ready!(Pin::new(&mut conn.read_datagram()).poll(cx))? {
+                Some(datagram) => datagram,
+                None => return Poll::Ready(Ok(None)),
+            };
+
+            // datagrams for unknown sessions are dropped
+            if datagram.stream_id() == self.connect_stream_id {
+                return Poll::Ready(Ok(Some(datagram.into_payload())));
+            }
+        })
+        .await
+    }
+
+    /// Accepts the next unidirectional stream associated with the session
+    pub async fn accept_uni(&self) -> Result<Option<RecvStream>, Error> {
+        poll_fn(|cx| {
+            let mut conn = self.conn.lock().unwrap();
+            let accepted = conn.inner.poll_accept_recv(cx);
+
+            // the connection only carries this session so streams for any other are rejected
+            let streams = &mut conn.inner.accepted_streams_mut().wt_uni_streams;
+            streams.retain_mut(|(session_id, stream)| {
+                if *session_id == self.session_id {
+                    return true;
+                }
+                quic::RecvStream::stop_sending(stream, WEBTRANSPORT_SESSION_GONE);
+                false
+            });
+
+            // hand out one stream at a time and leave the rest queued for the next call
+            if !streams.is_empty() {
+                let (_session_id, stream) = streams.remove(0);
+                return Poll::Ready(Ok(Some(stream)));
+            }
+
+            match accepted {
+                Ok(()) => Poll::Pending,
+                Err(err) if is_closed(&err) => Poll::Ready(Ok(None)),
+                Err(err) => Poll::Ready(Err(err)),
+            }
+        })
+        .await
+    }
+
+    /// Accepts the next bidirectional stream associated with the session
+    ///
+    /// HTTP/3 requests received on the connection are also returned so the application can
+    /// respond to them. Requests to establish another WebTransport session are rejected, since
+    /// the connection already carries this one.
+    pub async fn accept_bi(&self) -> Result<Option<AcceptedBi>, Error> {
+        loop {
+            let stream = poll_fn(|cx| self.conn.lock().unwrap().poll_accept_request(cx)).await;
+
+            let mut stream = match stream {
+                Ok(Some(stream)) => FrameStream::new(BufRecvStream::new(stream)),
+                Ok(None) => return Ok(None),
+                Err(err) if is_closed(&err) => return Ok(None),
+                Err(err) => match err.kind() {
+                    Kind::Application {
+                        code,
+                        reason,
+                        level: ErrorLevel::ConnectionError,
+                        ..
+                    } => {
+                        let reason = reason.unwrap_or_default();
+                        return Err(self.conn.lock().unwrap().close(code, reason));
+                    }
+                    _ => return Err(err),
+                },
+            };
+
+            // The first frame determines if this is a WebTransport stream or an HTTP/3 request
+            let frame = poll_fn(|cx| stream.poll_next(cx)).await;
+
+            match frame {
+                Ok(Some(Frame::WebTransportStream(session_id))) => {
+                    let mut stream = stream.into_inner();
+
+                    if session_id == self.session_id {
+                        return Ok(Some(AcceptedBi::BidiStream(stream)));
+                    }
+
+                    quic::RecvStream::stop_sending(&mut stream, WEBTRANSPORT_SESSION_GONE);
+                    quic::SendStream::reset(&mut stream, WEBTRANSPORT_SESSION_GONE);
+                    continue;
+                }
+                frame => {
+                    let request = self.conn.lock().unwrap().accept_with_frame(stream, frame)?;
+                    let Some(request) = request else {
+                        return Ok(None);
+                    };
+                    let (request, mut stream) = request.resolve().await?;
+
+                    if is_connect(&request) {
+                        let response = Response::builder()
+                            .status(StatusCode::TOO_MANY_REQUESTS)
+                            .body(())
+                            .unwrap();
+                        stream.send_response(response).await?;
+                        stream.finish().await?;
+                        continue;
+                    }
+
+                    return Ok(Some(AcceptedBi::Request(request, stream)));
+                }
+            }
+        }
+    }
+
+    /// Opens a bidirectional stream associated with the session
+    pub async fn open_bi(&self) -> Result<BidiStream, Error> {
+        let stream = poll_fn(|cx| {
+            quic::OpenStreams::<Bytes>::poll_open_bidi(&mut *self.opener.lock().unwrap(), cx)
+        })
+        .await?;
+        let mut stream = BufRecvStream::new(stream);
+
+        let mut header =
+            WriteBuf::<Bytes>::from(BidiStreamHeader::WebTransportBidi(self.session_id));
+        while header.has_remaining() {
+            poll_fn(|cx| stream.poll_send(cx, &mut header)).await?;
+        }
+
+        Ok(stream)
+    }
+
+    /// Opens a unidirectional stream associated with the session
+    pub async fn open_uni(&self) -> Result<SendStream, Error> {
+        let stream = poll_fn(|cx| {
+            quic::OpenStreams::<Bytes>::poll_open_send(&mut *self.opener.lock().unwrap(), cx)
+        })
+        .await?;
+        let mut stream = BufRecvStream::new(stream);
+
+        let mut header = WriteBuf::<Bytes>::from(UniStreamHeader::WebTransportUni(self.session_id));
+        while header.has_remaining() {
+            poll_fn(|cx| stream.poll_send(cx, &mut header)).await?;
+        }
+
+        Ok(stream)
+    }
+
+    /// Closes the session with an application error code and reason
+    ///
+    /// The reason is truncated to [`MAX_CLOSE_REASON_LEN`] bytes.
+    pub async fn close(&self, code: u32, reason: &str) -> Result<(), Error> {
+        let mut send = self.send.lock().await;
+        let Some(mut stream) = send.take() else {
+            // the session was already closed
+            return Ok(());
+        };
+
+        let mut len = reason.len().min(MAX_CLOSE_REASON_LEN);
+        while !reason.is_char_boundary(len) {
+            len -= 1;
+        }
+        let reason = &reason.as_bytes()[..len];
+
+        let capsule = CloseCapsule { code, reason };
+        stream.send_data(capsule.encode_to_bytes()).await?;
+        stream.finish().await?;
+
+        Ok(())
+    }
+
+    /// Waits for the peer to close the session
+    ///
+    /// If the peer finishes the CONNECT stream without sending a `CLOSE_WEBTRANSPORT_SESSION`
+    /// capsule, a code of `0` and an empty reason are returned.
+    pub async fn closed(&self) -> Result<CloseReason, Error> {
+        self.recv.lock().await.read_close().await
+    }
+}
+
+/// Returns `true` if the error indicates the connection was closed
+fn is_closed(err: &Error) -> bool {
+    match err.kind() {
+        Kind::Closed => true,
+        // `h3` reports the end of the incoming unidirectional streams as a protocol error
+        Kind::Application {
+            code,
+            reason: Some(reason),
+            ..
+        } => code == Code::H3_GENERAL_PROTOCOL_ERROR && &*reason == "Connection closed unexpected",
+        _ => false,
+    }
+}
+
+struct CloseCapsule<'a> {
+    code: u32,
+    reason: &'a [u8],
+}
+
+impl CloseCapsule<'_> {
+    fn encode_to_bytes(&self) -> Bytes {
+        let mut bytes = vec![0; self.encoding_size()];
+        let mut encoder = EncoderBuffer::new(&mut bytes);
+        encoder.encode(self);
+        bytes.into()
+    }
+}
+
+impl EncoderValue for CloseCapsule<'_> {
+    fn encode<E: Encoder>(&self, encoder: &mut E) {
+        encoder.encode(&VarInt::from_u32(CLOSE_WEBTRANSPORT_SESSION as u32));
+        encoder.encode_with_len_prefix::<VarInt, _>(&(self.code, self.reason));
+    }
+}
+
+/// Reads capsules from the receiving half of the CONNECT stream
+struct CapsuleReader {
+    stream: ConnectStream<adapter::RecvStream>,
+    buffer: BytesMut,
+}
+
+impl CapsuleReader {
+    async fn read_close(&mut self) -> Result<CloseReason, Error> {
+        loop {
+            while let Some((ty, value)) = self.decode_capsule()? {
+                // DRAIN_WEBTRANSPORT_SESSION and unknown capsules are ignored
+                if ty == CLOSE_WEBTRANSPORT_SESSION {
+                    return decode_close(value);
+                }
+            }
+
+            match self.stream.recv_data().await? {
+                Some(mut data) => {
+                    while data.has_remaining() {
+                        let chunk = data.chunk();
+                        self.buffer.extend_from_slice(chunk);
+                        let len = chunk.len();
+                        data.advance(len);
+                    }
+                }
+                None if self.buffer.is_empty() => return Ok(CloseReason::default()),
+                None => return Err(Code::H3_MESSAGE_ERROR.into()),
+            }
+        }
+    }
+
+    fn decode_capsule(&mut self) -> Result<Option<(u64, Bytes)>, Error> {
+        let buffer = DecoderBuffer::new(&self.buffer);
+        let res = buffer.decode::<VarInt>().and_then(|(ty, buffer)| {
+            let (value, _) = buffer.decode_slice_with_len_prefix::<VarInt>()?;
+            Ok((ty, value.len()))
+        });
+
+        let (ty, value_len) = match res {
+            Ok(v) => v,
+            Err(DecoderError::UnexpectedEof(_)) => return Ok(None),
+            Err(_) => return Err(Code::H3_MESSAGE_ERROR.into()),
+        };
+
+        let header_len = ty.encoding_size()
+            + VarInt::try_from(value_len)
+                .expect("length was decoded as a varint")
+                .encoding_size();
+        self.buffer.advance(header_len);
+        let value = self.buffer.split_to(value_len).freeze();
+
+        Ok(Some((ty.as_u64(), value)))
+    }
+}
+
+fn decode_close(value: Bytes) -> Result<CloseReason, Error> {
+    let buffer = DecoderBuffer::new(&value);
+    let (code, buffer) = buffer
+        .decode::<u32>()
+        .map_err(|_| Error::from(Code::H3_MESSAGE_ERROR))?;
+    let reason = buffer.into_less_safe_slice();
+
+    if reason.len() > MAX_CLOSE_REASON_LEN {
+        return Err(Code::H3_MESSAGE_ERROR.into());
+    }
+
+    let reason = core::str::from_utf8(reason)
+        .map_err(|_| Error::from(Code::H3_MESSAGE_ERROR))?
+        .to_string();
+
+    Ok(CloseReason { code, reason })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn error_code_round_trip() {
+        for code in (0..=u16::MAX as u32).chain([u32::MAX - 1, u32::MAX]) {
+            let http = error_code(code);
+            assert!((FIRST_ERROR_CODE..=LAST_ERROR_CODE).contains(&http));
+            assert_eq!(from_error_code(http), Some(code));
+        }
+
+        // reserved (greased) codepoints don't map to application codes
+        assert_eq!(from_error_code(0x52e4a40fa8f9), None);
+        assert_eq!(from_error_code(FIRST_ERROR_CODE - 1), None);
+        assert_eq!(from_error_code(LAST_ERROR_CODE + 1), None);
+    }
+
+    #[test]
+    fn close_capsule_round_trip() {
+        let capsule = CloseCapsule {
+            code: 42,
+            reason: b"goodbye",
+        };
+        let bytes = capsule.encode_to_bytes();
+
+        let buffer = DecoderBuffer::new(&bytes);
+        let (ty, buffer) = buffer.decode::<VarInt>().unwrap();
+        assert_eq!(ty.as_u64(), CLOSE_WEBTRANSPORT_SESSION);
+        let (value, buffer) = buffer.decode_slice_with_len_prefix::<VarInt>().unwrap();
+        assert!(buffer.is_empty());
+
+        let reason = decode_close(Bytes::copy_from_slice(value.into_less_safe_slice())).unwrap();
+        assert_eq!(
+            reason,
+            CloseReason {
+                code: 42,
+                reason: "goodbye".to_string()
+            }
+        );
+    }
+}
@@ -0,0 +1,194 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::Result;
+use core::time::Duration;
+use s2n_quic_core::{
+    endpoint,
+    event::log::{Data, HandshakeStatus, KeySpace, PacketSpace, Reader, Record},
+};
+use std::{collections::BTreeMap, fs, io};
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+pub struct Input {
+    /// Only include the connection with the given id
+    #[structopt(long)]
+    connection: Option<u64>,
+
+    /// The event log to read. Reads from stdin if omitted or `-`.
+    input: Option<String>,
+}
+
+impl Input {
+    pub fn connections(&self) -> Result<Vec<Connection>> {
+        let input: Box<dyn io::Read> = if self.input.as_ref().map_or(true, |v| v == "-") {
+            Box::new(io::stdin())
+        } else {
+            let reader = fs::File::open(self.input.as_ref().unwrap())?;
+            Box::new(io::BufReader::new(reader))
+        };
+
+        let reader = Reader::new(input)?;
+        let Log {
+            mut connections,
+            truncated,
+        } = Connection::load(reader)?;
+
+        if truncated {
+            eprintln!("warning: the log ends with a truncated record, which was skipped");
+        }
+
+        if let Some(id) = self.connection {
+            connections.retain(|connection| connection.id == id);
+        }
+
+        Ok(connections)
+    }
+}
+
+/// The connections in a log
+#[derive(Debug)]
+pub struct Log {
+    pub connections: Vec<Connection>,
+    /// The log ended partway through a record, usually because the writer was interrupted
+    ///
+    /// Every record before the truncated one is included.
+    pub truncated: bool,
+}
+
+/// All of the records for a single connection
+#[derive(Debug)]
+pub struct Connection {
+    pub id: u64,
+    pub endpoint_type: Option<endpoint::Type>,
+    /// The wall-clock time the connection started, relative to the Unix epoch
+    pub unix_start: Option<Duration>,
+    pub records: Vec<Record>,
+}
+
+impl Connection {
+    /// Groups the records in a log by connection, ordered by connection id
+    pub fn load<I: Iterator<Item = io::Result<Record>>>(records: I) -> Result<Log> {
+        let mut connections = BTreeMap::new();
+        let mut truncated = false;
+
+        for record in records {
+            let record = match record {
+                Ok(record) => record,
+                // stop at the last complete record
+                Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => {
+                    truncated = true;
+                    break;
+                }
+                Err(err) => return Err(err.into()),
+            };
+            let connection =
+                connections
+                    .entry(record.connection_id)
+                    .or_insert_with(|| Connection {
+                        id: record.connection_id,
+                        endpoint_type: None,
+                        unix_start: None,
+                        records: vec![],
+                    });
+
+            if let Data::ConnectionStarted {
+                endpoint_type,
+                unix_time,
+            } = record.data
+            {
+                connection.endpoint_type = Some(endpoint_type);
+                connection.unix_start = unix_time;
+            }
+
+            connection.records.push(record);
+        }
+
+        Ok(Log {
+            connections: connections.into_values().collect(),
+            truncated,
+        })
+    }
+
+    pub fn start(&self) -> Duration {
+        self.records
+            .first()
+            .map_or(Duration::ZERO, |record| record.timestamp)
+    }
+
+    pub fn end(&self) -> Duration {
+        self.records
+            .last()
+            .map_or(Duration::ZERO, |record| record.timestamp)
+    }
+
+    /// The time of the record, relative to the start of the connection
+    pub fn elapsed(&self, record: &Record) -> Duration {
+        record.timestamp.saturating_sub(self.start())
+    }
+
+    pub fn vantage_point(&self) -> &'static str {
+        match self.endpoint_type {
+            Some(endpoint::Type::Client) => "client",
+            Some(endpoint::Type::Server) => "server",
+            None => "unknown",
+        }
+    }
+
+    pub fn close_reason(&self) -> Option<&str> {
+        self.records.iter().find_map(|record| match &record.data {
+            Data::ConnectionClosed { reason } => Some(reason.as_str()),
+            _ => None,
+        })
+    }
+}
+
+/// The s2n-quic event name for the record
+pub fn name(data: &Data) -> &'static str {
+    match data {
+        Data::ConnectionStarted { .. } => "connectivity:connection_started",
+        Data::HandshakeStatusUpdated { .. } => "connectivity:handshake_status_updated",
+        Data::RecoveryMetrics { .. } => "recovery:metrics_updated",
+        Data::PacketLost { .. } => "recovery:packet_lost",
+        Data::ConnectionClosed { .. } => "connectivity:connection_closed",
+        Data::PacketSent { .. } => "transport:packet_sent",
+        Data::PacketReceived { .. } => "transport:packet_received",
+        Data::PacketDropped { .. } => "transport:packet_dropped",
+        Data::MtuUpdated { .. } => "connectivity:mtu_updated",
+        Data::KeySpaceDiscarded { .. } => "security:key_space_discarded",
+        Data::ActivePathUpdated { .. } => "connectivity:active_path_updated",
+        _ => "unknown",
+    }
+}
+
+pub fn handshake_status(status: HandshakeStatus) -> &'static str {
+    match status {
+        HandshakeStatus::Complete => "complete",
+        HandshakeStatus::Confirmed => "confirmed",
+        HandshakeStatus::HandshakeDoneAcked => "handshake_done_acked",
+        HandshakeStatus::HandshakeDoneLost => "handshake_done_lost",
+    }
+}
+
+pub fn packet_space(space: PacketSpace) -> &'static str {
+    match space {
+        PacketSpace::Initial => "initial",
+        PacketSpace::Handshake => "handshake",
+        PacketSpace::ApplicationData => "application_data",
+    }
+}
+
+pub fn key_space(space: KeySpace) -> &'static str {
+    match space {
+        KeySpace::Initial => "initial",
+        KeySpace::Handshake => "handshake",
+        KeySpace::ZeroRtt => "0rtt",
+        KeySpace::OneRtt => "1rtt",
+    }
+}
+
+/// Formats a duration as fractional milliseconds
+pub fn ms(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
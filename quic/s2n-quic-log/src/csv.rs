@@ -0,0 +1,131 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    connection::{self, ms, Connection, Input},
+    Result,
+};
+use s2n_quic_core::event::log::Data;
+use std::io::{self, Write};
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+pub struct Csv {
+    #[structopt(flatten)]
+    input: Input,
+}
+
+impl Csv {
+    pub fn run(&self) -> Result {
+        let connections = self.input.connections()?;
+
+        let stdout = io::stdout();
+        let mut out = io::BufWriter::new(stdout.lock());
+        write(&mut out, &connections)?;
+        out.flush()?;
+
+        Ok(())
+    }
+}
+
+const HEADER: &str = "connection_id,endpoint,time_ms,event,congestion_window,bytes_in_flight,smoothed_rtt_ms,min_rtt_ms,latest_rtt_ms,pto_count,packet_space,packet_number,bytes_lost,packet_len,detail";
+
+/// Writes one row per record, with the columns which don't apply to the event left empty
+pub fn write<W: Write>(out: &mut W, connections: &[Connection]) -> io::Result<()> {
+    writeln!(out, "{HEADER}")?;
+
+    for connection in connections {
+        for record in &connection.records {
+            write!(
+                out,
+                "{},{},{:.3},{},",
+                connection.id,
+                connection.vantage_point(),
+                ms(record.timestamp),
+                connection::name(&record.data),
+            )?;
+
+            match &record.data {
+                Data::HandshakeStatusUpdated { status } => {
+                    writeln!(out, ",,,,,,,,,,{}", connection::handshake_status(*status))?;
+                }
+                Data::RecoveryMetrics {
+                    min_rtt,
+                    smoothed_rtt,
+                    latest_rtt,
+                    pto_count,
+                    congestion_window,
+                    bytes_in_flight,
+                    ..
+                } => {
+                    writeln!(
+                        out,
+                        "{congestion_window},{bytes_in_flight},{:.3},{:.3},{:.3},{pto_count},,,,,",
+                        ms(*smoothed_rtt),
+                        ms(*min_rtt),
+                        ms(*latest_rtt),
+                    )?;
+                }
+                Data::PacketLost {
+                    space,
+                    packet_number,
+                    bytes_lost,
+                    ..
+                } => {
+                    writeln!(
+                        out,
+                        ",,,,,,{},{packet_number},{bytes_lost},,",
+                        connection::packet_space(*space)
+                    )?;
+                }
+                Data::PacketSent {
+                    space,
+                    packet_number,
+                    packet_len,
+                } => {
+                    writeln!(
+                        out,
+                        ",,,,,,{},{packet_number},,{packet_len},",
+                        connection::packet_space(*space)
+                    )?;
+                }
+                Data::PacketReceived {
+                    space,
+                    packet_number,
+                } => {
+                    writeln!(
+                        out,
+                        ",,,,,,{},{packet_number},,,",
+                        connection::packet_space(*space)
+                    )?;
+                }
+                Data::ConnectionClosed { reason } | Data::PacketDropped { reason } => {
+                    writeln!(out, ",,,,,,,,,,{}", escape(reason))?;
+                }
+                Data::MtuUpdated { mtu, cause, .. } => {
+                    writeln!(out, ",,,,,,,,,,{}", escape(&format!("{mtu} {cause}")))?;
+                }
+                Data::KeySpaceDiscarded { space } => {
+                    writeln!(out, ",,,,,,,,,,{}", connection::key_space(*space))?;
+                }
+                Data::ActivePathUpdated { path_id } => {
+                    writeln!(out, ",,,,,,,,,,path {path_id}")?;
+                }
+                _ => {
+                    writeln!(out, ",,,,,,,,,,")?;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Quotes a field if it contains any characters with special meaning in CSV
+fn escape(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
@@ -0,0 +1,34 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+pub use anyhow::Error;
+use structopt::StructOpt;
+
+pub type Result<T = (), E = Error> = core::result::Result<T, E>;
+
+mod connection;
+mod csv;
+mod qlog;
+mod summary;
+
+#[cfg(test)]
+mod tests;
+
+/// Decodes event logs written by `s2n_quic_core::event::log::Subscriber`
+#[derive(Debug, StructOpt)]
+enum Args {
+    /// Prints the timeline, recovery metrics, losses and close reason of each connection
+    Summary(summary::Summary),
+    /// Converts the log to qlog
+    Qlog(qlog::Qlog),
+    /// Converts the log to CSV, with one row per event
+    Csv(csv::Csv),
+}
+
+fn main() -> Result {
+    match Args::from_args() {
+        Args::Summary(args) => args.run(),
+        Args::Qlog(args) => args.run(),
+        Args::Csv(args) => args.run(),
+    }
+}
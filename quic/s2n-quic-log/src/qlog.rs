@@ -0,0 +1,177 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    connection::{self, ms, Connection, Input},
+    Result,
+};
+use s2n_quic_core::event::log::{Data, PacketSpace};
+use serde_json::{json, Value};
+use std::io::{self, Write};
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+pub struct Qlog {
+    #[structopt(flatten)]
+    input: Input,
+}
+
+impl Qlog {
+    pub fn run(&self) -> Result {
+        let connections = self.input.connections()?;
+
+        let stdout = io::stdout();
+        let mut out = io::BufWriter::new(stdout.lock());
+        serde_json::to_writer(&mut out, &convert(&connections))?;
+        writeln!(out)?;
+        out.flush()?;
+
+        Ok(())
+    }
+}
+
+/// Converts the connections to a qlog file with one trace per connection
+///
+/// See <https://datatracker.ietf.org/doc/draft-ietf-quic-qlog-main-schema/>
+pub fn convert(connections: &[Connection]) -> Value {
+    let traces: Vec<_> = connections.iter().map(trace).collect();
+
+    json!({
+        "qlog_version": "0.3",
+        "qlog_format": "JSON",
+        "title": "s2n-quic event log",
+        "traces": traces,
+    })
+}
+
+fn trace(connection: &Connection) -> Value {
+    let events: Vec<_> = connection
+        .records
+        .iter()
+        .filter_map(|record| {
+            let (name, data) = match &record.data {
+                Data::ConnectionStarted { .. } => ("connectivity:connection_started", json!({})),
+                Data::HandshakeStatusUpdated { status } => (
+                    "connectivity:connection_state_updated",
+                    json!({
+                        "new": format!("handshake_{}", connection::handshake_status(*status)),
+                    }),
+                ),
+                Data::RecoveryMetrics {
+                    min_rtt,
+                    smoothed_rtt,
+                    latest_rtt,
+                    rtt_variance,
+                    pto_count,
+                    congestion_window,
+                    bytes_in_flight,
+                    ..
+                } => (
+                    "recovery:metrics_updated",
+                    json!({
+                        "min_rtt": ms(*min_rtt),
+                        "smoothed_rtt": ms(*smoothed_rtt),
+                        "latest_rtt": ms(*latest_rtt),
+                        "rtt_variance": ms(*rtt_variance),
+                        "pto_count": pto_count,
+                        "congestion_window": congestion_window,
+                        "bytes_in_flight": bytes_in_flight,
+                    }),
+                ),
+                Data::PacketLost {
+                    space,
+                    packet_number,
+                    bytes_lost,
+                    ..
+                } => (
+                    "recovery:packet_lost",
+                    json!({
+                        "header": {
+                            "packet_type": packet_type(*space),
+                            "packet_number": packet_number,
+                            "length": bytes_lost,
+                        },
+                    }),
+                ),
+                Data::PacketSent {
+                    space,
+                    packet_number,
+                    packet_len,
+                } => (
+                    "transport:packet_sent",
+                    json!({
+                        "header": {
+                            "packet_type": packet_type(*space),
+                            "packet_number": packet_number,
+                        },
+                        "raw": { "length": packet_len },
+                    }),
+                ),
+                Data::PacketReceived {
+                    space,
+                    packet_number,
+                } => (
+                    "transport:packet_received",
+                    json!({
+                        "header": {
+                            "packet_type": packet_type(*space),
+                            "packet_number": packet_number,
+                        },
+                    }),
+                ),
+                Data::PacketDropped { reason } => {
+                    ("transport:packet_dropped", json!({ "trigger": reason }))
+                }
+                Data::MtuUpdated {
+                    mtu,
+                    search_complete,
+                    ..
+                } => (
+                    "connectivity:mtu_updated",
+                    json!({ "new": mtu, "done": search_complete }),
+                ),
+                Data::KeySpaceDiscarded { space } => (
+                    "security:key_discarded",
+                    json!({ "key_type": connection::key_space(*space) }),
+                ),
+                Data::ActivePathUpdated { path_id } => (
+                    "connectivity:active_path_updated",
+                    json!({ "path_id": path_id }),
+                ),
+                Data::ConnectionClosed { reason } => (
+                    "connectivity:connection_closed",
+                    json!({ "reason": reason }),
+                ),
+                _ => return None,
+            };
+
+            Some(json!({
+                "time": ms(connection.elapsed(record)),
+                "name": name,
+                "data": data,
+            }))
+        })
+        .collect();
+
+    let mut common_fields = json!({ "time_format": "relative" });
+    // qlog expects the reference time in milliseconds since the Unix epoch, which version 1
+    // logs didn't record
+    if let Some(unix_start) = connection.unix_start {
+        common_fields["reference_time"] = json!(ms(unix_start));
+    }
+
+    json!({
+        "title": format!("connection {}", connection.id),
+        "vantage_point": { "type": connection.vantage_point() },
+        "common_fields": common_fields,
+        "events": events,
+    })
+}
+
+fn packet_type(space: PacketSpace) -> &'static str {
+    match space {
+        PacketSpace::Initial => "initial",
+        PacketSpace::Handshake => "handshake",
+        PacketSpace::ApplicationData => "1RTT",
+    }
+}
@@ -0,0 +1,158 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    connection::{self, ms, Connection, Input},
+    Result,
+};
+use s2n_quic_core::event::log::Data;
+use std::io::{self, Write};
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+pub struct Summary {
+    #[structopt(flatten)]
+    input: Input,
+}
+
+impl Summary {
+    pub fn run(&self) -> Result {
+        let connections = self.input.connections()?;
+
+        let stdout = io::stdout();
+        let mut out = io::BufWriter::new(stdout.lock());
+
+        for connection in &connections {
+            write(&mut out, connection)?;
+        }
+
+        out.flush()?;
+
+        Ok(())
+    }
+}
+
+pub fn write<W: Write>(out: &mut W, connection: &Connection) -> io::Result<()> {
+    writeln!(
+        out,
+        "connection {} ({})",
+        connection.id,
+        connection.vantage_point()
+    )?;
+    writeln!(
+        out,
+        "  duration: {:.3}ms",
+        ms(connection.end().saturating_sub(connection.start()))
+    )?;
+
+    writeln!(out, "  timeline:")?;
+    for record in &connection.records {
+        let description = match &record.data {
+            Data::ConnectionStarted { .. } => "started".to_string(),
+            Data::HandshakeStatusUpdated { status } => {
+                format!("handshake {}", connection::handshake_status(*status))
+            }
+            Data::PacketLost {
+                space,
+                packet_number,
+                bytes_lost,
+                ..
+            } => format!(
+                "lost {} packet {packet_number} ({bytes_lost} bytes)",
+                connection::packet_space(*space)
+            ),
+            Data::ConnectionClosed { reason } => format!("closed: {reason}"),
+            Data::PacketDropped { reason } => format!("dropped packet: {reason}"),
+            Data::MtuUpdated {
+                mtu,
+                cause,
+                search_complete,
+                ..
+            } => {
+                let complete = if *search_complete {
+                    ", search complete"
+                } else {
+                    ""
+                };
+                format!("mtu {mtu} ({cause}{complete})")
+            }
+            Data::KeySpaceDiscarded { space } => {
+                format!("discarded {} keys", connection::key_space(*space))
+            }
+            Data::ActivePathUpdated { path_id } => format!("active path {path_id}"),
+            // recovery metrics are listed separately and packets are counted below
+            _ => continue,
+        };
+
+        writeln!(
+            out,
+            "    +{:.3}ms {description}",
+            ms(connection.elapsed(record))
+        )?;
+    }
+
+    writeln!(out, "  recovery:")?;
+    writeln!(
+        out,
+        "    {:>12} {:>10} {:>10} {:>14} {:>12} {:>12} {:>5}",
+        "time", "cwnd", "in_flight", "smoothed_rtt", "min_rtt", "latest_rtt", "pto"
+    )?;
+    for record in &connection.records {
+        if let Data::RecoveryMetrics {
+            min_rtt,
+            smoothed_rtt,
+            latest_rtt,
+            pto_count,
+            congestion_window,
+            bytes_in_flight,
+            ..
+        } = &record.data
+        {
+            writeln!(
+                out,
+                "    {:>12} {congestion_window:>10} {bytes_in_flight:>10} {:>14} {:>12} {:>12} {pto_count:>5}",
+                format!("+{:.3}ms", ms(connection.elapsed(record))),
+                format!("{:.3}ms", ms(*smoothed_rtt)),
+                format!("{:.3}ms", ms(*min_rtt)),
+                format!("{:.3}ms", ms(*latest_rtt)),
+            )?;
+        }
+    }
+
+    let (packets_lost, bytes_lost) = connection
+        .records
+        .iter()
+        .filter_map(|record| match &record.data {
+            Data::PacketLost { bytes_lost, .. } => Some(*bytes_lost as u64),
+            _ => None,
+        })
+        .fold((0u64, 0u64), |(packets, bytes), lost| {
+            (packets + 1, bytes + lost)
+        });
+    writeln!(out, "  losses: {packets_lost} packets, {bytes_lost} bytes")?;
+
+    let count = |f: fn(&Data) -> bool| {
+        connection
+            .records
+            .iter()
+            .filter(|record| f(&record.data))
+            .count()
+    };
+    writeln!(
+        out,
+        "  packets: {} sent, {} received, {} dropped",
+        count(|data| matches!(data, Data::PacketSent { .. })),
+        count(|data| matches!(data, Data::PacketReceived { .. })),
+        count(|data| matches!(data, Data::PacketDropped { .. })),
+    )?;
+
+    match connection.close_reason() {
+        Some(reason) => writeln!(out, "  close reason: {reason}")?,
+        None => writeln!(
+            out,
+            "  close reason: none (the log ended before the connection closed)"
+        )?,
+    }
+
+    writeln!(out)
+}
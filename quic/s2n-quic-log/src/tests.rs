@@ -0,0 +1,256 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    connection::{Connection, Log},
+    csv, qlog, summary,
+};
+use core::time::Duration;
+use s2n_quic_core::{
+    endpoint,
+    event::log::{Data, HandshakeStatus, KeySpace, PacketSpace, Reader, Record, Writer},
+};
+
+fn log() -> Vec<u8> {
+    let record = |connection_id: u64, ms: u64, data: Data| Record {
+        connection_id,
+        timestamp: Duration::from_millis(ms),
+        data,
+    };
+
+    let records = [
+        record(
+            1,
+            100,
+            Data::ConnectionStarted {
+                endpoint_type: endpoint::Type::Client,
+                unix_time: Some(Duration::from_secs(1_700_000_000)),
+            },
+        ),
+        record(
+            2,
+            150,
+            Data::ConnectionStarted {
+                endpoint_type: endpoint::Type::Client,
+                unix_time: None,
+            },
+        ),
+        record(
+            1,
+            105,
+            Data::PacketSent {
+                space: PacketSpace::Initial,
+                packet_number: 0,
+                packet_len: 1_200,
+            },
+        ),
+        record(
+            1,
+            110,
+            Data::HandshakeStatusUpdated {
+                status: HandshakeStatus::Confirmed,
+            },
+        ),
+        record(
+            1,
+            120,
+            Data::RecoveryMetrics {
+                path_id: 0,
+                min_rtt: Duration::from_millis(10),
+                smoothed_rtt: Duration::from_millis(12),
+                latest_rtt: Duration::from_millis(11),
+                rtt_variance: Duration::from_millis(2),
+                pto_count: 0,
+                congestion_window: 12_000,
+                bytes_in_flight: 1_200,
+            },
+        ),
+        record(
+            1,
+            130,
+            Data::PacketLost {
+                path_id: 0,
+                space: PacketSpace::ApplicationData,
+                packet_number: 42,
+                bytes_lost: 1_200,
+            },
+        ),
+        record(
+            1,
+            132,
+            Data::PacketReceived {
+                space: PacketSpace::ApplicationData,
+                packet_number: 7,
+            },
+        ),
+        record(
+            1,
+            134,
+            Data::MtuUpdated {
+                path_id: 0,
+                mtu: 1_472,
+                cause: "ProbeAcknowledged".into(),
+                search_complete: true,
+            },
+        ),
+        record(
+            1,
+            136,
+            Data::KeySpaceDiscarded {
+                space: KeySpace::Handshake,
+            },
+        ),
+        record(
+            1,
+            138,
+            Data::PacketDropped {
+                reason: "DecryptionFailed".into(),
+            },
+        ),
+        record(
+            1,
+            140,
+            Data::ConnectionClosed {
+                reason: "The connection was closed because the idle timer expired".into(),
+            },
+        ),
+    ];
+
+    let mut writer = Writer::new(vec![]).unwrap();
+    for record in &records {
+        writer.write(record).unwrap();
+    }
+    writer.into_inner()
+}
+
+fn connections() -> Vec<Connection> {
+    let log = log();
+    let log = Connection::load(Reader::new(&log[..]).unwrap()).unwrap();
+    assert!(!log.truncated);
+    log.connections
+}
+
+#[test]
+fn load_test() {
+    let connections = connections();
+    assert_eq!(connections.len(), 2);
+
+    let connection = &connections[0];
+    assert_eq!(connection.id, 1);
+    assert_eq!(connection.endpoint_type, Some(endpoint::Type::Client));
+    assert_eq!(connection.records.len(), 10);
+    assert_eq!(
+        connection.unix_start,
+        Some(Duration::from_secs(1_700_000_000))
+    );
+    assert_eq!(connection.start(), Duration::from_millis(100));
+    assert_eq!(connection.end(), Duration::from_millis(140));
+    assert!(connection.close_reason().unwrap().contains("idle timer"));
+
+    assert_eq!(connections[1].close_reason(), None);
+}
+
+#[test]
+fn truncated_log_test() {
+    let mut log = log();
+    // drop the end of the close record
+    log.truncate(log.len() - 10);
+
+    let Log {
+        connections,
+        truncated,
+    } = Connection::load(Reader::new(&log[..]).unwrap()).unwrap();
+
+    assert!(truncated);
+    assert_eq!(connections.len(), 2);
+    // every record up to the truncated one is loaded
+    assert_eq!(connections[0].records.len(), 9);
+    assert_eq!(connections[0].close_reason(), None);
+}
+
+#[test]
+fn summary_test() {
+    let mut out = vec![];
+    for connection in &connections() {
+        summary::write(&mut out, connection).unwrap();
+    }
+    let out = String::from_utf8(out).unwrap();
+
+    assert!(out.contains("connection 1 (client)"));
+    assert!(out.contains("duration: 40.000ms"));
+    assert!(out.contains("+10.000ms handshake confirmed"));
+    assert!(out.contains("+30.000ms lost application_data packet 42 (1200 bytes)"));
+    assert!(out.contains("losses: 1 packets, 1200 bytes"));
+    assert!(out.contains("packets: 1 sent, 1 received, 1 dropped"));
+    assert!(out.contains("+34.000ms mtu 1472 (ProbeAcknowledged, search complete)"));
+    assert!(out.contains("+36.000ms discarded handshake keys"));
+    assert!(out.contains("+38.000ms dropped packet: DecryptionFailed"));
+    assert!(out.contains("close reason: The connection was closed because the idle timer expired"));
+    assert!(out.contains("close reason: none"));
+}
+
+#[test]
+fn qlog_test() {
+    let qlog = qlog::convert(&connections());
+
+    assert_eq!(qlog["qlog_version"], "0.3");
+    let traces = qlog["traces"].as_array().unwrap();
+    assert_eq!(traces.len(), 2);
+
+    let trace = &traces[0];
+    assert_eq!(trace["vantage_point"]["type"], "client");
+    // the reference time is the wall-clock start of the connection
+    assert_eq!(
+        trace["common_fields"]["reference_time"],
+        1_700_000_000_000.0
+    );
+    // the start time is unknown for the second connection
+    assert!(traces[1]["common_fields"].get("reference_time").is_none());
+
+    let events = trace["events"].as_array().unwrap();
+    assert_eq!(events.len(), 10);
+    assert_eq!(events[1]["name"], "transport:packet_sent");
+    assert_eq!(events[1]["data"]["header"]["packet_type"], "initial");
+    assert_eq!(events[1]["data"]["raw"]["length"], 1_200);
+    assert_eq!(events[3]["name"], "recovery:metrics_updated");
+    assert_eq!(events[3]["time"], 20.0);
+    assert_eq!(events[3]["data"]["congestion_window"], 12_000);
+    assert_eq!(events[3]["data"]["smoothed_rtt"], 12.0);
+    assert_eq!(events[4]["data"]["header"]["packet_type"], "1RTT");
+    assert_eq!(events[4]["data"]["header"]["packet_number"], 42);
+    assert_eq!(events[5]["name"], "transport:packet_received");
+    assert_eq!(events[6]["name"], "connectivity:mtu_updated");
+    assert_eq!(events[6]["data"]["new"], 1_472);
+    assert_eq!(events[7]["name"], "security:key_discarded");
+    assert_eq!(events[8]["name"], "transport:packet_dropped");
+    assert_eq!(events[8]["data"]["trigger"], "DecryptionFailed");
+}
+
+#[test]
+fn csv_test() {
+    let mut out = vec![];
+    csv::write(&mut out, &connections()).unwrap();
+    let out = String::from_utf8(out).unwrap();
+    let lines: Vec<_> = out.lines().collect();
+
+    // header + 11 records
+    assert_eq!(lines.len(), 12);
+
+    let columns = lines[0].split(',').count();
+    for line in &lines {
+        assert_eq!(line.split(',').count(), columns, "{line}");
+    }
+
+    assert_eq!(
+        lines[2],
+        "1,client,105.000,transport:packet_sent,,,,,,,initial,0,,1200,"
+    );
+    assert_eq!(
+        lines[4],
+        "1,client,120.000,recovery:metrics_updated,12000,1200,12.000,10.000,11.000,0,,,,,"
+    );
+    assert_eq!(
+        lines[5],
+        "1,client,130.000,recovery:packet_lost,,,,,,,application_data,42,1200,,"
+    );
+}
@@ -24,6 +24,14 @@ pub type PathHandle = message::Handle;
 pub use builder::Builder;
 pub(crate) use clock::Clock;
 
+/// Returns a future which completes once `duration` has elapsed on the tokio runtime's clock
+#[inline]
+pub fn delay(
+    duration: core::time::Duration,
+) -> core::pin::Pin<Box<dyn core::future::Future<Output = ()> + Send>> {
+    Box::pin(tokio::time::sleep(duration))
+}
+
 #[derive(Debug, Default)]
 pub struct Io {
     builder: Builder,
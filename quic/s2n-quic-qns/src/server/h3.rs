@@ -10,16 +10,33 @@ use futures::StreamExt;
 use h3::{quic::BidiStream, server::RequestStream};
 use http::StatusCode;
 use s2n_quic::Connection;
-use s2n_quic_h3::h3;
+use s2n_quic_h3::{
+    h3,
+    webtransport::{self, AcceptedBi, Session},
+};
 use std::{path::Path, sync::Arc, time::Duration};
-use tokio::time::timeout;
+use tokio::{io::AsyncWriteExt, time::timeout};
 
 pub async fn handle_connection(connection: Connection, www_dir: Arc<Path>) {
-    let mut conn = h3::server::Connection::new(s2n_quic_h3::Connection::new(connection))
+    let mut conn = webtransport::server_builder()
+        .build(s2n_quic_h3::Connection::new(connection))
         .await
         .unwrap();
 
     while let Ok(Some((req, mut stream))) = conn.accept().await {
+        if webtransport::is_connect(&req) {
+            // the WebTransport session takes over the rest of the connection
+            match Session::accept(req, stream, conn).await {
+                Ok(session) => {
+                    if let Err(err) = handle_webtransport(session).await {
+                        eprintln!("WebTransport session error: {err:?}");
+                    }
+                }
+                Err(err) => eprintln!("WebTransport session rejected: {err:?}"),
+            }
+            return;
+        }
+
         match req.uri().path() {
             "" | "/" => {
                 tokio::spawn(async move {
@@ -114,3 +131,75 @@ where
 
     Ok(())
 }
+
+/// Echoes all of the streams and datagrams received on the session back to the peer
+async fn handle_webtransport(session: Session) -> Result<()> {
+    let session = Arc::new(session);
+
+    let bidi = tokio::spawn({
+        let session = session.clone();
+        async move {
+            while let Some(accepted) = session.accept_bi().await? {
+                match accepted {
+                    AcceptedBi::BidiStream(stream) => {
+                        tokio::spawn(async move {
+                            let (mut send, mut recv) = stream.split();
+                            tokio::io::copy(&mut recv, &mut send).await?;
+                            send.shutdown().await?;
+                            <crate::Result<()>>::Ok(())
+                        });
+                    }
+                    AcceptedBi::Request(_req, mut stream) => {
+                        tokio::spawn(async move {
+                            let resp = http::Response::builder()
+                                .status(StatusCode::NOT_FOUND)
+                                .body(())?;
+                            stream.send_response(resp).await?;
+                            stream.finish().await?;
+                            <crate::Result<()>>::Ok(())
+                        });
+                    }
+                }
+            }
+            <crate::Result<()>>::Ok(())
+        }
+    });
+
+    let uni = tokio::spawn({
+        let session = session.clone();
+        async move {
+            while let Some(mut recv) = session.accept_uni().await? {
+                let session = session.clone();
+                tokio::spawn(async move {
+                    let mut send = session.open_uni().await?;
+                    tokio::io::copy(&mut recv, &mut send).await?;
+                    send.shutdown().await?;
+                    <crate::Result<()>>::Ok(())
+                });
+            }
+            <crate::Result<()>>::Ok(())
+        }
+    });
+
+    let datagrams = tokio::spawn({
+        let session = session.clone();
+        async move {
+            while let Some(datagram) = session.accept_datagram().await? {
+                session.send_datagram(datagram)?;
+            }
+            <crate::Result<()>>::Ok(())
+        }
+    });
+
+    let reason = session.closed().await;
+    eprintln!(
+        "WebTransport session {:?}: {reason:?}",
+        session.session_id()
+    );
+
+    bidi.abort();
+    uni.abort();
+    datagrams.abort();
+
+    Ok(())
+}
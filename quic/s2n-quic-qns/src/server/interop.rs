@@ -10,7 +10,7 @@ use crate::{
 };
 use s2n_quic::{
     provider::{
-        endpoint_limits,
+        datagram, endpoint_limits,
         event::{events, Subscriber},
     },
     Server,
@@ -100,10 +100,17 @@ impl Interop {
 
         let io = self.io.build()?;
 
+        // enable datagrams for WebTransport sessions over h3
+        let datagram = datagram::default::Endpoint::builder()
+            .with_send_capacity(200)?
+            .with_recv_capacity(200)?
+            .build()?;
+
         let server = Server::builder()
             .with_io(io)?
             .with_endpoint_limits(endpoint_limits)?
             .with_limits(limits)?
+            .with_datagram(datagram)?
             .with_event((
                 EventSubscriber,
                 s2n_quic::provider::event::tracing::Subscriber::default(),
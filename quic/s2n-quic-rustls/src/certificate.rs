@@ -80,6 +80,18 @@ cert_type!(
     into_certificate,
     Vec<rustls::pki_types::CertificateDer<'static>>
 );
+cert_type!(
+    PublicKey,
+    IntoPublicKey,
+    into_public_key,
+    rustls::pki_types::SubjectPublicKeyInfoDer<'static>
+);
+cert_type!(
+    CertificateRevocationList,
+    IntoCertificateRevocationList,
+    into_certificate_revocation_list,
+    Vec<rustls::pki_types::CertificateRevocationListDer<'static>>
+);
 
 impl IntoCertificate for Vec<Vec<u8>> {
     fn into_certificate(self) -> Result<Certificate, Error> {
@@ -94,7 +106,9 @@ impl IntoCertificate for Vec<Vec<u8>> {
 
 mod pem {
     use rustls::{
-        pki_types::{CertificateDer, PrivateKeyDer},
+        pki_types::{
+            CertificateDer, CertificateRevocationListDer, PrivateKeyDer, SubjectPublicKeyInfoDer,
+        },
         Error,
     };
 
@@ -105,6 +119,36 @@ mod pem {
             .collect()
     }
 
+    pub fn into_certificate_revocation_list(
+        contents: &[u8],
+    ) -> Result<Vec<CertificateRevocationListDer<'static>>, Error> {
+        let mut cursor = std::io::Cursor::new(contents);
+        rustls_pemfile::crls(&mut cursor)
+            .map(|crl| {
+                crl.map_err(|_| {
+                    Error::General("Could not read certificate revocation list".to_string())
+                })
+            })
+            .collect()
+    }
+
+    pub fn into_public_key(contents: &[u8]) -> Result<SubjectPublicKeyInfoDer<'static>, Error> {
+        let mut cursor = std::io::Cursor::new(contents);
+        let keys: Result<Vec<_>, Error> = rustls_pemfile::public_keys(&mut cursor)
+            .map(|key| key.map_err(|_| Error::General("Could not read public key".to_string())))
+            .collect();
+        let mut keys = keys?;
+        match keys.len() {
+            1 => Ok(keys.pop().unwrap()),
+            0 => Err(Error::General(
+                "could not load any valid public keys".to_string(),
+            )),
+            len => Err(Error::General(format!(
+                "Unexpected number of keys: {len} (only 1 supported)"
+            ))),
+        }
+    }
+
     pub fn into_private_key(contents: &[u8]) -> Result<PrivateKeyDer<'static>, Error> {
         let mut cursor = std::io::Cursor::new(contents);
 
@@ -152,7 +196,9 @@ mod pem {
 
 mod der {
     use rustls::{
-        pki_types::{CertificateDer, PrivateKeyDer},
+        pki_types::{
+            CertificateDer, CertificateRevocationListDer, PrivateKeyDer, SubjectPublicKeyInfoDer,
+        },
         Error,
     };
 
@@ -161,6 +207,17 @@ mod der {
         Ok(vec![CertificateDer::from(contents)])
     }
 
+    pub fn into_certificate_revocation_list(
+        contents: Vec<u8>,
+    ) -> Result<Vec<CertificateRevocationListDer<'static>>, Error> {
+        // der files only have a single crl
+        Ok(vec![CertificateRevocationListDer::from(contents)])
+    }
+
+    pub fn into_public_key(contents: Vec<u8>) -> Result<SubjectPublicKeyInfoDer<'static>, Error> {
+        Ok(SubjectPublicKeyInfoDer::from(contents))
+    }
+
     pub fn into_private_key(contents: Vec<u8>) -> Result<PrivateKeyDer<'static>, Error> {
         // PKCS #8 is used since it's capable of encoding RSA as well as other key
         // types (eg. ECDSA). Additionally, multiple attacks have been discovered
@@ -191,4 +248,16 @@ mod tests {
         let _ = CERT_DER.into_certificate().unwrap();
         let _ = KEY_DER.into_private_key().unwrap();
     }
+
+    #[test]
+    fn load_public_key() {
+        let pem = RPK_SERVER_PUBLIC_KEY.into_public_key().unwrap();
+        let der = RPK_SERVER_PUBLIC_KEY_DER.into_public_key().unwrap();
+        assert_eq!(pem.0, der.0);
+        let _ = RPK_SERVER_KEY.into_private_key().unwrap();
+        let _ = RPK_CLIENT_KEY.into_private_key().unwrap();
+
+        // certificates aren't public keys
+        assert!(CERT_PEM.into_public_key().is_err());
+    }
 }
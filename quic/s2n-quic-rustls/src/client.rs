@@ -1,9 +1,19 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{certificate, cipher_suite::default_crypto_provider, session::Session, Error};
+use crate::{
+    certificate, cipher_suite::default_crypto_provider, raw_public_key::PinnedPublicKeys,
+    resumption::SessionStore, session::Session, Error,
+};
 use core::convert::TryFrom;
-use rustls::{ClientConfig, ConfigBuilder, WantsVerifier};
+use rustls::{
+    client::{
+        AlwaysResolvesClientRawPublicKeys, EchConfig, EchMode, Resumption, WebPkiServerVerifier,
+    },
+    crypto::{aws_lc_rs::hpke, CryptoProvider},
+    pki_types::EchConfigListBytes,
+    ClientConfig, ConfigBuilder, WantsVerifier,
+};
 use s2n_codec::EncoderValue;
 use s2n_quic_core::{application::ServerName, crypto::tls};
 use std::sync::Arc;
@@ -11,15 +21,27 @@ use std::sync::Arc;
 /// Create a QUIC client specific [rustls::ConfigBuilder].
 ///
 /// Uses aws_lc_rs as the crypto provider and sets QUIC specific protocol versions.
-fn default_config_builder() -> Result<ConfigBuilder<ClientConfig, WantsVerifier>, rustls::Error> {
-    let tls13_cipher_suite_crypto_provider = default_crypto_provider()?;
-    ClientConfig::builder_with_provider(tls13_cipher_suite_crypto_provider.into())
-        .with_protocol_versions(crate::PROTOCOL_VERSIONS)
+fn default_config_builder(
+    crypto_provider: Arc<CryptoProvider>,
+    ech_mode: Option<EchMode>,
+) -> Result<ConfigBuilder<ClientConfig, WantsVerifier>, rustls::Error> {
+    let builder = ClientConfig::builder_with_provider(crypto_provider);
+    match ech_mode {
+        // ECH is only supported with TLS 1.3 so this also sets the protocol versions
+        Some(ech_mode) => builder.with_ech(ech_mode),
+        None => builder.with_protocol_versions(crate::PROTOCOL_VERSIONS),
+    }
 }
 
 #[derive(Clone)]
 pub struct Client {
     config: Arc<ClientConfig>,
+    /// Set if the session should process session tickets after the handshake
+    session_tickets: bool,
+    /// Set if servers authenticate with raw public keys instead of certificates
+    raw_public_keys: bool,
+    /// Set if the ClientHello is encrypted
+    ech: bool,
 }
 
 impl Client {
@@ -32,9 +54,7 @@ impl Client {
     /// - QUIC compliant ciphersuites
     #[deprecated = "client and server builders should be used instead"]
     pub fn new(config: ClientConfig) -> Self {
-        Self {
-            config: Arc::new(config),
-        }
+        Self::from(Arc::new(config))
     }
 
     pub fn builder() -> Builder {
@@ -61,7 +81,12 @@ impl From<ClientConfig> for Client {
 // TODO this should be removed after removing deprecated re-exports
 impl From<Arc<ClientConfig>> for Client {
     fn from(config: Arc<ClientConfig>) -> Self {
-        Self { config }
+        Self {
+            config,
+            session_tickets: false,
+            raw_public_keys: false,
+            ech: false,
+        }
     }
 }
 
@@ -95,7 +120,11 @@ impl tls::Endpoint for Client {
         )
         .expect("could not create rustls client session");
 
-        Session::new(session.into(), Some(server_name))
+        let mut session = Session::new(session.into(), Some(server_name));
+        session.receive_session_tickets(self.session_tickets);
+        session.peer_raw_public_key(self.raw_public_keys);
+        session.offer_ech(self.ech);
+        session
     }
 
     fn max_tag_length(&self) -> usize {
@@ -103,10 +132,21 @@ impl tls::Endpoint for Client {
     }
 }
 
+/// The credentials presented to servers which request client authentication
+enum Identity {
+    Certificate(certificate::Certificate, certificate::PrivateKey),
+    RawPublicKey(certificate::PrivateKey),
+}
+
 pub struct Builder {
     cert_store: rustls::RootCertStore,
+    public_keys: Vec<certificate::PublicKey>,
     application_protocols: Vec<Vec<u8>>,
     key_log: Option<Arc<dyn rustls::KeyLog>>,
+    client_identity: Option<Identity>,
+    crls: Vec<rustls::pki_types::CertificateRevocationListDer<'static>>,
+    session_store: Option<SessionStore>,
+    ech_config: Option<EchConfig>,
 }
 
 impl Default for Builder {
@@ -119,8 +159,13 @@ impl Builder {
     pub fn new() -> Self {
         Self {
             cert_store: rustls::RootCertStore::empty(),
+            public_keys: Vec::new(),
             application_protocols: vec![b"h3".to_vec()],
             key_log: None,
+            client_identity: None,
+            crls: Vec::new(),
+            session_store: None,
+            ech_config: None,
         }
     }
 
@@ -138,6 +183,93 @@ impl Builder {
         Ok(self)
     }
 
+    /// Sets the certificate and private key presented to servers which request client
+    /// authentication (mutual TLS)
+    pub fn with_client_identity<
+        C: certificate::IntoCertificate,
+        PK: certificate::IntoPrivateKey,
+    >(
+        mut self,
+        certificate: C,
+        private_key: PK,
+    ) -> Result<Self, Error> {
+        let certificate = certificate.into_certificate()?;
+        let private_key = private_key.into_private_key()?;
+        self.client_identity = Some(Identity::Certificate(certificate, private_key));
+        Ok(self)
+    }
+
+    /// Trusts a server which authenticates with the given raw public key instead of a
+    /// certificate chain (<https://www.rfc-editor.org/rfc/rfc7250>)
+    ///
+    /// Multiple keys can be trusted. Once a public key is trusted, servers can't
+    /// authenticate with certificates.
+    pub fn with_trusted_public_key<K: certificate::IntoPublicKey>(
+        mut self,
+        public_key: K,
+    ) -> Result<Self, Error> {
+        let public_key = public_key.into_public_key()?;
+        self.public_keys.push(public_key);
+        Ok(self)
+    }
+
+    /// Presents the raw public key of `private_key` to servers which request client
+    /// authentication, instead of a certificate chain
+    ///
+    /// Servers must be configured to trust the public key with
+    /// [`server::Builder::with_trusted_public_key`](crate::server::Builder::with_trusted_public_key).
+    pub fn with_raw_public_key_identity<PK: certificate::IntoPrivateKey>(
+        mut self,
+        private_key: PK,
+    ) -> Result<Self, Error> {
+        let private_key = private_key.into_private_key()?;
+        self.client_identity = Some(Identity::RawPublicKey(private_key));
+        Ok(self)
+    }
+
+    /// Adds certificate revocation lists which are checked when verifying server certificates
+    pub fn with_certificate_revocation_list<C: certificate::IntoCertificateRevocationList>(
+        mut self,
+        crl: C,
+    ) -> Result<Self, Error> {
+        let crls = crl.into_certificate_revocation_list()?;
+        self.crls.extend(crls.0);
+        Ok(self)
+    }
+
+    /// Stores session tickets issued by servers in the provided store and uses them to
+    /// resume later handshakes
+    ///
+    /// The same store can be used by multiple clients.
+    pub fn with_session_store(mut self, store: SessionStore) -> Result<Self, Error> {
+        self.session_store = Some(store);
+        Ok(self)
+    }
+
+    /// Encrypts the ClientHello with one of the configurations in the provided ECHConfigList
+    /// (<https://datatracker.ietf.org/doc/draft-ietf-tls-esni/>)
+    ///
+    /// Encrypted Client Hello (ECH) hides the server name and other sensitive extensions
+    /// from on-path observers; only the public name of the selected configuration is sent
+    /// in the clear. The ECHConfigList is usually retrieved from the HTTPS DNS record of
+    /// the server.
+    ///
+    /// If the server rejects ECH, the handshake fails after the server authenticates itself
+    /// for the public name. The outcome is reported with the `TlsEchStatus` event, along
+    /// with the `ECHConfigList` the server provided for retrying, which can be passed to
+    /// this method to establish a new connection.
+    ///
+    /// Note that neither TLS provider supports ECH on servers: rustls and s2n-tls don't
+    /// implement decrypting the ClientHello, so servers can't be configured with HPKE keys.
+    /// The server must be fronted by an ECH-capable implementation for the offer to be
+    /// accepted.
+    pub fn with_ech_config_list<L: AsRef<[u8]>>(mut self, config_list: L) -> Result<Self, Error> {
+        let config_list = EchConfigListBytes::from(config_list.as_ref());
+        let config = EchConfig::new(config_list, hpke::ALL_SUPPORTED_SUITES)?;
+        self.ech_config = Some(config);
+        Ok(self)
+    }
+
     pub fn with_max_cert_chain_depth(self, len: u16) -> Result<Self, Error> {
         // TODO is there a way to configure this?
         let _ = len;
@@ -152,14 +284,24 @@ impl Builder {
         Ok(self)
     }
 
+    /// Logs TLS secrets to the file specified by the `SSLKEYLOGFILE` environment variable
     pub fn with_key_logging(mut self) -> Result<Self, Error> {
         self.key_log = Some(Arc::new(rustls::KeyLogFile::new()));
         Ok(self)
     }
 
+    /// Logs TLS secrets to the provided [`KeyLog`](s2n_quic_core::crypto::tls::KeyLog)
+    ///
+    /// **WARNING**: the logged secrets can be used to decrypt all traffic and should only be
+    /// enabled for debugging.
+    pub fn with_key_log<K: tls::KeyLog>(mut self, key_log: K) -> Result<Self, Error> {
+        self.key_log = Some(crate::keylog::KeyLog::wrap(key_log));
+        Ok(self)
+    }
+
     pub fn build(self) -> Result<Client, Error> {
         // TODO load system root store?
-        if self.cert_store.is_empty() {
+        if self.cert_store.is_empty() && self.public_keys.is_empty() {
             //= https://www.rfc-editor.org/rfc/rfc9001#section-4.4
             //# A client MUST authenticate the identity of the server.
             return Err(
@@ -167,9 +309,48 @@ impl Builder {
             );
         }
 
-        let mut config = default_config_builder()?
-            .with_root_certificates(self.cert_store)
-            .with_no_client_auth();
+        let crypto_provider = Arc::new(default_crypto_provider()?);
+        let ech = self.ech_config.is_some();
+        let ech_mode = self.ech_config.map(EchMode::Enable);
+        let builder = default_config_builder(crypto_provider.clone(), ech_mode)?;
+        let raw_public_keys = !self.public_keys.is_empty();
+        let builder = if raw_public_keys {
+            if !self.cert_store.is_empty() {
+                return Err(rustls::Error::General(
+                    "trusted certificates and public keys can't be combined".to_string(),
+                )
+                .into());
+            }
+
+            let algorithms = crypto_provider.signature_verification_algorithms;
+            let verifier = PinnedPublicKeys::new(self.public_keys, algorithms);
+            builder
+                .dangerous()
+                .with_custom_certificate_verifier(Arc::new(verifier))
+        } else if self.crls.is_empty() {
+            builder.with_root_certificates(self.cert_store)
+        } else {
+            let verifier = WebPkiServerVerifier::builder_with_provider(
+                self.cert_store.into(),
+                crypto_provider,
+            )
+            .with_crls(self.crls)
+            .build()
+            .map_err(|err| rustls::Error::General(err.to_string()))?;
+            builder.with_webpki_verifier(verifier)
+        };
+
+        let mut config = match self.client_identity {
+            Some(Identity::Certificate(certificate, private_key)) => {
+                builder.with_client_auth_cert(certificate.0, private_key.0)?
+            }
+            Some(Identity::RawPublicKey(private_key)) => {
+                let key = crate::raw_public_key::certified_key(private_key)?;
+                let resolver = AlwaysResolvesClientRawPublicKeys::new(key);
+                builder.with_client_cert_resolver(Arc::new(resolver))
+            }
+            None => builder.with_no_client_auth(),
+        };
 
         config.max_fragment_size = None;
         config.alpn_protocols = self.application_protocols;
@@ -178,7 +359,16 @@ impl Builder {
             config.key_log = key_log;
         }
 
-        #[allow(deprecated)]
-        Ok(Client::new(config))
+        config.resumption = match self.session_store.as_ref() {
+            Some(store) => Resumption::store(store.0.clone()),
+            None => Resumption::disabled(),
+        };
+
+        Ok(Client {
+            config: Arc::new(config),
+            session_tickets: self.session_store.is_some(),
+            raw_public_keys,
+            ech,
+        })
     }
 }
@@ -0,0 +1,58 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Encrypted Client Hello
+//!
+//! See <https://datatracker.ietf.org/doc/draft-ietf-tls-esni/>
+
+use s2n_codec::{u24, DecoderBuffer, DecoderError};
+
+/// The `encrypted_extensions` handshake type
+///
+/// See <https://www.rfc-editor.org/rfc/rfc8446#section-4>
+const ENCRYPTED_EXTENSIONS: u8 = 8;
+
+/// The `encrypted_client_hello` extension type
+///
+/// See <https://datatracker.ietf.org/doc/html/draft-ietf-tls-esni#section-11.1>
+const ENCRYPTED_CLIENT_HELLO: u16 = 0xfe0d;
+
+/// Reads the server's retry configurations from the start of its handshake flight
+///
+/// The server sends them as an `ECHConfigList` in the `encrypted_client_hello` extension of
+/// EncryptedExtensions, which is the first message in the handshake packet space. rustls only
+/// exposes the decoded configurations, so the encoding is read from the message instead.
+///
+/// Returns `None` if the message hasn't been completely received yet.
+pub(crate) fn retry_configs(handshake: &[u8]) -> Option<Option<Vec<u8>>> {
+    let buffer = DecoderBuffer::new(handshake);
+    let (msg_type, buffer) = buffer.decode::<u8>().ok()?;
+    let (len, buffer) = buffer.decode::<u24>().ok()?;
+    let (message, _) = buffer.decode_slice(u32::from(len) as usize).ok()?;
+
+    if msg_type != ENCRYPTED_EXTENSIONS {
+        return Some(None);
+    }
+
+    // malformed messages are rejected by rustls so they don't have any configurations
+    Some(find_extension(message).ok().flatten())
+}
+
+/// Returns the `retry_configs` from the `encrypted_client_hello` extension, if present
+fn find_extension(message: DecoderBuffer) -> Result<Option<Vec<u8>>, DecoderError> {
+    let (mut extensions, _) = message.decode_slice_with_len_prefix::<u16>()?;
+
+    while !extensions.is_empty() {
+        let (extension_type, remaining) = extensions.decode::<u16>()?;
+        let (data, remaining) = remaining.decode_slice_with_len_prefix::<u16>()?;
+
+        if extension_type == ENCRYPTED_CLIENT_HELLO {
+            // the extension only contains the `ECHConfigList`
+            return Ok(Some(data.into_less_safe_slice().to_vec()));
+        }
+
+        extensions = remaining;
+    }
+
+    Ok(None)
+}
@@ -0,0 +1,31 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use s2n_quic_core::crypto::tls;
+use std::sync::Arc;
+
+/// Forwards secrets from rustls to a [`tls::KeyLog`]
+pub(crate) struct KeyLog(Arc<dyn tls::KeyLog>);
+
+impl KeyLog {
+    pub fn wrap<K: tls::KeyLog>(key_log: K) -> Arc<dyn rustls::KeyLog> {
+        Arc::new(Self(Arc::new(key_log)))
+    }
+}
+
+impl core::fmt::Debug for KeyLog {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("KeyLog").finish_non_exhaustive()
+    }
+}
+
+impl rustls::KeyLog for KeyLog {
+    fn log(&self, label: &str, client_random: &[u8], secret: &[u8]) {
+        self.0
+            .log(&tls::keylog::current(), label, client_random, secret)
+    }
+
+    fn will_log(&self, label: &str) -> bool {
+        self.0.will_log(label)
+    }
+}
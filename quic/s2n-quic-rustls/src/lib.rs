@@ -24,11 +24,15 @@ pub static DEFAULT_CIPHERSUITES: &[rustls::SupportedCipherSuite] =
 type Error = Box<dyn std::error::Error + Send + Sync + 'static>;
 
 mod cipher_suite;
+mod ech;
 mod error;
+mod keylog;
+mod raw_public_key;
 mod session;
 
 pub mod certificate;
 pub mod client;
+pub mod resumption;
 pub mod server;
 
 pub use client::Client;
@@ -114,4 +118,555 @@ mod tests {
 
         pair.finish();
     }
+
+    fn mtls_pair(
+        client: client::Builder,
+        server: server::Builder,
+    ) -> tls::testing::Pair<session::Session, session::Session> {
+        let mut client = client
+            .with_certificate(MTLS_CA_CERT)
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let mut server = server
+            .with_certificate(MTLS_SERVER_CERT, MTLS_SERVER_KEY)
+            .unwrap()
+            .with_trusted_certificate(MTLS_CA_CERT)
+            .unwrap()
+            .build()
+            .unwrap();
+
+        tls::testing::Pair::new(&mut server, &mut client, "localhost".into())
+    }
+
+    #[test]
+    fn mtls_client_server_test() {
+        let client = client::Builder::new()
+            .with_client_identity(MTLS_CLIENT_CERT, MTLS_CLIENT_KEY)
+            .unwrap();
+        let server = server::Builder::new().with_client_authentication().unwrap();
+        let mut pair = mtls_pair(client, server);
+
+        while pair.is_handshaking() {
+            pair.poll(None).unwrap();
+        }
+
+        pair.finish();
+
+        // the server has access to the client's certificate chain
+        let chain = tls::TlsSession::peer_cert_chain_der(&pair.server.session).unwrap();
+        let expected = certificate::IntoCertificate::into_certificate(MTLS_CLIENT_CERT).unwrap();
+        assert_eq!(chain[0], expected.0[0].to_vec());
+    }
+
+    #[test]
+    fn mtls_missing_client_identity_test() {
+        let client = client::Builder::new();
+        let server = server::Builder::new().with_client_authentication().unwrap();
+        let mut pair = mtls_pair(client, server);
+
+        let mut result = Ok(());
+        while result.is_ok() && pair.is_handshaking() {
+            result = pair.poll(None);
+        }
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn mtls_optional_client_authentication_test() {
+        let client = client::Builder::new();
+        let server = server::Builder::new()
+            .with_optional_client_authentication()
+            .unwrap();
+        let mut pair = mtls_pair(client, server);
+
+        while pair.is_handshaking() {
+            pair.poll(None).unwrap();
+        }
+
+        pair.finish();
+    }
+
+    #[test]
+    fn mtls_missing_trust_store_test() {
+        let result = server::Builder::new()
+            .with_certificate(MTLS_SERVER_CERT, MTLS_SERVER_KEY)
+            .unwrap()
+            .with_client_authentication()
+            .unwrap()
+            .build();
+        assert!(result.is_err());
+    }
+
+    fn raw_public_key_pair(
+        client: client::Builder,
+        server: server::Builder,
+    ) -> tls::testing::Pair<session::Session, session::Session> {
+        let mut client = client
+            .with_trusted_public_key(RPK_SERVER_PUBLIC_KEY)
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let mut server = server
+            .with_raw_public_key(RPK_SERVER_KEY)
+            .unwrap()
+            .build()
+            .unwrap();
+
+        tls::testing::Pair::new(&mut server, &mut client, "localhost".into())
+    }
+
+    #[test]
+    fn raw_public_key_test() {
+        let mut pair = raw_public_key_pair(client::Builder::new(), server::Builder::new());
+
+        while pair.is_handshaking() {
+            pair.poll(None).unwrap();
+        }
+
+        pair.finish();
+        pair.assert_peer_public_keys(RPK_SERVER_PUBLIC_KEY_DER, None);
+
+        // raw public keys aren't certificate chains
+        assert!(tls::TlsSession::peer_cert_chain_der(&pair.client.session).is_err());
+    }
+
+    #[test]
+    fn raw_public_key_mutual_test() {
+        let client = client::Builder::new()
+            .with_raw_public_key_identity(RPK_CLIENT_KEY)
+            .unwrap();
+        let server = server::Builder::new()
+            .with_trusted_public_key(RPK_CLIENT_PUBLIC_KEY)
+            .unwrap()
+            .with_client_authentication()
+            .unwrap();
+        let mut pair = raw_public_key_pair(client, server);
+
+        while pair.is_handshaking() {
+            pair.poll(None).unwrap();
+        }
+
+        pair.finish();
+        pair.assert_peer_public_keys(RPK_SERVER_PUBLIC_KEY_DER, Some(RPK_CLIENT_PUBLIC_KEY_DER));
+    }
+
+    #[test]
+    fn raw_public_key_untrusted_test() {
+        let mut client = client::Builder::new()
+            .with_trusted_public_key(RPK_SERVER_PUBLIC_KEY)
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let mut server = server::Builder::new()
+            .with_raw_public_key(RPK_UNTRUSTED_KEY)
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let mut pair = tls::testing::Pair::new(&mut server, &mut client, "localhost".into());
+
+        let mut result = Ok(());
+        while result.is_ok() && pair.is_handshaking() {
+            result = pair.poll(None);
+        }
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn raw_public_key_untrusted_client_test() {
+        let client = client::Builder::new()
+            .with_raw_public_key_identity(RPK_UNTRUSTED_KEY)
+            .unwrap();
+        let server = server::Builder::new()
+            .with_trusted_public_key(RPK_CLIENT_PUBLIC_KEY)
+            .unwrap()
+            .with_client_authentication()
+            .unwrap();
+        let mut pair = raw_public_key_pair(client, server);
+
+        let mut result = Ok(());
+        while result.is_ok() && pair.is_handshaking() {
+            result = pair.poll(None);
+        }
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn raw_public_key_certificate_mismatch_test() {
+        // the client expects a raw public key but the server presents a certificate
+        let mut client = client::Builder::new()
+            .with_trusted_public_key(RPK_SERVER_PUBLIC_KEY)
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let mut server = server::Builder::new()
+            .with_certificate(CERT_PEM, KEY_PEM)
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let mut pair = tls::testing::Pair::new(&mut server, &mut client, "localhost".into());
+
+        let mut result = Ok(());
+        while result.is_ok() && pair.is_handshaking() {
+            result = pair.poll(None);
+        }
+        assert!(result.is_err());
+    }
+
+    /// Encodes an ECHConfigList with a single X25519/HKDF-SHA256/AES-128-GCM configuration
+    fn ech_config_list(public_name: &str) -> Vec<u8> {
+        let mut contents = vec![];
+        // config_id
+        contents.push(1);
+        // kem_id: DHKEM(X25519, HKDF-SHA256)
+        contents.extend_from_slice(&0x0020u16.to_be_bytes());
+        // public_key: the X25519 base point
+        let mut public_key = [0u8; 32];
+        public_key[0] = 9;
+        contents.extend_from_slice(&(public_key.len() as u16).to_be_bytes());
+        contents.extend_from_slice(&public_key);
+        // cipher_suites: HKDF-SHA256, AES-128-GCM
+        contents.extend_from_slice(&4u16.to_be_bytes());
+        contents.extend_from_slice(&0x0001u16.to_be_bytes());
+        contents.extend_from_slice(&0x0001u16.to_be_bytes());
+        // maximum_name_length
+        contents.push(0);
+        contents.push(public_name.len() as u8);
+        contents.extend_from_slice(public_name.as_bytes());
+        // extensions
+        contents.extend_from_slice(&0u16.to_be_bytes());
+
+        let mut config = vec![];
+        // version: draft-ietf-tls-esni-18
+        config.extend_from_slice(&0xfe0du16.to_be_bytes());
+        config.extend_from_slice(&(contents.len() as u16).to_be_bytes());
+        config.extend_from_slice(&contents);
+
+        let mut list = (config.len() as u16).to_be_bytes().to_vec();
+        list.extend_from_slice(&config);
+        list
+    }
+
+    #[test]
+    fn ech_rejected_test() {
+        let mut client = client::Builder::new()
+            .with_certificate(CERT_PEM)
+            .unwrap()
+            .with_ech_config_list(ech_config_list("localhost"))
+            .unwrap()
+            .build()
+            .unwrap();
+
+        // the server doesn't support ECH so it uses the outer ClientHello and authenticates
+        // itself for the public name
+        let mut server = server::Builder::new()
+            .with_certificate(CERT_PEM, KEY_PEM)
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let mut pair =
+            tls::testing::Pair::new(&mut server, &mut client, "secret.example.com".into());
+
+        let mut result = Ok(());
+        while result.is_ok() && pair.is_handshaking() {
+            result = pair.poll(None);
+        }
+        assert!(result.is_err());
+
+        assert_eq!(
+            pair.client.context.ech_status,
+            Some(tls::EchStatus::Rejected)
+        );
+        // the server doesn't know about ECH so it doesn't provide any retry configurations
+        assert_eq!(pair.client.context.ech_retry_configs, None);
+        assert_eq!(pair.server.context.ech_status, None);
+
+        // the server only sees the public name, even though ECH was rejected
+        assert_eq!(
+            pair.server.context.server_name.as_deref(),
+            Some(&b"localhost"[..])
+        );
+    }
+
+    #[test]
+    fn ech_retry_configs_test() {
+        let config_list = ech_config_list("localhost");
+
+        let encrypted_extensions = |extensions: &[(u16, &[u8])]| {
+            let mut list = vec![];
+            for (extension_type, data) in extensions {
+                list.extend_from_slice(&extension_type.to_be_bytes());
+                list.extend_from_slice(&(data.len() as u16).to_be_bytes());
+                list.extend_from_slice(data);
+            }
+            let mut message = vec![8];
+            message.extend_from_slice(&(list.len() as u32 + 2).to_be_bytes()[1..]);
+            message.extend_from_slice(&(list.len() as u16).to_be_bytes());
+            message.extend_from_slice(&list);
+            message
+        };
+
+        // retry configurations are reported in the same format they are configured with
+        let message = encrypted_extensions(&[(0x0010, b"\x00\x03\x02h3"), (0xfe0d, &config_list)]);
+        assert_eq!(
+            ech::retry_configs(&message),
+            Some(Some(config_list.clone()))
+        );
+        assert!(client::Builder::new()
+            .with_ech_config_list(ech::retry_configs(&message).unwrap().unwrap())
+            .is_ok());
+
+        // the message is buffered until it's complete
+        assert_eq!(ech::retry_configs(&message[..message.len() - 1]), None);
+        assert_eq!(ech::retry_configs(&message[..2]), None);
+
+        // servers that don't support ECH don't send the extension
+        let message = encrypted_extensions(&[(0x0010, b"\x00\x03\x02h3")]);
+        assert_eq!(ech::retry_configs(&message), Some(None));
+
+        // other messages don't have any configurations
+        let mut message = encrypted_extensions(&[(0xfe0d, &config_list)]);
+        message[0] = 11;
+        assert_eq!(ech::retry_configs(&message), Some(None));
+    }
+
+    #[test]
+    fn ech_invalid_config_list_test() {
+        assert!(client::Builder::new()
+            .with_ech_config_list([0u8, 1, 2])
+            .is_err());
+
+        // unsupported versions are skipped, which leaves no usable configurations
+        let mut config_list = ech_config_list("localhost");
+        config_list[2] = 0xff;
+        assert!(client::Builder::new()
+            .with_ech_config_list(config_list)
+            .is_err());
+    }
+
+    #[test]
+    fn no_ech_test() {
+        let mut pair = {
+            let mut client = client::Builder::new()
+                .with_certificate(CERT_PEM)
+                .unwrap()
+                .build()
+                .unwrap();
+            let mut server = server::Builder::new()
+                .with_certificate(CERT_PEM, KEY_PEM)
+                .unwrap()
+                .build()
+                .unwrap();
+            tls::testing::Pair::new(&mut server, &mut client, "localhost".into())
+        };
+
+        while pair.is_handshaking() {
+            pair.poll(None).unwrap();
+        }
+
+        pair.finish();
+        assert_eq!(pair.client.context.ech_status, None);
+    }
+
+    #[test]
+    fn key_log_test() {
+        use std::sync::Arc;
+
+        let client_key_log = Arc::new(tls::keylog::Buffer::default());
+        let server_key_log = Arc::new(tls::keylog::Buffer::default());
+
+        let mut client = client::Builder::new()
+            .with_certificate(CERT_PEM)
+            .unwrap()
+            .with_key_log(client_key_log.clone())
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let mut server = server::Builder::new()
+            .with_certificate(CERT_PEM, KEY_PEM)
+            .unwrap()
+            .with_key_log(server_key_log.clone())
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let mut pair = tls::testing::Pair::new(&mut server, &mut client, "localhost".into());
+        pair.client.context.connection_id = Some(1);
+        pair.server.context.connection_id = Some(2);
+
+        while pair.is_handshaking() {
+            pair.poll(None).unwrap();
+        }
+
+        pair.finish();
+
+        // secrets should be attributed to the connection which derived them
+        assert_eq!(client_key_log.take_connection(2), "");
+        assert_eq!(server_key_log.take_connection(1), "");
+
+        let mut client_lines: Vec<_> = client_key_log
+            .take_connection(1)
+            .lines()
+            .map(String::from)
+            .collect();
+        let mut server_lines: Vec<_> = server_key_log
+            .take_connection(2)
+            .lines()
+            .map(String::from)
+            .collect();
+        client_lines.sort();
+        server_lines.sort();
+
+        assert!(!client_lines.is_empty());
+        assert_eq!(client_lines, server_lines);
+    }
+
+    #[test]
+    fn resumption_test() {
+        let store = resumption::SessionStore::default();
+        let ticketer = resumption::SessionTicketer::new().unwrap();
+
+        let mut client = client::Builder::new()
+            .with_certificate(CERT_PEM)
+            .unwrap()
+            .with_session_store(store.clone())
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let mut server = server::Builder::new()
+            .with_certificate(CERT_PEM, KEY_PEM)
+            .unwrap()
+            .with_session_ticketer(ticketer.clone())
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let mut pair = tls::testing::Pair::new(&mut server, &mut client, "localhost".into());
+
+        while pair.is_handshaking() {
+            pair.poll(None).unwrap();
+        }
+
+        pair.finish();
+
+        assert!(
+            !pair.client.context.application.rx.is_empty(),
+            "expected session ticket message in RX"
+        );
+        assert!(!tls::Session::should_discard_session(&pair.client.session));
+        tls::Session::process_post_handshake_message(
+            &mut pair.client.session,
+            &mut pair.client.context,
+        )
+        .unwrap();
+        assert!(tls::Session::should_discard_session(&pair.client.session));
+
+        // the server presents a certificate the client doesn't trust so the handshake
+        // can only succeed by resuming the previous session
+        let mut server = server::Builder::new()
+            .with_certificate(UNTRUSTED_CERT_PEM, UNTRUSTED_KEY_PEM)
+            .unwrap()
+            .with_session_ticketer(ticketer)
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let mut pair = tls::testing::Pair::new(&mut server, &mut client, "localhost".into());
+
+        while pair.is_handshaking() {
+            pair.poll(None).unwrap();
+        }
+
+        pair.finish();
+    }
+
+    #[test]
+    fn partial_ticket_test() {
+        let mut client = client::Builder::new()
+            .with_certificate(CERT_PEM)
+            .unwrap()
+            .with_session_store(resumption::SessionStore::default())
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let ticketer = resumption::SessionTicketer::with_rotation_interval(
+            core::time::Duration::from_secs(60),
+        )
+        .unwrap();
+        let mut server = server::Builder::new()
+            .with_certificate(CERT_PEM, KEY_PEM)
+            .unwrap()
+            .with_session_ticketer(ticketer)
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let mut pair = tls::testing::Pair::new(&mut server, &mut client, "localhost".into());
+
+        while pair.is_handshaking() {
+            pair.poll(None).unwrap();
+        }
+
+        pair.finish();
+
+        // deliver a partial ticket first, as if it was split across CRYPTO frames
+        let mut ticket: Vec<u8> = pair
+            .client
+            .context
+            .application
+            .rx
+            .drain(..)
+            .flat_map(|chunk| chunk.to_vec())
+            .collect();
+        let rest = ticket.split_off(8);
+
+        for part in [ticket, rest] {
+            assert!(!tls::Session::should_discard_session(&pair.client.session));
+            pair.client.context.application.rx.push_back(part.into());
+            tls::Session::process_post_handshake_message(
+                &mut pair.client.session,
+                &mut pair.client.context,
+            )
+            .unwrap();
+        }
+
+        assert!(tls::Session::should_discard_session(&pair.client.session));
+    }
+
+    #[test]
+    fn no_resumption_test() {
+        let mut client = client::Builder::new()
+            .with_certificate(CERT_PEM)
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let mut server = server::Builder::new()
+            .with_certificate(CERT_PEM, KEY_PEM)
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let mut pair = tls::testing::Pair::new(&mut server, &mut client, "localhost".into());
+
+        while pair.is_handshaking() {
+            pair.poll(None).unwrap();
+        }
+
+        pair.finish();
+
+        // servers don't issue tickets unless a ticketer is configured
+        assert!(pair.client.context.application.rx.is_empty());
+        assert!(tls::Session::should_discard_session(&pair.client.session));
+    }
 }
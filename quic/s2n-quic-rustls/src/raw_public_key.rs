@@ -0,0 +1,174 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Authentication with raw public keys instead of certificate chains
+//!
+//! See <https://www.rfc-editor.org/rfc/rfc7250>
+
+use crate::certificate::{PrivateKey, PublicKey};
+use rustls::{
+    client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier},
+    crypto::{verify_tls13_signature_with_raw_key, WebPkiSupportedAlgorithms},
+    pki_types::{CertificateDer, ServerName, SubjectPublicKeyInfoDer, UnixTime},
+    server::danger::{ClientCertVerified, ClientCertVerifier},
+    sign::CertifiedKey,
+    CertificateError, DigitallySignedStruct, DistinguishedName, Error, PeerIncompatible,
+    SignatureScheme,
+};
+use std::sync::Arc;
+
+/// Creates a [`CertifiedKey`] which presents the public key of `private_key` to the peer
+pub(crate) fn certified_key(private_key: PrivateKey) -> Result<Arc<CertifiedKey>, Error> {
+    let key = rustls::crypto::aws_lc_rs::sign::any_supported_type(&private_key.0)
+        .map_err(|_| Error::General("invalid private key".into()))?;
+    let public_key = key
+        .public_key()
+        .ok_or_else(|| Error::General("unsupported private key type".into()))?;
+
+    // the raw public key takes the place of the end-entity certificate
+    let public_key = CertificateDer::from(public_key.to_vec());
+
+    Ok(Arc::new(CertifiedKey::new(vec![public_key], key)))
+}
+
+/// Verifies that the peer presents one of a set of pinned public keys
+#[derive(Debug)]
+pub(crate) struct PinnedPublicKeys {
+    keys: Vec<SubjectPublicKeyInfoDer<'static>>,
+    algorithms: WebPkiSupportedAlgorithms,
+    mandatory: bool,
+}
+
+impl PinnedPublicKeys {
+    pub fn new(keys: Vec<PublicKey>, algorithms: WebPkiSupportedAlgorithms) -> Self {
+        Self {
+            keys: keys.into_iter().map(|key| key.0).collect(),
+            algorithms,
+            mandatory: true,
+        }
+    }
+
+    /// Allows clients to connect without presenting a public key
+    pub fn allow_unauthenticated(mut self) -> Self {
+        self.mandatory = false;
+        self
+    }
+
+    fn verify(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        intermediates: &[CertificateDer<'_>],
+    ) -> Result<(), Error> {
+        if !intermediates.is_empty() {
+            return Err(Error::InvalidCertificate(CertificateError::BadEncoding));
+        }
+
+        if self
+            .keys
+            .iter()
+            .any(|key| key.as_ref() == end_entity.as_ref())
+        {
+            Ok(())
+        } else {
+            Err(Error::InvalidCertificate(CertificateError::UnknownIssuer))
+        }
+    }
+
+    fn verify_signature(
+        &self,
+        message: &[u8],
+        public_key: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, Error> {
+        let public_key = SubjectPublicKeyInfoDer::from(public_key.as_ref());
+        verify_tls13_signature_with_raw_key(message, &public_key, dss, &self.algorithms)
+    }
+}
+
+impl ServerCertVerifier for PinnedPublicKeys {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        intermediates: &[CertificateDer<'_>],
+        _server_name: &ServerName<'_>,
+        _ocsp_response: &[u8],
+        _now: UnixTime,
+    ) -> Result<ServerCertVerified, Error> {
+        self.verify(end_entity, intermediates)?;
+        Ok(ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        _message: &[u8],
+        _cert: &CertificateDer<'_>,
+        _dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, Error> {
+        // QUIC requires TLS 1.3
+        Err(PeerIncompatible::Tls13RequiredForQuic.into())
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, Error> {
+        self.verify_signature(message, cert, dss)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.algorithms.supported_schemes()
+    }
+
+    fn requires_raw_public_keys(&self) -> bool {
+        true
+    }
+}
+
+impl ClientCertVerifier for PinnedPublicKeys {
+    fn client_auth_mandatory(&self) -> bool {
+        self.mandatory
+    }
+
+    fn root_hint_subjects(&self) -> &[DistinguishedName] {
+        &[]
+    }
+
+    fn verify_client_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        intermediates: &[CertificateDer<'_>],
+        _now: UnixTime,
+    ) -> Result<ClientCertVerified, Error> {
+        self.verify(end_entity, intermediates)?;
+        Ok(ClientCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        _message: &[u8],
+        _cert: &CertificateDer<'_>,
+        _dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, Error> {
+        // QUIC requires TLS 1.3
+        Err(PeerIncompatible::Tls13RequiredForQuic.into())
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, Error> {
+        self.verify_signature(message, cert, dss)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.algorithms.supported_schemes()
+    }
+
+    fn requires_raw_public_keys(&self) -> bool {
+        true
+    }
+}
@@ -0,0 +1,208 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Session resumption support
+//!
+//! Servers configured with a [`SessionTicketer`] issue session tickets after the handshake
+//! completes. Clients configured with a [`SessionStore`] save the tickets and use them to
+//! resume later handshakes with the same server name, which skips certificate authentication.
+//!
+//! Note that session tickets are only exchanged if the `unstable_resumption` feature is
+//! enabled in s2n-quic.
+
+use crate::Error;
+use core::time::Duration;
+use rustls::{
+    client::{ClientSessionMemoryCache, ClientSessionStore},
+    crypto::{aws_lc_rs, GetRandomFailed},
+    server::ProducesTickets,
+    TicketRotator,
+};
+use s2n_quic_crypto::aws_lc_aead::{self as aead, Aad, Nonce, RandomizedNonceKey};
+use std::sync::Arc;
+
+/// The number of sessions stored by [`SessionStore::default`]
+const DEFAULT_CAPACITY: usize = 256;
+
+/// The length of the random name which identifies the key used to encrypt a ticket
+const KEY_NAME_LEN: usize = 16;
+
+/// Stores session tickets received by clients
+///
+/// The store is cheap to clone and all clones refer to the same set of sessions, which allows
+/// it to be shared between multiple clients.
+#[derive(Clone)]
+pub struct SessionStore(pub(crate) Arc<dyn ClientSessionStore>);
+
+impl SessionStore {
+    /// Creates an in-memory store which holds sessions for up to `capacity` server names
+    pub fn new(capacity: usize) -> Self {
+        Self(Arc::new(ClientSessionMemoryCache::new(capacity)))
+    }
+}
+
+impl Default for SessionStore {
+    fn default() -> Self {
+        Self::new(DEFAULT_CAPACITY)
+    }
+}
+
+impl core::fmt::Debug for SessionStore {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("SessionStore").field(&self.0).finish()
+    }
+}
+
+/// Encrypts session tickets issued by servers
+///
+/// Ticket keys are generated randomly and rotated periodically. Tickets are accepted for up
+/// to twice the rotation interval. The ticketer is cheap to clone and all clones use the same
+/// keys, which allows tickets to be resumed on any server sharing the ticketer.
+#[derive(Clone, Debug)]
+pub struct SessionTicketer(pub(crate) Arc<dyn ProducesTickets>);
+
+impl SessionTicketer {
+    /// Creates a ticketer which rotates its keys every 6 hours
+    pub fn new() -> Result<Self, Error> {
+        Ok(Self(aws_lc_rs::Ticketer::new()?))
+    }
+
+    /// Creates a ticketer which rotates its keys at the given interval
+    ///
+    /// The interval is rounded down to whole seconds and must be at least one second.
+    pub fn with_rotation_interval(interval: Duration) -> Result<Self, Error> {
+        // tickets are accepted for twice the interval so cap it to keep the lifetime in range
+        let lifetime = u32::try_from(interval.as_secs())
+            .unwrap_or(u32::MAX)
+            .min(u32::MAX / 2);
+        if lifetime == 0 {
+            return Err(rustls::Error::General(
+                "ticket rotation interval must be at least one second".to_string(),
+            )
+            .into());
+        }
+
+        let rotator = TicketRotator::new(lifetime, generate_ticketer)?;
+        Ok(Self(Arc::new(rotator)))
+    }
+}
+
+/// Generates a ticketer with a fresh random key
+///
+/// The keys are rotated by the outer [`TicketRotator`].
+fn generate_ticketer() -> Result<Box<dyn ProducesTickets>, GetRandomFailed> {
+    Ok(Box::new(AeadTicketer::new()?))
+}
+
+/// Encrypts tickets with a single AES-256-GCM key
+///
+/// Tickets are encoded as `key_name || nonce || ciphertext || tag`, with the key name
+/// authenticated as associated data.
+struct AeadTicketer {
+    key: RandomizedNonceKey,
+    key_name: [u8; KEY_NAME_LEN],
+}
+
+impl AeadTicketer {
+    fn new() -> Result<Self, GetRandomFailed> {
+        let random = aws_lc_rs::default_provider().secure_random;
+
+        let mut key = [0u8; 32];
+        random.fill(&mut key)?;
+        let key = RandomizedNonceKey::new(&aead::AES_256_GCM, &key).map_err(|_| GetRandomFailed)?;
+
+        let mut key_name = [0u8; KEY_NAME_LEN];
+        random.fill(&mut key_name)?;
+
+        Ok(Self { key, key_name })
+    }
+}
+
+impl ProducesTickets for AeadTicketer {
+    fn enabled(&self) -> bool {
+        true
+    }
+
+    fn lifetime(&self) -> u32 {
+        // the lifetime is enforced by the outer `TicketRotator`
+        u32::MAX
+    }
+
+    fn encrypt(&self, plain: &[u8]) -> Option<Vec<u8>> {
+        let mut payload = plain.to_vec();
+        let nonce = self
+            .key
+            .seal_in_place_append_tag(Aad::from(&self.key_name), &mut payload)
+            .ok()?;
+
+        let mut ticket = Vec::with_capacity(KEY_NAME_LEN + aead::NONCE_LEN + payload.len());
+        ticket.extend_from_slice(&self.key_name);
+        ticket.extend_from_slice(nonce.as_ref());
+        ticket.extend_from_slice(&payload);
+        Some(ticket)
+    }
+
+    fn decrypt(&self, cipher: &[u8]) -> Option<Vec<u8>> {
+        let key_name = cipher.get(..KEY_NAME_LEN)?;
+        if key_name != self.key_name {
+            return None;
+        }
+
+        let cipher = &cipher[KEY_NAME_LEN..];
+        let nonce = Nonce::try_assume_unique_for_key(cipher.get(..aead::NONCE_LEN)?).ok()?;
+
+        let mut payload = cipher[aead::NONCE_LEN..].to_vec();
+        let len = self
+            .key
+            .open_in_place(nonce, Aad::from(&self.key_name), &mut payload)
+            .ok()?
+            .len();
+        payload.truncate(len);
+        Some(payload)
+    }
+}
+
+impl core::fmt::Debug for AeadTicketer {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        // the key is deliberately omitted
+        f.debug_struct("AeadTicketer").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rotation_interval_test() {
+        let ticketer = SessionTicketer::with_rotation_interval(Duration::from_secs(60)).unwrap();
+        // tickets are accepted for twice the rotation interval
+        assert_eq!(ticketer.0.lifetime(), 120);
+
+        let ticketer =
+            SessionTicketer::with_rotation_interval(Duration::from_secs(24 * 60 * 60)).unwrap();
+        assert_eq!(ticketer.0.lifetime(), 2 * 24 * 60 * 60);
+
+        assert!(SessionTicketer::with_rotation_interval(Duration::from_millis(500)).is_err());
+    }
+
+    #[test]
+    fn ticketer_round_trip_test() {
+        let ticketer = AeadTicketer::new().unwrap();
+        let ticket = ticketer.encrypt(b"hello").unwrap();
+        assert_eq!(ticketer.decrypt(&ticket).unwrap(), b"hello");
+
+        // tampered tickets are rejected
+        for idx in 0..ticket.len() {
+            let mut tampered = ticket.clone();
+            tampered[idx] ^= 1;
+            assert!(ticketer.decrypt(&tampered).is_none());
+        }
+        assert!(ticketer.decrypt(&ticket[..ticket.len() - 1]).is_none());
+        assert!(ticketer.decrypt(&[]).is_none());
+
+        // tickets from other keys are rejected
+        let other = AeadTicketer::new().unwrap();
+        assert!(other.decrypt(&ticket).is_none());
+    }
+}
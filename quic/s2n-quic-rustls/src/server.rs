@@ -1,8 +1,15 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{certificate, cipher_suite::default_crypto_provider, session::Session, Error};
-use rustls::{crypto::aws_lc_rs, ConfigBuilder, ServerConfig, WantsVerifier};
+use crate::{
+    certificate, cipher_suite::default_crypto_provider, raw_public_key::PinnedPublicKeys,
+    resumption::SessionTicketer, session::Session, Error,
+};
+use rustls::{
+    crypto::{aws_lc_rs, CryptoProvider},
+    server::{danger::ClientCertVerifier, AlwaysResolvesServerRawPublicKeys, WebPkiClientVerifier},
+    ConfigBuilder, RootCertStore, ServerConfig, WantsVerifier,
+};
 use s2n_codec::EncoderValue;
 use s2n_quic_core::{application::ServerName, crypto::tls};
 use std::sync::Arc;
@@ -10,15 +17,26 @@ use std::sync::Arc;
 /// Create a QUIC server specific [rustls::ConfigBuilder].
 ///
 /// Uses aws_lc_rs as the crypto provider and sets QUIC specific protocol versions.
-fn default_config_builder() -> Result<ConfigBuilder<ServerConfig, WantsVerifier>, rustls::Error> {
-    let tls13_cipher_suite_crypto_provider = default_crypto_provider()?;
-    ServerConfig::builder_with_provider(tls13_cipher_suite_crypto_provider.into())
+fn default_config_builder(
+    crypto_provider: Arc<CryptoProvider>,
+) -> Result<ConfigBuilder<ServerConfig, WantsVerifier>, rustls::Error> {
+    ServerConfig::builder_with_provider(crypto_provider)
         .with_protocol_versions(crate::PROTOCOL_VERSIONS)
 }
 
+/// Determines whether the server requests a certificate from the client
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ClientAuth {
+    None,
+    Optional,
+    Required,
+}
+
 #[derive(Clone)]
 pub struct Server {
     config: Arc<ServerConfig>,
+    /// Set if clients authenticate with raw public keys instead of certificates
+    raw_public_keys: bool,
 }
 
 impl Server {
@@ -31,9 +49,7 @@ impl Server {
     /// - QUIC compliant ciphersuites
     #[deprecated = "client and server builders should be used instead"]
     pub fn new(config: ServerConfig) -> Self {
-        Self {
-            config: Arc::new(config),
-        }
+        Self::from(Arc::new(config))
     }
 
     pub fn builder() -> Builder {
@@ -59,7 +75,10 @@ impl From<ServerConfig> for Server {
 // TODO this should be removed after removing deprecated re-exports
 impl From<Arc<ServerConfig>> for Server {
     fn from(config: Arc<ServerConfig>) -> Self {
-        Self { config }
+        Self {
+            config,
+            raw_public_keys: false,
+        }
     }
 }
 
@@ -81,7 +100,9 @@ impl tls::Endpoint for Server {
         )
         .expect("could not create rustls server session");
 
-        Session::new(session.into(), None)
+        let mut session = Session::new(session.into(), None);
+        session.peer_raw_public_key(self.raw_public_keys);
+        session
     }
 
     fn new_client_session<Params: EncoderValue>(
@@ -102,6 +123,12 @@ pub struct Builder {
     application_protocols: Vec<Vec<u8>>,
     key_log: Option<Arc<dyn rustls::KeyLog>>,
     prefer_server_cipher_suite_order: bool,
+    client_auth: ClientAuth,
+    client_cert_store: RootCertStore,
+    client_cert_verifier: Option<Arc<dyn ClientCertVerifier>>,
+    client_public_keys: Vec<certificate::PublicKey>,
+    crls: Vec<rustls::pki_types::CertificateRevocationListDer<'static>>,
+    ticketer: Option<SessionTicketer>,
 }
 
 impl Default for Builder {
@@ -117,6 +144,12 @@ impl Builder {
             application_protocols: vec![b"h3".to_vec()],
             key_log: None,
             prefer_server_cipher_suite_order: true,
+            client_auth: ClientAuth::None,
+            client_cert_store: RootCertStore::empty(),
+            client_cert_verifier: None,
+            client_public_keys: Vec::new(),
+            crls: Vec::new(),
+            ticketer: None,
         }
     }
 
@@ -133,6 +166,22 @@ impl Builder {
         Ok(self)
     }
 
+    /// Authenticates the server with the raw public key of `private_key` instead of a
+    /// certificate chain (<https://www.rfc-editor.org/rfc/rfc7250>)
+    ///
+    /// Clients must be configured to trust the public key with
+    /// [`client::Builder::with_trusted_public_key`](crate::client::Builder::with_trusted_public_key).
+    pub fn with_raw_public_key<PK: certificate::IntoPrivateKey>(
+        mut self,
+        private_key: PK,
+    ) -> Result<Self, Error> {
+        let private_key = private_key.into_private_key()?;
+        let key = crate::raw_public_key::certified_key(private_key)?;
+        let resolver = AlwaysResolvesServerRawPublicKeys::new(key);
+        self.cert_resolver = Some(Arc::new(resolver));
+        Ok(self)
+    }
+
     #[deprecated = "client and server builders should be used instead"]
     pub fn with_cert_resolver(
         mut self,
@@ -150,11 +199,28 @@ impl Builder {
         Ok(self)
     }
 
+    /// Issues session tickets encrypted by the provided ticketer after each handshake, which
+    /// clients can use to resume later connections
+    pub fn with_session_ticketer(mut self, ticketer: SessionTicketer) -> Result<Self, Error> {
+        self.ticketer = Some(ticketer);
+        Ok(self)
+    }
+
+    /// Logs TLS secrets to the file specified by the `SSLKEYLOGFILE` environment variable
     pub fn with_key_logging(mut self) -> Result<Self, Error> {
         self.key_log = Some(Arc::new(rustls::KeyLogFile::new()));
         Ok(self)
     }
 
+    /// Logs TLS secrets to the provided [`KeyLog`](s2n_quic_core::crypto::tls::KeyLog)
+    ///
+    /// **WARNING**: the logged secrets can be used to decrypt all traffic and should only be
+    /// enabled for debugging.
+    pub fn with_key_log<K: tls::KeyLog>(mut self, key_log: K) -> Result<Self, Error> {
+        self.key_log = Some(crate::keylog::KeyLog::wrap(key_log));
+        Ok(self)
+    }
+
     /// If enabled, the cipher suite order of the client is ignored, and the top cipher suite
     /// in the server list that the client supports is chosen (default: true)
     pub fn with_prefer_server_cipher_suite_order(mut self, enabled: bool) -> Result<Self, Error> {
@@ -162,8 +228,148 @@ impl Builder {
         Ok(self)
     }
 
-    pub fn build(self) -> Result<Server, Error> {
-        let builder = default_config_builder()?.with_no_client_auth();
+    /// Adds a certificate authority which is trusted to issue client certificates
+    ///
+    /// All of the certificates contained in `certificate` are trusted.
+    pub fn with_trusted_certificate<C: certificate::IntoCertificate>(
+        mut self,
+        certificate: C,
+    ) -> Result<Self, Error> {
+        let certificates = certificate.into_certificate()?;
+        if certificates.0.is_empty() {
+            return Err(rustls::Error::General(
+                "Certificate chain needs to have at least one entry".to_string(),
+            )
+            .into());
+        }
+        for certificate in certificates.0 {
+            self.client_cert_store
+                .add(certificate)
+                .map_err(|err| rustls::Error::General(err.to_string()))?;
+        }
+        Ok(self)
+    }
+
+    /// Adds a raw public key which clients can authenticate with instead of a certificate
+    ///
+    /// Client authentication must be enabled with [`Self::with_client_authentication`] or
+    /// [`Self::with_optional_client_authentication`]. Once a public key is trusted, clients
+    /// can't authenticate with certificates.
+    pub fn with_trusted_public_key<K: certificate::IntoPublicKey>(
+        mut self,
+        public_key: K,
+    ) -> Result<Self, Error> {
+        let public_key = public_key.into_public_key()?;
+        self.client_public_keys.push(public_key);
+        Ok(self)
+    }
+
+    /// Configures this server instance to require client authentication (mutual TLS).
+    ///
+    /// Client certificates are verified against the certificates added with
+    /// [`Self::with_trusted_certificate`] and raw public keys against the keys added with
+    /// [`Self::with_trusted_public_key`], unless a custom verifier is configured with
+    /// [`Self::with_client_certificate_verifier`].
+    pub fn with_client_authentication(mut self) -> Result<Self, Error> {
+        self.client_auth = ClientAuth::Required;
+        Ok(self)
+    }
+
+    /// Configures this server instance to request, but not require, client authentication.
+    ///
+    /// Clients which present a certificate must present a valid one. Clients which
+    /// don't present a certificate are still allowed to connect.
+    pub fn with_optional_client_authentication(mut self) -> Result<Self, Error> {
+        self.client_auth = ClientAuth::Optional;
+        Ok(self)
+    }
+
+    /// Adds certificate revocation lists which are checked when verifying client certificates
+    pub fn with_certificate_revocation_list<C: certificate::IntoCertificateRevocationList>(
+        mut self,
+        crl: C,
+    ) -> Result<Self, Error> {
+        let crls = crl.into_certificate_revocation_list()?;
+        self.crls.extend(crls.0);
+        Ok(self)
+    }
+
+    /// Sets a custom verifier which is invoked when a client certificate is presented
+    /// during the mutual TLS handshake
+    ///
+    /// The verifier determines whether client authentication is offered or required,
+    /// replacing the settings of this builder.
+    #[deprecated = "client and server builders should be used instead"]
+    pub fn with_client_certificate_verifier(
+        mut self,
+        verifier: Arc<dyn ClientCertVerifier>,
+    ) -> Result<Self, Error> {
+        self.client_cert_verifier = Some(verifier);
+        Ok(self)
+    }
+
+    fn client_cert_verifier(
+        &mut self,
+        crypto_provider: &Arc<CryptoProvider>,
+    ) -> Result<Option<Arc<dyn ClientCertVerifier>>, Error> {
+        if let Some(verifier) = self.client_cert_verifier.take() {
+            return Ok(Some(verifier));
+        }
+
+        if self.client_auth == ClientAuth::None {
+            return Ok(None);
+        }
+
+        if !self.client_public_keys.is_empty() {
+            if !self.client_cert_store.is_empty() {
+                return Err(rustls::Error::General(
+                    "trusted client certificates and public keys can't be combined".to_string(),
+                )
+                .into());
+            }
+
+            let keys = core::mem::take(&mut self.client_public_keys);
+            let algorithms = crypto_provider.signature_verification_algorithms;
+            let mut verifier = PinnedPublicKeys::new(keys, algorithms);
+            if self.client_auth == ClientAuth::Optional {
+                verifier = verifier.allow_unauthenticated();
+            }
+            return Ok(Some(Arc::new(verifier)));
+        }
+
+        if self.client_cert_store.is_empty() {
+            return Err(rustls::Error::General(
+                "missing trusted client certificate authority".to_string(),
+            )
+            .into());
+        }
+
+        let roots = core::mem::replace(&mut self.client_cert_store, RootCertStore::empty());
+        let mut builder =
+            WebPkiClientVerifier::builder_with_provider(Arc::new(roots), crypto_provider.clone())
+                .with_crls(core::mem::take(&mut self.crls));
+
+        if self.client_auth == ClientAuth::Optional {
+            builder = builder.allow_unauthenticated();
+        }
+
+        let verifier = builder
+            .build()
+            .map_err(|err| rustls::Error::General(err.to_string()))?;
+        Ok(Some(verifier))
+    }
+
+    pub fn build(mut self) -> Result<Server, Error> {
+        let crypto_provider = Arc::new(default_crypto_provider()?);
+        let builder = default_config_builder(crypto_provider.clone())?;
+        let verifier = self.client_cert_verifier(&crypto_provider)?;
+        let raw_public_keys = verifier
+            .as_ref()
+            .is_some_and(|verifier| verifier.requires_raw_public_keys());
+        let builder = match verifier {
+            Some(verifier) => builder.with_client_cert_verifier(verifier),
+            None => builder.with_no_client_auth(),
+        };
 
         let mut config = if let Some(cert_resolver) = self.cert_resolver {
             builder.with_cert_resolver(cert_resolver)
@@ -182,8 +388,16 @@ impl Builder {
             config.key_log = key_log;
         }
 
-        #[allow(deprecated)]
-        Ok(Server::new(config))
+        // only issue tickets if the application has opted into resumption
+        match self.ticketer {
+            Some(ticketer) => config.ticketer = ticketer.0,
+            None => config.send_tls13_tickets = 0,
+        }
+
+        Ok(Server {
+            config: Arc::new(config),
+            raw_public_keys,
+        })
     }
 }
 
@@ -22,6 +22,18 @@ pub struct Session {
     emitted_server_name: bool,
     emitted_application_protocol: bool,
     server_name: Option<ServerName>,
+    /// Set if the client should keep the session to receive session tickets
+    receive_session_tickets: bool,
+    received_ticket: bool,
+    /// Set if the peer authenticates with a raw public key instead of a certificate chain
+    peer_raw_public_key: bool,
+    /// Set if the client encrypts the ClientHello
+    offered_ech: bool,
+    ech_status: Option<tls::EchStatus>,
+    /// The server's handshake messages, buffered until EncryptedExtensions is complete
+    encrypted_extensions: Option<Vec<u8>>,
+    ech_retry_configs: Option<Vec<u8>>,
+    emitted_ech_status: bool,
 }
 
 impl tls::TlsSession for Session {
@@ -61,6 +73,9 @@ impl tls::TlsSession for Session {
 
     fn peer_cert_chain_der(&self) -> Result<Vec<Vec<u8>>, tls::ChainError> {
         let err = tls::ChainError::failure();
+        if self.peer_raw_public_key {
+            return Err(err);
+        }
         Ok(self
             .connection
             .peer_certificates()
@@ -69,6 +84,15 @@ impl tls::TlsSession for Session {
             .map(|v| v.to_vec())
             .collect())
     }
+
+    fn peer_public_key_der(&self) -> Result<Vec<u8>, tls::ChainError> {
+        let err = tls::ChainError::failure();
+        if !self.peer_raw_public_key {
+            return Err(err);
+        }
+        let public_key = self.connection.peer_certificates().ok_or(err)?.first();
+        Ok(public_key.ok_or(tls::ChainError::failure())?.to_vec())
+    }
 }
 
 impl fmt::Debug for Session {
@@ -91,13 +115,55 @@ impl Session {
             emitted_server_name: false,
             emitted_application_protocol: false,
             server_name,
+            receive_session_tickets: false,
+            received_ticket: false,
+            peer_raw_public_key: false,
+            offered_ech: false,
+            ech_status: None,
+            encrypted_extensions: None,
+            ech_retry_configs: None,
+            emitted_ech_status: false,
         }
     }
 
+    /// Keeps the session after the handshake until a session ticket is received
+    pub fn receive_session_tickets(&mut self, enabled: bool) {
+        self.receive_session_tickets = enabled;
+    }
+
+    /// Marks the peer's end-entity certificate as a raw public key
+    pub fn peer_raw_public_key(&mut self, enabled: bool) {
+        self.peer_raw_public_key = enabled;
+    }
+
+    /// Marks the ClientHello as encrypted so the outcome can be reported
+    pub fn offer_ech(&mut self, enabled: bool) {
+        self.offered_ech = enabled;
+        self.encrypted_extensions = enabled.then(Vec::new);
+    }
+
     fn receive(&mut self, crypto_data: &[u8]) -> Result<(), transport::Error> {
+        if self.rx_phase == HandshakePhase::Handshake {
+            if let Some(buffer) = self.encrypted_extensions.as_mut() {
+                buffer.extend_from_slice(crypto_data);
+                if let Some(retry_configs) = crate::ech::retry_configs(buffer) {
+                    self.ech_retry_configs = retry_configs;
+                    self.encrypted_extensions = None;
+                }
+            }
+        }
+
         self.connection
             .read_hs(crypto_data)
-            .map_err(crate::error::reason)
+            .map_err(|error| {
+                if let rustls::Error::PeerIncompatible(
+                    rustls::PeerIncompatible::ServerRejectedEncryptedClientHello(_),
+                ) = &error
+                {
+                    self.ech_status = Some(tls::EchStatus::Rejected);
+                }
+                crate::error::reason(error)
+            })
             .map_err(|reason| {
                 //= https://www.rfc-editor.org/rfc/rfc9001#section-4.8
                 //# QUIC is only able to convey an alert level of "fatal".  In TLS 1.3,
@@ -189,10 +255,21 @@ impl Session {
         context: &mut C,
     ) -> Poll<Result<(), transport::Error>> {
         if self.tx_phase == HandshakePhase::Application && !self.connection.is_handshaking() {
+            // rustls aborts the handshake if the server rejects ECH
+            if self.offered_ech && self.ech_status.is_none() {
+                self.ech_status = Some(tls::EchStatus::Accepted);
+            }
+
             // attempt to emit server_name and application_protocol events prior to completing the
             // handshake
             self.emit_events(context)?;
 
+            // servers queue session tickets once the handshake completes so make sure they're
+            // sent before the session is discarded
+            if !self.emitted_handshake_complete {
+                self.transmit(context)?;
+            }
+
             // the handshake is complete!
             if !self.emitted_handshake_complete {
                 self.rx_phase.transition();
@@ -214,6 +291,12 @@ impl Session {
         &mut self,
         context: &mut C,
     ) -> Poll<Result<(), transport::Error>> {
+        // Clients that are waiting for session tickets process them in
+        // `process_post_handshake_message` instead
+        if self.emitted_handshake_complete {
+            return Poll::Ready(Ok(()));
+        }
+
         // Tracks if we have attempted to receive data at least once
         let mut has_tried_receive = false;
 
@@ -249,68 +332,73 @@ impl Session {
                 context.on_zero_rtt_keys(key, header_key, self.application_parameters()?)?;
             }
 
-            loop {
-                // make sure we can send data before pulling it out of rustls
-                let can_send = match self.tx_phase {
-                    HandshakePhase::Initial => context.can_send_initial(),
-                    HandshakePhase::Handshake => context.can_send_handshake(),
-                    HandshakePhase::Application => context.can_send_application(),
-                };
+            self.transmit(context)?;
+        }
+    }
 
-                if !can_send {
-                    break;
-                }
+    /// Sends any pending handshake messages and applies key changes
+    fn transmit<C: tls::Context<Self>>(&mut self, context: &mut C) -> Result<(), transport::Error> {
+        loop {
+            // make sure we can send data before pulling it out of rustls
+            let can_send = match self.tx_phase {
+                HandshakePhase::Initial => context.can_send_initial(),
+                HandshakePhase::Handshake => context.can_send_handshake(),
+                HandshakePhase::Application => context.can_send_application(),
+            };
+
+            if !can_send {
+                break;
+            }
 
-                let mut transmission_buffer = vec![];
+            let mut transmission_buffer = vec![];
 
-                let key_change = self.connection.write_hs(&mut transmission_buffer);
+            let key_change = self.connection.write_hs(&mut transmission_buffer);
 
-                // if we didn't upgrade the key or transmit anything then we're waiting for
-                // more reads
-                if key_change.is_none() && transmission_buffer.is_empty() {
-                    break;
-                }
+            // if we didn't upgrade the key or transmit anything then we're waiting for
+            // more reads
+            if key_change.is_none() && transmission_buffer.is_empty() {
+                break;
+            }
+
+            // fill the correct buffer according to the handshake phase
+            match self.tx_phase {
+                HandshakePhase::Initial => context.send_initial(transmission_buffer.into()),
+                HandshakePhase::Handshake => context.send_handshake(transmission_buffer.into()),
+                HandshakePhase::Application => context.send_application(transmission_buffer.into()),
+            }
 
-                // fill the correct buffer according to the handshake phase
-                match self.tx_phase {
-                    HandshakePhase::Initial => context.send_initial(transmission_buffer.into()),
-                    HandshakePhase::Handshake => context.send_handshake(transmission_buffer.into()),
-                    HandshakePhase::Application => {
-                        context.send_application(transmission_buffer.into())
+            if let Some(key_change) = key_change {
+                let cipher_suite = self
+                    .connection
+                    .negotiated_cipher_suite()
+                    .expect("cipher_suite should be negotiated")
+                    .suite();
+                match key_change {
+                    quic::KeyChange::Handshake { keys } => {
+                        let (key, header_key) = PacketKeys::new(keys, cipher_suite);
+
+                        context.on_handshake_keys(key, header_key)?;
+
+                        // Transition both phases to Handshake
+                        self.tx_phase.transition();
+                        self.rx_phase.transition();
                     }
-                }
+                    quic::KeyChange::OneRtt { keys, next } => {
+                        let (key, header_key) = OneRttKey::new(keys, next, cipher_suite);
+
+                        let application_parameters = self.application_parameters()?;
 
-                if let Some(key_change) = key_change {
-                    let cipher_suite = self
-                        .connection
-                        .negotiated_cipher_suite()
-                        .expect("cipher_suite should be negotiated")
-                        .suite();
-                    match key_change {
-                        quic::KeyChange::Handshake { keys } => {
-                            let (key, header_key) = PacketKeys::new(keys, cipher_suite);
-
-                            context.on_handshake_keys(key, header_key)?;
-
-                            // Transition both phases to Handshake
-                            self.tx_phase.transition();
-                            self.rx_phase.transition();
-                        }
-                        quic::KeyChange::OneRtt { keys, next } => {
-                            let (key, header_key) = OneRttKey::new(keys, next, cipher_suite);
-
-                            let application_parameters = self.application_parameters()?;
-
-                            context.on_one_rtt_keys(key, header_key, application_parameters)?;
-
-                            // Transition the tx_phase to Application
-                            // Note: the rx_phase is transitioned when the handshake is complete
-                            self.tx_phase.transition();
-                        }
+                        context.on_one_rtt_keys(key, header_key, application_parameters)?;
+
+                        // Transition the tx_phase to Application
+                        // Note: the rx_phase is transitioned when the handshake is complete
+                        self.tx_phase.transition();
                     }
                 }
             }
         }
+
+        Ok(())
     }
 
     fn emit_events<C: tls::Context<Self>>(
@@ -330,6 +418,17 @@ impl Session {
                 self.emitted_application_protocol = true;
             }
         }
+        if !self.emitted_ech_status {
+            if let Some(status) = self.ech_status {
+                // retry configurations are only meaningful if the server rejected ECH
+                let retry_configs = self
+                    .ech_retry_configs
+                    .as_deref()
+                    .filter(|_| status == tls::EchStatus::Rejected);
+                context.on_ech_status(status, retry_configs)?;
+                self.emitted_ech_status = true;
+            }
+        }
 
         Ok(())
     }
@@ -352,12 +451,40 @@ impl tls::Session for Session {
         &mut self,
         context: &mut C,
     ) -> Poll<Result<(), transport::Error>> {
-        let result = self.poll_impl(context);
+        let result = tls::keylog::scope(context.key_log_context(), || self.poll_impl(context));
         // attempt to emit server_name and application_protocol events prior to possibly
         // returning with an error
         self.emit_events(context)?;
         result
     }
+
+    fn process_post_handshake_message<C: tls::Context<Self>>(
+        &mut self,
+        context: &mut C,
+    ) -> Result<(), transport::Error> {
+        // QUIC doesn't permit KeyUpdate messages so session tickets are the only
+        // post-handshake messages a client receives
+        while let Some(crypto_data) = context.receive_application(None) {
+            self.receive(&crypto_data)?;
+        }
+
+        // CRYPTO frames may split a ticket so only stop once one is fully processed
+        if let Connection::Client(client) = &self.connection {
+            self.received_ticket |= client.tls13_tickets_received() > 0;
+        }
+
+        Ok(())
+    }
+
+    fn should_discard_session(&self) -> bool {
+        // Only clients process post-handshake messages
+        if !matches!(self.connection, Connection::Client(_)) {
+            return true;
+        }
+
+        // Discard the session once a ticket is received, or immediately if resumption is disabled
+        !self.receive_session_tickets || self.received_ticket
+    }
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
@@ -3,7 +3,7 @@
 
 use crate::{
     certificate::{IntoCertificate, IntoPrivateKey},
-    keylog::KeyLogHandle,
+    keylog::{KeyLog, KeyLogHandle},
     params::Params,
     session::Session,
     ConfigLoader,
@@ -165,10 +165,23 @@ impl Builder {
         Ok(self)
     }
 
-    pub fn with_key_logging(mut self) -> Result<Self, Error> {
-        use crate::keylog::KeyLog;
+    /// Logs TLS secrets to the file specified by the `SSLKEYLOGFILE` environment variable
+    pub fn with_key_logging(self) -> Result<Self, Error> {
+        let keylog = KeyLog::try_open();
+        self.set_key_log(keylog)
+    }
+
+    /// Logs TLS secrets to the provided [`KeyLog`](s2n_quic_core::crypto::tls::KeyLog)
+    ///
+    /// **WARNING**: the logged secrets can be used to decrypt all traffic and should only be
+    /// enabled for debugging.
+    pub fn with_key_log<K: tls::KeyLog>(self, key_log: K) -> Result<Self, Error> {
+        let keylog = KeyLog::new(Arc::new(key_log));
+        self.set_key_log(Some(keylog))
+    }
 
-        self.keylog = KeyLog::try_open();
+    fn set_key_log(mut self, keylog: Option<KeyLogHandle>) -> Result<Self, Error> {
+        self.keylog = keylog;
 
         unsafe {
             // Safety: the KeyLog is stored on `self` to ensure it outlives `config`
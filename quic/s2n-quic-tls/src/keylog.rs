@@ -2,6 +2,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use libc::{c_int, c_void};
+use s2n_quic_core::crypto::tls::{
+    self,
+    keylog::{self, Line},
+};
 use s2n_tls::ffi::*;
 use std::{
     fs::{File, OpenOptions},
@@ -11,9 +15,13 @@ use std::{
 
 pub type KeyLogHandle = Arc<KeyLog>;
 
-pub struct KeyLog(Mutex<BufWriter<File>>);
+pub struct KeyLog(Arc<dyn tls::KeyLog>);
 
 impl KeyLog {
+    pub fn new(key_log: Arc<dyn tls::KeyLog>) -> KeyLogHandle {
+        Arc::new(Self(key_log))
+    }
+
     pub fn try_open() -> Option<KeyLogHandle> {
         let path = std::env::var("SSLKEYLOGFILE").ok()?;
         let file = OpenOptions::new()
@@ -23,9 +31,8 @@ impl KeyLog {
             .ok()?;
         let file = BufWriter::new(file);
         let file = Mutex::new(file);
-        let keylog = Self(file);
-        let keylog = Arc::new(keylog);
-        Some(keylog)
+        let keylog = KeyLogFile(file);
+        Some(Self::new(Arc::new(keylog)))
     }
 
     pub unsafe extern "C" fn callback(
@@ -34,19 +41,32 @@ impl KeyLog {
         logline: *mut u8,
         len: usize,
     ) -> c_int {
-        let handle = &mut *(ctx as *mut Self);
+        let handle = &*(ctx as *const Self);
         let logline = core::slice::from_raw_parts(logline, len);
+        let context = keylog::current();
 
-        // ignore any errors
-        let _ = handle.on_logline(logline);
+        let parsed = Line::parse(logline, |line| {
+            if handle.0.will_log(line.label) {
+                handle
+                    .0
+                    .log(&context, line.label, line.client_random, line.secret);
+            }
+        });
+
+        if parsed.is_none() {
+            handle.0.on_malformed_line(&context, logline);
+        }
 
         0
     }
+}
+
+struct KeyLogFile(Mutex<BufWriter<File>>);
 
-    fn on_logline(&mut self, logline: &[u8]) -> Option<()> {
+impl KeyLogFile {
+    fn write<T: core::fmt::Display>(&self, line: T) -> Option<()> {
         let mut file = self.0.lock().ok()?;
-        file.write_all(logline).ok()?;
-        file.write_all(b"\n").ok()?;
+        writeln!(file, "{line}").ok()?;
 
         // ensure keys are immediately written so tools can use them
         file.flush().ok()?;
@@ -54,3 +74,22 @@ impl KeyLog {
         Some(())
     }
 }
+
+impl tls::KeyLog for KeyLogFile {
+    fn log(&self, _context: &keylog::Context, label: &str, client_random: &[u8], secret: &[u8]) {
+        // ignore any errors
+        let _ = self.write(Line {
+            label,
+            client_random,
+            secret,
+        });
+    }
+
+    fn on_malformed_line(&self, _context: &keylog::Context, line: &[u8]) {
+        // record the line as a comment so it's visible without breaking parsers
+        let _ = self.write(format_args!(
+            "# malformed key log line: {:?}",
+            String::from_utf8_lossy(line)
+        ));
+    }
+}
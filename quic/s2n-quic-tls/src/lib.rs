@@ -54,6 +54,7 @@ mod session;
 pub mod certificate;
 pub mod client;
 pub mod server;
+pub mod sni;
 
 pub use client::Client;
 pub use s2n_tls::*;
@@ -3,9 +3,10 @@
 
 use crate::{
     certificate::{Format, IntoCertificate, IntoPrivateKey},
-    keylog::KeyLogHandle,
+    keylog::{KeyLog, KeyLogHandle},
     params::Params,
     session::Session,
+    sni::{CertificateResolver, Shared, Template},
     ConfigLoader,
 };
 use s2n_codec::EncoderValue;
@@ -19,6 +20,7 @@ use s2n_tls::{
     config::{self, Config},
     enums::ClientAuthType,
     error::Error,
+    security,
 };
 use std::sync::Arc;
 
@@ -76,6 +78,10 @@ impl<L: ConfigLoader> From<Server<L>> for Config {
 pub struct Builder {
     config: config::Builder,
     keylog: Option<KeyLogHandle>,
+    resolver: Option<CertificateResolver>,
+    template: Template,
+    /// Set when the config was accessed directly, which can't be applied to a resolver
+    config_mut_used: bool,
 }
 
 impl Default for Builder {
@@ -89,12 +95,21 @@ impl Default for Builder {
         Self {
             config,
             keylog: None,
+            resolver: None,
+            template: Template::default(),
+            config_mut_used: false,
         }
     }
 }
 
 impl Builder {
+    /// Returns the underlying s2n-tls config builder
+    ///
+    /// Changes made through the returned builder can't be applied to the certificates of a
+    /// [`CertificateResolver`], so [`Builder::build`] fails if this is combined with
+    /// [`Builder::with_certificate_resolver`].
     pub fn config_mut(&mut self) -> &mut s2n_tls::config::Builder {
+        self.config_mut_used = true;
         &mut self.config
     }
 
@@ -103,7 +118,10 @@ impl Builder {
         mut self,
         handler: T,
     ) -> Result<Self, Error> {
-        self.config.set_client_hello_callback(handler)?;
+        let handler: Arc<dyn ClientHelloCallback> = Arc::new(handler);
+        self.config
+            .set_client_hello_callback(Shared(handler.clone()))?;
+        self.template.client_hello = Some(handler);
         Ok(self)
     }
 
@@ -112,7 +130,19 @@ impl Builder {
         mut self,
         handler: T,
     ) -> Result<Self, Error> {
-        self.config.set_private_key_callback(handler)?;
+        let handler: Arc<dyn PrivateKeyCallback> = Arc::new(handler);
+        self.config
+            .set_private_key_callback(Shared(handler.clone()))?;
+        self.template.private_key = Some(handler);
+        Ok(self)
+    }
+
+    /// Sets the security policy used for the handshake
+    ///
+    /// The policy must support TLS 1.3.
+    pub fn with_security_policy(mut self, policy: &security::Policy) -> Result<Self, Error> {
+        self.config.set_security_policy(policy)?;
+        self.template.security_policy = Some(policy.clone());
         Ok(self)
     }
 
@@ -120,7 +150,13 @@ impl Builder {
         mut self,
         protocols: P,
     ) -> Result<Self, Error> {
-        self.config.set_application_protocol_preference(protocols)?;
+        let protocols: Vec<Vec<u8>> = protocols
+            .into_iter()
+            .map(|protocol| protocol.as_ref().to_vec())
+            .collect();
+        self.config
+            .set_application_protocol_preference(&protocols)?;
+        self.template.application_protocols = protocols;
         Ok(self)
     }
 
@@ -152,6 +188,9 @@ impl Builder {
             .as_pem()
             .expect("pem is currently the only certificate format supported");
         self.config.trust_pem(certificate)?;
+        self.template
+            .trusted_certificates
+            .push(certificate.to_vec());
         Ok(self)
     }
 
@@ -166,12 +205,15 @@ impl Builder {
     /// Calling this method after a method that modifies the trust store will clear it.
     pub fn with_empty_trust_store(mut self) -> Result<Self, Error> {
         self.config.wipe_trust_store()?;
+        self.template.empty_trust_store = true;
+        self.template.trusted_certificates.clear();
         Ok(self)
     }
 
     /// Configures this server instance to require client authentication (mutual TLS).
     pub fn with_client_authentication(mut self) -> Result<Self, Error> {
         self.config.set_client_auth_type(ClientAuthType::Required)?;
+        self.template.client_auth = true;
         Ok(self)
     }
 
@@ -179,11 +221,10 @@ impl Builder {
     /// server instance when a client certificate is presented during the mutual TLS handshake.
     #[deprecated(note = "use `with_verify_host_name_callback` instead")]
     pub fn with_verify_client_certificate_handler<T: 'static + VerifyHostNameCallback>(
-        mut self,
+        self,
         handler: T,
     ) -> Result<Self, Error> {
-        self.config.set_verify_host_callback(handler)?;
-        Ok(self)
+        self.with_verify_host_name_callback(handler)
     }
 
     /// Set the host name verification callback.
@@ -194,14 +235,48 @@ impl Builder {
         mut self,
         handler: T,
     ) -> Result<Self, Error> {
-        self.config.set_verify_host_callback(handler)?;
+        let handler: Arc<dyn VerifyHostNameCallback> = Arc::new(handler);
+        self.config
+            .set_verify_host_callback(Shared(handler.clone()))?;
+        self.template.verify_host_name = Some(handler);
         Ok(self)
     }
 
-    pub fn with_key_logging(mut self) -> Result<Self, Error> {
-        use crate::keylog::KeyLog;
+    /// Selects the certificate for each connection based on the hostname sent by the client
+    ///
+    /// The application protocols, trust store, client authentication, security policy, callbacks
+    /// and key logging settings of this builder are applied to each certificate in the resolver.
+    /// The client hello handler, if any, is called after the resolver has selected the
+    /// certificate. Changes made through [`Builder::config_mut`] can't be applied, so
+    /// [`Builder::build`] returns an error if it was used.
+    ///
+    /// Connections that don't match a certificate in the resolver use the certificate
+    /// configured with [`Builder::with_certificate`], if any.
+    pub fn with_certificate_resolver(
+        mut self,
+        resolver: CertificateResolver,
+    ) -> Result<Self, Error> {
+        self.resolver = Some(resolver);
+        Ok(self)
+    }
+
+    /// Logs TLS secrets to the file specified by the `SSLKEYLOGFILE` environment variable
+    pub fn with_key_logging(self) -> Result<Self, Error> {
+        let keylog = KeyLog::try_open();
+        self.set_key_log(keylog)
+    }
+
+    /// Logs TLS secrets to the provided [`KeyLog`](s2n_quic_core::crypto::tls::KeyLog)
+    ///
+    /// **WARNING**: the logged secrets can be used to decrypt all traffic and should only be
+    /// enabled for debugging.
+    pub fn with_key_log<K: tls::KeyLog>(self, key_log: K) -> Result<Self, Error> {
+        let keylog = KeyLog::new(Arc::new(key_log));
+        self.set_key_log(Some(keylog))
+    }
 
-        self.keylog = KeyLog::try_open();
+    fn set_key_log(mut self, keylog: Option<KeyLogHandle>) -> Result<Self, Error> {
+        self.keylog = keylog;
 
         unsafe {
             // Safety: the KeyLog is stored on `self` to ensure it outlives `config`
@@ -222,8 +297,26 @@ impl Builder {
         #[cfg(feature = "fips")]
         assert!(s2n_tls::init::fips_mode()?.is_enabled());
 
+        let mut config = self.config;
+
+        if let Some(resolver) = self.resolver {
+            if self.config_mut_used {
+                return Err(Error::application(
+                    "`config_mut` can't be combined with a certificate resolver".into(),
+                ));
+            }
+
+            let template = Template {
+                keylog: self.keylog.clone(),
+                ..self.template
+            };
+            resolver.bind(template)?;
+            // the resolver calls the application's client hello handler, if any
+            config.set_client_hello_callback(resolver)?;
+        }
+
         Ok(Server {
-            loader: self.config.build()?,
+            loader: config.build()?,
             keylog: self.keylog,
             params: Default::default(),
         })
@@ -124,6 +124,7 @@ impl tls::Session for Session {
     where
         W: tls::Context<Self>,
     {
+        let key_log_context = context.key_log_context();
         let mut callback: Callback<W, Self> = Callback {
             context,
             endpoint: self.endpoint,
@@ -142,7 +143,8 @@ impl tls::Session for Session {
             callback.set(&mut self.connection);
         }
 
-        let result = self.connection.poll_negotiate().map_ok(|_| ());
+        let result =
+            tls::keylog::scope(key_log_context, || self.connection.poll_negotiate()).map_ok(|_| ());
 
         callback.unset(&mut self.connection)?;
 
@@ -170,6 +172,7 @@ impl tls::Session for Session {
     where
         W: tls::Context<Self>,
     {
+        let key_log_context = context.key_log_context();
         let mut callback: Callback<W, Self> = Callback {
             context,
             endpoint: self.endpoint,
@@ -188,10 +191,10 @@ impl tls::Session for Session {
             callback.set(&mut self.connection);
         }
 
-        let result = self
-            .connection
-            .quic_process_post_handshake_message()
-            .map(|_| ());
+        let result = tls::keylog::scope(key_log_context, || {
+            self.connection.quic_process_post_handshake_message()
+        })
+        .map(|_| ());
 
         callback.unset(&mut self.connection)?;
 
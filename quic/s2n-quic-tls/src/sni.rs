@@ -0,0 +1,373 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Selects the server certificate based on the Server Name Indication (SNI) sent by the client
+//!
+//! A [`CertificateResolver`] maps hostnames to certificate chains and can be updated at runtime
+//! while the server is accepting connections. Hostnames may either be exact, such as
+//! `example.com`, or wildcards, such as `*.example.com`, which match a single label.
+//! Connections that don't match any hostname use the default certificate, if configured, and
+//! otherwise the certificate configured on the server [`Builder`](crate::server::Builder).
+
+use crate::{
+    certificate::{Format, IntoCertificate, IntoPrivateKey},
+    keylog::{KeyLog, KeyLogHandle},
+};
+use core::pin::Pin;
+use s2n_tls::{
+    callbacks::{
+        ClientHelloCallback, ConnectionFuture, PrivateKeyCallback, PrivateKeyOperation,
+        VerifyHostNameCallback,
+    },
+    cert_chain::{self, CertificateChain},
+    config::{self, Config},
+    connection::Connection,
+    enums::ClientAuthType,
+    error::Error,
+    security,
+};
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard},
+};
+
+/// A set of certificates selected by the hostname requested by the client
+///
+/// The resolver is cheap to clone and all clones refer to the same set of certificates,
+/// which allows the certificates to be updated after the server has been built.
+///
+/// Note that a resolver should only be used with a single server, since the server's
+/// protocol and client authentication settings are applied to each certificate.
+#[derive(Clone, Default)]
+pub struct CertificateResolver {
+    inner: Arc<RwLock<Inner>>,
+}
+
+impl CertificateResolver {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds or replaces the certificate for the given hostname
+    ///
+    /// The hostname can either be exact or a wildcard in the form of `*.example.com`.
+    pub fn insert<C: IntoCertificate, PK: IntoPrivateKey>(
+        &self,
+        hostname: &str,
+        certificate: C,
+        private_key: PK,
+    ) -> Result<(), Error> {
+        let entry = Entry::new(certificate, private_key)?;
+        let mut inner = self.write();
+        let entry = inner.configure(entry)?;
+        inner.hosts.insert(normalize(hostname), entry);
+        Ok(())
+    }
+
+    /// Removes the certificate for the given hostname
+    ///
+    /// Returns `true` if the hostname was present.
+    pub fn remove(&self, hostname: &str) -> bool {
+        self.write().hosts.remove(&normalize(hostname)).is_some()
+    }
+
+    /// Sets the certificate used when the client's hostname doesn't match any other entry
+    pub fn set_default<C: IntoCertificate, PK: IntoPrivateKey>(
+        &self,
+        certificate: C,
+        private_key: PK,
+    ) -> Result<(), Error> {
+        let entry = Entry::new(certificate, private_key)?;
+        let mut inner = self.write();
+        let entry = inner.configure(entry)?;
+        inner.default = Some(entry);
+        Ok(())
+    }
+
+    /// Removes the default certificate
+    pub fn clear_default(&self) {
+        self.write().default = None;
+    }
+
+    /// Returns the number of hostnames with a certificate
+    pub fn len(&self) -> usize {
+        self.read().hosts.len()
+    }
+
+    /// Returns `true` if no hostnames have a certificate
+    pub fn is_empty(&self) -> bool {
+        self.read().hosts.is_empty()
+    }
+
+    /// Binds the resolver to the server settings and rebuilds the config for each certificate
+    pub(crate) fn bind(&self, template: Template) -> Result<(), Error> {
+        let mut inner = self.write();
+        inner.template = Some(template);
+
+        let hosts = core::mem::take(&mut inner.hosts);
+        for (hostname, entry) in hosts {
+            let entry = inner.configure(entry)?;
+            inner.hosts.insert(hostname, entry);
+        }
+
+        if let Some(entry) = inner.default.take() {
+            inner.default = Some(inner.configure(entry)?);
+        }
+
+        Ok(())
+    }
+
+    fn resolve(&self, hostname: Option<&str>) -> Option<Config> {
+        let inner = self.read();
+
+        let entry = hostname
+            .map(normalize)
+            .and_then(|hostname| inner.lookup(&hostname))
+            .or(inner.default.as_ref())?;
+
+        entry.config.clone()
+    }
+
+    fn read(&self) -> RwLockReadGuard<Inner> {
+        // the map is always left consistent so recover from poisoning
+        self.inner.read().unwrap_or_else(|err| err.into_inner())
+    }
+
+    fn write(&self) -> RwLockWriteGuard<Inner> {
+        self.inner.write().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+impl ClientHelloCallback for CertificateResolver {
+    fn on_client_hello(
+        &self,
+        connection: &mut Connection,
+    ) -> Result<Option<Pin<Box<dyn ConnectionFuture>>>, Error> {
+        let hostname = connection.server_name();
+        let has_hostname = hostname.is_some();
+
+        // fall back to the server's config if nothing matches
+        if let Some(config) = self.resolve(hostname) {
+            connection.set_config(config)?;
+
+            if has_hostname {
+                connection.server_name_extension_used();
+            }
+        }
+
+        let handler = self
+            .read()
+            .template
+            .as_ref()
+            .and_then(|template| template.client_hello.clone());
+
+        match handler {
+            Some(handler) => handler.on_client_hello(connection),
+            None => Ok(None),
+        }
+    }
+}
+
+#[derive(Default)]
+struct Inner {
+    template: Option<Template>,
+    hosts: HashMap<String, Entry>,
+    default: Option<Entry>,
+}
+
+impl Inner {
+    fn lookup(&self, hostname: &str) -> Option<&Entry> {
+        if let Some(entry) = self.hosts.get(hostname) {
+            return Some(entry);
+        }
+
+        // wildcards only match a single label
+        let (_, parent) = hostname.split_once('.')?;
+        self.hosts.get(&format!("*.{parent}"))
+    }
+
+    /// Builds the config for the entry if the resolver is bound to a server
+    fn configure(&self, mut entry: Entry) -> Result<Entry, Error> {
+        if let Some(template) = self.template.as_ref() {
+            entry.config = Some(template.build(entry.chain.clone())?);
+        }
+        Ok(entry)
+    }
+}
+
+struct Entry {
+    chain: CertificateChain<'static>,
+    config: Option<Config>,
+}
+
+impl Entry {
+    fn new<C: IntoCertificate, PK: IntoPrivateKey>(
+        certificate: C,
+        private_key: PK,
+    ) -> Result<Self, Error> {
+        let certificate = certificate.into_certificate()?.0;
+        let certificate = certificate
+            .as_pem()
+            .expect("pem is currently the only certificate format supported");
+
+        let mut chain = cert_chain::Builder::new()?;
+        match private_key.into_private_key()?.0 {
+            Format::Pem(bytes) => chain.load_pem(certificate, bytes.as_ref())?,
+            Format::None => chain.load_public_pem(certificate)?,
+            Format::Der(_) => panic!("der private keys not supported"),
+        };
+
+        Ok(Self {
+            chain: chain.build()?,
+            config: None,
+        })
+    }
+}
+
+/// The server settings applied to each certificate in a [`CertificateResolver`]
+#[derive(Clone)]
+pub(crate) struct Template {
+    pub application_protocols: Vec<Vec<u8>>,
+    pub client_auth: bool,
+    pub empty_trust_store: bool,
+    pub trusted_certificates: Vec<Vec<u8>>,
+    pub security_policy: Option<security::Policy>,
+    pub verify_host_name: Option<Arc<dyn VerifyHostNameCallback>>,
+    pub private_key: Option<Arc<dyn PrivateKeyCallback>>,
+    /// Called by the resolver since it replaces the server's client hello callback
+    pub client_hello: Option<Arc<dyn ClientHelloCallback>>,
+    pub keylog: Option<KeyLogHandle>,
+}
+
+impl Default for Template {
+    fn default() -> Self {
+        Self {
+            application_protocols: vec![b"h3".to_vec()],
+            client_auth: false,
+            empty_trust_store: false,
+            trusted_certificates: Vec::new(),
+            security_policy: None,
+            verify_host_name: None,
+            private_key: None,
+            client_hello: None,
+            keylog: None,
+        }
+    }
+}
+
+impl Template {
+    fn build(&self, chain: CertificateChain<'static>) -> Result<Config, Error> {
+        let mut config = config::Builder::default();
+        config.enable_quic()?;
+        config.set_security_policy(
+            self.security_policy
+                .as_ref()
+                .unwrap_or(crate::DEFAULT_POLICY),
+        )?;
+        config.set_application_protocol_preference(&self.application_protocols)?;
+        config.load_chain(chain)?;
+
+        if self.empty_trust_store {
+            config.wipe_trust_store()?;
+        }
+
+        for certificate in &self.trusted_certificates {
+            config.trust_pem(certificate)?;
+        }
+
+        if self.client_auth {
+            config.set_client_auth_type(ClientAuthType::Required)?;
+        }
+
+        if let Some(handler) = self.verify_host_name.as_ref() {
+            config.set_verify_host_callback(Shared(handler.clone()))?;
+        }
+
+        if let Some(handler) = self.private_key.as_ref() {
+            config.set_private_key_callback(Shared(handler.clone()))?;
+        }
+
+        unsafe {
+            // Safety: the KeyLog is stored on the template which outlives `config`
+            if let Some(keylog) = self.keylog.as_ref() {
+                config
+                    .set_key_log_callback(Some(KeyLog::callback), Arc::as_ptr(keylog) as *mut _)?;
+            }
+        }
+
+        config.build()
+    }
+}
+
+/// Shares a callback between the server's config and the config of each certificate
+pub(crate) struct Shared<T: ?Sized>(pub Arc<T>);
+
+impl VerifyHostNameCallback for Shared<dyn VerifyHostNameCallback> {
+    fn verify_host_name(&self, host_name: &str) -> bool {
+        self.0.verify_host_name(host_name)
+    }
+}
+
+impl PrivateKeyCallback for Shared<dyn PrivateKeyCallback> {
+    fn handle_operation(
+        &self,
+        connection: &mut Connection,
+        operation: PrivateKeyOperation,
+    ) -> Result<Option<Pin<Box<dyn ConnectionFuture>>>, Error> {
+        self.0.handle_operation(connection, operation)
+    }
+}
+
+impl ClientHelloCallback for Shared<dyn ClientHelloCallback> {
+    fn on_client_hello(
+        &self,
+        connection: &mut Connection,
+    ) -> Result<Option<Pin<Box<dyn ConnectionFuture>>>, Error> {
+        self.0.on_client_hello(connection)
+    }
+}
+
+fn normalize(hostname: &str) -> String {
+    hostname.trim_end_matches('.').to_ascii_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use s2n_quic_core::crypto::tls::testing::certificates::{CERT_PEM, KEY_PEM};
+
+    #[test]
+    fn lookup_test() {
+        let resolver = CertificateResolver::new();
+        resolver.insert("example.com", CERT_PEM, KEY_PEM).unwrap();
+        resolver.insert("*.Example.com", CERT_PEM, KEY_PEM).unwrap();
+        resolver
+            .insert("api.example.com", CERT_PEM, KEY_PEM)
+            .unwrap();
+
+        let inner = resolver.read();
+        let lookup = |hostname: &str| {
+            inner
+                .lookup(&normalize(hostname))
+                .map(|entry| entry as *const Entry)
+        };
+
+        let exact = inner.hosts.get("example.com").map(|e| e as *const _);
+        let wildcard = inner.hosts.get("*.example.com").map(|e| e as *const _);
+        let api = inner.hosts.get("api.example.com").map(|e| e as *const _);
+
+        assert_eq!(lookup("example.com"), exact);
+        assert_eq!(lookup("EXAMPLE.com."), exact);
+        assert_eq!(lookup("www.example.com"), wildcard);
+        assert_eq!(lookup("api.example.com"), api);
+        assert_eq!(lookup("a.b.example.com"), None);
+        assert_eq!(lookup("example.org"), None);
+    }
+
+    #[test]
+    fn invalid_key_test() {
+        let resolver = CertificateResolver::new();
+        assert!(resolver.insert("example.com", CERT_PEM, "").is_err());
+        assert!(resolver.is_empty());
+    }
+}
@@ -515,3 +515,235 @@ fn config_loader() {
     // make sure the server can actually create a session
     let _ = server.new_server_session(&1);
 }
+
+fn s2n_server_with_resolver(resolver: &crate::sni::CertificateResolver) -> server::Server {
+    server::Builder::default()
+        .with_certificate_resolver(resolver.clone())
+        .unwrap()
+        .build()
+        .unwrap()
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn s2n_client_s2n_server_sni_resolver_test() {
+    let resolver = crate::sni::CertificateResolver::new();
+    resolver
+        .insert("localhost", UNTRUSTED_CERT_PEM, UNTRUSTED_KEY_PEM)
+        .unwrap();
+    resolver.insert("*.example.com", CERT_PEM, KEY_PEM).unwrap();
+
+    let mut server_endpoint = s2n_server_with_resolver(&resolver);
+
+    // the client doesn't trust the certificate for `localhost`
+    let mut client_endpoint = s2n_client();
+    assert!(run_result(&mut server_endpoint, &mut client_endpoint, None).is_err());
+
+    // certificates can be updated after the server is built
+    resolver.insert("localhost", CERT_PEM, KEY_PEM).unwrap();
+    run(&mut server_endpoint, &mut client_endpoint, None);
+
+    // unmatched hostnames use the default certificate
+    assert!(resolver.remove("localhost"));
+    resolver.set_default(CERT_PEM, KEY_PEM).unwrap();
+    run(&mut server_endpoint, &mut client_endpoint, None);
+
+    // without a default, the handshake fails since the server has no certificate
+    resolver.clear_default();
+    assert!(run_result(&mut server_endpoint, &mut client_endpoint, None).is_err());
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn s2n_client_s2n_server_sni_resolver_client_auth_test() {
+    let resolver = crate::sni::CertificateResolver::new();
+    resolver.insert("localhost", CERT_PEM, KEY_PEM).unwrap();
+
+    let server = |verifier: Box<dyn Fn(server::Builder) -> Result<server::Builder, Error>>| {
+        let builder = server::Builder::default()
+            .with_empty_trust_store()?
+            .with_client_authentication()?
+            .with_trusted_certificate(CERT_PEM)?
+            .with_certificate_resolver(resolver.clone())?;
+        verifier(builder)?.build()
+    };
+
+    // the host name callback is applied to the resolved certificate
+    let mut server_endpoint = server(Box::new(|builder| {
+        builder.with_verify_host_name_callback(RejectAllClientCertificatesHandler::default())
+    }))
+    .unwrap();
+    let mut client_endpoint = s2n_client_with_client_auth().unwrap();
+    assert!(run_result(&mut server_endpoint, &mut client_endpoint, None).is_err());
+
+    let mut server_endpoint = server(Box::new(|builder| {
+        builder.with_verify_host_name_callback(VerifyHostNameClientCertVerifier::new("qlaws.qlaws"))
+    }))
+    .unwrap();
+    run(&mut server_endpoint, &mut client_endpoint, None);
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn s2n_client_s2n_server_sni_resolver_ch_callback_test() {
+    let resolver = crate::sni::CertificateResolver::new();
+    resolver.insert("localhost", CERT_PEM, KEY_PEM).unwrap();
+
+    // the application's client hello handler is called after the certificate is resolved
+    let handler = MyCallbackHandler::new(2);
+    let done = handler.done.clone();
+    let mut server_endpoint = server::Builder::default()
+        .with_certificate_resolver(resolver)
+        .unwrap()
+        .with_client_hello_handler(handler)
+        .unwrap()
+        .build()
+        .unwrap();
+    let mut client_endpoint = s2n_client();
+
+    run(
+        &mut server_endpoint,
+        &mut client_endpoint,
+        Some(done.clone()),
+    );
+    assert!(done.load(Ordering::SeqCst));
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn s2n_client_s2n_server_sni_resolver_pkey_callback_test() {
+    let resolver = crate::sni::CertificateResolver::new();
+    resolver
+        .insert("localhost", CERT_PEM, certificate::OFFLOAD_PRIVATE_KEY)
+        .unwrap();
+
+    // the private key for the resolved certificate is only available through the handler
+    let handler = MyCallbackHandler::new(2);
+    let done = handler.done.clone();
+    let mut server_endpoint = server::Builder::default()
+        .with_certificate_resolver(resolver)
+        .unwrap()
+        .with_private_key_handler(handler)
+        .unwrap()
+        .build()
+        .unwrap();
+    let mut client_endpoint = s2n_client();
+
+    run(
+        &mut server_endpoint,
+        &mut client_endpoint,
+        Some(done.clone()),
+    );
+    assert!(done.load(Ordering::SeqCst));
+}
+
+#[test]
+fn sni_resolver_config_mut_test() {
+    let mut builder = server::Builder::default()
+        .with_certificate_resolver(crate::sni::CertificateResolver::new())
+        .unwrap();
+    builder
+        .config_mut()
+        .set_security_policy(&s2n_tls::security::DEFAULT_TLS13)
+        .unwrap();
+
+    // changes to the config can't be applied to the resolved certificates
+    assert!(builder.build().is_err());
+
+    // use the builder method instead
+    server::Builder::default()
+        .with_certificate_resolver(crate::sni::CertificateResolver::new())
+        .unwrap()
+        .with_security_policy(&s2n_tls::security::DEFAULT_TLS13)
+        .unwrap()
+        .build()
+        .unwrap();
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn s2n_client_rustls_server_key_log_test() {
+    use s2n_quic_core::crypto::tls::keylog::Buffer;
+
+    let client_key_log = Arc::new(Buffer::default());
+    let server_key_log = Arc::new(Buffer::default());
+
+    let mut client_endpoint = client::Builder::default()
+        .with_certificate(CERT_PEM)
+        .unwrap()
+        .with_key_log(client_key_log.clone())
+        .unwrap()
+        .build()
+        .unwrap();
+    let mut server_endpoint = s2n_quic_rustls::server::Builder::default()
+        .with_certificate(CERT_PEM, KEY_PEM)
+        .unwrap()
+        .with_key_log(server_key_log.clone())
+        .unwrap()
+        .build()
+        .unwrap();
+
+    let mut pair = tls::testing::Pair::new(
+        &mut server_endpoint,
+        &mut client_endpoint,
+        "localhost".into(),
+    );
+    pair.client.context.connection_id = Some(1);
+    pair.server.context.connection_id = Some(2);
+
+    while pair.is_handshaking() {
+        pair.poll(None).unwrap();
+    }
+    pair.finish();
+
+    // secrets should be attributed to the connection which derived them
+    assert_eq!(client_key_log.take_connection(2), "");
+    assert_eq!(server_key_log.take_connection(1), "");
+
+    // both providers should log the same secrets in the same format
+    let mut client_lines: Vec<_> = client_key_log
+        .take_connection(1)
+        .lines()
+        .map(String::from)
+        .collect();
+    let mut server_lines: Vec<_> = server_key_log
+        .take_connection(2)
+        .lines()
+        .map(String::from)
+        .collect();
+    client_lines.sort();
+    server_lines.sort();
+
+    assert!(!client_lines.is_empty());
+    assert_eq!(client_lines, server_lines);
+    assert_eq!(client_key_log.malformed_lines(), 0);
+}
+
+#[test]
+fn key_log_malformed_line_test() {
+    use s2n_quic_core::crypto::tls::keylog::{self, Buffer};
+
+    let buffer = Arc::new(Buffer::default());
+    let handle = crate::keylog::KeyLog::new(buffer.clone());
+
+    let callback = |line: &[u8]| {
+        let mut line = line.to_vec();
+        let context = keylog::Context::new(Some(1));
+        keylog::scope(context, || unsafe {
+            // Safety: the handle and line outlive the call
+            crate::keylog::KeyLog::callback(
+                Arc::as_ptr(&handle) as *mut _,
+                core::ptr::null_mut(),
+                line.as_mut_ptr(),
+                line.len(),
+            )
+        })
+    };
+
+    assert_eq!(callback(b"LABEL 01 02"), 0);
+    assert_eq!(callback(b"LABEL zz 02"), 0);
+    assert_eq!(callback(b"LABEL 01"), 0);
+
+    assert_eq!(buffer.malformed_lines(), 2);
+    assert_eq!(buffer.take_connection(1), "LABEL 01 02\n");
+}
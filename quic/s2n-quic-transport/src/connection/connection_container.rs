@@ -486,6 +486,21 @@ impl<C: connection::Trait, L: connection::Lock<C>> InterestLists<C, L> {
             );
         }
 
+        // Close client connections which are still handshaking after every attempt waiting on
+        // them was dropped, rather than letting them run until the handshake completes or
+        // times out.
+        if <C::Config as endpoint::Config>::ENDPOINT_TYPE.is_client()
+            && !interests.accept
+            && !interests.finalization
+            && self
+                .waiting_for_open
+                .get(&id)
+                .map_or(false, |senders| senders.iter().all(|s| s.is_canceled()))
+        {
+            node.inner
+                .write(|conn| conn.application_close(Some(application::Error::UNKNOWN)))?;
+        }
+
         // Accepted connections are only automatically pushed into the accepted connections queue.
         if interests.accept {
             node.inner.write(|conn| {
@@ -5,9 +5,11 @@ use super::handle::CloseReceiver;
 use crate::{connection, endpoint::handle::CloseSender};
 use alloc::sync::Arc;
 use core::{
+    pin::Pin,
     sync::atomic::{AtomicBool, Ordering},
     task::{Context, Poll, Waker},
 };
+use futures_core::Stream;
 
 /// Held by library. Used to receive close attempts and track close state.
 #[derive(Debug)]
@@ -30,12 +32,14 @@ impl CloseHandle {
     }
 
     /// Returns `Poll::Ready` if there is interest in closing the endpoint.
-    pub fn poll_interest(&mut self) -> Poll<()> {
+    ///
+    /// The task in `cx` is woken up once a close attempt is made.
+    pub fn poll_interest(&mut self, cx: &mut Context) -> Poll<()> {
         if self.first_waker.is_some() {
             Poll::Ready(())
         } else {
-            match self.close_receiver.try_next() {
-                Ok(Some(waker)) => {
+            match Stream::poll_next(Pin::new(&mut self.close_receiver), cx) {
+                Poll::Ready(Some(waker)) => {
                     self.first_waker = Some(waker);
                     Poll::Ready(())
                 }
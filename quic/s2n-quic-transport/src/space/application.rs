@@ -296,6 +296,9 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
             context.publisher,
         );
 
+        self.datagram_manager
+            .on_congestion_update(context.path_manager.active_path(), context.timestamp);
+
         // reset the keep alive timer after sending an ack-eliciting packet
         if outcome.ack_elicitation.is_ack_eliciting() {
             self.keep_alive.reset(context.timestamp);
@@ -480,6 +483,9 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
             publisher,
         );
 
+        self.datagram_manager
+            .on_congestion_update(path_manager.active_path(), timestamp);
+
         match self.skip_counter {
             Some(skip_counter) if skip_counter == 0 => {
                 if self.tx_packet_numbers.should_skip_packet_number() {
@@ -572,6 +578,7 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
                 path_manager,
                 tx_packet_numbers: &mut self.tx_packet_numbers,
                 dc_manager: &mut self.dc_manager,
+                datagram_manager: &mut self.datagram_manager,
             },
         )
     }
@@ -717,6 +724,7 @@ struct RecoveryContext<'a, Config: endpoint::Config> {
     path_manager: &'a mut path::Manager<Config>,
     tx_packet_numbers: &'a mut TxPacketNumbers,
     dc_manager: &'a mut dc::Manager<Config>,
+    datagram_manager: &'a mut datagram::Manager<Config>,
 }
 
 impl<'a, Config: endpoint::Config> recovery::Context<Config> for RecoveryContext<'a, Config> {
@@ -781,6 +789,7 @@ impl<'a, Config: endpoint::Config> recovery::Context<Config> for RecoveryContext
         self.stream_manager.on_packet_ack(packet_number_range);
         self.local_id_registry.on_packet_ack(packet_number_range);
         self.path_manager.on_packet_ack(packet_number_range);
+        self.datagram_manager.on_packet_ack(packet_number_range);
     }
 
     fn on_packet_ack(&mut self, timestamp: Timestamp, packet_number_range: &PacketNumberRange) {
@@ -802,6 +811,7 @@ impl<'a, Config: endpoint::Config> recovery::Context<Config> for RecoveryContext
         self.stream_manager.on_packet_loss(packet_number_range);
         self.local_id_registry.on_packet_loss(packet_number_range);
         self.path_manager.on_packet_loss(packet_number_range);
+        self.datagram_manager.on_packet_loss(packet_number_range);
     }
 
     fn on_rtt_update(&mut self, now: Timestamp) {
@@ -883,7 +893,12 @@ impl<Config: endpoint::Config> PacketSpace<Config> for ApplicationSpace<Config>
             random_generator,
             &mut context,
             publisher,
-        )
+        )?;
+
+        self.datagram_manager
+            .on_congestion_update(path_manager.active_path(), timestamp);
+
+        Ok(())
     }
 
     fn handle_connection_close_frame(
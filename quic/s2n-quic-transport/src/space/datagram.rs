@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    endpoint,
+    endpoint, path,
     stream::Manager as _,
     transmission::{
         interest::{self, Provider},
@@ -12,9 +12,12 @@ use crate::{
 use core::task::Poll;
 use s2n_codec::EncoderValue;
 use s2n_quic_core::{
-    datagram::{Endpoint, ReceiveContext, Receiver, Sender, WriteError},
+    datagram::{CongestionInfo, Endpoint, ReceiveContext, Receiver, Sender, WriteError},
     frame::{self, datagram::DatagramRef},
+    packet::number::{PacketNumber, PacketNumberRange},
     query,
+    recovery::CongestionController as _,
+    time::Timestamp,
     varint::VarInt,
 };
 
@@ -68,6 +71,30 @@ impl<Config: endpoint::Config> Manager<Config> {
         self.receiver.on_datagram(&context, datagram.data);
     }
 
+    /// Notifies the sender that a range of packets was acknowledged by the peer
+    #[inline]
+    pub fn on_packet_ack(&mut self, packet_number_range: &PacketNumberRange) {
+        self.sender.on_packet_ack(packet_number_range);
+    }
+
+    /// Notifies the sender that a range of packets was declared lost
+    #[inline]
+    pub fn on_packet_loss(&mut self, packet_number_range: &PacketNumberRange) {
+        self.sender.on_packet_loss(packet_number_range);
+    }
+
+    /// Notifies the sender of the current congestion state of the active path
+    #[inline]
+    pub fn on_congestion_update(&mut self, path: &path::Path<Config>, timestamp: Timestamp) {
+        let info = CongestionInfo::new(
+            path.congestion_controller.congestion_window(),
+            path.congestion_controller.bytes_in_flight(),
+            path.rtt_estimator.smoothed_rtt(),
+            timestamp,
+        );
+        self.sender.on_congestion_update(&info);
+    }
+
     pub fn datagram_mut(&mut self, query: &mut dyn query::QueryMut) -> Poll<()> {
         // Try to execute the query on the sender side. If that fails, try the receiver side.
         match query.execute_mut(&mut self.sender) {
@@ -150,4 +177,13 @@ impl<'a, C: WriteContext> s2n_quic_core::datagram::Packet for Packet<'a, C> {
     fn datagrams_prioritized(&self) -> bool {
         self.datagrams_prioritized
     }
+
+    /// Returns the packet number of the packet being written
+    fn packet_number(&self) -> Option<PacketNumber> {
+        Some(self.context.packet_number())
+    }
+
+    fn current_time(&self) -> Option<Timestamp> {
+        Some(self.context.current_time())
+    }
 }
@@ -518,6 +518,20 @@ impl<'a, Config: endpoint::Config, Pub: event::ConnectionPublisher>
         Ok(())
     }
 
+    fn on_ech_status(
+        &mut self,
+        status: tls::EchStatus,
+        retry_configs: Option<&[u8]>,
+    ) -> Result<(), transport::Error> {
+        self.publisher
+            .on_tls_ech_status(event::builder::TlsEchStatus {
+                status: status.into_event(),
+                retry_configs,
+            });
+
+        Ok(())
+    }
+
     fn on_tls_exporter_ready(
         &mut self,
         session: &impl tls::TlsSession,
@@ -670,6 +684,13 @@ impl<'a, Config: endpoint::Config, Pub: event::ConnectionPublisher>
         self.waker
     }
 
+    fn connection_id(&self) -> Option<u64> {
+        match self.publisher.subject() {
+            event::api::Subject::Connection { id, .. } => Some(id),
+            _ => None,
+        }
+    }
+
     fn on_client_application_params(
         &mut self,
         client_params: ApplicationParameters,
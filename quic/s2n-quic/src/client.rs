@@ -14,10 +14,12 @@ use core::{
 use s2n_quic_transport::endpoint::{connect, handle::Connector};
 
 mod builder;
+mod happy_eyeballs;
 mod providers;
 
 pub use builder::*;
 pub use connect::Connect;
+pub use happy_eyeballs::{HappyEyeballs, HappyEyeballsAttempt};
 pub use providers::*;
 
 /// A QUIC client endpoint, capable of opening connections
@@ -25,6 +27,7 @@ pub use providers::*;
 pub struct Client {
     connector: Connector,
     local_addr: s2n_quic_core::inet::SocketAddress,
+    delay: io::Delay,
 }
 
 impl fmt::Debug for Client {
@@ -110,6 +113,40 @@ impl Client {
         ConnectionAttempt(attempt)
     }
 
+    /// Establishes a connection to the first responsive address out of a set of
+    /// candidate addresses
+    ///
+    /// Connection attempts are started one after another with a short delay in between,
+    /// alternating between IPv6 and IPv4 addresses, as described in
+    /// [RFC 8305](https://www.rfc-editor.org/rfc/rfc8305). The first attempt to complete
+    /// its handshake is returned. The remaining attempts are dropped, which closes their
+    /// connections, including those still in the middle of a handshake.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use std::error::Error;
+    /// use s2n_quic::{client::HappyEyeballs, Client};
+    /// use std::{net::SocketAddr, path::Path};
+    ///
+    /// # async fn connect() -> Result<(), Box<dyn Error>> {
+    /// let client = Client::builder()
+    ///     .with_tls(Path::new("./certs/cert.pem"))?
+    ///     .with_io("[::]:0")?
+    ///     .start()?;
+    ///
+    /// let v6: SocketAddr = "[2001:db8::1]:443".parse()?;
+    /// let v4: SocketAddr = "192.0.2.1:443".parse()?;
+    /// let connect = HappyEyeballs::new([v6, v4]).with_server_name("localhost");
+    /// let connection = client.connect_happy_eyeballs(connect).await?;
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn connect_happy_eyeballs(&self, connect: HappyEyeballs) -> HappyEyeballsAttempt {
+        HappyEyeballsAttempt::new(self.clone(), connect)
+    }
+
     /// Wait for the client endpoint to finish handling all outstanding connections
     ///
     /// Notifies the endpoint of application interest in closing the endpoint. The
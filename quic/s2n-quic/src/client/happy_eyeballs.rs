@@ -0,0 +1,233 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{Client, ConnectionAttempt};
+use crate::connection::{self, Connection};
+use core::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+use futures::stream::{FuturesUnordered, StreamExt};
+use s2n_quic_core::{application::ServerName, inet::SocketAddress};
+use s2n_quic_transport::endpoint::connect::Connect;
+use std::collections::VecDeque;
+
+/// The recommended delay between connection attempts
+///
+/// See <https://www.rfc-editor.org/rfc/rfc8305#section-8>
+const DEFAULT_ATTEMPT_DELAY: Duration = Duration::from_millis(250);
+
+/// The smallest delay allowed between connection attempts
+///
+/// See <https://www.rfc-editor.org/rfc/rfc8305#section-8>
+const MIN_ATTEMPT_DELAY: Duration = Duration::from_millis(10);
+
+/// A connection request for a peer that is reachable through multiple addresses
+///
+/// Addresses are attempted in the order they are provided, after interleaving
+/// address families so that a broken IPv6 or IPv4 network doesn't delay the other.
+#[derive(Clone, Debug)]
+pub struct HappyEyeballs {
+    addresses: Vec<SocketAddress>,
+    server_name: Option<ServerName>,
+    attempt_delay: Duration,
+}
+
+impl HappyEyeballs {
+    /// Creates a connection request with the specified remote addresses
+    pub fn new<I, Addr>(addresses: I) -> Self
+    where
+        I: IntoIterator<Item = Addr>,
+        Addr: Into<SocketAddress>,
+    {
+        Self {
+            addresses: addresses.into_iter().map(Into::into).collect(),
+            server_name: None,
+            attempt_delay: DEFAULT_ATTEMPT_DELAY,
+        }
+    }
+
+    /// Specifies the server name to use for each connection attempt
+    ///
+    /// Since every attempt uses the same server name, any session ticket stored for the
+    /// server is shared between them.
+    #[must_use]
+    pub fn with_server_name<Name: Into<ServerName>>(self, server_name: Name) -> Self {
+        Self {
+            server_name: Some(server_name.into()),
+            ..self
+        }
+    }
+
+    /// Specifies the delay between starting connection attempts
+    ///
+    /// Defaults to 250ms. Values below 10ms are raised to 10ms.
+    #[must_use]
+    pub fn with_attempt_delay(self, attempt_delay: Duration) -> Self {
+        Self {
+            attempt_delay: attempt_delay.max(MIN_ATTEMPT_DELAY),
+            ..self
+        }
+    }
+
+    /// Returns the addresses in the order they will be attempted
+    ///
+    /// The family of the first address is preferred, after which families alternate.
+    ///
+    /// See <https://www.rfc-editor.org/rfc/rfc8305#section-4>
+    fn sorted_addresses(&self) -> VecDeque<SocketAddress> {
+        let Some(first) = self.addresses.first() else {
+            return VecDeque::new();
+        };
+        let prefers_v6 = is_ipv6(first);
+
+        let (mut preferred, mut other): (VecDeque<_>, VecDeque<_>) = self
+            .addresses
+            .iter()
+            .copied()
+            .partition(|addr| is_ipv6(addr) == prefers_v6);
+
+        let mut sorted = VecDeque::with_capacity(self.addresses.len());
+        loop {
+            match (preferred.pop_front(), other.pop_front()) {
+                (None, None) => break,
+                (a, b) => sorted.extend(a.into_iter().chain(b)),
+            }
+        }
+        sorted
+    }
+
+    fn connect(&self, address: SocketAddress) -> Connect {
+        let connect = Connect::new(address);
+        match &self.server_name {
+            Some(server_name) => connect.with_server_name(server_name.clone()),
+            None => connect,
+        }
+    }
+}
+
+#[inline]
+fn is_ipv6(address: &SocketAddress) -> bool {
+    matches!(address, SocketAddress::IpV6(_))
+}
+
+/// A future which resolves to the first connection to complete its handshake
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct HappyEyeballsAttempt {
+    client: Client,
+    connect: HappyEyeballs,
+    addresses: VecDeque<SocketAddress>,
+    attempts: FuturesUnordered<ConnectionAttempt>,
+    delay: Option<Pin<Box<dyn Future<Output = ()> + Send>>>,
+    error: Option<connection::Error>,
+}
+
+impl HappyEyeballsAttempt {
+    pub(super) fn new(client: Client, connect: HappyEyeballs) -> Self {
+        let addresses = connect.sorted_addresses();
+        Self {
+            client,
+            connect,
+            addresses,
+            attempts: FuturesUnordered::new(),
+            delay: None,
+            error: None,
+        }
+    }
+}
+
+impl Future for HappyEyeballsAttempt {
+    type Output = Result<Connection, connection::Error>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+
+        loop {
+            // start the next attempt if nothing is in flight, the current attempt
+            // failed, or the attempt delay has elapsed
+            let mut start_next = this.attempts.is_empty();
+
+            if let Some(delay) = this.delay.as_mut() {
+                if delay.as_mut().poll(cx).is_ready() {
+                    this.delay = None;
+                    start_next = true;
+                }
+            }
+
+            while let Poll::Ready(Some(result)) = this.attempts.poll_next_unpin(cx) {
+                match result {
+                    // dropping the remaining attempts closes them
+                    Ok(connection) => return Poll::Ready(Ok(connection)),
+                    Err(error) => {
+                        this.error = Some(error);
+                        start_next = true;
+                    }
+                }
+            }
+
+            if !start_next {
+                return Poll::Pending;
+            }
+
+            if let Some(address) = this.addresses.pop_front() {
+                let attempt = this.client.connect(this.connect.connect(address));
+                this.attempts.push(attempt);
+                this.delay = Some((this.client.delay)(this.connect.attempt_delay));
+                // poll the new attempt and delay to register interest
+                continue;
+            }
+
+            this.delay = None;
+
+            if this.attempts.is_empty() {
+                let error = this
+                    .error
+                    .take()
+                    .unwrap_or_else(|| connection::Error::invalid_configuration("no addresses"));
+                return Poll::Ready(Err(error));
+            }
+
+            return Poll::Pending;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::SocketAddr;
+
+    #[test]
+    fn interleaves_families() {
+        let addrs: Vec<SocketAddr> = [
+            "[2001:db8::1]:443",
+            "[2001:db8::2]:443",
+            "[2001:db8::3]:443",
+            "192.0.2.1:443",
+            "192.0.2.2:443",
+        ]
+        .iter()
+        .map(|addr| addr.parse().unwrap())
+        .collect();
+
+        let sorted: Vec<SocketAddr> = HappyEyeballs::new(addrs.clone())
+            .sorted_addresses()
+            .into_iter()
+            .map(Into::into)
+            .collect();
+
+        assert_eq!(
+            sorted,
+            [addrs[0], addrs[3], addrs[1], addrs[4], addrs[2]].to_vec()
+        );
+    }
+
+    #[test]
+    fn attempt_delay_floor() {
+        let connect = HappyEyeballs::new([SocketAddr::from(([127, 0, 0, 1], 443))])
+            .with_attempt_delay(Duration::ZERO);
+        assert_eq!(connect.attempt_delay, MIN_ATTEMPT_DELAY);
+    }
+}
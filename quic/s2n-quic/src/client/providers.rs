@@ -134,12 +134,15 @@ impl<
 
         let (endpoint, connector) = endpoint::Endpoint::new_client(endpoint_config);
 
+        let delay = io.delay();
+
         // Start the IO last
         let local_addr = io.start(endpoint).map_err(StartError::new)?;
 
         Ok(Client {
             connector,
             local_addr,
+            delay,
         })
     }
 }
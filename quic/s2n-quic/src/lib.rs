@@ -29,6 +29,13 @@
 //! endpoint and connection events to the application's configured
 //! [`tracing::Subscriber`](https://docs.rs/tracing/latest/tracing/trait.Subscriber.html).
 //!
+//! ### `provider-event-serde`
+//!
+//! Implements [`serde::Serialize`](https://docs.rs/serde/latest/serde/trait.Serialize.html) for
+//! all of the types in [`provider::event::events`]. Every [`provider::event::Event`] and
+//! [`provider::event::Meta`] is also required to be serializable, which allows a single
+//! [`provider::event::Subscriber::on_event`] implementation to export all events.
+//!
 //! ### `provider-tls-default`
 //!
 //! _Enabled by default_
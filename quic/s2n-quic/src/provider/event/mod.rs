@@ -9,7 +9,7 @@ pub use s2n_quic_core::{
     event::{
         api as events,
         api::{ConnectionInfo, ConnectionMeta},
-        supervisor, Event, Meta, Subscriber, Timestamp,
+        filter, log, rate_limit, sample, supervisor, Event, Meta, Subscriber, Timestamp,
     },
     query,
 };
@@ -3,9 +3,13 @@
 
 //! Provides IO support for an endpoint
 
+use core::{future::Future, pin::Pin, time::Duration};
 use s2n_quic_core::{endpoint::Endpoint, inet::SocketAddress, path::Handle as PathHandle};
 use std::io;
 
+/// Returns a future which completes once the provided duration has elapsed
+pub type Delay = fn(Duration) -> Pin<Box<dyn Future<Output = ()> + Send>>;
+
 pub trait Provider: 'static {
     type PathHandle: PathHandle;
     type Error: 'static + core::fmt::Display + Send + Sync;
@@ -14,6 +18,15 @@ pub trait Provider: 'static {
         self,
         endpoint: E,
     ) -> Result<SocketAddress, Self::Error>;
+
+    /// Returns a function for creating timers on the runtime driving the provider
+    ///
+    /// This is used by the client to stagger connection attempts, so the timers must be
+    /// driven by the same clock as the endpoint. Defaults to timers on the tokio runtime;
+    /// providers driven by a different clock should override this.
+    fn delay(&self) -> Delay {
+        s2n_quic_platform::io::tokio::delay
+    }
 }
 
 #[cfg(any(test, feature = "unstable-provider-io-testing"))]
@@ -22,6 +22,10 @@ impl super::Provider for Io {
         let (_join_handle, local_addr) = Io::start(self, endpoint)?;
         Ok(local_addr)
     }
+
+    fn delay(&self) -> super::Delay {
+        |duration| Box::pin(time::delay(duration))
+    }
 }
 
 /// Runs a single test with the given network
@@ -6,6 +6,10 @@
 use cfg_if::cfg_if;
 use s2n_quic_core::crypto;
 
+pub mod reload;
+
+pub use crypto::tls::keylog::{self, KeyLog};
+
 pub trait Provider {
     type Server: 'static + crypto::tls::Endpoint;
     type Client: 'static + crypto::tls::Endpoint;
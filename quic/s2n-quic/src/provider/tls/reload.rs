@@ -0,0 +1,385 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Provides a TLS endpoint which can swap its key material without restarting
+//!
+//! The [`Reloadable`] provider wraps a function which builds a TLS endpoint, such as
+//! [`s2n_tls::Server`](super::s2n_tls::Server) or [`rustls::Server`](super::rustls::Server).
+//! The function is called again whenever [`Handle::reload`] is invoked or, optionally,
+//! when one of the watched files changes. The new endpoint is only used if it was
+//! built successfully; otherwise the current endpoint is kept. Each swap applies to
+//! handshakes started afterwards; existing connections are not affected.
+//!
+//! ```rust,no_run
+//! # use std::{error::Error, path::Path, time::Duration};
+//! use s2n_quic::{provider::tls::{self, reload::Reloadable}, Server};
+//!
+//! # fn main() -> Result<(), Box<dyn Error + Send + Sync>> {
+//! let tls = Reloadable::builder(|| {
+//!     tls::default::Server::builder()
+//!         .with_certificate(Path::new("cert.pem"), Path::new("key.pem"))?
+//!         .build()
+//! })
+//! .with_watched_file("cert.pem")
+//! .with_watched_file("key.pem")
+//! .with_poll_interval(Duration::from_secs(60))
+//! .build()?;
+//!
+//! // the handle can be used to trigger a reload from elsewhere in the application
+//! let handle = tls.handle();
+//!
+//! let server = Server::builder()
+//!     .with_tls(tls)?
+//!     .with_io("127.0.0.1:443")?
+//!     .start()?;
+//!
+//! handle.reload()?;
+//! # Ok(())
+//! # }
+//! ```
+
+use super::Provider;
+use s2n_codec::EncoderValue;
+use s2n_quic_core::{application::ServerName, crypto::tls};
+use std::{
+    collections::VecDeque,
+    fs,
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        mpsc, Arc, Mutex, MutexGuard, Weak,
+    },
+    thread,
+    time::{Duration, Instant, SystemTime},
+};
+
+pub type Error = Box<dyn std::error::Error + Send + Sync>;
+
+type LoadFn<E> = dyn Fn() -> Result<E, Error> + Send + Sync;
+type ErrorFn = dyn Fn(&Error) + Send + Sync;
+
+/// The default maximum handshake duration from the connection limits
+const DEFAULT_MAX_HANDSHAKE_DURATION: Duration = Duration::from_secs(10);
+
+/// A TLS provider which rebuilds its endpoint on demand or when watched files change
+pub struct Reloadable<E> {
+    handle: Handle<E>,
+    current: E,
+    /// Endpoints replaced by a reload, along with the time they were replaced
+    ///
+    /// Sessions created by an endpoint may reference resources that it owns, such as key
+    /// log callbacks. Replaced endpoints are kept alive until every handshake they started
+    /// has either completed or exceeded the maximum handshake duration.
+    retired: VecDeque<(Instant, E)>,
+    max_handshake_duration: Duration,
+    generation: u64,
+}
+
+impl<E: tls::Endpoint> Reloadable<E> {
+    /// Creates a builder with the function used to load the TLS endpoint
+    ///
+    /// The function is called once when the provider is built and again on every reload.
+    pub fn builder<F, Err>(load: F) -> Builder<E>
+    where
+        F: Fn() -> Result<E, Err> + 'static + Send + Sync,
+        Err: Into<Error>,
+    {
+        Builder {
+            load: Box::new(move || load().map_err(Into::into)),
+            files: Vec::new(),
+            poll_interval: None,
+            max_handshake_duration: DEFAULT_MAX_HANDSHAKE_DURATION,
+            on_error: Box::new(|_error| {}),
+        }
+    }
+
+    /// Returns a handle which can be used to reload the endpoint
+    pub fn handle(&self) -> Handle<E> {
+        self.handle.clone()
+    }
+
+    /// Switches to the most recently loaded endpoint, if any
+    ///
+    /// Loading happens on the reloading thread so this only takes the lock when the
+    /// generation changed.
+    fn refresh(&mut self) -> &mut E {
+        let shared = &self.handle.shared;
+        let generation = shared.generation.load(Ordering::Acquire);
+        if generation != self.generation {
+            if let Some(endpoint) = shared.lock().pending.take() {
+                let previous = core::mem::replace(&mut self.current, endpoint);
+                self.retired.push_back((Instant::now(), previous));
+            }
+            self.generation = generation;
+        }
+
+        while let Some((replaced_at, _)) = self.retired.front() {
+            if replaced_at.elapsed() <= self.max_handshake_duration {
+                break;
+            }
+            self.retired.pop_front();
+        }
+
+        &mut self.current
+    }
+}
+
+pub struct Builder<E> {
+    load: Box<LoadFn<E>>,
+    files: Vec<PathBuf>,
+    poll_interval: Option<Duration>,
+    max_handshake_duration: Duration,
+    on_error: Box<ErrorFn>,
+}
+
+impl<E: tls::Endpoint> Builder<E> {
+    /// Adds a file which causes the endpoint to be reloaded when it is modified
+    ///
+    /// Files are only checked if a poll interval is configured with
+    /// [`Builder::with_poll_interval`] or [`Handle::reload_if_modified`] is called.
+    #[must_use]
+    pub fn with_watched_file<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.files.push(path.into());
+        self
+    }
+
+    /// Sets how often watched files are checked for modifications
+    ///
+    /// Files are checked and the endpoint is loaded on a background thread, so handshakes
+    /// never wait on the file system. If the reload fails, the error is passed to the
+    /// [error handler](Builder::with_error_handler) and attempted again after the next interval.
+    /// The thread exits once the provider and all of its handles are dropped.
+    #[must_use]
+    pub fn with_poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = Some(interval);
+        self
+    }
+
+    /// Sets how long handshakes are allowed to take
+    ///
+    /// Endpoints replaced by a reload are kept alive for this long, so handshakes which they
+    /// started can complete. This should be at least the `max_handshake_duration` configured
+    /// in the connection [limits](crate::provider::limits). Defaults to 10 seconds, which
+    /// matches the default limit.
+    #[must_use]
+    pub fn with_max_handshake_duration(mut self, duration: Duration) -> Self {
+        self.max_handshake_duration = duration;
+        self
+    }
+
+    /// Sets the function called when a background reload fails
+    ///
+    /// By default, errors are ignored. The number of failed reloads is available from
+    /// [`Handle::failures`].
+    #[must_use]
+    pub fn with_error_handler<F>(mut self, on_error: F) -> Self
+    where
+        F: Fn(&Error) + 'static + Send + Sync,
+    {
+        self.on_error = Box::new(on_error);
+        self
+    }
+
+    /// Loads the initial endpoint and builds the provider
+    pub fn build(self) -> Result<Reloadable<E>, Error> {
+        let fingerprints = fingerprints(&self.files);
+        let current = (self.load)()?;
+
+        let (stop, stopped) = match self.poll_interval {
+            Some(_) => {
+                let (stop, stopped) = mpsc::channel();
+                (Some(stop), Some(stopped))
+            }
+            None => (None, None),
+        };
+
+        let shared = Arc::new(Shared {
+            load: self.load,
+            files: self.files,
+            on_error: self.on_error,
+            state: Mutex::new(State {
+                pending: None,
+                fingerprints,
+            }),
+            generation: AtomicU64::new(0),
+            failures: AtomicU64::new(0),
+            _stop: stop,
+        });
+
+        if let (Some(interval), Some(stopped)) = (self.poll_interval, stopped) {
+            let shared = Arc::downgrade(&shared);
+            thread::Builder::new()
+                .name("s2n-quic-tls-reload".into())
+                .spawn(move || watch(shared, interval, stopped))?;
+        }
+
+        Ok(Reloadable {
+            handle: Handle { shared },
+            current,
+            retired: VecDeque::new(),
+            max_handshake_duration: self.max_handshake_duration,
+            generation: 0,
+        })
+    }
+}
+
+/// Polls the watched files until the provider is dropped
+fn watch<E>(shared: Weak<Shared<E>>, interval: Duration, stopped: mpsc::Receiver<()>) {
+    // the sender is never used so this only returns early once it's dropped with the provider
+    while let Err(mpsc::RecvTimeoutError::Timeout) = stopped.recv_timeout(interval) {
+        let Some(shared) = shared.upgrade() else {
+            return;
+        };
+
+        // failures are retried on the next interval since the fingerprints aren't updated;
+        // this handles files being replaced one at a time
+        if let Err(error) = shared.reload_if_modified() {
+            shared.failures.fetch_add(1, Ordering::Relaxed);
+            (shared.on_error)(&error);
+        }
+    }
+}
+
+/// A handle to a [`Reloadable`] provider
+pub struct Handle<E> {
+    shared: Arc<Shared<E>>,
+}
+
+impl<E> Clone for Handle<E> {
+    fn clone(&self) -> Self {
+        Self {
+            shared: self.shared.clone(),
+        }
+    }
+}
+
+impl<E: tls::Endpoint> Handle<E> {
+    /// Loads a new endpoint and uses it for all subsequent handshakes
+    ///
+    /// The endpoint is loaded on the calling thread. If loading fails, the error is
+    /// returned and the current endpoint is kept.
+    pub fn reload(&self) -> Result<(), Error> {
+        let fingerprints = fingerprints(&self.shared.files);
+        let endpoint = (self.shared.load)()?;
+        self.shared.swap(endpoint, fingerprints);
+        Ok(())
+    }
+
+    /// Reloads the endpoint if any of the watched files changed since the last successful load
+    ///
+    /// Returns `true` if a new endpoint was loaded.
+    pub fn reload_if_modified(&self) -> Result<bool, Error> {
+        self.shared.reload_if_modified()
+    }
+
+    /// Returns the number of times the endpoint has been successfully reloaded
+    pub fn generation(&self) -> u64 {
+        self.shared.generation.load(Ordering::Acquire)
+    }
+
+    /// Returns the number of background reloads which failed
+    pub fn failures(&self) -> u64 {
+        self.shared.failures.load(Ordering::Relaxed)
+    }
+}
+
+struct Shared<E> {
+    load: Box<LoadFn<E>>,
+    files: Vec<PathBuf>,
+    on_error: Box<ErrorFn>,
+    state: Mutex<State<E>>,
+    generation: AtomicU64,
+    failures: AtomicU64,
+    /// Disconnects the reloading thread when dropped
+    _stop: Option<mpsc::Sender<()>>,
+}
+
+impl<E> Shared<E> {
+    fn lock(&self) -> MutexGuard<State<E>> {
+        // the state is always left consistent so recover from poisoning
+        self.state.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    fn reload_if_modified(&self) -> Result<bool, Error> {
+        let fingerprints = fingerprints(&self.files);
+        if self.lock().fingerprints == fingerprints {
+            return Ok(false);
+        }
+
+        let endpoint = (self.load)()?;
+        self.swap(endpoint, fingerprints);
+        Ok(true)
+    }
+
+    /// Publishes a loaded endpoint for the provider to pick up on the next handshake
+    fn swap(&self, endpoint: E, fingerprints: Vec<Option<Fingerprint>>) {
+        let mut state = self.lock();
+        state.pending = Some(endpoint);
+        state.fingerprints = fingerprints;
+        // updated while holding the lock so the provider always finds the pending endpoint
+        self.generation.fetch_add(1, Ordering::Release);
+    }
+}
+
+struct State<E> {
+    /// The most recently loaded endpoint which hasn't been picked up by the provider yet
+    pending: Option<E>,
+    fingerprints: Vec<Option<Fingerprint>>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Fingerprint {
+    modified: Option<SystemTime>,
+    len: u64,
+}
+
+fn fingerprints(files: &[PathBuf]) -> Vec<Option<Fingerprint>> {
+    files
+        .iter()
+        .map(|path| {
+            let metadata = fs::metadata(path).ok()?;
+            Some(Fingerprint {
+                modified: metadata.modified().ok(),
+                len: metadata.len(),
+            })
+        })
+        .collect()
+}
+
+impl<E: tls::Endpoint> tls::Endpoint for Reloadable<E> {
+    type Session = E::Session;
+
+    fn new_server_session<Params: EncoderValue>(
+        &mut self,
+        transport_parameters: &Params,
+    ) -> Self::Session {
+        self.refresh().new_server_session(transport_parameters)
+    }
+
+    fn new_client_session<Params: EncoderValue>(
+        &mut self,
+        transport_parameters: &Params,
+        server_name: ServerName,
+    ) -> Self::Session {
+        self.refresh()
+            .new_client_session(transport_parameters, server_name)
+    }
+
+    fn max_tag_length(&self) -> usize {
+        self.current.max_tag_length()
+    }
+}
+
+impl<E: tls::Endpoint> Provider for Reloadable<E> {
+    type Server = Self;
+    type Client = Self;
+    type Error = core::convert::Infallible;
+
+    fn start_server(self) -> Result<Self::Server, Self::Error> {
+        Ok(self)
+    }
+
+    fn start_client(self) -> Result<Self::Client, Self::Error> {
+        Ok(self)
+    }
+}
@@ -31,13 +31,19 @@ use setup::*;
 mod blackhole;
 mod connection_migration;
 mod deduplicate;
+mod event_combinators;
+mod event_log;
+mod event_serde;
 mod handshake_cid_rotation;
+mod happy_eyeballs;
 mod interceptor;
 mod mtu;
 mod no_tls;
 mod pto;
 mod self_test;
 mod skip_packets;
+mod tls_reload;
+mod wait_idle;
 
 // TODO: https://github.com/aws/s2n-quic/issues/1726
 //
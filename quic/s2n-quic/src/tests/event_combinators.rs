@@ -0,0 +1,92 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::provider::event::{
+    filter, rate_limit, sample, ConnectionInfo, ConnectionMeta, Subscriber,
+};
+
+/// Returns the number of `PacketSent` events the client subscriber observed in a single connection
+fn client_packets_sent<S, F>(wrap: F) -> usize
+where
+    S: Subscriber,
+    F: FnOnce(recorder::PacketSent) -> S,
+{
+    let model = Model::default();
+    let recorder = recorder::PacketSent::new();
+    let events = recorder.events();
+    let subscriber = wrap(recorder);
+
+    test(model, |handle| {
+        let server_addr = server(handle)?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().build().unwrap())?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event((tracing_events(), subscriber))?
+            .with_random(Random::with_seed(123))?
+            .start()?;
+
+        start_client(client, server_addr, Data::new(1000))?;
+
+        Ok(server_addr)
+    })
+    .unwrap();
+
+    let events = events.lock().unwrap();
+    events.len()
+}
+
+#[test]
+fn sample_all_connections() {
+    let expected = client_packets_sent(|subscriber| subscriber);
+    assert!(expected > 0);
+
+    let actual = client_packets_sent(|subscriber| sample::Subscriber::one_in(subscriber, 1));
+    assert_eq!(expected, actual);
+}
+
+#[test]
+fn sample_no_connections() {
+    let actual = client_packets_sent(|subscriber| {
+        sample::Subscriber::new(subscriber, |_: &ConnectionMeta, _: &ConnectionInfo| false)
+    });
+    assert_eq!(0, actual);
+
+    let actual = client_packets_sent(|subscriber| sample::Subscriber::one_in(subscriber, 0));
+    assert_eq!(0, actual);
+}
+
+#[test]
+fn filter_allow() {
+    let expected = client_packets_sent(|subscriber| subscriber);
+
+    let actual = client_packets_sent(|subscriber| {
+        filter::Subscriber::allow(subscriber, &["transport:packet_sent"])
+    });
+    assert_eq!(expected, actual);
+
+    let actual = client_packets_sent(|subscriber| {
+        filter::Subscriber::allow(subscriber, &["transport:frame_sent"])
+    });
+    assert_eq!(0, actual);
+}
+
+#[test]
+fn filter_deny() {
+    let actual = client_packets_sent(|subscriber| {
+        filter::Subscriber::deny(subscriber, &["transport:packet_sent"])
+    });
+    assert_eq!(0, actual);
+}
+
+#[test]
+fn rate_limit() {
+    let expected = client_packets_sent(|subscriber| subscriber);
+    assert!(expected > 3);
+
+    let actual = client_packets_sent(|subscriber| {
+        rate_limit::Subscriber::new(subscriber, 3, Duration::from_secs(3600))
+    });
+    assert_eq!(3, actual);
+}
@@ -0,0 +1,85 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::provider::event::log::{self, HandshakeStatus, Reader};
+use std::io;
+
+/// Collects the log into a shared buffer so it can be read back after the endpoint is dropped
+#[derive(Clone, Default)]
+struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+impl io::Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn record_connection() {
+    let model = Model::default();
+    let buffer = SharedBuffer::default();
+    let subscriber = log::Subscriber::new(buffer.clone()).unwrap();
+
+    test(model, |handle| {
+        let server_addr = server(handle)?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().build().unwrap())?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event((tracing_events(), subscriber))?
+            .with_random(Random::with_seed(123))?
+            .start()?;
+
+        start_client(client, server_addr, Data::new(1000))?;
+
+        Ok(server_addr)
+    })
+    .unwrap();
+
+    let log = buffer.0.lock().unwrap();
+    let records: Vec<_> = Reader::new(&log[..])
+        .unwrap()
+        .collect::<io::Result<_>>()
+        .unwrap();
+
+    assert!(matches!(
+        records.first().map(|record| &record.data),
+        Some(log::Data::ConnectionStarted {
+            endpoint_type: s2n_quic_core::endpoint::Type::Client,
+            unix_time: Some(_),
+        })
+    ));
+    assert!(records
+        .iter()
+        .any(|record| matches!(record.data, log::Data::PacketSent { .. })));
+    assert!(records
+        .iter()
+        .any(|record| matches!(record.data, log::Data::PacketReceived { .. })));
+    assert!(records.iter().any(|record| matches!(
+        record.data,
+        log::Data::HandshakeStatusUpdated {
+            status: HandshakeStatus::Confirmed
+        }
+    )));
+    assert!(records
+        .iter()
+        .any(|record| matches!(record.data, log::Data::RecoveryMetrics { .. })));
+    // only the CONNECTION_CLOSE packet is sent after the connection is closed
+    let closed = records
+        .iter()
+        .position(|record| matches!(record.data, log::Data::ConnectionClosed { .. }))
+        .unwrap();
+    assert!(records[closed + 1..]
+        .iter()
+        .all(|record| matches!(record.data, log::Data::PacketSent { .. })));
+
+    // records are written in the order they were emitted
+    assert!(records
+        .windows(2)
+        .all(|pair| pair[0].timestamp <= pair[1].timestamp));
+}
@@ -0,0 +1,90 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::provider::event::{ConnectionInfo, ConnectionMeta, Event, Meta, Subscriber};
+use serde_json::{json, Value};
+
+/// Serializes every event to JSON through the generic `on_event` callback
+#[derive(Clone, Default)]
+struct JsonEvents {
+    events: Arc<Mutex<Vec<Value>>>,
+}
+
+impl Subscriber for JsonEvents {
+    type ConnectionContext = ();
+
+    fn create_connection_context(
+        &mut self,
+        _meta: &ConnectionMeta,
+        _info: &ConnectionInfo,
+    ) -> Self::ConnectionContext {
+    }
+
+    fn on_event<M: Meta, E: Event>(&mut self, meta: &M, event: &E) {
+        let event = json!({
+            "name": E::NAME,
+            "meta": meta,
+            "event": event,
+        });
+        self.events.lock().unwrap().push(event);
+    }
+}
+
+#[test]
+fn serialize_events() {
+    let model = Model::default();
+    let subscriber = JsonEvents::default();
+    let events = subscriber.events.clone();
+
+    test(model, |handle| {
+        let server_addr = server(handle)?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().build().unwrap())?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event((tracing_events(), subscriber))?
+            .with_random(Random::with_seed(123))?
+            .start()?;
+
+        start_client(client, server_addr, Data::new(1000))?;
+
+        Ok(server_addr)
+    })
+    .unwrap();
+
+    let events = events.lock().unwrap();
+    let find = |name: &str, subject: &str| {
+        events
+            .iter()
+            .find(|event| {
+                event["name"] == name
+                    && event["meta"].get("id").is_some() == (subject == "connection")
+            })
+            .unwrap_or_else(|| panic!("missing {subject} {name} event"))
+    };
+
+    let event = find("platform:event_loop_wakeup", "endpoint");
+    assert_eq!(event["meta"]["endpoint_type"], json!({ "Client": {} }));
+    assert!(event["meta"]["timestamp"]["secs"].is_u64());
+    assert!(event["event"]["rx_ready"].is_boolean());
+
+    let event = find("transport:packet_sent", "connection");
+    assert!(event["meta"]["id"].is_u64());
+    assert!(event["event"]["packet_header"]["Initial"]["number"].is_u64());
+
+    // borrowed fields are serialized as well
+    let event = find("connectivity:connection_started", "connection");
+    assert!(event["event"]["path"]["remote_addr"].is_object());
+    assert!(event["event"]["path"]["remote_cid"]["bytes"].is_array());
+
+    let event = find("transport:server_name_information", "connection");
+    assert_eq!(event["event"]["chosen_server_name"], "localhost");
+
+    let event = find("connectivity:tls_exporter_ready", "connection");
+    assert!(event["event"]["session"].is_object());
+
+    // connection errors are serialized with their `Display` implementation
+    let event = find("connectivity:connection_closed", "connection");
+    assert!(event["event"]["error"].is_string());
+}
@@ -0,0 +1,164 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::client::HappyEyeballs;
+
+/// Ensures the client falls back to the next address when the first one is unresponsive,
+/// without waiting for the first handshake to time out
+#[test]
+fn unresponsive_address_fallback() {
+    let model = Model::default();
+    let network_delay = Duration::from_millis(10);
+    model.set_delay(network_delay);
+
+    test(model, |handle| {
+        let server_addr = server(handle)?;
+        let client = build_client(handle)?;
+
+        primary::spawn(async move {
+            // nothing is listening on this address
+            let unresponsive: SocketAddr = "[2001:db8::1]:443".parse().unwrap();
+            let connect = HappyEyeballs::new([unresponsive, server_addr])
+                .with_server_name("localhost")
+                .with_attempt_delay(Duration::from_millis(100));
+
+            let start = io::now();
+            let connection = client.connect_happy_eyeballs(connect).await.unwrap();
+            let elapsed = io::now() - start;
+
+            assert_eq!(connection.remote_addr().unwrap(), server_addr);
+            // the second attempt was started after the attempt delay
+            assert!(elapsed >= Duration::from_millis(100));
+            assert!(elapsed < Duration::from_secs(1));
+        });
+
+        Ok(server_addr)
+    })
+    .unwrap();
+}
+
+/// Ensures an error is returned once every address has failed
+#[test]
+fn all_addresses_fail() {
+    let model = Model::default();
+
+    test(model, |handle| {
+        let client = build_client(handle)?;
+
+        primary::spawn(async move {
+            // nothing is listening on either address so both handshakes time out
+            let unreachable: [SocketAddr; 2] = [
+                "[2001:db8::1]:443".parse().unwrap(),
+                "192.0.2.1:443".parse().unwrap(),
+            ];
+            let connect = HappyEyeballs::new(unreachable).with_server_name("localhost");
+
+            let start = io::now();
+            let error = client.connect_happy_eyeballs(connect).await.unwrap_err();
+            let elapsed = io::now() - start;
+
+            assert!(
+                matches!(
+                    error,
+                    crate::connection::Error::MaxHandshakeDurationExceeded { .. }
+                ),
+                "{error}"
+            );
+            // the attempts ran concurrently so the error is returned after a single timeout
+            assert!(elapsed < Duration::from_secs(15), "{elapsed:?}");
+        });
+
+        Ok(())
+    })
+    .unwrap();
+}
+
+/// Ensures the first attempt to complete wins when several are in flight, and that the
+/// losing attempt is closed instead of being left to finish its handshake
+#[test]
+fn race_closes_losing_attempt() {
+    let model = Model::default();
+    model.set_delay(Duration::from_millis(10));
+    let closed = recorder::ConnectionClosed::new();
+    let closed_events = closed.events();
+
+    test(model, |handle| {
+        let first = server(handle)?;
+        let second = server(handle)?;
+        let client = Client::builder()
+            .with_io(handle.builder().build().unwrap())?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event((tracing_events(), closed))?
+            .with_random(Random::with_seed(123))?
+            .start()?;
+
+        primary::spawn(async move {
+            // the second attempt starts before the first handshake can complete
+            let connect = HappyEyeballs::new([first, second])
+                .with_server_name("localhost")
+                .with_attempt_delay(Duration::from_millis(10));
+
+            let connection = client.connect_happy_eyeballs(connect).await.unwrap();
+            assert_eq!(connection.remote_addr().unwrap(), first);
+
+            delay(Duration::from_secs(1)).await;
+
+            // only the losing attempt was closed
+            let closed = closed_events.lock().unwrap();
+            assert_eq!(closed.len(), 1, "{closed:?}");
+            drop(closed);
+            drop(connection);
+        });
+
+        Ok(())
+    })
+    .unwrap();
+}
+
+/// Ensures an attempt that is still handshaking is closed once it loses the race, rather
+/// than being driven until the handshake times out
+#[test]
+fn cancel_in_flight_attempt() {
+    let model = Model::default();
+    model.set_delay(Duration::from_millis(10));
+    let closed = recorder::ConnectionClosed::new();
+    let closed_events = closed.events();
+
+    test(model, |handle| {
+        let server_addr = server(handle)?;
+        let client = Client::builder()
+            .with_io(handle.builder().build().unwrap())?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event((tracing_events(), closed))?
+            .with_random(Random::with_seed(123))?
+            .start()?;
+
+        primary::spawn(async move {
+            let unresponsive: SocketAddr = "[2001:db8::1]:443".parse().unwrap();
+            let connect = HappyEyeballs::new([unresponsive, server_addr])
+                .with_server_name("localhost")
+                .with_attempt_delay(Duration::from_millis(100));
+
+            let connection = client.connect_happy_eyeballs(connect).await.unwrap();
+            assert_eq!(connection.remote_addr().unwrap(), server_addr);
+
+            // well before the 10s handshake timeout
+            delay(Duration::from_secs(5)).await;
+
+            let closed = closed_events.lock().unwrap();
+            assert!(
+                closed.iter().any(|event| matches!(
+                    event.error,
+                    crate::connection::Error::Application { .. }
+                )),
+                "{closed:?}"
+            );
+            drop(closed);
+            drop(connection);
+        });
+
+        Ok(())
+    })
+    .unwrap();
+}
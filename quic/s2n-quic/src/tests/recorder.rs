@@ -167,3 +167,5 @@ event_recorder!(
         }
     }
 );
+
+event_recorder!(ConnectionClosed, ConnectionClosed, on_connection_closed);
@@ -0,0 +1,244 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::provider::tls::{self, reload::Reloadable};
+use s2n_quic_core::crypto::tls::Endpoint as TlsEndpoint;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+fn load_server() -> std::result::Result<tls::default::Server, tls::reload::Error> {
+    let server = tls::default::Server::builder()
+        .with_certificate(certificates::CERT_PEM, certificates::KEY_PEM)?
+        .build()?;
+    Ok(server)
+}
+
+#[test]
+fn reload_applies_to_new_handshakes() {
+    let model = Model::default();
+    let loads = Arc::new(AtomicUsize::new(0));
+    let fail = Arc::new(AtomicBool::new(false));
+
+    test(model, |handle| {
+        let tls = Reloadable::builder({
+            let loads = loads.clone();
+            let fail = fail.clone();
+            move || {
+                if fail.load(Ordering::Relaxed) {
+                    return Err("invalid certificate".into());
+                }
+                loads.fetch_add(1, Ordering::Relaxed);
+                load_server()
+            }
+        })
+        .build()
+        .unwrap();
+        let reload = tls.handle();
+
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(tls)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+        let addr = start_server(server)?;
+
+        let client = build_client(handle)?;
+        let fail = fail.clone();
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            client.connect(connect.clone()).await.unwrap();
+
+            reload.reload().unwrap();
+            assert_eq!(reload.generation(), 1);
+            client.connect(connect.clone()).await.unwrap();
+
+            // a failed reload keeps the current endpoint
+            fail.store(true, Ordering::Relaxed);
+            assert!(reload.reload().is_err());
+            assert_eq!(reload.generation(), 1);
+            client.connect(connect).await.unwrap();
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    assert_eq!(loads.load(Ordering::Relaxed), 2);
+}
+
+#[test]
+fn reload_if_modified() {
+    let dir = std::env::temp_dir().join(format!("s2n-quic-tls-reload-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let cert = dir.join("cert.pem");
+    let key = dir.join("key.pem");
+    std::fs::write(&cert, certificates::CERT_PEM).unwrap();
+    std::fs::write(&key, certificates::KEY_PEM).unwrap();
+
+    let tls = Reloadable::builder({
+        let cert = cert.clone();
+        let key = key.clone();
+        move || {
+            tls::default::Server::builder()
+                .with_certificate(cert.as_path(), key.as_path())?
+                .build()
+        }
+    })
+    .with_watched_file(&cert)
+    .with_watched_file(&key)
+    .build()
+    .unwrap();
+    let handle = tls.handle();
+
+    assert!(!handle.reload_if_modified().unwrap());
+    assert_eq!(handle.generation(), 0);
+
+    // a partially written key fails to load and keeps the current endpoint
+    std::fs::write(&key, "").unwrap();
+    assert!(handle.reload_if_modified().is_err());
+    assert_eq!(handle.generation(), 0);
+
+    std::fs::write(&key, certificates::KEY_PEM).unwrap();
+    std::fs::write(&cert, format!("{}\n", certificates::CERT_PEM)).unwrap();
+    assert!(handle.reload_if_modified().unwrap());
+    assert_eq!(handle.generation(), 1);
+    assert!(!handle.reload_if_modified().unwrap());
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn reload_in_background() {
+    let dir = std::env::temp_dir().join(format!(
+        "s2n-quic-tls-reload-background-{}",
+        std::process::id()
+    ));
+    std::fs::create_dir_all(&dir).unwrap();
+    let cert = dir.join("cert.pem");
+    let key = dir.join("key.pem");
+    std::fs::write(&cert, certificates::CERT_PEM).unwrap();
+    std::fs::write(&key, certificates::KEY_PEM).unwrap();
+
+    let errors = Arc::new(AtomicUsize::new(0));
+    let tls = Reloadable::builder({
+        let cert = cert.clone();
+        let key = key.clone();
+        move || {
+            tls::default::Server::builder()
+                .with_certificate(cert.as_path(), key.as_path())?
+                .build()
+        }
+    })
+    .with_watched_file(&cert)
+    .with_watched_file(&key)
+    .with_poll_interval(Duration::from_millis(5))
+    .with_error_handler({
+        let errors = errors.clone();
+        move |_| {
+            errors.fetch_add(1, Ordering::Relaxed);
+        }
+    })
+    .build()
+    .unwrap();
+    let handle = tls.handle();
+
+    let wait_for = |condition: &dyn Fn() -> bool| {
+        let deadline = std::time::Instant::now() + Duration::from_secs(10);
+        while !condition() {
+            assert!(std::time::Instant::now() < deadline, "timed out");
+            std::thread::sleep(Duration::from_millis(5));
+        }
+    };
+
+    // a partially written key is reported and keeps the current endpoint
+    std::fs::write(&key, "").unwrap();
+    wait_for(&|| handle.failures() > 0);
+    assert_eq!(handle.generation(), 0);
+    assert_eq!(errors.load(Ordering::Relaxed) as u64, handle.failures());
+
+    std::fs::write(&key, certificates::KEY_PEM).unwrap();
+    std::fs::write(&cert, format!("{}\n", certificates::CERT_PEM)).unwrap();
+    wait_for(&|| handle.generation() == 1);
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+/// Counts how many endpoints have been dropped
+struct Tracked {
+    server: tls::default::Server,
+    drops: Arc<AtomicUsize>,
+}
+
+impl Drop for Tracked {
+    fn drop(&mut self) {
+        self.drops.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+impl TlsEndpoint for Tracked {
+    type Session = <tls::default::Server as TlsEndpoint>::Session;
+
+    fn new_server_session<Params: s2n_codec::EncoderValue>(
+        &mut self,
+        transport_parameters: &Params,
+    ) -> Self::Session {
+        self.server.new_server_session(transport_parameters)
+    }
+
+    fn new_client_session<Params: s2n_codec::EncoderValue>(
+        &mut self,
+        transport_parameters: &Params,
+        server_name: s2n_quic_core::application::ServerName,
+    ) -> Self::Session {
+        self.server
+            .new_client_session(transport_parameters, server_name)
+    }
+
+    fn max_tag_length(&self) -> usize {
+        self.server.max_tag_length()
+    }
+}
+
+#[test]
+fn replaced_endpoints_outlive_handshakes() {
+    let drops = Arc::new(AtomicUsize::new(0));
+    let build = |max_handshake_duration| {
+        Reloadable::builder({
+            let drops = drops.clone();
+            move || {
+                Ok::<_, tls::reload::Error>(Tracked {
+                    server: load_server()?,
+                    drops: drops.clone(),
+                })
+            }
+        })
+        .with_max_handshake_duration(max_handshake_duration)
+        .build()
+        .unwrap()
+    };
+    let params: &[u8] = &[];
+
+    // several reloads in quick succession keep every endpoint with a handshake in progress
+    let mut tls = build(Duration::from_secs(60));
+    let handle = tls.handle();
+    let _session = tls.new_server_session(&params);
+    for _ in 0..3 {
+        handle.reload().unwrap();
+        let _session = tls.new_server_session(&params);
+    }
+    assert_eq!(drops.load(Ordering::Relaxed), 0);
+    drop(tls);
+    assert_eq!(drops.load(Ordering::Relaxed), 4);
+
+    // endpoints are released once their handshakes can no longer be in progress
+    let mut tls = build(Duration::ZERO);
+    let handle = tls.handle();
+    for _ in 0..3 {
+        handle.reload().unwrap();
+        let _session = tls.new_server_session(&params);
+    }
+    std::thread::sleep(Duration::from_millis(1));
+    let _session = tls.new_server_session(&params);
+    assert_eq!(drops.load(Ordering::Relaxed), 4 + 3);
+}
@@ -0,0 +1,39 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+
+/// Ensures that `wait_idle` completes on an endpoint that doesn't have any connections
+#[test]
+fn wait_idle_without_connections() {
+    let model = Model::default();
+    test(model, |handle| {
+        let mut client = build_client(handle)?;
+        primary::spawn(async move {
+            client.wait_idle().await.unwrap();
+        });
+        Ok(())
+    })
+    .unwrap();
+}
+
+/// Ensures that `wait_idle` completes after the client's connections are closed
+#[test]
+fn wait_idle_after_connection_close() {
+    let model = Model::default();
+    test(model, |handle| {
+        let addr = server(handle)?;
+        let mut client = build_client(handle)?;
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let connection = client.connect(connect).await.unwrap();
+            connection.close(123u8.into());
+
+            // give the endpoint time to go idle before waiting on it
+            delay(Duration::from_secs(1)).await;
+            client.wait_idle().await.unwrap();
+        });
+        Ok(())
+    })
+    .unwrap();
+}